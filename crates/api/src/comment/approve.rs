@@ -0,0 +1,110 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  comment::{ApproveComment, CommentResponse},
+  utils::{blocking, get_local_user_view_from_jwt, is_mod_or_admin},
+};
+use lemmy_apub::protocol::activities::create_or_update::comment::CreateOrUpdateComment;
+use lemmy_db_schema::{
+  source::{
+    comment::Comment,
+    moderator::{ModApproveComment, ModApproveCommentForm},
+    person::Person,
+  },
+  traits::Crud,
+};
+use lemmy_db_views::structs::CommentView;
+use lemmy_utils::{error::LemmyError, utils::scrape_text_for_mentions, ConnectionId};
+use lemmy_websocket::{
+  send::{send_comment_ws_message, send_local_notifs},
+  LemmyContext,
+  UserOperation,
+};
+
+#[async_trait::async_trait(?Send)]
+impl Perform for ApproveComment {
+  type Response = CommentResponse;
+
+  #[tracing::instrument(skip(context, websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    websocket_id: Option<ConnectionId>,
+  ) -> Result<CommentResponse, LemmyError> {
+    let data: &ApproveComment = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let comment_id = data.comment_id;
+    let orig_comment = blocking(context.pool(), move |conn| {
+      CommentView::read(conn, comment_id, None)
+    })
+    .await??;
+
+    // Verify that only the mods can approve
+    is_mod_or_admin(
+      context.pool(),
+      local_user_view.person.id,
+      orig_comment.community.id,
+    )
+    .await?;
+
+    if !orig_comment.comment.pending {
+      return Err(LemmyError::from_message("not_held_for_approval"));
+    }
+
+    // Rejected comments are removed
+    let approve = data.approve;
+    let updated_comment = blocking(context.pool(), move |conn| {
+      if !approve {
+        Comment::update_removed(conn, comment_id, true)?;
+      }
+      Comment::update_pending(conn, comment_id, false)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_update_comment"))?;
+
+    // Mod tables
+    let form = ModApproveCommentForm {
+      mod_person_id: local_user_view.person.id,
+      comment_id: data.comment_id,
+      reason: data.reason.to_owned(),
+      approved: Some(approve),
+    };
+    blocking(context.pool(), move |conn| {
+      ModApproveComment::create(conn, &form)
+    })
+    .await??;
+
+    // The comment didn't send out any notifications and wasn't federated yet
+    let recipient_ids = if approve {
+      let creator_id = updated_comment.creator_id;
+      let creator = blocking(context.pool(), move |conn| Person::read(conn, creator_id)).await??;
+      let mentions = scrape_text_for_mentions(&updated_comment.content);
+      let recipient_ids = send_local_notifs(
+        mentions,
+        &updated_comment,
+        &creator,
+        &orig_comment.post,
+        true,
+        context,
+      )
+      .await?;
+      CreateOrUpdateComment::send_approved(updated_comment.into(), context).await?;
+      recipient_ids
+    } else {
+      Vec::new()
+    };
+
+    send_comment_ws_message(
+      data.comment_id,
+      UserOperation::ApproveComment,
+      websocket_id,
+      None,
+      Some(local_user_view.person.id),
+      recipient_ids,
+      context,
+    )
+    .await
+  }
+}
@@ -2,7 +2,13 @@ use crate::Perform;
 use actix_web::web::Data;
 use lemmy_api_common::{
   comment::{CommentResponse, CreateCommentLike},
-  utils::{blocking, check_community_ban, check_downvotes_enabled, get_local_user_view_from_jwt},
+  utils::{
+    blocking,
+    check_community_ban,
+    check_community_visible,
+    check_downvotes_enabled,
+    get_local_user_view_from_jwt,
+  },
 };
 use lemmy_apub::{
   fetcher::post_or_comment::PostOrComment,
@@ -55,6 +61,12 @@ impl Perform for CreateCommentLike {
       context.pool(),
     )
     .await?;
+    check_community_visible(
+      orig_comment.community.id,
+      Some(&local_user_view.person),
+      context.pool(),
+    )
+    .await?;
 
     // Add parent poster or commenter to recipients
     let comment_reply = blocking(context.pool(), move |conn| {
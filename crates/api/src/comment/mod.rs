@@ -1,2 +1,3 @@
+mod approve;
 mod like;
 mod save;
@@ -2,7 +2,7 @@ use crate::Perform;
 use actix_web::web::Data;
 use lemmy_api_common::{
   comment::{CommentResponse, SaveComment},
-  utils::{blocking, get_local_user_view_from_jwt},
+  utils::{blocking, check_community_visible, get_local_user_view_from_jwt},
 };
 use lemmy_db_schema::{
   source::comment::{CommentSaved, CommentSavedForm},
@@ -49,6 +49,12 @@ impl Perform for SaveComment {
       CommentView::read(conn, comment_id, Some(person_id))
     })
     .await??;
+    check_community_visible(
+      comment_view.community.id,
+      Some(&local_user_view.person),
+      context.pool(),
+    )
+    .await?;
 
     Ok(CommentResponse {
       comment_view,
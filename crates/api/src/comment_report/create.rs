@@ -3,7 +3,7 @@ use activitypub_federation::core::object_id::ObjectId;
 use actix_web::web::Data;
 use lemmy_api_common::{
   comment::{CommentReportResponse, CreateCommentReport},
-  utils::{blocking, check_community_ban, get_local_user_view_from_jwt},
+  utils::{blocking, check_community_ban, check_community_visible, get_local_user_view_from_jwt},
 };
 use lemmy_apub::protocol::activities::community::report::Report;
 use lemmy_db_schema::{
@@ -46,6 +46,12 @@ impl Perform for CreateCommentReport {
     .await??;
 
     check_community_ban(person_id, comment_view.community.id, context.pool()).await?;
+    check_community_visible(
+      comment_view.community.id,
+      Some(&local_user_view.person),
+      context.pool(),
+    )
+    .await?;
 
     let report_form = CommentReportForm {
       creator_id: person_id,
@@ -0,0 +1,51 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  automod::validate_automod_rule,
+  community::{AutomodRulesResponse, EditAutomodRule},
+  utils::{blocking, get_local_user_view_from_jwt, is_mod_or_admin},
+};
+use lemmy_db_schema::{
+  source::automod_rule::{AutomodRule, AutomodRuleForm},
+  traits::Crud,
+  utils::naive_now,
+};
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for EditAutomodRule {
+  type Response = AutomodRulesResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<AutomodRulesResponse, LemmyError> {
+    let data: &EditAutomodRule = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let rule_id = data.id;
+    let orig_rule =
+      blocking(context.pool(), move |conn| AutomodRule::read(conn, rule_id)).await??;
+    let community_id = orig_rule.community_id;
+    is_mod_or_admin(context.pool(), local_user_view.person.id, community_id).await?;
+    validate_automod_rule(&data.rule)?;
+
+    let form = AutomodRuleForm {
+      community_id,
+      rule: data.rule.clone(),
+      updated: Some(naive_now()),
+    };
+    let rules = blocking(context.pool(), move |conn| {
+      AutomodRule::update(conn, rule_id, &form)?;
+      AutomodRule::list_for_community(conn, community_id)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_update_automod_rule"))?;
+
+    Ok(AutomodRulesResponse { rules })
+  }
+}
@@ -0,0 +1,35 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  community::{AutomodRulesResponse, ListAutomodRules},
+  utils::{blocking, get_local_user_view_from_jwt, is_mod_or_admin},
+};
+use lemmy_db_schema::source::automod_rule::AutomodRule;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for ListAutomodRules {
+  type Response = AutomodRulesResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<AutomodRulesResponse, LemmyError> {
+    let data: &ListAutomodRules = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let community_id = data.community_id;
+    is_mod_or_admin(context.pool(), local_user_view.person.id, community_id).await?;
+
+    let rules = blocking(context.pool(), move |conn| {
+      AutomodRule::list_for_community(conn, community_id)
+    })
+    .await??;
+
+    Ok(AutomodRulesResponse { rules })
+  }
+}
@@ -0,0 +1,40 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  community::{BackfillCommunity, BackfillCommunityResponse},
+  utils::{blocking, get_local_user_view_from_jwt, is_mod_or_admin},
+};
+use lemmy_apub::fetcher::community_backfill::spawn_community_backfill;
+use lemmy_db_schema::{source::community::Community, traits::Crud};
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for BackfillCommunity {
+  type Response = BackfillCommunityResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<BackfillCommunityResponse, LemmyError> {
+    let data: &BackfillCommunity = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let community_id = data.community_id;
+    is_mod_or_admin(context.pool(), local_user_view.person.id, community_id).await?;
+
+    let community = blocking(context.pool(), move |conn| {
+      Community::read(conn, community_id)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_community"))?;
+
+    // The import can take a long time, so it runs in the background
+    spawn_community_backfill(community.into(), context.get_ref().clone())?;
+
+    Ok(BackfillCommunityResponse { success: true })
+  }
+}
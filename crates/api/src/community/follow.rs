@@ -11,13 +11,10 @@ use lemmy_api_common::{
 };
 use lemmy_apub::{
   objects::community::ApubCommunity,
-  protocol::activities::following::{
-    follow::FollowCommunity as FollowCommunityApub,
-    undo_follow::UndoFollowCommunity,
-  },
+  protocol::activities::following::{follow::Follow, undo_follow::UndoFollow},
 };
 use lemmy_db_schema::{
-  source::community::{Community, CommunityFollower, CommunityFollowerForm},
+  source::community::{Community, CommunityFollower, CommunityFollowerForm, CommunityVisibility},
   traits::{Crud, Followable},
 };
 use lemmy_db_views_actor::structs::CommunityView;
@@ -44,10 +41,11 @@ impl Perform for FollowCommunity {
     })
     .await??
     .into();
+    // Follows of private communities stay pending until a mod approves them
     let community_follower_form = CommunityFollowerForm {
       community_id: data.community_id,
       person_id: local_user_view.person.id,
-      pending: false,
+      pending: community.visibility == CommunityVisibility::Private,
     };
 
     if community.local {
@@ -69,11 +67,9 @@ impl Perform for FollowCommunity {
     } else if data.follow {
       // Dont actually add to the community followers here, because you need
       // to wait for the accept
-      FollowCommunityApub::send(&local_user_view.person.clone().into(), &community, context)
-        .await?;
+      Follow::send(&local_user_view.person.clone().into(), &community, context).await?;
     } else {
-      UndoFollowCommunity::send(&local_user_view.person.clone().into(), &community, context)
-        .await?;
+      UndoFollow::send(&local_user_view.person.clone().into(), &community, context).await?;
       let unfollow = move |conn: &'_ _| CommunityFollower::unfollow(conn, &community_follower_form);
       blocking(context.pool(), unfollow)
         .await?
@@ -1,6 +1,12 @@
 mod add_mod;
+mod automod_rule;
+mod backfill;
 mod ban;
 mod block;
 mod follow;
 mod hide;
+mod mod_queue;
+mod pending_follows;
+mod post_notifications;
 mod transfer;
+mod wiki;
@@ -0,0 +1,65 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  community::{ListModQueue, ListModQueueResponse},
+  utils::{blocking, get_local_user_view_from_jwt, is_mod_or_admin},
+};
+use lemmy_db_schema::{CommentSortType, SortType};
+use lemmy_db_views::{comment_view::CommentQuery, post_view::PostQuery};
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for ListModQueue {
+  type Response = ListModQueueResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<ListModQueueResponse, LemmyError> {
+    let data: &ListModQueue = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let person_id = local_user_view.person.id;
+    let admin = local_user_view.person.admin;
+    let community_id = data.community_id;
+    if let Some(community_id) = community_id {
+      is_mod_or_admin(context.pool(), person_id, community_id).await?;
+    }
+
+    let page = data.page;
+    let limit = data.limit;
+    let (posts, comments) = blocking(context.pool(), move |conn| {
+      let posts = PostQuery::builder()
+        .conn(conn)
+        .sort(Some(SortType::Old))
+        .community_id(community_id)
+        .my_person_id(Some(person_id))
+        .show_nsfw(Some(true))
+        .pending_only(Some(true))
+        .admin(Some(admin))
+        .page(page)
+        .limit(limit)
+        .build()
+        .list()?;
+      let comments = CommentQuery::builder()
+        .conn(conn)
+        .sort(Some(CommentSortType::Old))
+        .community_id(community_id)
+        .my_person_id(Some(person_id))
+        .pending_only(Some(true))
+        .admin(Some(admin))
+        .page(page)
+        .limit(limit)
+        .build()
+        .list()?;
+      Ok((posts, comments)) as Result<_, LemmyError>
+    })
+    .await??;
+
+    Ok(ListModQueueResponse { posts, comments })
+  }
+}
@@ -0,0 +1,119 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  community::{
+    ApproveFollow,
+    ApproveFollowResponse,
+    ListPendingFollows,
+    ListPendingFollowsResponse,
+  },
+  utils::{blocking, get_local_user_view_from_jwt, is_mod_or_admin},
+};
+use lemmy_apub::{
+  objects::{community::ApubCommunity, person::ApubPerson},
+  protocol::activities::following::{accept::AcceptFollow, reject::RejectFollow},
+};
+use lemmy_db_schema::{
+  source::{
+    community::{Community, CommunityFollower, CommunityFollowerForm},
+    person::Person,
+  },
+  traits::{Crud, Followable},
+};
+use lemmy_db_views_actor::structs::CommunityFollowerView;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for ListPendingFollows {
+  type Response = ListPendingFollowsResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<ListPendingFollowsResponse, LemmyError> {
+    let data: &ListPendingFollows = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let community_id = data.community_id;
+    is_mod_or_admin(context.pool(), local_user_view.person.id, community_id).await?;
+
+    let page = data.page;
+    let limit = data.limit;
+    let items = blocking(context.pool(), move |conn| {
+      CommunityFollowerView::list_pending(conn, community_id, page, limit)
+    })
+    .await??;
+
+    Ok(ListPendingFollowsResponse { items })
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Perform for ApproveFollow {
+  type Response = ApproveFollowResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<ApproveFollowResponse, LemmyError> {
+    let data: &ApproveFollow = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let community_id = data.community_id;
+    is_mod_or_admin(context.pool(), local_user_view.person.id, community_id).await?;
+
+    let community: ApubCommunity = blocking(context.pool(), move |conn| {
+      Community::read(conn, community_id)
+    })
+    .await??
+    .into();
+    // Follows of remote communities are approved by the instance of the community
+    if !community.local {
+      return Err(LemmyError::from_message(
+        "cant_approve_follow_of_remote_community",
+      ));
+    }
+
+    let follower_id = data.follower_id;
+    let follower: ApubPerson =
+      blocking(context.pool(), move |conn| Person::read(conn, follower_id))
+        .await??
+        .into();
+
+    if data.approve {
+      blocking(context.pool(), move |conn| {
+        CommunityFollower::follow_accepted(conn, community_id, follower_id)
+      })
+      .await?
+      .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_follow"))?;
+      if !follower.local {
+        AcceptFollow::send_approved(&follower, &community, context).await?;
+      }
+    } else {
+      let community_follower_form = CommunityFollowerForm {
+        community_id,
+        person_id: follower_id,
+        pending: true,
+      };
+      let removed = blocking(context.pool(), move |conn| {
+        CommunityFollower::unfollow(conn, &community_follower_form)
+      })
+      .await??;
+      if removed == 0 {
+        return Err(LemmyError::from_message("couldnt_find_follow"));
+      }
+      if !follower.local {
+        RejectFollow::send(&follower, &community, context).await?;
+      }
+    }
+
+    Ok(ApproveFollowResponse { success: true })
+  }
+}
@@ -0,0 +1,59 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  community::{CommunityPostSubscriptionResponse, SubscribeToCommunityPosts},
+  utils::{
+    blocking,
+    check_community_deleted_or_removed,
+    check_community_visible,
+    get_local_user_view_from_jwt,
+  },
+};
+use lemmy_db_schema::source::post_notification::{
+  CommunityPostSubscription,
+  CommunityPostSubscriptionForm,
+};
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for SubscribeToCommunityPosts {
+  type Response = CommunityPostSubscriptionResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<CommunityPostSubscriptionResponse, LemmyError> {
+    let data: &SubscribeToCommunityPosts = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let community_id = data.community_id;
+    let form = CommunityPostSubscriptionForm {
+      person_id: local_user_view.person.id,
+      community_id,
+    };
+
+    if data.subscribe {
+      check_community_deleted_or_removed(community_id, context.pool()).await?;
+      check_community_visible(community_id, Some(&local_user_view.person), context.pool()).await?;
+
+      let subscribe = move |conn: &'_ _| CommunityPostSubscription::subscribe(conn, &form);
+      blocking(context.pool(), subscribe)
+        .await?
+        .map_err(|e| LemmyError::from_error_message(e, "couldnt_subscribe_to_community_posts"))?;
+    } else {
+      let unsubscribe = move |conn: &'_ _| CommunityPostSubscription::unsubscribe(conn, &form);
+      blocking(context.pool(), unsubscribe)
+        .await?
+        .map_err(|e| LemmyError::from_error_message(e, "couldnt_subscribe_to_community_posts"))?;
+    }
+
+    Ok(CommunityPostSubscriptionResponse {
+      community_id,
+      subscribed: data.subscribe,
+    })
+  }
+}
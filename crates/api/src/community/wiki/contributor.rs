@@ -0,0 +1,86 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  utils::{
+    blocking,
+    check_private_instance,
+    get_local_user_view_from_jwt,
+    get_local_user_view_from_jwt_opt,
+    is_mod_or_admin,
+  },
+  wiki::{AddWikiContributor, ListWikiContributors, WikiContributorsResponse},
+};
+use lemmy_db_schema::source::wiki_page::{WikiContributor, WikiContributorForm};
+use lemmy_db_views_actor::structs::WikiContributorView;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for AddWikiContributor {
+  type Response = WikiContributorsResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<WikiContributorsResponse, LemmyError> {
+    let data: &AddWikiContributor = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let community_id = data.community_id;
+    is_mod_or_admin(context.pool(), local_user_view.person.id, community_id).await?;
+
+    let form = WikiContributorForm {
+      community_id,
+      person_id: data.person_id,
+    };
+    if data.added {
+      blocking(context.pool(), move |conn| {
+        WikiContributor::add(conn, &form)
+      })
+      .await?
+      .map_err(|e| LemmyError::from_error_message(e, "couldnt_add_wiki_contributor"))?;
+    } else {
+      blocking(context.pool(), move |conn| {
+        WikiContributor::remove(conn, &form)
+      })
+      .await?
+      .map_err(|e| LemmyError::from_error_message(e, "couldnt_add_wiki_contributor"))?;
+    }
+
+    let contributors = blocking(context.pool(), move |conn| {
+      WikiContributorView::for_community(conn, community_id)
+    })
+    .await??;
+
+    Ok(WikiContributorsResponse { contributors })
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Perform for ListWikiContributors {
+  type Response = WikiContributorsResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<WikiContributorsResponse, LemmyError> {
+    let data: &ListWikiContributors = self;
+    let local_user_view =
+      get_local_user_view_from_jwt_opt(data.auth.as_ref(), context.pool(), context.secret())
+        .await?;
+    check_private_instance(&local_user_view, context.pool()).await?;
+
+    let community_id = data.community_id;
+    let contributors = blocking(context.pool(), move |conn| {
+      WikiContributorView::for_community(conn, community_id)
+    })
+    .await??;
+
+    Ok(WikiContributorsResponse { contributors })
+  }
+}
@@ -0,0 +1,70 @@
+use crate::{
+  community::wiki::{check_can_edit_wiki, check_wiki_page_content, check_wiki_page_name},
+  Perform,
+};
+use actix_web::web::Data;
+use lemmy_api_common::{
+  utils::{blocking, check_community_deleted_or_removed, get_local_user_view_from_jwt},
+  wiki::{CreateWikiPage, WikiPageResponse},
+};
+use lemmy_apub::generate_wiki_page_url;
+use lemmy_db_schema::{
+  source::{
+    community::Community,
+    wiki_page::{WikiPage, WikiPageForm},
+  },
+  traits::Crud,
+};
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for CreateWikiPage {
+  type Response = WikiPageResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<WikiPageResponse, LemmyError> {
+    let data: &CreateWikiPage = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let community_id = data.community_id;
+    check_can_edit_wiki(local_user_view.person.id, community_id, context.pool()).await?;
+    check_community_deleted_or_removed(community_id, context.pool()).await?;
+    check_wiki_page_name(&data.name)?;
+    let (title, body) =
+      check_wiki_page_content(&data.title, &data.body, &data.summary, context).await?;
+
+    let community = blocking(context.pool(), move |conn| {
+      Community::read(conn, community_id)
+    })
+    .await??;
+    let form = WikiPageForm {
+      community_id,
+      name: data.name.to_owned(),
+      title,
+      body,
+      ap_id: generate_wiki_page_url(&community.actor_id, &data.name)?,
+      local: Some(true),
+      deleted: None,
+      published: None,
+      updated: None,
+    };
+    let editor_id = local_user_view.person.id;
+    let summary = data.summary.to_owned();
+    let wiki_page = blocking(context.pool(), move |conn| {
+      WikiPage::create_with_revision(conn, &form, editor_id, summary)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_create_wiki_page"))?;
+
+    Ok(WikiPageResponse {
+      wiki_page,
+      can_edit: true,
+    })
+  }
+}
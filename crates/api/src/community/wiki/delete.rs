@@ -0,0 +1,44 @@
+use crate::{community::wiki::get_wiki_page, Perform};
+use actix_web::web::Data;
+use lemmy_api_common::{
+  utils::{blocking, get_local_user_view_from_jwt, is_mod_or_admin},
+  wiki::{DeleteWikiPage, WikiPageResponse},
+};
+use lemmy_db_schema::source::wiki_page::WikiPage;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for DeleteWikiPage {
+  type Response = WikiPageResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<WikiPageResponse, LemmyError> {
+    let data: &DeleteWikiPage = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let orig = get_wiki_page(data.id, context.pool()).await?;
+    is_mod_or_admin(context.pool(), local_user_view.person.id, orig.community_id).await?;
+    if !orig.local {
+      return Err(LemmyError::from_message("no_wiki_edit_allowed"));
+    }
+
+    let wiki_page_id = orig.id;
+    let deleted = data.deleted;
+    let wiki_page = blocking(context.pool(), move |conn| {
+      WikiPage::update_deleted(conn, wiki_page_id, deleted)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_update_wiki_page"))?;
+
+    Ok(WikiPageResponse {
+      wiki_page,
+      can_edit: true,
+    })
+  }
+}
@@ -0,0 +1,61 @@
+use crate::{
+  community::wiki::{can_edit_wiki, check_wiki_page_visible, get_wiki_page},
+  Perform,
+};
+use actix_web::web::Data;
+use lemmy_api_common::{
+  utils::{blocking, check_private_instance, get_local_user_view_from_jwt_opt},
+  wiki::{diff_lines, GetWikiPageDiff, WikiPageDiffResponse},
+};
+use lemmy_db_schema::source::wiki_page::WikiPageRevision;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for GetWikiPageDiff {
+  type Response = WikiPageDiffResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<WikiPageDiffResponse, LemmyError> {
+    let data: &GetWikiPageDiff = self;
+    let local_user_view =
+      get_local_user_view_from_jwt_opt(data.auth.as_ref(), context.pool(), context.secret())
+        .await?;
+    check_private_instance(&local_user_view, context.pool()).await?;
+
+    let revision_id = data.revision_id;
+    let compare_to = data.compare_to;
+    let (from, to) = blocking(context.pool(), move |conn| {
+      let to = WikiPageRevision::read(conn, revision_id)?;
+      let from = match compare_to {
+        Some(compare_to) => Some(WikiPageRevision::read(conn, compare_to)?),
+        None => to.previous(conn)?,
+      };
+      Ok((from, to)) as Result<(Option<WikiPageRevision>, WikiPageRevision), LemmyError>
+    })
+    .await?
+    .map_err(|e| e.with_message("couldnt_find_wiki_page_revision"))?;
+    if from
+      .as_ref()
+      .map(|f| f.wiki_page_id)
+      .unwrap_or(to.wiki_page_id)
+      != to.wiki_page_id
+    {
+      return Err(LemmyError::from_message("couldnt_find_wiki_page_revision"));
+    }
+
+    let wiki_page = get_wiki_page(to.wiki_page_id, context.pool()).await?;
+    let person_id = local_user_view.map(|l| l.person.id);
+    let can_edit = can_edit_wiki(person_id, wiki_page.community_id, context.pool()).await?;
+    check_wiki_page_visible(&wiki_page, can_edit)?;
+
+    let old_body = from.as_ref().map(|f| f.body.as_str()).unwrap_or_default();
+    let diff = diff_lines(old_body, &to.body);
+
+    Ok(WikiPageDiffResponse { from, to, diff })
+  }
+}
@@ -0,0 +1,52 @@
+use crate::{
+  community::wiki::{check_can_edit_wiki, check_wiki_page_content, get_wiki_page},
+  Perform,
+};
+use actix_web::web::Data;
+use lemmy_api_common::{
+  utils::{blocking, get_local_user_view_from_jwt},
+  wiki::{EditWikiPage, WikiPageResponse},
+};
+use lemmy_db_schema::source::wiki_page::{WikiPage, WikiPageRevisionForm};
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for EditWikiPage {
+  type Response = WikiPageResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<WikiPageResponse, LemmyError> {
+    let data: &EditWikiPage = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let orig = get_wiki_page(data.id, context.pool()).await?;
+    check_can_edit_wiki(local_user_view.person.id, orig.community_id, context.pool()).await?;
+    if orig.deleted {
+      return Err(LemmyError::from_message("deleted"));
+    }
+
+    let title = data.title.to_owned().unwrap_or(orig.title);
+    let (title, body) = check_wiki_page_content(&title, &data.body, &data.summary, context).await?;
+    let form = WikiPageRevisionForm {
+      wiki_page_id: orig.id,
+      editor_id: local_user_view.person.id,
+      title,
+      body,
+      summary: data.summary.to_owned(),
+    };
+    let wiki_page = blocking(context.pool(), move |conn| WikiPage::edit(conn, &form))
+      .await?
+      .map_err(|e| LemmyError::from_error_message(e, "couldnt_update_wiki_page"))?;
+
+    Ok(WikiPageResponse {
+      wiki_page,
+      can_edit: true,
+    })
+  }
+}
@@ -0,0 +1,40 @@
+use crate::{community::wiki::can_edit_wiki, Perform};
+use actix_web::web::Data;
+use lemmy_api_common::{
+  utils::{blocking, check_private_instance, get_local_user_view_from_jwt_opt},
+  wiki::{ListWikiPages, ListWikiPagesResponse},
+};
+use lemmy_db_schema::source::wiki_page::WikiPage;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for ListWikiPages {
+  type Response = ListWikiPagesResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<ListWikiPagesResponse, LemmyError> {
+    let data: &ListWikiPages = self;
+    let local_user_view =
+      get_local_user_view_from_jwt_opt(data.auth.as_ref(), context.pool(), context.secret())
+        .await?;
+    check_private_instance(&local_user_view, context.pool()).await?;
+
+    let community_id = data.community_id;
+    let person_id = local_user_view.map(|l| l.person.id);
+    let can_edit = can_edit_wiki(person_id, community_id, context.pool()).await?;
+    let wiki_pages = blocking(context.pool(), move |conn| {
+      WikiPage::list_for_community(conn, community_id)
+    })
+    .await??;
+
+    Ok(ListWikiPagesResponse {
+      wiki_pages,
+      can_edit,
+    })
+  }
+}
@@ -0,0 +1,121 @@
+use lemmy_api_common::{
+  slur_filter::slur_filter,
+  utils::{blocking, check_community_ban, is_mod_or_admin},
+};
+use lemmy_db_schema::{
+  newtypes::{CommunityId, PersonId, WikiPageId},
+  source::{
+    community::Community,
+    wiki_page::{WikiContributor, WikiPage},
+  },
+  traits::Crud,
+  utils::DbPool,
+};
+use lemmy_utils::{
+  error::LemmyError,
+  utils::{check_slurs, check_slurs_opt, is_valid_post_title},
+};
+use lemmy_websocket::LemmyContext;
+
+mod contributor;
+mod create;
+mod delete;
+mod diff;
+mod edit;
+mod list;
+mod read;
+mod revert;
+mod revisions;
+
+/// Mods, admins and approved contributors can edit the wiki of a local community. The wikis of
+/// remote communities are read-only copies.
+async fn can_edit_wiki(
+  person_id: Option<PersonId>,
+  community_id: CommunityId,
+  pool: &DbPool,
+) -> Result<bool, LemmyError> {
+  let person_id = match person_id {
+    Some(p) => p,
+    None => return Ok(false),
+  };
+  let community = blocking(pool, move |conn| Community::read(conn, community_id))
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_community"))?;
+  if !community.local
+    || check_community_ban(person_id, community_id, pool)
+      .await
+      .is_err()
+  {
+    return Ok(false);
+  }
+  if is_mod_or_admin(pool, person_id, community_id).await.is_ok() {
+    return Ok(true);
+  }
+  let is_contributor = blocking(pool, move |conn| {
+    WikiContributor::is_contributor(conn, community_id, person_id)
+  })
+  .await??;
+  Ok(is_contributor)
+}
+
+async fn check_can_edit_wiki(
+  person_id: PersonId,
+  community_id: CommunityId,
+  pool: &DbPool,
+) -> Result<(), LemmyError> {
+  if can_edit_wiki(Some(person_id), community_id, pool).await? {
+    Ok(())
+  } else {
+    Err(LemmyError::from_message("no_wiki_edit_allowed"))
+  }
+}
+
+/// Deleted pages are only visible to those who could restore them
+fn check_wiki_page_visible(wiki_page: &WikiPage, can_edit: bool) -> Result<(), LemmyError> {
+  if wiki_page.deleted && !can_edit {
+    Err(LemmyError::from_message("couldnt_find_wiki_page"))
+  } else {
+    Ok(())
+  }
+}
+
+fn check_wiki_page_name(name: &str) -> Result<(), LemmyError> {
+  let valid = !name.is_empty()
+    && name.chars().count() <= 100
+    && name
+      .chars()
+      .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+  if valid {
+    Ok(())
+  } else {
+    Err(LemmyError::from_message("invalid_wiki_page_name"))
+  }
+}
+
+async fn get_wiki_page(wiki_page_id: WikiPageId, pool: &DbPool) -> Result<WikiPage, LemmyError> {
+  blocking(pool, move |conn| WikiPage::read(conn, wiki_page_id))
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_wiki_page"))
+}
+
+/// Checks the title, body and edit summary of a new revision, returning the title and body with
+/// the slur filter applied.
+async fn check_wiki_page_content(
+  title: &str,
+  body: &str,
+  summary: &Option<String>,
+  context: &LemmyContext,
+) -> Result<(String, String), LemmyError> {
+  if !is_valid_post_title(title) {
+    return Err(LemmyError::from_message("invalid_wiki_page_title"));
+  }
+  let slur_regex = &context.settings().slur_regex();
+  check_slurs(title, slur_regex)?;
+  check_slurs(body, slur_regex)?;
+  check_slurs_opt(summary, slur_regex)?;
+  let slur_filter = slur_filter(context.pool()).await?;
+  Ok((
+    slur_filter.apply_without_hold(title)?,
+    slur_filter.apply_without_hold(body)?,
+  ))
+}
@@ -0,0 +1,47 @@
+use crate::{
+  community::wiki::{can_edit_wiki, check_wiki_page_visible},
+  Perform,
+};
+use actix_web::web::Data;
+use lemmy_api_common::{
+  utils::{blocking, check_private_instance, get_local_user_view_from_jwt_opt},
+  wiki::{GetWikiPage, WikiPageResponse},
+};
+use lemmy_db_schema::source::wiki_page::WikiPage;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for GetWikiPage {
+  type Response = WikiPageResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<WikiPageResponse, LemmyError> {
+    let data: &GetWikiPage = self;
+    let local_user_view =
+      get_local_user_view_from_jwt_opt(data.auth.as_ref(), context.pool(), context.secret())
+        .await?;
+    check_private_instance(&local_user_view, context.pool()).await?;
+
+    let community_id = data.community_id;
+    let name = data.name.to_owned();
+    let wiki_page = blocking(context.pool(), move |conn| {
+      WikiPage::read_by_name(conn, community_id, &name)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_wiki_page"))?;
+
+    let person_id = local_user_view.map(|l| l.person.id);
+    let can_edit = can_edit_wiki(person_id, community_id, context.pool()).await?;
+    check_wiki_page_visible(&wiki_page, can_edit)?;
+
+    Ok(WikiPageResponse {
+      wiki_page,
+      can_edit,
+    })
+  }
+}
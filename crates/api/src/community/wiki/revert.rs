@@ -0,0 +1,59 @@
+use crate::{
+  community::wiki::{check_can_edit_wiki, check_wiki_page_content, get_wiki_page},
+  Perform,
+};
+use actix_web::web::Data;
+use lemmy_api_common::{
+  utils::{blocking, get_local_user_view_from_jwt},
+  wiki::{RevertWikiPage, WikiPageResponse},
+};
+use lemmy_db_schema::source::wiki_page::{WikiPage, WikiPageRevision, WikiPageRevisionForm};
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for RevertWikiPage {
+  type Response = WikiPageResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<WikiPageResponse, LemmyError> {
+    let data: &RevertWikiPage = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let revision_id = data.revision_id;
+    let revision = blocking(context.pool(), move |conn| {
+      WikiPageRevision::read(conn, revision_id)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_wiki_page_revision"))?;
+    let orig = get_wiki_page(revision.wiki_page_id, context.pool()).await?;
+    check_can_edit_wiki(local_user_view.person.id, orig.community_id, context.pool()).await?;
+    if orig.deleted {
+      return Err(LemmyError::from_message("deleted"));
+    }
+
+    // The slur filter may have changed since the revision was made
+    let (title, body) =
+      check_wiki_page_content(&revision.title, &revision.body, &data.summary, context).await?;
+    let form = WikiPageRevisionForm {
+      wiki_page_id: orig.id,
+      editor_id: local_user_view.person.id,
+      title,
+      body,
+      summary: data.summary.to_owned(),
+    };
+    let wiki_page = blocking(context.pool(), move |conn| WikiPage::edit(conn, &form))
+      .await?
+      .map_err(|e| LemmyError::from_error_message(e, "couldnt_update_wiki_page"))?;
+
+    Ok(WikiPageResponse {
+      wiki_page,
+      can_edit: true,
+    })
+  }
+}
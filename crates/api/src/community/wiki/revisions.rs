@@ -0,0 +1,44 @@
+use crate::{
+  community::wiki::{can_edit_wiki, check_wiki_page_visible, get_wiki_page},
+  Perform,
+};
+use actix_web::web::Data;
+use lemmy_api_common::{
+  utils::{blocking, check_private_instance, get_local_user_view_from_jwt_opt},
+  wiki::{ListWikiPageRevisions, ListWikiPageRevisionsResponse},
+};
+use lemmy_db_views_actor::structs::WikiPageRevisionView;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for ListWikiPageRevisions {
+  type Response = ListWikiPageRevisionsResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<ListWikiPageRevisionsResponse, LemmyError> {
+    let data: &ListWikiPageRevisions = self;
+    let local_user_view =
+      get_local_user_view_from_jwt_opt(data.auth.as_ref(), context.pool(), context.secret())
+        .await?;
+    check_private_instance(&local_user_view, context.pool()).await?;
+
+    let wiki_page = get_wiki_page(data.id, context.pool()).await?;
+    let person_id = local_user_view.map(|l| l.person.id);
+    let can_edit = can_edit_wiki(person_id, wiki_page.community_id, context.pool()).await?;
+    check_wiki_page_visible(&wiki_page, can_edit)?;
+
+    let page = data.page;
+    let limit = data.limit;
+    let revisions = blocking(context.pool(), move |conn| {
+      WikiPageRevisionView::list_for_page(conn, wiki_page.id, page, limit)
+    })
+    .await??;
+
+    Ok(ListWikiPageRevisionsResponse { revisions })
+  }
+}
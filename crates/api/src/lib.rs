@@ -1,6 +1,14 @@
 use actix_web::{web, web::Data};
 use captcha::Captcha;
-use lemmy_api_common::{comment::*, community::*, person::*, post::*, site::*, websocket::*};
+use lemmy_api_common::{
+  comment::*,
+  community::*,
+  person::*,
+  post::*,
+  site::*,
+  websocket::*,
+  wiki::*,
+};
 use lemmy_utils::{error::LemmyError, ConnectionId};
 use lemmy_websocket::{serialize_websocket_message, LemmyContext, UserOperation};
 use serde::Deserialize;
@@ -37,6 +45,9 @@ pub async fn match_websocket_operation(
     UserOperation::Login => do_websocket_operation::<Login>(context, id, op, data).await,
     UserOperation::GetCaptcha => do_websocket_operation::<GetCaptcha>(context, id, op, data).await,
     UserOperation::GetReplies => do_websocket_operation::<GetReplies>(context, id, op, data).await,
+    UserOperation::GetPostNotifications => {
+      do_websocket_operation::<GetPostNotifications>(context, id, op, data).await
+    }
     UserOperation::AddAdmin => do_websocket_operation::<AddAdmin>(context, id, op, data).await,
     UserOperation::GetUnreadRegistrationApplicationCount => {
       do_websocket_operation::<GetUnreadRegistrationApplicationCount>(context, id, op, data).await
@@ -54,6 +65,9 @@ pub async fn match_websocket_operation(
     UserOperation::BlockPerson => {
       do_websocket_operation::<BlockPerson>(context, id, op, data).await
     }
+    UserOperation::FollowPerson => {
+      do_websocket_operation::<FollowPerson>(context, id, op, data).await
+    }
     UserOperation::GetPersonMentions => {
       do_websocket_operation::<GetPersonMentions>(context, id, op, data).await
     }
@@ -63,6 +77,9 @@ pub async fn match_websocket_operation(
     UserOperation::MarkCommentReplyAsRead => {
       do_websocket_operation::<MarkCommentReplyAsRead>(context, id, op, data).await
     }
+    UserOperation::MarkPostNotificationAsRead => {
+      do_websocket_operation::<MarkPostNotificationAsRead>(context, id, op, data).await
+    }
     UserOperation::MarkAllAsRead => {
       do_websocket_operation::<MarkAllAsRead>(context, id, op, data).await
     }
@@ -93,6 +110,21 @@ pub async fn match_websocket_operation(
     UserOperation::VerifyEmail => {
       do_websocket_operation::<VerifyEmail>(context, id, op, data).await
     }
+    UserOperation::Unsubscribe => {
+      do_websocket_operation::<Unsubscribe>(context, id, op, data).await
+    }
+    UserOperation::GetPushPublicKey => {
+      do_websocket_operation::<GetPushPublicKey>(context, id, op, data).await
+    }
+    UserOperation::CreatePushSubscription => {
+      do_websocket_operation::<CreatePushSubscription>(context, id, op, data).await
+    }
+    UserOperation::DeletePushSubscription => {
+      do_websocket_operation::<DeletePushSubscription>(context, id, op, data).await
+    }
+    UserOperation::ListPushSubscriptions => {
+      do_websocket_operation::<ListPushSubscriptions>(context, id, op, data).await
+    }
 
     // Private Message ops
     UserOperation::MarkPrivateMessageAsRead => {
@@ -111,6 +143,30 @@ pub async fn match_websocket_operation(
     UserOperation::PurgeComment => {
       do_websocket_operation::<PurgeComment>(context, id, op, data).await
     }
+    UserOperation::ListSlurFilterEntries => {
+      do_websocket_operation::<ListSlurFilterEntries>(context, id, op, data).await
+    }
+    UserOperation::CreateSlurFilterEntry => {
+      do_websocket_operation::<CreateSlurFilterEntry>(context, id, op, data).await
+    }
+    UserOperation::EditSlurFilterEntry => {
+      do_websocket_operation::<EditSlurFilterEntry>(context, id, op, data).await
+    }
+    UserOperation::DeleteSlurFilterEntry => {
+      do_websocket_operation::<DeleteSlurFilterEntry>(context, id, op, data).await
+    }
+    UserOperation::ListFailedEmails => {
+      do_websocket_operation::<ListFailedEmails>(context, id, op, data).await
+    }
+    UserOperation::ListActivityDeliveries => {
+      do_websocket_operation::<ListActivityDeliveries>(context, id, op, data).await
+    }
+    UserOperation::ListRelays => do_websocket_operation::<ListRelays>(context, id, op, data).await,
+    UserOperation::AddRelay => do_websocket_operation::<AddRelay>(context, id, op, data).await,
+    UserOperation::RemoveRelay => {
+      do_websocket_operation::<RemoveRelay>(context, id, op, data).await
+    }
+    UserOperation::RotateKeys => do_websocket_operation::<RotateKeys>(context, id, op, data).await,
     UserOperation::Search => do_websocket_operation::<Search>(context, id, op, data).await,
     UserOperation::ResolveObject => {
       do_websocket_operation::<ResolveObject>(context, id, op, data).await
@@ -118,12 +174,66 @@ pub async fn match_websocket_operation(
     UserOperation::TransferCommunity => {
       do_websocket_operation::<TransferCommunity>(context, id, op, data).await
     }
+    UserOperation::BackfillCommunity => {
+      do_websocket_operation::<BackfillCommunity>(context, id, op, data).await
+    }
+    UserOperation::ListAutomodRules => {
+      do_websocket_operation::<ListAutomodRules>(context, id, op, data).await
+    }
+    UserOperation::CreateAutomodRule => {
+      do_websocket_operation::<CreateAutomodRule>(context, id, op, data).await
+    }
+    UserOperation::EditAutomodRule => {
+      do_websocket_operation::<EditAutomodRule>(context, id, op, data).await
+    }
+    UserOperation::DeleteAutomodRule => {
+      do_websocket_operation::<DeleteAutomodRule>(context, id, op, data).await
+    }
+    UserOperation::GetWikiPage => {
+      do_websocket_operation::<GetWikiPage>(context, id, op, data).await
+    }
+    UserOperation::ListWikiPages => {
+      do_websocket_operation::<ListWikiPages>(context, id, op, data).await
+    }
+    UserOperation::CreateWikiPage => {
+      do_websocket_operation::<CreateWikiPage>(context, id, op, data).await
+    }
+    UserOperation::EditWikiPage => {
+      do_websocket_operation::<EditWikiPage>(context, id, op, data).await
+    }
+    UserOperation::DeleteWikiPage => {
+      do_websocket_operation::<DeleteWikiPage>(context, id, op, data).await
+    }
+    UserOperation::ListWikiPageRevisions => {
+      do_websocket_operation::<ListWikiPageRevisions>(context, id, op, data).await
+    }
+    UserOperation::GetWikiPageDiff => {
+      do_websocket_operation::<GetWikiPageDiff>(context, id, op, data).await
+    }
+    UserOperation::RevertWikiPage => {
+      do_websocket_operation::<RevertWikiPage>(context, id, op, data).await
+    }
+    UserOperation::AddWikiContributor => {
+      do_websocket_operation::<AddWikiContributor>(context, id, op, data).await
+    }
+    UserOperation::ListWikiContributors => {
+      do_websocket_operation::<ListWikiContributors>(context, id, op, data).await
+    }
     UserOperation::LeaveAdmin => do_websocket_operation::<LeaveAdmin>(context, id, op, data).await,
 
     // Community ops
     UserOperation::FollowCommunity => {
       do_websocket_operation::<FollowCommunity>(context, id, op, data).await
     }
+    UserOperation::ListPendingFollows => {
+      do_websocket_operation::<ListPendingFollows>(context, id, op, data).await
+    }
+    UserOperation::ApproveFollow => {
+      do_websocket_operation::<ApproveFollow>(context, id, op, data).await
+    }
+    UserOperation::SubscribeToCommunityPosts => {
+      do_websocket_operation::<SubscribeToCommunityPosts>(context, id, op, data).await
+    }
     UserOperation::BlockCommunity => {
       do_websocket_operation::<BlockCommunity>(context, id, op, data).await
     }
@@ -137,6 +247,15 @@ pub async fn match_websocket_operation(
     // Post ops
     UserOperation::LockPost => do_websocket_operation::<LockPost>(context, id, op, data).await,
     UserOperation::StickyPost => do_websocket_operation::<StickyPost>(context, id, op, data).await,
+    UserOperation::ApprovePost => {
+      do_websocket_operation::<ApprovePost>(context, id, op, data).await
+    }
+    UserOperation::ApproveComment => {
+      do_websocket_operation::<ApproveComment>(context, id, op, data).await
+    }
+    UserOperation::ListModQueue => {
+      do_websocket_operation::<ListModQueue>(context, id, op, data).await
+    }
     UserOperation::CreatePostLike => {
       do_websocket_operation::<CreatePostLike>(context, id, op, data).await
     }
@@ -144,6 +263,10 @@ pub async fn match_websocket_operation(
       do_websocket_operation::<MarkPostAsRead>(context, id, op, data).await
     }
     UserOperation::SavePost => do_websocket_operation::<SavePost>(context, id, op, data).await,
+    UserOperation::SubscribeToThread => {
+      do_websocket_operation::<SubscribeToThread>(context, id, op, data).await
+    }
+    UserOperation::MuteThread => do_websocket_operation::<MuteThread>(context, id, op, data).await,
     UserOperation::CreatePostReport => {
       do_websocket_operation::<CreatePostReport>(context, id, op, data).await
     }
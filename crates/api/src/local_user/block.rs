@@ -5,7 +5,10 @@ use lemmy_api_common::{
   utils::{blocking, get_local_user_view_from_jwt},
 };
 use lemmy_db_schema::{
-  source::person_block::{PersonBlock, PersonBlockForm},
+  source::{
+    person_block::{PersonBlock, PersonBlockForm},
+    person_follower::{PersonFollower, PersonFollowerForm},
+  },
   traits::Blockable,
 };
 use lemmy_db_views_actor::structs::PersonViewSafe;
@@ -53,6 +56,17 @@ impl Perform for BlockPerson {
       blocking(context.pool(), block)
         .await?
         .map_err(|e| LemmyError::from_error_message(e, "person_block_already_exists"))?;
+
+      // The blocked person can't follow anymore
+      let follower_form = PersonFollowerForm {
+        person_id,
+        follower_id: target_id,
+        pending: false,
+      };
+      blocking(context.pool(), move |conn| {
+        PersonFollower::unfollow(conn, &follower_form)
+      })
+      .await??;
     } else {
       let unblock = move |conn: &'_ _| PersonBlock::unblock(conn, &person_block_form);
       blocking(context.pool(), unblock)
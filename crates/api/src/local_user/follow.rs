@@ -0,0 +1,94 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  person::{FollowPerson, FollowPersonResponse},
+  utils::{blocking, check_person_block, get_local_user_view_from_jwt},
+};
+use lemmy_apub::protocol::activities::following::{follow::Follow, undo_follow::UndoFollow};
+use lemmy_db_schema::{
+  source::{
+    person::Person,
+    person_follower::{PersonFollower, PersonFollowerForm},
+  },
+  traits::Crud,
+};
+use lemmy_db_views_actor::structs::PersonViewSafe;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for FollowPerson {
+  type Response = FollowPersonResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<FollowPersonResponse, LemmyError> {
+    let data: &FollowPerson = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let target_id = data.person_id;
+    let person_id = local_user_view.person.id;
+
+    if target_id == person_id {
+      return Err(LemmyError::from_message("cant_follow_yourself"));
+    }
+
+    let target = blocking(context.pool(), move |conn| Person::read(conn, target_id))
+      .await?
+      .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_person"))?;
+
+    let person_follower_form = PersonFollowerForm {
+      person_id: target_id,
+      follower_id: person_id,
+      pending: false,
+    };
+
+    if data.follow {
+      check_person_block(person_id, target_id, context.pool()).await?;
+      if target.local {
+        let follow = move |conn: &'_ _| PersonFollower::follow(conn, &person_follower_form);
+        blocking(context.pool(), follow)
+          .await?
+          .map_err(|e| LemmyError::from_error_message(e, "person_follower_already_exists"))?;
+      } else {
+        // The follow stays pending until the remote person accepts it
+        Follow::send_to_person(
+          &local_user_view.person.clone().into(),
+          &target.into(),
+          context,
+        )
+        .await?;
+      }
+    } else {
+      if !target.local {
+        UndoFollow::send_to_person(
+          &local_user_view.person.clone().into(),
+          &target.into(),
+          context,
+        )
+        .await?;
+      }
+      let unfollow = move |conn: &'_ _| PersonFollower::unfollow(conn, &person_follower_form);
+      blocking(context.pool(), unfollow)
+        .await?
+        .map_err(|e| LemmyError::from_error_message(e, "person_follower_already_exists"))?;
+    }
+
+    let (person_view, follow) = blocking(context.pool(), move |conn| {
+      let person_view = PersonViewSafe::read(conn, target_id)?;
+      let follow = PersonFollower::read(conn, target_id, person_id).ok();
+      Ok::<_, diesel::result::Error>((person_view, follow))
+    })
+    .await??;
+
+    Ok(FollowPersonResponse {
+      person_view,
+      followed: follow.is_some(),
+      pending: follow.map(|f| f.pending).unwrap_or(false),
+    })
+  }
+}
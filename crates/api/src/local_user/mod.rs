@@ -3,11 +3,14 @@ mod ban_person;
 mod block;
 mod change_password;
 mod change_password_after_reset;
+mod follow;
 mod get_captcha;
 mod list_banned;
 mod login;
 mod notifications;
+mod push_subscription;
 mod report_count;
 mod reset_password;
 mod save_settings;
+mod unsubscribe;
 mod verify_email;
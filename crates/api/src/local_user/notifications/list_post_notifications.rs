@@ -0,0 +1,44 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  person::{GetPostNotifications, GetPostNotificationsResponse},
+  utils::{blocking, get_local_user_view_from_jwt},
+};
+use lemmy_db_views_actor::post_notification_view::PostNotificationQuery;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for GetPostNotifications {
+  type Response = GetPostNotificationsResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<GetPostNotificationsResponse, LemmyError> {
+    let data: &GetPostNotifications = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let page = data.page;
+    let limit = data.limit;
+    let unread_only = data.unread_only;
+    let person_id = local_user_view.person.id;
+
+    let post_notifications = blocking(context.pool(), move |conn| {
+      PostNotificationQuery::builder()
+        .conn(conn)
+        .recipient_id(person_id)
+        .unread_only(unread_only)
+        .page(page)
+        .limit(limit)
+        .build()
+        .list()
+    })
+    .await??;
+
+    Ok(GetPostNotificationsResponse { post_notifications })
+  }
+}
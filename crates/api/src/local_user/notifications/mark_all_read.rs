@@ -7,6 +7,7 @@ use lemmy_api_common::{
 use lemmy_db_schema::source::{
   comment_reply::CommentReply,
   person_mention::PersonMention,
+  post_notification::PostNotification,
   private_message::PrivateMessage,
 };
 use lemmy_utils::{error::LemmyError, ConnectionId};
@@ -41,6 +42,13 @@ impl Perform for MarkAllAsRead {
     .await?
     .map_err(|e| LemmyError::from_error_message(e, "couldnt_update_comment"))?;
 
+    // Mark all post notifications as read
+    blocking(context.pool(), move |conn| {
+      PostNotification::mark_all_as_read(conn, person_id)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_update_post"))?;
+
     // Mark all private_messages as read
     blocking(context.pool(), move |conn| {
       PrivateMessage::mark_all_as_read(conn, person_id)
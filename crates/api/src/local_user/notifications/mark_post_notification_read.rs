@@ -0,0 +1,52 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  person::{MarkPostNotificationAsRead, PostNotificationResponse},
+  utils::{blocking, get_local_user_view_from_jwt},
+};
+use lemmy_db_schema::{source::post_notification::PostNotification, traits::Crud};
+use lemmy_db_views_actor::structs::PostNotificationView;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for MarkPostNotificationAsRead {
+  type Response = PostNotificationResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<PostNotificationResponse, LemmyError> {
+    let data = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let post_notification_id = data.post_notification_id;
+    let read_post_notification = blocking(context.pool(), move |conn| {
+      PostNotification::read(conn, post_notification_id)
+    })
+    .await??;
+
+    if local_user_view.person.id != read_post_notification.recipient_id {
+      return Err(LemmyError::from_message("couldnt_update_post"));
+    }
+
+    let read = data.read;
+    let update_notification =
+      move |conn: &'_ _| PostNotification::update_read(conn, post_notification_id, read);
+    blocking(context.pool(), update_notification)
+      .await?
+      .map_err(|e| LemmyError::from_error_message(e, "couldnt_update_post"))?;
+
+    let post_notification_view = blocking(context.pool(), move |conn| {
+      PostNotificationView::read(conn, post_notification_id)
+    })
+    .await??;
+
+    Ok(PostNotificationResponse {
+      post_notification_view,
+    })
+  }
+}
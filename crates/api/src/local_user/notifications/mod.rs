@@ -1,6 +1,8 @@
 mod list_mentions;
+mod list_post_notifications;
 mod list_replies;
 mod mark_all_read;
 mod mark_mention_read;
+mod mark_post_notification_read;
 mod mark_reply_read;
 mod unread_count;
@@ -5,7 +5,7 @@ use lemmy_api_common::{
   utils::{blocking, get_local_user_view_from_jwt},
 };
 use lemmy_db_views::structs::PrivateMessageView;
-use lemmy_db_views_actor::structs::{CommentReplyView, PersonMentionView};
+use lemmy_db_views_actor::structs::{CommentReplyView, PersonMentionView, PostNotificationView};
 use lemmy_utils::{error::LemmyError, ConnectionId};
 use lemmy_websocket::LemmyContext;
 
@@ -40,10 +40,16 @@ impl Perform for GetUnreadCount {
     })
     .await??;
 
+    let post_notifications = blocking(context.pool(), move |conn| {
+      PostNotificationView::get_unread_post_notifications(conn, person_id)
+    })
+    .await??;
+
     let res = Self::Response {
       replies,
       mentions,
       private_messages,
+      post_notifications,
     };
 
     Ok(res)
@@ -0,0 +1,65 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  person::{CreatePushSubscription, PushSubscriptionsResponse},
+  utils::{blocking, get_local_user_view_from_jwt},
+};
+use lemmy_db_schema::source::push_subscription::{PushSubscription, PushSubscriptionForm};
+use lemmy_utils::{
+  error::LemmyError,
+  web_push::{validate_push_endpoint, validate_subscription_keys},
+  ConnectionId,
+};
+use lemmy_websocket::LemmyContext;
+
+/// Browsers and apps of a single user, more are most likely stale
+const MAX_PUSH_SUBSCRIPTIONS: usize = 20;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for CreatePushSubscription {
+  type Response = PushSubscriptionsResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<PushSubscriptionsResponse, LemmyError> {
+    let data: &CreatePushSubscription = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    validate_push_endpoint(&data.endpoint)?;
+    validate_subscription_keys(&data.keys.p256dh, &data.keys.auth)?;
+
+    let local_user_id = local_user_view.local_user.id;
+    let existing = blocking(context.pool(), move |conn| {
+      PushSubscription::list_for_local_user(conn, local_user_id)
+    })
+    .await??;
+    if existing.len() >= MAX_PUSH_SUBSCRIPTIONS
+      && !existing.iter().any(|s| s.endpoint == data.endpoint)
+    {
+      return Err(LemmyError::from_message("too_many_push_subscriptions"));
+    }
+
+    let form = PushSubscriptionForm {
+      local_user_id,
+      endpoint: data.endpoint.to_owned(),
+      p256dh: data.keys.p256dh.to_owned(),
+      auth: data.keys.auth.to_owned(),
+      notify_mentions: data.notify_mentions.unwrap_or(true),
+      notify_replies: data.notify_replies.unwrap_or(true),
+      notify_private_messages: data.notify_private_messages.unwrap_or(true),
+      notify_new_posts: data.notify_new_posts.unwrap_or(true),
+    };
+    let push_subscriptions = blocking(context.pool(), move |conn| {
+      PushSubscription::upsert(conn, &form)?;
+      PushSubscription::list_for_local_user(conn, local_user_id)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_create_push_subscription"))?;
+
+    Ok(PushSubscriptionsResponse { push_subscriptions })
+  }
+}
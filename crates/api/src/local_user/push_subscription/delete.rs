@@ -0,0 +1,36 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  person::{DeletePushSubscription, PushSubscriptionsResponse},
+  utils::{blocking, get_local_user_view_from_jwt},
+};
+use lemmy_db_schema::source::push_subscription::PushSubscription;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for DeletePushSubscription {
+  type Response = PushSubscriptionsResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<PushSubscriptionsResponse, LemmyError> {
+    let data: &DeletePushSubscription = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let local_user_id = local_user_view.local_user.id;
+    let endpoint = data.endpoint.to_owned();
+    let push_subscriptions = blocking(context.pool(), move |conn| {
+      PushSubscription::delete_for_local_user(conn, local_user_id, &endpoint)?;
+      PushSubscription::list_for_local_user(conn, local_user_id)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_delete_push_subscription"))?;
+
+    Ok(PushSubscriptionsResponse { push_subscriptions })
+  }
+}
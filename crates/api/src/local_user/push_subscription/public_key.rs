@@ -0,0 +1,21 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::person::{GetPushPublicKey, GetPushPublicKeyResponse};
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for GetPushPublicKey {
+  type Response = GetPushPublicKeyResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<GetPushPublicKeyResponse, LemmyError> {
+    Ok(GetPushPublicKeyResponse {
+      public_key: context.secret().vapid_public_key.to_owned(),
+    })
+  }
+}
@@ -7,6 +7,7 @@ use lemmy_api_common::{
 use lemmy_db_schema::{
   source::{
     local_user::{LocalUser, LocalUserForm},
+    local_user_keyword_filter::{LocalUserKeywordFilter, LocalUserKeywordFilterForm},
     person::{Person, PersonForm},
     site::Site,
   },
@@ -83,6 +84,21 @@ impl Perform for SaveUserSettings {
     }
 
     let local_user_id = local_user_view.local_user.id;
+    let keyword_filter_forms = data.keyword_filters.as_ref().map(|filters| {
+      filters
+        .iter()
+        .map(|f| LocalUserKeywordFilterForm {
+          local_user_id,
+          community_id: f.community_id,
+          keyword: f.keyword.trim().to_string(),
+          regex: f.regex,
+        })
+        .collect::<Vec<_>>()
+    });
+    if let Some(forms) = &keyword_filter_forms {
+      check_keyword_filters(forms, context).await?;
+    }
+
     let person_id = local_user_view.person.id;
     let default_listing_type = data.default_listing_type;
     let default_sort_type = data.default_sort_type;
@@ -110,6 +126,7 @@ impl Perform for SaveUserSettings {
       matrix_user_id,
       bot_account,
       ban_expires: None,
+      instance_id: None,
     };
 
     blocking(context.pool(), move |conn| {
@@ -135,6 +152,8 @@ impl Perform for SaveUserSettings {
       send_notifications_to_email: data.send_notifications_to_email,
       email_verified: None,
       accepted_application: None,
+      email_digest_frequency: data.email_digest_frequency,
+      last_email_digest: None,
     };
 
     let local_user_res = blocking(context.pool(), move |conn| {
@@ -156,6 +175,14 @@ impl Perform for SaveUserSettings {
       }
     };
 
+    if let Some(forms) = keyword_filter_forms {
+      blocking(context.pool(), move |conn| {
+        LocalUserKeywordFilter::replace(conn, local_user_id, &forms)
+      })
+      .await?
+      .map_err(|e| LemmyError::from_error_message(e, "couldnt_update_keyword_filters"))?;
+    }
+
     // Return the jwt
     Ok(LoginResponse {
       jwt: Some(
@@ -171,3 +198,36 @@ impl Perform for SaveUserSettings {
     })
   }
 }
+
+const MAX_KEYWORD_FILTERS: usize = 50;
+const MAX_KEYWORD_FILTER_LENGTH: usize = 200;
+
+async fn check_keyword_filters(
+  forms: &[LocalUserKeywordFilterForm],
+  context: &Data<LemmyContext>,
+) -> Result<(), LemmyError> {
+  if forms.len() > MAX_KEYWORD_FILTERS {
+    return Err(LemmyError::from_message("too_many_keyword_filters"));
+  }
+  if forms
+    .iter()
+    .any(|f| f.keyword.is_empty() || f.keyword.chars().count() > MAX_KEYWORD_FILTER_LENGTH)
+  {
+    return Err(LemmyError::from_message("invalid_keyword_filter"));
+  }
+  let patterns = forms
+    .iter()
+    .filter(|f| f.regex)
+    .map(|f| f.keyword.clone())
+    .collect::<Vec<_>>();
+  let all_valid = blocking(context.pool(), move |conn| {
+    patterns
+      .iter()
+      .all(|p| LocalUserKeywordFilter::is_valid_pattern(conn, p))
+  })
+  .await?;
+  if !all_valid {
+    return Err(LemmyError::from_message("invalid_regex"));
+  }
+  Ok(())
+}
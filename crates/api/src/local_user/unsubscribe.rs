@@ -0,0 +1,51 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  person::{Unsubscribe, UnsubscribeResponse},
+  utils::blocking,
+};
+use lemmy_db_schema::{
+  newtypes::LocalUserId,
+  source::local_user::{LocalUser, LocalUserForm},
+  traits::Crud,
+};
+use lemmy_utils::{claims::UnsubscribeClaims, error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for Unsubscribe {
+  type Response = UnsubscribeResponse;
+
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<Self::Response, LemmyError> {
+    let claims = UnsubscribeClaims::decode(&self.token, &context.secret().jwt_secret)
+      .map_err(|e| e.with_message("invalid_unsubscribe_token"))?
+      .claims;
+    let local_user_id = LocalUserId(claims.sub);
+
+    // Changing the password revokes all unsubscribe links which were sent before
+    let local_user = blocking(context.pool(), move |conn| {
+      LocalUser::read(conn, local_user_id)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "invalid_unsubscribe_token"))?;
+    if local_user.validator_time.timestamp() > claims.iat {
+      return Err(LemmyError::from_message("invalid_unsubscribe_token"));
+    }
+
+    let form = LocalUserForm {
+      send_notifications_to_email: Some(false),
+      ..LocalUserForm::default()
+    };
+    blocking(context.pool(), move |conn| {
+      LocalUser::update(conn, local_user_id, &form)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_update_user"))?;
+
+    Ok(UnsubscribeResponse {})
+  }
+}
@@ -0,0 +1,89 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  post::{ApprovePost, PostResponse},
+  utils::{blocking, get_local_user_view_from_jwt, is_mod_or_admin},
+};
+use lemmy_apub::protocol::activities::create_or_update::post::CreateOrUpdatePost;
+use lemmy_db_schema::{
+  source::{
+    moderator::{ModApprovePost, ModApprovePostForm},
+    post::Post,
+  },
+  traits::Crud,
+};
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::{
+  send::{send_post_notifs, send_post_ws_message},
+  LemmyContext,
+  UserOperation,
+};
+
+#[async_trait::async_trait(?Send)]
+impl Perform for ApprovePost {
+  type Response = PostResponse;
+
+  #[tracing::instrument(skip(context, websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    websocket_id: Option<ConnectionId>,
+  ) -> Result<PostResponse, LemmyError> {
+    let data: &ApprovePost = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let post_id = data.post_id;
+    let orig_post = blocking(context.pool(), move |conn| Post::read(conn, post_id)).await??;
+
+    // Verify that only the mods can approve
+    is_mod_or_admin(
+      context.pool(),
+      local_user_view.person.id,
+      orig_post.community_id,
+    )
+    .await?;
+
+    if !orig_post.pending {
+      return Err(LemmyError::from_message("not_held_for_approval"));
+    }
+
+    // Rejected posts are removed
+    let approve = data.approve;
+    let updated_post = blocking(context.pool(), move |conn| {
+      if !approve {
+        Post::update_removed(conn, post_id, true)?;
+      }
+      Post::update_pending(conn, post_id, false)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_update_post"))?;
+
+    // Mod tables
+    let form = ModApprovePostForm {
+      mod_person_id: local_user_view.person.id,
+      post_id: data.post_id,
+      reason: data.reason.to_owned(),
+      approved: Some(approve),
+    };
+    blocking(context.pool(), move |conn| {
+      ModApprovePost::create(conn, &form)
+    })
+    .await??;
+
+    // The post wasn't federated yet
+    if approve {
+      send_post_notifs(updated_post.clone(), context);
+      CreateOrUpdatePost::send_approved(updated_post.into(), context).await?;
+    }
+
+    send_post_ws_message(
+      data.post_id,
+      UserOperation::ApprovePost,
+      websocket_id,
+      Some(local_user_view.person.id),
+      context,
+    )
+    .await
+  }
+}
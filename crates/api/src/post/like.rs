@@ -6,6 +6,7 @@ use lemmy_api_common::{
     blocking,
     check_community_ban,
     check_community_deleted_or_removed,
+    check_community_visible,
     check_downvotes_enabled,
     get_local_user_view_from_jwt,
     mark_post_as_read,
@@ -50,6 +51,12 @@ impl Perform for CreatePostLike {
       .into();
 
     check_community_ban(local_user_view.person.id, post.community_id, context.pool()).await?;
+    check_community_visible(
+      post.community_id,
+      Some(&local_user_view.person),
+      context.pool(),
+    )
+    .await?;
     check_community_deleted_or_removed(post.community_id, context.pool()).await?;
 
     let like_form = PostLikeForm {
@@ -2,7 +2,13 @@ use crate::Perform;
 use actix_web::web::Data;
 use lemmy_api_common::{
   post::{MarkPostAsRead, PostResponse},
-  utils::{blocking, get_local_user_view_from_jwt, mark_post_as_read, mark_post_as_unread},
+  utils::{
+    blocking,
+    check_community_visible,
+    get_local_user_view_from_jwt,
+    mark_post_as_read,
+    mark_post_as_unread,
+  },
 };
 use lemmy_db_views::structs::PostView;
 use lemmy_utils::{error::LemmyError, ConnectionId};
@@ -37,6 +43,12 @@ impl Perform for MarkPostAsRead {
       PostView::read(conn, post_id, Some(person_id))
     })
     .await??;
+    check_community_visible(
+      post_view.community.id,
+      Some(&local_user_view.person),
+      context.pool(),
+    )
+    .await?;
 
     let res = Self::Response { post_view };
 
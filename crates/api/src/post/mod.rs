@@ -1,6 +1,8 @@
+mod approve;
 mod get_link_metadata;
 mod like;
 mod lock;
 mod mark_read;
 mod save;
 mod sticky;
+mod thread;
@@ -2,7 +2,7 @@ use crate::Perform;
 use actix_web::web::Data;
 use lemmy_api_common::{
   post::{PostResponse, SavePost},
-  utils::{blocking, get_local_user_view_from_jwt, mark_post_as_read},
+  utils::{blocking, check_community_visible, get_local_user_view_from_jwt, mark_post_as_read},
 };
 use lemmy_db_schema::{
   source::post::{PostSaved, PostSavedForm},
@@ -49,6 +49,12 @@ impl Perform for SavePost {
       PostView::read(conn, post_id, Some(person_id))
     })
     .await??;
+    check_community_visible(
+      post_view.community.id,
+      Some(&local_user_view.person),
+      context.pool(),
+    )
+    .await?;
 
     // Mark the post as read
     mark_post_as_read(person_id, post_id, context.pool()).await?;
@@ -11,11 +11,16 @@ use lemmy_api_common::{
   },
 };
 use lemmy_apub::{
-  objects::post::ApubPost,
-  protocol::activities::{create_or_update::post::CreateOrUpdatePost, CreateOrUpdateType},
+  objects::{community::ApubCommunity, post::ApubPost},
+  protocol::activities::{
+    community::{add_featured::AddFeatured, remove_featured::RemoveFeatured},
+    create_or_update::post::CreateOrUpdatePost,
+    CreateOrUpdateType,
+  },
 };
 use lemmy_db_schema::{
   source::{
+    community::Community,
     moderator::{ModStickyPost, ModStickyPostForm},
     post::Post,
   },
@@ -77,15 +82,27 @@ impl Perform for StickyPost {
     })
     .await??;
 
-    // Apub updates
-    // TODO stickied should pry work like locked for ease of use
+    // Apub updates. The post update is still sent for older versions, which don't know about the
+    // featured collection.
+    let actor = local_user_view.person.clone().into();
     CreateOrUpdatePost::send(
-      updated_post,
-      &local_user_view.person.clone().into(),
+      updated_post.clone(),
+      &actor,
       CreateOrUpdateType::Update,
       context,
     )
     .await?;
+    let community_id = updated_post.community_id;
+    let community: ApubCommunity = blocking(context.pool(), move |conn| {
+      Community::read(conn, community_id)
+    })
+    .await??
+    .into();
+    if stickied {
+      AddFeatured::send(&community, updated_post, &actor, context).await?;
+    } else {
+      RemoveFeatured::send(&community, updated_post, &actor, context).await?;
+    }
 
     send_post_ws_message(
       data.post_id,
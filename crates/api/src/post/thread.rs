@@ -0,0 +1,163 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  post::{MuteThread, SubscribeToThread, ThreadSubscriptionResponse},
+  utils::{blocking, check_community_visible, get_local_user_view_from_jwt, get_post},
+};
+use lemmy_db_schema::{
+  newtypes::{CommentId, PersonId, PostId},
+  source::{
+    comment::Comment,
+    person::Person,
+    thread_subscription::{ThreadMute, ThreadMuteForm, ThreadSubscription, ThreadSubscriptionForm},
+  },
+  traits::Crud,
+  utils::DbPool,
+};
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for SubscribeToThread {
+  type Response = ThreadSubscriptionResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<ThreadSubscriptionResponse, LemmyError> {
+    let data: &SubscribeToThread = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let (post_id, comment_id) = resolve_thread(
+      data.post_id,
+      data.comment_id,
+      &local_user_view.person,
+      context.pool(),
+    )
+    .await?;
+    let form = ThreadSubscriptionForm {
+      person_id: local_user_view.person.id,
+      post_id,
+      comment_id,
+    };
+
+    if data.subscribe {
+      let subscribe = move |conn: &'_ _| ThreadSubscription::subscribe(conn, &form);
+      blocking(context.pool(), subscribe)
+        .await?
+        .map_err(|e| LemmyError::from_error_message(e, "couldnt_subscribe_to_thread"))?;
+    } else {
+      let unsubscribe = move |conn: &'_ _| {
+        ThreadSubscription::unsubscribe(conn, form.person_id, form.post_id, form.comment_id)
+      };
+      blocking(context.pool(), unsubscribe)
+        .await?
+        .map_err(|e| LemmyError::from_error_message(e, "couldnt_subscribe_to_thread"))?;
+    }
+
+    thread_subscription_response(
+      local_user_view.person.id,
+      post_id,
+      comment_id,
+      context.pool(),
+    )
+    .await
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Perform for MuteThread {
+  type Response = ThreadSubscriptionResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<ThreadSubscriptionResponse, LemmyError> {
+    let data: &MuteThread = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let (post_id, comment_id) = resolve_thread(
+      data.post_id,
+      data.comment_id,
+      &local_user_view.person,
+      context.pool(),
+    )
+    .await?;
+    let form = ThreadMuteForm {
+      person_id: local_user_view.person.id,
+      post_id,
+      comment_id,
+    };
+
+    if data.mute {
+      let mute = move |conn: &'_ _| ThreadMute::mute(conn, &form);
+      blocking(context.pool(), mute)
+        .await?
+        .map_err(|e| LemmyError::from_error_message(e, "couldnt_mute_thread"))?;
+    } else {
+      let unmute =
+        move |conn: &'_ _| ThreadMute::unmute(conn, form.person_id, form.post_id, form.comment_id);
+      blocking(context.pool(), unmute)
+        .await?
+        .map_err(|e| LemmyError::from_error_message(e, "couldnt_mute_thread"))?;
+    }
+
+    thread_subscription_response(
+      local_user_view.person.id,
+      post_id,
+      comment_id,
+      context.pool(),
+    )
+    .await
+  }
+}
+
+/// Returns the post and optional comment which make up the thread. For comment threads the post
+/// is taken from the comment. Fails if the person can't see the community of the thread.
+async fn resolve_thread(
+  post_id: Option<PostId>,
+  comment_id: Option<CommentId>,
+  person: &Person,
+  pool: &DbPool,
+) -> Result<(PostId, Option<CommentId>), LemmyError> {
+  let (post_id, comment_id) = match (post_id, comment_id) {
+    (Some(post_id), None) => (post_id, None),
+    (None, Some(comment_id)) => {
+      let comment = blocking(pool, move |conn| Comment::read(conn, comment_id))
+        .await?
+        .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_comment"))?;
+      (comment.post_id, Some(comment.id))
+    }
+    _ => return Err(LemmyError::from_message("invalid_thread")),
+  };
+  let post = get_post(post_id, pool).await?;
+  check_community_visible(post.community_id, Some(person), pool).await?;
+  Ok((post.id, comment_id))
+}
+
+async fn thread_subscription_response(
+  person_id: PersonId,
+  post_id: PostId,
+  comment_id: Option<CommentId>,
+  pool: &DbPool,
+) -> Result<ThreadSubscriptionResponse, LemmyError> {
+  let (subscribed, muted) = blocking(pool, move |conn| {
+    let subscription = ThreadSubscription::read(conn, person_id, post_id, comment_id)?;
+    let mute = ThreadMute::read(conn, person_id, post_id, comment_id)?;
+    Ok::<_, diesel::result::Error>((subscription.is_some(), mute.is_some()))
+  })
+  .await??;
+
+  Ok(ThreadSubscriptionResponse {
+    post_id,
+    comment_id,
+    subscribed,
+    muted,
+  })
+}
@@ -0,0 +1,41 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  site::{ListActivityDeliveries, ListActivityDeliveriesResponse},
+  utils::{blocking, get_local_user_view_from_jwt, is_admin},
+};
+use lemmy_db_schema::source::activity_delivery::ActivityDelivery;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for ListActivityDeliveries {
+  type Response = ListActivityDeliveriesResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<ListActivityDeliveriesResponse, LemmyError> {
+    let data: &ListActivityDeliveries = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+    is_admin(&local_user_view)?;
+
+    let failed_only = data.failed_only.unwrap_or(false);
+    let page = data.page;
+    let limit = data.limit;
+    let (deliveries, (pending_count, failed_count)) = blocking(context.pool(), move |conn| {
+      let deliveries = ActivityDelivery::list(conn, failed_only, page, limit)?;
+      Ok((deliveries, ActivityDelivery::count(conn)?)) as Result<_, LemmyError>
+    })
+    .await??;
+
+    Ok(ListActivityDeliveriesResponse {
+      pending_count,
+      failed_count,
+      deliveries,
+    })
+  }
+}
@@ -0,0 +1,35 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  site::{ListFailedEmails, ListFailedEmailsResponse},
+  utils::{blocking, get_local_user_view_from_jwt, is_admin},
+};
+use lemmy_db_schema::source::email_queue::EmailQueue;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for ListFailedEmails {
+  type Response = ListFailedEmailsResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<ListFailedEmailsResponse, LemmyError> {
+    let data: &ListFailedEmails = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+    is_admin(&local_user_view)?;
+
+    let page = data.page;
+    let limit = data.limit;
+    let emails = blocking(context.pool(), move |conn| {
+      EmailQueue::list_failed(conn, page, limit)
+    })
+    .await??;
+
+    Ok(ListFailedEmailsResponse { emails })
+  }
+}
@@ -57,7 +57,8 @@ impl Perform for LeaveAdmin {
     let site_view = blocking(context.pool(), SiteView::read_local).await??;
     let admins = blocking(context.pool(), PersonViewSafe::admins).await??;
 
-    let federated_instances = build_federated_instances(context.pool(), context.settings()).await?;
+    let federated_instances =
+      build_federated_instances(context.pool(), context.settings(), false).await?;
 
     Ok(GetSiteResponse {
       site_view: Some(site_view),
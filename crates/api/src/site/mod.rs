@@ -1,6 +1,11 @@
+mod activity_deliveries;
+mod failed_emails;
 mod leave_admin;
 mod mod_log;
 mod purge;
 mod registration_applications;
+mod relay;
 mod resolve_object;
+mod rotate_keys;
 mod search;
+mod slur_filter;
@@ -11,6 +11,8 @@ use lemmy_db_views_moderator::structs::{
   AdminPurgePostView,
   ModAddCommunityView,
   ModAddView,
+  ModApproveCommentView,
+  ModApprovePostView,
   ModBanFromCommunityView,
   ModBanView,
   ModHideCommunityView,
@@ -86,6 +88,16 @@ impl Perform for GetModlog {
     })
     .await??;
 
+    let approved_posts = blocking(context.pool(), move |conn| {
+      ModApprovePostView::list(conn, community_id, mod_person_id, page, limit)
+    })
+    .await??;
+
+    let approved_comments = blocking(context.pool(), move |conn| {
+      ModApproveCommentView::list(conn, community_id, mod_person_id, page, limit)
+    })
+    .await??;
+
     // These arrays are only for the full modlog, when a community isn't given
     let (
       removed_communities,
@@ -129,6 +141,8 @@ impl Perform for GetModlog {
       admin_purged_posts,
       admin_purged_comments,
       hidden_communities,
+      approved_posts,
+      approved_comments,
     })
   }
 }
@@ -66,7 +66,12 @@ impl Perform for ApproveRegistrationApplication {
       .await??;
 
       if approved_local_user_view.local_user.email.is_some() {
-        send_application_approved_email(&approved_local_user_view, context.settings())?;
+        send_application_approved_email(
+          &approved_local_user_view,
+          context.pool(),
+          context.settings(),
+        )
+        .await?;
       }
     }
 
@@ -0,0 +1,38 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  site::{AddRelay, RelaysResponse},
+  utils::{blocking, get_local_user_view_from_jwt, is_admin},
+};
+use lemmy_apub::protocol::activities::relay::follow::RelayFollow;
+use lemmy_db_schema::source::relay::Relay;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+use url::Url;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for AddRelay {
+  type Response = RelaysResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<RelaysResponse, LemmyError> {
+    let data: &AddRelay = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+    is_admin(&local_user_view)?;
+    if !context.settings().federation.enabled {
+      return Err(LemmyError::from_message("federation_disabled"));
+    }
+
+    let relay_url = Url::parse(&data.relay_url)
+      .map_err(|e| LemmyError::from_error_message(e, "invalid_relay_url"))?;
+    RelayFollow::send(relay_url, context).await?;
+
+    let relays = blocking(context.pool(), Relay::list).await??;
+    Ok(RelaysResponse { relays })
+  }
+}
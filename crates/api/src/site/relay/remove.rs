@@ -0,0 +1,36 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  site::{RelaysResponse, RemoveRelay},
+  utils::{blocking, get_local_user_view_from_jwt, is_admin},
+};
+use lemmy_apub::protocol::activities::relay::undo_follow::UndoRelayFollow;
+use lemmy_db_schema::source::relay::Relay;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for RemoveRelay {
+  type Response = RelaysResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<RelaysResponse, LemmyError> {
+    let data: &RemoveRelay = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+    is_admin(&local_user_view)?;
+
+    let relay_id = data.id;
+    let relay = blocking(context.pool(), move |conn| Relay::read(conn, relay_id))
+      .await?
+      .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_relay"))?;
+    UndoRelayFollow::send(&relay, context).await?;
+
+    let relays = blocking(context.pool(), Relay::list).await??;
+    Ok(RelaysResponse { relays })
+  }
+}
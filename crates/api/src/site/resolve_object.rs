@@ -3,10 +3,15 @@ use actix_web::web::Data;
 use diesel::NotFound;
 use lemmy_api_common::{
   site::{ResolveObject, ResolveObjectResponse},
-  utils::{blocking, check_private_instance, get_local_user_view_from_jwt_opt},
+  utils::{
+    blocking,
+    check_community_visible,
+    check_private_instance,
+    get_local_user_view_from_jwt_opt,
+  },
 };
 use lemmy_apub::fetcher::search::{search_by_apub_id, SearchableObjects};
-use lemmy_db_schema::{newtypes::PersonId, utils::DbPool};
+use lemmy_db_schema::{source::person::Person, utils::DbPool};
 use lemmy_db_views::structs::{CommentView, PostView};
 use lemmy_db_views_actor::structs::{CommunityView, PersonViewSafe};
 use lemmy_utils::{error::LemmyError, ConnectionId};
@@ -30,7 +35,7 @@ impl Perform for ResolveObject {
     let res = search_by_apub_id(&self.q, context)
       .await
       .map_err(|e| e.with_message("couldnt_find_object"))?;
-    convert_response(res, local_user_view.map(|l| l.person.id), context.pool())
+    convert_response(res, local_user_view.map(|l| l.person), context.pool())
       .await
       .map_err(|e| e.with_message("couldnt_find_object"))
   }
@@ -38,9 +43,10 @@ impl Perform for ResolveObject {
 
 async fn convert_response(
   object: SearchableObjects,
-  user_id: Option<PersonId>,
+  person: Option<Person>,
   pool: &DbPool,
 ) -> Result<ResolveObjectResponse, LemmyError> {
+  let user_id = person.as_ref().map(|p| p.id);
   let removed_or_deleted;
   let mut res = ResolveObjectResponse {
     comment: None,
@@ -61,11 +67,15 @@ async fn convert_response(
     }
     Post(p) => {
       removed_or_deleted = p.deleted || p.removed;
+      check_community_visible(p.community_id, person.as_ref(), pool).await?;
       res.post = Some(blocking(pool, move |conn| PostView::read(conn, p.id, user_id)).await??)
     }
     Comment(c) => {
       removed_or_deleted = c.deleted || c.removed;
-      res.comment = Some(blocking(pool, move |conn| CommentView::read(conn, c.id, user_id)).await??)
+      let comment_view =
+        blocking(pool, move |conn| CommentView::read(conn, c.id, user_id)).await??;
+      check_community_visible(comment_view.community.id, person.as_ref(), pool).await?;
+      res.comment = Some(comment_view)
     }
   };
   // if the object was deleted from database, dont return it
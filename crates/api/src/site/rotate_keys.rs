@@ -0,0 +1,146 @@
+use crate::Perform;
+use activitypub_federation::core::signatures::generate_actor_keypair;
+use actix_web::{rt::spawn, web::Data};
+use lemmy_api_common::{
+  site::{RotateKeys, RotateKeysResponse},
+  utils::{blocking, get_local_user_view_from_jwt, is_admin},
+};
+use lemmy_apub::{
+  fetcher::signed_fetch::clear_site_key_cache,
+  protocol::activities::update_actor::UpdateActor,
+};
+use lemmy_db_schema::{
+  source::{community::Community, person::Person, site::Site},
+  traits::Crud,
+};
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+use tracing::{error, info};
+
+#[async_trait::async_trait(?Send)]
+impl Perform for RotateKeys {
+  type Response = RotateKeysResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<RotateKeysResponse, LemmyError> {
+    let data: &RotateKeys = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+    is_admin(&local_user_view)?;
+
+    let person_id = data.person_id;
+    let community_id = data.community_id;
+    if person_id.is_none() && community_id.is_none() {
+      let (persons, communities) = blocking(context.pool(), move |conn| {
+        Ok((Person::list_local(conn)?, Community::list_local(conn)?))
+          as Result<(Vec<Person>, Vec<Community>), LemmyError>
+      })
+      .await??;
+      // Including the instance actor
+      let rotated = (persons.len() + communities.len() + 1) as i64;
+      let context = context.get_ref().clone();
+      spawn(async move {
+        rotate_all_keys(persons, communities, &context).await;
+      });
+      return Ok(RotateKeysResponse { rotated });
+    }
+
+    let (persons, communities) = blocking(context.pool(), move |conn| {
+      let persons = person_id
+        .map(|id| Person::read(conn, id))
+        .transpose()?
+        .into_iter()
+        .collect::<Vec<Person>>();
+      let communities = community_id
+        .map(|id| Community::read(conn, id))
+        .transpose()?
+        .into_iter()
+        .collect::<Vec<Community>>();
+      Ok((persons, communities)) as Result<(Vec<Person>, Vec<Community>), LemmyError>
+    })
+    .await??;
+    if persons.iter().any(|p| !p.local) || communities.iter().any(|c| !c.local) {
+      return Err(LemmyError::from_message("cant_rotate_keys_of_remote_actor"));
+    }
+
+    let mut rotated = 0;
+    for person in persons {
+      rotate_person_key(person, context).await?;
+      rotated += 1;
+    }
+    for community in communities {
+      rotate_community_key(community, context).await?;
+      rotated += 1;
+    }
+    Ok(RotateKeysResponse { rotated })
+  }
+}
+
+/// Rotates the keys of all local actors one after another, which takes a while on big instances.
+/// Failures are logged so that the remaining actors are still rotated.
+async fn rotate_all_keys(
+  persons: Vec<Person>,
+  communities: Vec<Community>,
+  context: &LemmyContext,
+) {
+  for person in persons {
+    let actor_id = person.actor_id.clone();
+    if let Err(e) = rotate_person_key(person, context).await {
+      error!("Failed to rotate key of {}: {}", actor_id, e);
+    }
+  }
+  for community in communities {
+    let actor_id = community.actor_id.clone();
+    if let Err(e) = rotate_community_key(community, context).await {
+      error!("Failed to rotate key of {}: {}", actor_id, e);
+    }
+  }
+  if let Err(e) = rotate_site_key(context).await {
+    error!("Failed to rotate key of the instance actor: {}", e);
+  }
+  info!("Finished rotating the keys of all local actors");
+}
+
+async fn rotate_person_key(person: Person, context: &LemmyContext) -> Result<(), LemmyError> {
+  let keypair = generate_actor_keypair()?;
+  let person = blocking(context.pool(), move |conn| {
+    Person::update_keys(conn, person.id, &keypair.private_key, &keypair.public_key)
+  })
+  .await?
+  .map_err(|e| LemmyError::from_error_message(e, "couldnt_rotate_keys"))?;
+  UpdateActor::send_person(person.into(), context).await
+}
+
+async fn rotate_community_key(
+  community: Community,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  let keypair = generate_actor_keypair()?;
+  let community = blocking(context.pool(), move |conn| {
+    Community::update_keys(
+      conn,
+      community.id,
+      &keypair.private_key,
+      &keypair.public_key,
+    )
+  })
+  .await?
+  .map_err(|e| LemmyError::from_error_message(e, "couldnt_rotate_keys"))?;
+  UpdateActor::send_community(community.into(), context).await
+}
+
+async fn rotate_site_key(context: &LemmyContext) -> Result<(), LemmyError> {
+  let keypair = generate_actor_keypair()?;
+  let site = blocking(context.pool(), move |conn| {
+    let site = Site::read_local_site(conn)?;
+    Site::update_keys(conn, site.id, &keypair.private_key, &keypair.public_key)
+  })
+  .await?
+  .map_err(|e| LemmyError::from_error_message(e, "couldnt_rotate_keys"))?;
+  clear_site_key_cache();
+  UpdateActor::send_site(site.into(), context).await
+}
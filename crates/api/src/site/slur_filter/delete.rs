@@ -0,0 +1,38 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  site::{DeleteSlurFilterEntry, SlurFilterEntriesResponse},
+  slur_filter::clear_slur_filter_cache,
+  utils::{blocking, get_local_user_view_from_jwt, is_admin},
+};
+use lemmy_db_schema::{source::slur_filter_entry::SlurFilterEntry, traits::Crud};
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for DeleteSlurFilterEntry {
+  type Response = SlurFilterEntriesResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<SlurFilterEntriesResponse, LemmyError> {
+    let data: &DeleteSlurFilterEntry = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+    is_admin(&local_user_view)?;
+
+    let entry_id = data.id;
+    let entries = blocking(context.pool(), move |conn| {
+      SlurFilterEntry::delete(conn, entry_id)?;
+      SlurFilterEntry::list(conn)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_delete_slur_filter_entry"))?;
+    clear_slur_filter_cache();
+
+    Ok(SlurFilterEntriesResponse { entries })
+  }
+}
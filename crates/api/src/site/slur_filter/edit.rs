@@ -0,0 +1,48 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  site::{EditSlurFilterEntry, SlurFilterEntriesResponse},
+  slur_filter::{clear_slur_filter_cache, validate_slur_filter_pattern},
+  utils::{blocking, get_local_user_view_from_jwt, is_admin},
+};
+use lemmy_db_schema::{
+  source::slur_filter_entry::{SlurFilterEntry, SlurFilterEntryForm},
+  traits::Crud,
+  utils::naive_now,
+};
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for EditSlurFilterEntry {
+  type Response = SlurFilterEntriesResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<SlurFilterEntriesResponse, LemmyError> {
+    let data: &EditSlurFilterEntry = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+    is_admin(&local_user_view)?;
+    validate_slur_filter_pattern(&data.pattern)?;
+
+    let entry_id = data.id;
+    let form = SlurFilterEntryForm {
+      pattern: data.pattern.to_owned(),
+      action: data.action,
+      updated: Some(naive_now()),
+    };
+    let entries = blocking(context.pool(), move |conn| {
+      SlurFilterEntry::update(conn, entry_id, &form)?;
+      SlurFilterEntry::list(conn)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_update_slur_filter_entry"))?;
+    clear_slur_filter_cache();
+
+    Ok(SlurFilterEntriesResponse { entries })
+  }
+}
@@ -0,0 +1,30 @@
+use crate::Perform;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  site::{ListSlurFilterEntries, SlurFilterEntriesResponse},
+  utils::{blocking, get_local_user_view_from_jwt, is_admin},
+};
+use lemmy_db_schema::source::slur_filter_entry::SlurFilterEntry;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl Perform for ListSlurFilterEntries {
+  type Response = SlurFilterEntriesResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<SlurFilterEntriesResponse, LemmyError> {
+    let data: &ListSlurFilterEntries = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+    is_admin(&local_user_view)?;
+
+    let entries = blocking(context.pool(), SlurFilterEntry::list).await??;
+
+    Ok(SlurFilterEntriesResponse { entries })
+  }
+}
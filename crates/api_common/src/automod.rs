@@ -0,0 +1,524 @@
+use crate::utils::blocking;
+use activitypub_federation::core::signatures::generate_actor_keypair;
+use chrono::{Duration, NaiveDateTime};
+use diesel::{result::Error::NotFound, PgConnection};
+use lemmy_db_schema::{
+  newtypes::{CommentId, CommunityId, DbUrl, PersonId, PostId},
+  source::{
+    automod_rule::{AutomodAction, AutomodRule, AutomodRuleType},
+    comment::Comment,
+    community::Community,
+    moderator::{ModRemoveComment, ModRemoveCommentForm, ModRemovePost, ModRemovePostForm},
+    person::{Person, PersonForm},
+    post::Post,
+    site::Site,
+  },
+  traits::{ApubActor, Crud},
+  utils::{naive_now, DbPool},
+};
+use lemmy_db_views_actor::structs::CommunityView;
+use lemmy_utils::{error::LemmyError, settings::SETTINGS};
+use regex::{Regex, RegexBuilder};
+use url::Url;
+
+/// How many numbered variants of the name `automod` are tried for the bot account
+const MAX_AUTOMOD_NAME_ATTEMPTS: i32 = 100;
+
+/// What should happen to a new post or comment after checking it against the automod rules.
+#[derive(Debug, PartialEq)]
+pub enum AutomodOutcome {
+  Allow,
+  Hold,
+  Remove { reason: String },
+}
+
+/// The parts of a new or edited post or comment that automod rules look at.
+struct AutomodContent<'a> {
+  /// Only set for posts
+  title: Option<&'a str>,
+  body: Option<&'a str>,
+  /// Only set for posts
+  url: Option<&'a Url>,
+  creator_published: NaiveDateTime,
+  /// Rules about the account only apply to new content
+  is_edit: bool,
+}
+
+/// Checks a new or edited post against the automod rules of its community. If a rule with action
+/// `Reject` matches, an error is returned.
+#[tracing::instrument(skip_all)]
+pub async fn check_post_automod(
+  name: &str,
+  body: &Option<String>,
+  url: Option<&Url>,
+  is_edit: bool,
+  creator: &Person,
+  community_id: CommunityId,
+  pool: &DbPool,
+) -> Result<AutomodOutcome, LemmyError> {
+  let content = AutomodContent {
+    title: Some(name),
+    body: body.as_deref(),
+    url,
+    creator_published: creator.published,
+    is_edit,
+  };
+  check_automod(&content, creator.id, community_id, pool).await
+}
+
+/// Checks a new or edited comment against the automod rules of its community. If a rule with
+/// action `Reject` matches, an error is returned.
+#[tracing::instrument(skip_all)]
+pub async fn check_comment_automod(
+  content: &str,
+  is_edit: bool,
+  creator: &Person,
+  community_id: CommunityId,
+  pool: &DbPool,
+) -> Result<AutomodOutcome, LemmyError> {
+  let content = AutomodContent {
+    title: None,
+    body: Some(content),
+    url: None,
+    creator_published: creator.published,
+    is_edit,
+  };
+  check_automod(&content, creator.id, community_id, pool).await
+}
+
+async fn check_automod(
+  content: &AutomodContent<'_>,
+  creator_id: PersonId,
+  community_id: CommunityId,
+  pool: &DbPool,
+) -> Result<AutomodOutcome, LemmyError> {
+  let (rules, is_mod_or_admin) = blocking(pool, move |conn| {
+    let rules = AutomodRule::list_for_community(conn, community_id)?;
+    let is_mod_or_admin = CommunityView::is_mod_or_admin(conn, creator_id, community_id);
+    Ok((rules, is_mod_or_admin)) as Result<(Vec<AutomodRule>, bool), LemmyError>
+  })
+  .await??;
+
+  // Mods and admins are exempt from automod
+  if is_mod_or_admin {
+    return Ok(AutomodOutcome::Allow);
+  }
+
+  let mut outcome = AutomodOutcome::Allow;
+  for rule in &rules {
+    match rule_matches(&rule.rule, content, naive_now()) {
+      Some((AutomodAction::Reject, _)) => {
+        return Err(LemmyError::from_message("automod_rejected"));
+      }
+      // The first matching removal wins over any holds
+      Some((AutomodAction::Remove, reason))
+        if !matches!(outcome, AutomodOutcome::Remove { .. }) =>
+      {
+        outcome = AutomodOutcome::Remove { reason };
+      }
+      Some((AutomodAction::Hold, _)) if outcome == AutomodOutcome::Allow => {
+        outcome = AutomodOutcome::Hold;
+      }
+      _ => {}
+    }
+  }
+  Ok(outcome)
+}
+
+/// Returns true if new content needs to be approved by a mod before it becomes visible, either
+/// because of the community settings, because an automod rule matched or because it matched a
+/// slur filter entry with action `Hold`.
+#[tracing::instrument(skip_all)]
+pub async fn is_held_for_approval(
+  outcome: &AutomodOutcome,
+  slur_filter_held: bool,
+  community: &Community,
+  creator_id: PersonId,
+  pool: &DbPool,
+) -> Result<bool, LemmyError> {
+  if *outcome == AutomodOutcome::Hold {
+    return Ok(true);
+  }
+  if !community.require_approval && !slur_filter_held {
+    return Ok(false);
+  }
+  let community_id = community.id;
+  let is_mod_or_admin = blocking(pool, move |conn| {
+    CommunityView::is_mod_or_admin(conn, creator_id, community_id)
+  })
+  .await?;
+  Ok(!is_mod_or_admin)
+}
+
+/// Returns true if an edited post or comment needs to be approved (again) by a mod, because an
+/// automod rule or a slur filter entry with action `Hold` matched.
+#[tracing::instrument(skip_all)]
+pub async fn is_edit_held_for_approval(
+  outcome: &AutomodOutcome,
+  slur_filter_held: bool,
+  community_id: CommunityId,
+  creator_id: PersonId,
+  pool: &DbPool,
+) -> Result<bool, LemmyError> {
+  if *outcome == AutomodOutcome::Hold {
+    return Ok(true);
+  }
+  if !slur_filter_held {
+    return Ok(false);
+  }
+  let is_mod_or_admin = blocking(pool, move |conn| {
+    CommunityView::is_mod_or_admin(conn, creator_id, community_id)
+  })
+  .await?;
+  Ok(!is_mod_or_admin)
+}
+
+/// Returns the action and the modlog reason if the rule matches the content.
+fn rule_matches(
+  rule: &AutomodRuleType,
+  content: &AutomodContent,
+  now: NaiveDateTime,
+) -> Option<(AutomodAction, String)> {
+  match rule {
+    AutomodRuleType::MinAccountAge { days, action } => {
+      if !content.is_edit && content.creator_published > now - Duration::days(*days) {
+        Some((
+          *action,
+          format!("Automod: account is younger than {} days", days),
+        ))
+      } else {
+        None
+      }
+    }
+    AutomodRuleType::ContentRegex { patterns, action } => {
+      let texts = [content.title, content.body];
+      patterns
+        .iter()
+        .filter_map(|p| build_automod_regex(p).ok())
+        .find(|r| texts.iter().flatten().any(|t| r.is_match(t)))
+        .map(|r| {
+          (
+            *action,
+            format!("Automod: content matches `{}`", r.as_str()),
+          )
+        })
+    }
+    AutomodRuleType::FlairRequired { flairs, action } => {
+      let title = content.title?.trim_start().to_lowercase();
+      let has_flair = flairs
+        .iter()
+        .any(|f| title.starts_with(&format!("[{}]", f.to_lowercase())));
+      if has_flair {
+        None
+      } else {
+        Some((
+          *action,
+          "Automod: post title is missing a flair".to_string(),
+        ))
+      }
+    }
+    AutomodRuleType::BannedWords { words, action } => {
+      let regex = build_banned_words_regex(words).ok()?;
+      let texts = [content.title, content.body];
+      let word = texts.iter().flatten().find_map(|t| regex.find(t))?.as_str();
+      Some((*action, format!("Automod: contains banned word `{}`", word)))
+    }
+    AutomodRuleType::BannedDomains { domains, action } => {
+      let host = content.url?.host_str()?.to_lowercase();
+      let domain = domains.iter().find(|d| is_same_or_subdomain(&host, d))?;
+      Some((
+        *action,
+        format!("Automod: links to banned domain `{}`", domain),
+      ))
+    }
+    // Handled by the scheduled task
+    AutomodRuleType::AutoLock { .. } => None,
+  }
+}
+
+fn build_automod_regex(pattern: &str) -> Result<Regex, regex::Error> {
+  RegexBuilder::new(pattern).case_insensitive(true).build()
+}
+
+/// Matches any of the words, but not as part of a longer word.
+fn build_banned_words_regex(words: &[String]) -> Result<Regex, regex::Error> {
+  let is_word_char = |c: Option<char>| c.map(|c| c.is_alphanumeric() || c == '_') == Some(true);
+  let words = words
+    .iter()
+    .map(|w| {
+      let w = w.trim();
+      // Word boundaries only work next to word characters, eg not for `c++`
+      let start = if is_word_char(w.chars().next()) {
+        r"\b"
+      } else {
+        ""
+      };
+      let end = if is_word_char(w.chars().last()) {
+        r"\b"
+      } else {
+        ""
+      };
+      format!("{}{}{}", start, regex::escape(w), end)
+    })
+    .collect::<Vec<_>>()
+    .join("|");
+  build_automod_regex(&words)
+}
+
+fn is_same_or_subdomain(host: &str, domain: &str) -> bool {
+  let domain = domain.trim().trim_start_matches('.').to_lowercase();
+  host == domain || host.ends_with(&format!(".{}", domain))
+}
+
+/// Checks that a rule submitted by a mod is valid.
+pub fn validate_automod_rule(rule: &AutomodRuleType) -> Result<(), LemmyError> {
+  match rule {
+    AutomodRuleType::MinAccountAge { days, .. } | AutomodRuleType::AutoLock { days } => {
+      if *days < 1 {
+        return Err(LemmyError::from_message("invalid_automod_rule"));
+      }
+    }
+    AutomodRuleType::ContentRegex { patterns, .. } => {
+      if patterns.is_empty() {
+        return Err(LemmyError::from_message("invalid_automod_rule"));
+      }
+      for pattern in patterns {
+        build_automod_regex(pattern)
+          .map_err(|e| LemmyError::from_error_message(e, "invalid_regex"))?;
+      }
+    }
+    AutomodRuleType::FlairRequired { flairs, .. } => {
+      if flairs.is_empty() {
+        return Err(LemmyError::from_message("invalid_automod_rule"));
+      }
+    }
+    AutomodRuleType::BannedWords { words, .. } => {
+      if words.is_empty() || words.iter().any(|w| w.trim().is_empty()) {
+        return Err(LemmyError::from_message("invalid_automod_rule"));
+      }
+    }
+    AutomodRuleType::BannedDomains { domains, .. } => {
+      let is_invalid = |d: &String| {
+        let d = d.trim().trim_start_matches('.');
+        d.is_empty() || d.contains(|c: char| c == '/' || c == ':' || c.is_whitespace())
+      };
+      if domains.is_empty() || domains.iter().any(is_invalid) {
+        return Err(LemmyError::from_message("invalid_automod_rule"));
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Automod actions are written to the modlog in the name of the automod bot account of the site,
+/// so that they aren't mistaken for actions of a human mod. If the account is missing, it is
+/// created on the spot.
+pub fn automod_person_id(conn: &PgConnection) -> Result<PersonId, LemmyError> {
+  let site = Site::read_local_site(conn)?;
+  match site.automod_person_id {
+    Some(person_id) => Ok(person_id),
+    None => insert_automod_person(conn, site.id, &SETTINGS.get_protocol_and_hostname()),
+  }
+}
+
+/// Creates the local bot account which automod actions are attributed to, if the local site
+/// doesn't have one yet.
+pub fn create_automod_person(
+  conn: &PgConnection,
+  protocol_and_hostname: &str,
+) -> Result<(), LemmyError> {
+  let site = match Site::read_local_site(conn) {
+    Ok(site) => site,
+    // Created together with the site
+    Err(NotFound) => return Ok(()),
+    Err(e) => return Err(e.into()),
+  };
+  if site.automod_person_id.is_none() {
+    insert_automod_person(conn, site.id, protocol_and_hostname)?;
+  }
+  Ok(())
+}
+
+/// If the name `automod` is taken, a number is appended.
+fn insert_automod_person(
+  conn: &PgConnection,
+  site_id: i32,
+  protocol_and_hostname: &str,
+) -> Result<PersonId, LemmyError> {
+  let mut name = None;
+  for i in 1..=MAX_AUTOMOD_NAME_ATTEMPTS {
+    let candidate = match i {
+      1 => "automod".to_string(),
+      i => format!("automod_{}", i),
+    };
+    match Person::read_from_name(conn, &candidate, true) {
+      Ok(_) => continue,
+      Err(NotFound) => {
+        name = Some(candidate);
+        break;
+      }
+      Err(e) => return Err(e.into()),
+    }
+  }
+  let name = name.ok_or_else(|| LemmyError::from_message("no_free_name_for_automod"))?;
+
+  let keypair = generate_actor_keypair()?;
+  let actor_id: DbUrl = Url::parse(&format!("{}/u/{}", protocol_and_hostname, name))?.into();
+  let form = PersonForm {
+    name,
+    display_name: Some(Some("Automod".to_string())),
+    actor_id: Some(actor_id.clone()),
+    private_key: Some(Some(keypair.private_key)),
+    public_key: Some(keypair.public_key),
+    inbox_url: Some(Url::parse(&format!("{}/inbox", actor_id))?.into()),
+    shared_inbox_url: Some(Some(
+      Url::parse(&format!("{}/inbox", protocol_and_hostname))?.into(),
+    )),
+    bot_account: Some(true),
+    ..PersonForm::default()
+  };
+  let person = Person::create(conn, &form)?;
+  Site::update_automod_person(conn, site_id, person.id)?;
+  Ok(person.id)
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn remove_post_by_automod(
+  post_id: PostId,
+  reason: String,
+  pool: &DbPool,
+) -> Result<Post, LemmyError> {
+  blocking(pool, move |conn| {
+    let post = Post::update_removed(conn, post_id, true)?;
+    let form = ModRemovePostForm {
+      mod_person_id: automod_person_id(conn)?,
+      post_id,
+      reason: Some(reason),
+      removed: Some(true),
+    };
+    ModRemovePost::create(conn, &form)?;
+    Ok(post)
+  })
+  .await?
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn remove_comment_by_automod(
+  comment_id: CommentId,
+  reason: String,
+  pool: &DbPool,
+) -> Result<Comment, LemmyError> {
+  blocking(pool, move |conn| {
+    let comment = Comment::update_removed(conn, comment_id, true)?;
+    let form = ModRemoveCommentForm {
+      mod_person_id: automod_person_id(conn)?,
+      comment_id,
+      reason: Some(reason),
+      removed: Some(true),
+    };
+    ModRemoveComment::create(conn, &form)?;
+    Ok(comment)
+  })
+  .await?
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn content<'a>(title: Option<&'a str>, body: &'a str, age_days: i64) -> AutomodContent<'a> {
+    AutomodContent {
+      title,
+      body: Some(body),
+      url: None,
+      creator_published: naive_now() - Duration::days(age_days),
+      is_edit: false,
+    }
+  }
+
+  #[test]
+  fn test_min_account_age() {
+    let rule = AutomodRuleType::MinAccountAge {
+      days: 3,
+      action: AutomodAction::Remove,
+    };
+    let matched = rule_matches(&rule, &content(None, "hi", 1), naive_now());
+    assert_eq!(Some(AutomodAction::Remove), matched.map(|m| m.0));
+    assert!(rule_matches(&rule, &content(None, "hi", 5), naive_now()).is_none());
+  }
+
+  #[test]
+  fn test_content_regex() {
+    let rule = AutomodRuleType::ContentRegex {
+      patterns: vec!["cheap (pills|watches)".to_string()],
+      action: AutomodAction::Reject,
+    };
+    let spam = content(Some("CHEAP Watches here"), "", 100);
+    let matched = rule_matches(&rule, &spam, naive_now());
+    assert_eq!(Some(AutomodAction::Reject), matched.map(|m| m.0));
+    let ham = content(Some("Watches"), "are not cheap", 100);
+    assert!(rule_matches(&rule, &ham, naive_now()).is_none());
+  }
+
+  #[test]
+  fn test_flair_required() {
+    let rule = AutomodRuleType::FlairRequired {
+      flairs: vec!["Question".to_string(), "Meta".to_string()],
+      action: AutomodAction::Remove,
+    };
+    let with_flair = content(Some("[question] How do I..."), "", 100);
+    assert!(rule_matches(&rule, &with_flair, naive_now()).is_none());
+    let without_flair = content(Some("How do I..."), "", 100);
+    assert!(rule_matches(&rule, &without_flair, naive_now()).is_some());
+    // Comments have no title, so they never need a flair
+    let comment = content(None, "How do I...", 100);
+    assert!(rule_matches(&rule, &comment, naive_now()).is_none());
+  }
+
+  #[test]
+  fn test_banned_words() {
+    let rule = AutomodRuleType::BannedWords {
+      words: vec!["scam".to_string(), "c++".to_string()],
+      action: AutomodAction::Hold,
+    };
+    let matched = rule_matches(&rule, &content(None, "what a SCAM!", 100), naive_now());
+    assert_eq!(Some(AutomodAction::Hold), matched.map(|m| m.0));
+    assert!(rule_matches(&rule, &content(Some("I like c++"), "", 100), naive_now()).is_some());
+    // Only whole words match
+    assert!(rule_matches(&rule, &content(None, "scampi", 100), naive_now()).is_none());
+  }
+
+  #[test]
+  fn test_banned_domains() {
+    let rule = AutomodRuleType::BannedDomains {
+      domains: vec!["example.com".to_string()],
+      action: AutomodAction::Reject,
+    };
+    let post = |url: &str| {
+      let url = Url::parse(url).unwrap();
+      rule_matches(
+        &rule,
+        &AutomodContent {
+          url: Some(&url),
+          ..content(Some("Link"), "", 100)
+        },
+        naive_now(),
+      )
+    };
+    assert!(post("https://example.com/page").is_some());
+    assert!(post("https://www.Example.com/page").is_some());
+    assert!(post("https://notexample.com/page").is_none());
+    assert!(rule_matches(&rule, &content(Some("Link"), "", 100), naive_now()).is_none());
+  }
+
+  #[test]
+  fn test_validate_automod_rule() {
+    let invalid_regex = AutomodRuleType::ContentRegex {
+      patterns: vec!["(unclosed".to_string()],
+      action: AutomodAction::Remove,
+    };
+    assert!(validate_automod_rule(&invalid_regex).is_err());
+    assert!(validate_automod_rule(&AutomodRuleType::AutoLock { days: 0 }).is_err());
+    assert!(validate_automod_rule(&AutomodRuleType::AutoLock { days: 30 }).is_ok());
+  }
+}
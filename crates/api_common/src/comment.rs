@@ -45,6 +45,15 @@ pub struct RemoveComment {
   pub auth: Sensitive<String>,
 }
 
+/// Approves or rejects a comment which is held for approval. Rejected comments are removed.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ApproveComment {
+  pub comment_id: CommentId,
+  pub approve: bool,
+  pub reason: Option<String>,
+  pub auth: Sensitive<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct SaveComment {
   pub comment_id: CommentId,
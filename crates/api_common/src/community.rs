@@ -1,11 +1,22 @@
 use crate::sensitive::Sensitive;
 use lemmy_db_schema::{
-  newtypes::{CommunityId, PersonId},
-  source::site::Site,
+  newtypes::{AutomodRuleId, CommunityId, MultiCommunityId, PersonId},
+  source::{
+    automod_rule::{AutomodRule, AutomodRuleType},
+    community::CommunityVisibility,
+    site::Site,
+  },
   ListingType,
   SortType,
 };
-use lemmy_db_views_actor::structs::{CommunityModeratorView, CommunityView, PersonViewSafe};
+use lemmy_db_views::structs::{CommentView, PostView};
+use lemmy_db_views_actor::structs::{
+  CommunityFollowerView,
+  CommunityModeratorView,
+  CommunityView,
+  MultiCommunityView,
+  PersonViewSafe,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -33,6 +44,8 @@ pub struct CreateCommunity {
   pub banner: Option<String>,
   pub nsfw: Option<bool>,
   pub posting_restricted_to_mods: Option<bool>,
+  pub require_approval: Option<bool>,
+  pub visibility: Option<CommunityVisibility>,
   pub auth: Sensitive<String>,
 }
 
@@ -94,9 +107,23 @@ pub struct EditCommunity {
   pub banner: Option<String>,
   pub nsfw: Option<bool>,
   pub posting_restricted_to_mods: Option<bool>,
+  pub require_approval: Option<bool>,
+  pub visibility: Option<CommunityVisibility>,
   pub auth: Sensitive<String>,
 }
 
+/// Import the full history of posts and comments from a remote community.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BackfillCommunity {
+  pub community_id: CommunityId,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BackfillCommunityResponse {
+  pub success: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct HideCommunity {
   pub community_id: CommunityId,
@@ -128,6 +155,48 @@ pub struct FollowCommunity {
   pub auth: Sensitive<String>,
 }
 
+/// Follows of a private community which are waiting for approval by a mod
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ListPendingFollows {
+  pub community_id: CommunityId,
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ListPendingFollowsResponse {
+  pub items: Vec<CommunityFollowerView>,
+}
+
+/// Approve or deny a pending follow of a private community
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ApproveFollow {
+  pub community_id: CommunityId,
+  pub follower_id: PersonId,
+  pub approve: bool,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApproveFollowResponse {
+  pub success: bool,
+}
+
+/// Get a notification in the inbox, by email and by push for every new post in the community
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SubscribeToCommunityPosts {
+  pub community_id: CommunityId,
+  pub subscribe: bool,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CommunityPostSubscriptionResponse {
+  pub community_id: CommunityId,
+  pub subscribed: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct BlockCommunity {
   pub community_id: CommunityId,
@@ -147,3 +216,103 @@ pub struct TransferCommunity {
   pub person_id: PersonId,
   pub auth: Sensitive<String>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ListAutomodRules {
+  pub community_id: CommunityId,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreateAutomodRule {
+  pub community_id: CommunityId,
+  pub rule: AutomodRuleType,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EditAutomodRule {
+  pub id: AutomodRuleId,
+  pub rule: AutomodRuleType,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DeleteAutomodRule {
+  pub id: AutomodRuleId,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AutomodRulesResponse {
+  pub rules: Vec<AutomodRule>,
+}
+
+/// Lists the posts and comments which are held for approval. Without a community, this lists
+/// them for all communities you moderate.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ListModQueue {
+  pub community_id: Option<CommunityId>,
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ListModQueueResponse {
+  pub posts: Vec<PostView>,
+  pub comments: Vec<CommentView>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CreateMultiCommunity {
+  pub name: String,
+  pub description: Option<String>,
+  /// Public feeds can be viewed and shared by anyone
+  pub public: Option<bool>,
+  pub community_ids: Vec<CommunityId>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct EditMultiCommunity {
+  pub id: MultiCommunityId,
+  pub name: Option<String>,
+  pub description: Option<String>,
+  pub public: Option<bool>,
+  /// Replaces the member communities of the feed
+  pub community_ids: Option<Vec<CommunityId>>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DeleteMultiCommunity {
+  pub id: MultiCommunityId,
+  pub auth: Sensitive<String>,
+}
+
+/// Private feeds can only be read by their creator
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GetMultiCommunity {
+  pub id: MultiCommunityId,
+  pub auth: Option<Sensitive<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MultiCommunityResponse {
+  pub multi_community_view: MultiCommunityView,
+}
+
+/// Lists your own feeds if `mine` is set, otherwise all public feeds
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ListMultiCommunities {
+  pub mine: Option<bool>,
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Option<Sensitive<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ListMultiCommunitiesResponse {
+  pub multi_communities: Vec<MultiCommunityView>,
+}
@@ -1,14 +1,21 @@
+#[cfg(feature = "full")]
+pub mod automod;
 pub mod comment;
 pub mod community;
 pub mod person;
 pub mod post;
 #[cfg(feature = "full")]
+pub mod push;
+#[cfg(feature = "full")]
 pub mod request;
 pub mod sensitive;
 pub mod site;
 #[cfg(feature = "full")]
+pub mod slur_filter;
+#[cfg(feature = "full")]
 pub mod utils;
 pub mod websocket;
+pub mod wiki;
 
 pub extern crate lemmy_db_schema;
 pub extern crate lemmy_db_views;
@@ -5,6 +5,7 @@ use lemmy_db_views_actor::structs::{
   CommunityModeratorView,
   PersonMentionView,
   PersonViewSafe,
+  PostNotificationView,
 };
 use serde::{Deserialize, Serialize};
 
@@ -14,7 +15,15 @@ pub struct Login {
   pub password: Sensitive<String>,
 }
 use lemmy_db_schema::{
-  newtypes::{CommentReplyId, CommunityId, PersonId, PersonMentionId, PrivateMessageId},
+  newtypes::{
+    CommentReplyId,
+    CommunityId,
+    PersonId,
+    PersonMentionId,
+    PostNotificationId,
+    PrivateMessageId,
+  },
+  source::{local_user::EmailDigestFrequency, push_subscription::PushSubscription},
   CommentSortType,
   SortType,
 };
@@ -65,13 +74,27 @@ pub struct SaveUserSettings {
   pub matrix_user_id: Option<String>,
   pub show_avatars: Option<bool>,
   pub send_notifications_to_email: Option<bool>,
+  /// Whether notification emails are sent one by one, or batched into a digest
+  pub email_digest_frequency: Option<EmailDigestFrequency>,
   pub bot_account: Option<bool>,
   pub show_bot_accounts: Option<bool>,
   pub show_read_posts: Option<bool>,
   pub show_new_post_notifs: Option<bool>,
+  /// Replaces all of the personal keyword filters
+  pub keyword_filters: Option<Vec<KeywordFilter>>,
   pub auth: Sensitive<String>,
 }
 
+/// Hides posts and comments matching the keyword (case insensitive) from the user.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct KeywordFilter {
+  pub keyword: String,
+  /// Treat the keyword as a regex
+  pub regex: bool,
+  /// Only hide matching content in this community
+  pub community_id: Option<CommunityId>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct ChangePassword {
   pub new_password: Sensitive<String>,
@@ -175,6 +198,21 @@ pub struct BlockPersonResponse {
   pub blocked: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FollowPerson {
+  pub person_id: PersonId,
+  pub follow: bool,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FollowPersonResponse {
+  pub person_view: PersonViewSafe,
+  pub followed: bool,
+  /// Follows of remote persons are pending until they are accepted
+  pub pending: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct GetReplies {
   pub sort: Option<CommentSortType>,
@@ -217,6 +255,32 @@ pub struct CommentReplyResponse {
   pub comment_reply_view: CommentReplyView,
 }
 
+/// New posts in communities which the user subscribed to with SubscribeToCommunityPosts
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GetPostNotifications {
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub unread_only: Option<bool>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetPostNotificationsResponse {
+  pub post_notifications: Vec<PostNotificationView>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MarkPostNotificationAsRead {
+  pub post_notification_id: PostNotificationId,
+  pub read: bool,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PostNotificationResponse {
+  pub post_notification_view: PostNotificationView,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct DeleteAccount {
   pub password: Sensitive<String>,
@@ -310,6 +374,7 @@ pub struct GetUnreadCountResponse {
   pub replies: i64,
   pub mentions: i64,
   pub private_messages: i64,
+  pub post_notifications: i64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Default)]
@@ -319,3 +384,57 @@ pub struct VerifyEmail {
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct VerifyEmailResponse {}
+
+/// Disables notification emails, using the token from the link in such an email.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Unsubscribe {
+  pub token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UnsubscribeResponse {}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GetPushPublicKey {}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetPushPublicKeyResponse {
+  /// The VAPID key, to be passed as `applicationServerKey` when subscribing to push messages
+  pub public_key: Option<String>,
+}
+
+/// The `keys` of a subscription from the browser Push API, base64url encoded
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PushSubscriptionKeys {
+  pub p256dh: String,
+  pub auth: String,
+}
+
+/// Creates a push subscription, or updates it if the endpoint already exists. All kinds of
+/// notifications are enabled by default.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CreatePushSubscription {
+  pub endpoint: String,
+  pub keys: PushSubscriptionKeys,
+  pub notify_mentions: Option<bool>,
+  pub notify_replies: Option<bool>,
+  pub notify_private_messages: Option<bool>,
+  pub notify_new_posts: Option<bool>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DeletePushSubscription {
+  pub endpoint: String,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ListPushSubscriptions {
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PushSubscriptionsResponse {
+  pub push_subscriptions: Vec<PushSubscription>,
+}
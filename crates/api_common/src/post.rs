@@ -1,6 +1,6 @@
 use crate::sensitive::Sensitive;
 use lemmy_db_schema::{
-  newtypes::{CommentId, CommunityId, DbUrl, PostId, PostReportId},
+  newtypes::{CommentId, CommunityId, DbUrl, MultiCommunityId, PostId, PostReportId},
   ListingType,
   SortType,
 };
@@ -17,6 +17,16 @@ pub struct CreatePost {
   pub body: Option<String>,
   pub honeypot: Option<String>,
   pub nsfw: Option<bool>,
+  /// Create the post as a crosspost of an existing post in another community
+  pub crosspost_of: Option<PostId>,
+  pub auth: Sensitive<String>,
+}
+
+/// Crosspost an existing post into another community, copying its title, link and body.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CrosspostPost {
+  pub post_id: PostId,
+  pub community_id: CommunityId,
   pub auth: Sensitive<String>,
 }
 
@@ -38,6 +48,8 @@ pub struct GetPostResponse {
   pub community_view: CommunityView,
   pub moderators: Vec<CommunityModeratorView>,
   pub online: usize,
+  /// Crossposts and other posts linking the same page
+  pub other_discussions: Vec<PostView>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -48,6 +60,8 @@ pub struct GetPosts {
   pub limit: Option<i64>,
   pub community_id: Option<CommunityId>,
   pub community_name: Option<String>,
+  /// Only list posts from the communities of this feed
+  pub multi_community_id: Option<MultiCommunityId>,
   pub saved_only: Option<bool>,
   pub auth: Option<Sensitive<String>>,
 }
@@ -96,6 +110,15 @@ pub struct MarkPostAsRead {
   pub auth: Sensitive<String>,
 }
 
+/// Approves or rejects a post which is held for approval. Rejected posts are removed.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ApprovePost {
+  pub post_id: PostId,
+  pub approve: bool,
+  pub reason: Option<String>,
+  pub auth: Sensitive<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct LockPost {
   pub post_id: PostId,
@@ -117,6 +140,34 @@ pub struct SavePost {
   pub auth: Sensitive<String>,
 }
 
+/// Get notified about all new comments in a post, or only about those below the given comment.
+/// Exactly one of post_id and comment_id must be set.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SubscribeToThread {
+  pub post_id: Option<PostId>,
+  pub comment_id: Option<CommentId>,
+  pub subscribe: bool,
+  pub auth: Sensitive<String>,
+}
+
+/// Stop all notifications from a post or from below a comment, including direct replies.
+/// Exactly one of post_id and comment_id must be set.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MuteThread {
+  pub post_id: Option<PostId>,
+  pub comment_id: Option<CommentId>,
+  pub mute: bool,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ThreadSubscriptionResponse {
+  pub post_id: PostId,
+  pub comment_id: Option<CommentId>,
+  pub subscribed: bool,
+  pub muted: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct CreatePostReport {
   pub post_id: PostId,
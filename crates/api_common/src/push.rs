@@ -0,0 +1,193 @@
+use crate::{request::build_user_agent, utils::blocking};
+use actix_web::{http::StatusCode, rt::task::spawn_blocking};
+use lemmy_db_schema::{
+  newtypes::LocalUserId,
+  source::{push_subscription::PushSubscription, secret::Secret},
+  utils::DbPool,
+};
+use lemmy_utils::{
+  error::LemmyError,
+  settings::structs::Settings,
+  web_push::{
+    encrypt_payload,
+    is_public_ip,
+    validate_push_endpoint,
+    vapid_authorization,
+    VapidKeys,
+  },
+  REQWEST_TIMEOUT,
+};
+use reqwest::{redirect::Policy, Client};
+use serde::Serialize;
+use std::net::{SocketAddr, ToSocketAddrs};
+use tracing::warn;
+use url::Url;
+
+/// How long the push service should keep undelivered messages, in seconds
+const PUSH_TTL: &str = "86400";
+/// Payloads are limited to about 4kb, so long texts are cut off
+const MAX_PUSH_BODY_CHARS: usize = 500;
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PushCategory {
+  Mention,
+  Reply,
+  PrivateMessage,
+  NewPost,
+}
+
+/// The decrypted content of a push message, which is shown by the service worker or app
+#[derive(Serialize, Debug)]
+pub struct PushNotification {
+  pub category: PushCategory,
+  pub title: String,
+  pub body: String,
+  /// Where the client should navigate to when the notification is clicked
+  pub url: String,
+}
+
+/// Sends a push notification to all subscriptions of the user which opted into the category.
+/// This runs in the background, so that slow push services don't delay the api response.
+pub fn send_push_notification(
+  local_user_id: LocalUserId,
+  mut notification: PushNotification,
+  pool: &DbPool,
+  secret: &Secret,
+  settings: &Settings,
+) {
+  let keys = match (&secret.vapid_private_key, &secret.vapid_public_key) {
+    (Some(private_key), Some(public_key)) => VapidKeys {
+      private_key: private_key.to_owned(),
+      public_key: public_key.to_owned(),
+    },
+    _ => return,
+  };
+  notification.body = notification
+    .body
+    .chars()
+    .take(MAX_PUSH_BODY_CHARS)
+    .collect();
+  let subject = settings.get_protocol_and_hostname();
+  let user_agent = build_user_agent(settings);
+  let pool = pool.clone();
+  actix_web::rt::spawn(async move {
+    if let Err(e) = send_push_notification_inner(
+      local_user_id,
+      &notification,
+      &keys,
+      &subject,
+      &user_agent,
+      &pool,
+    )
+    .await
+    {
+      warn!("Failed to send push notification: {}", e);
+    }
+  });
+}
+
+async fn send_push_notification_inner(
+  local_user_id: LocalUserId,
+  notification: &PushNotification,
+  keys: &VapidKeys,
+  subject: &str,
+  user_agent: &str,
+  pool: &DbPool,
+) -> Result<(), LemmyError> {
+  let subscriptions = blocking(pool, move |conn| {
+    PushSubscription::list_for_local_user(conn, local_user_id)
+  })
+  .await??;
+  let payload = serde_json::to_vec(notification)?;
+  for subscription in subscriptions {
+    let wanted = match notification.category {
+      PushCategory::Mention => subscription.notify_mentions,
+      PushCategory::Reply => subscription.notify_replies,
+      PushCategory::PrivateMessage => subscription.notify_private_messages,
+      PushCategory::NewPost => subscription.notify_new_posts,
+    };
+    if !wanted {
+      continue;
+    }
+    if let Err(e) =
+      send_push_message(&subscription, &payload, keys, subject, user_agent, pool).await
+    {
+      warn!(
+        "Failed to send push message to {}: {}",
+        subscription.endpoint, e
+      );
+    }
+  }
+  Ok(())
+}
+
+async fn send_push_message(
+  subscription: &PushSubscription,
+  payload: &[u8],
+  keys: &VapidKeys,
+  subject: &str,
+  user_agent: &str,
+  pool: &DbPool,
+) -> Result<(), LemmyError> {
+  let endpoint = validate_push_endpoint(&subscription.endpoint)?;
+  let client = push_client(&endpoint, user_agent).await?;
+  let body = encrypt_payload(payload, &subscription.p256dh, &subscription.auth)?;
+  let authorization = vapid_authorization(&endpoint, keys, subject)?;
+  let response = client
+    .post(endpoint)
+    .header("TTL", PUSH_TTL)
+    .header("Content-Encoding", "aes128gcm")
+    .header("Content-Type", "application/octet-stream")
+    .header("Authorization", authorization)
+    .body(body)
+    .send()
+    .await?;
+
+  let status = response.status();
+  if status == StatusCode::NOT_FOUND || status == StatusCode::GONE {
+    // The subscription expired or the user revoked the permission
+    let endpoint = subscription.endpoint.to_owned();
+    blocking(pool, move |conn| {
+      PushSubscription::delete_for_endpoint(conn, &endpoint)
+    })
+    .await??;
+  } else if !status.is_success() {
+    return Err(LemmyError::from_message(&format!(
+      "push service returned {}",
+      status
+    )));
+  }
+  Ok(())
+}
+
+/// Builds a client which only connects to the resolved address of the endpoint, if it is public.
+/// Pinning the address means that the domain can't resolve to an internal one at request time.
+/// Redirects aren't followed, and the request doesn't go through the middleware of the federation
+/// client.
+async fn push_client(endpoint: &Url, user_agent: &str) -> Result<Client, LemmyError> {
+  let domain = endpoint
+    .host_str()
+    .ok_or_else(|| LemmyError::from_message("invalid_push_subscription"))?
+    .to_owned();
+  let domain_ = domain.clone();
+  let addrs = spawn_blocking(move || {
+    (domain_.as_str(), 443)
+      .to_socket_addrs()
+      .map(|a| a.collect::<Vec<SocketAddr>>())
+  })
+  .await??;
+  // All addresses have to be public, otherwise the domain could be used for DNS rebinding
+  let addr = match addrs.first() {
+    Some(addr) if addrs.iter().all(|a| is_public_ip(&a.ip())) => *addr,
+    _ => return Err(LemmyError::from_message("invalid_push_subscription")),
+  };
+  Ok(
+    Client::builder()
+      .user_agent(user_agent)
+      .timeout(REQWEST_TIMEOUT)
+      .redirect(Policy::none())
+      .resolve(&domain, addr)
+      .build()?,
+  )
+}
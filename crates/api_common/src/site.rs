@@ -1,6 +1,14 @@
 use crate::sensitive::Sensitive;
 use lemmy_db_schema::{
-  newtypes::{CommentId, CommunityId, PersonId, PostId},
+  newtypes::{CommentId, CommunityId, PersonId, PostId, RelayId, SlurFilterEntryId},
+  source::{
+    activity_delivery::ActivityDelivery,
+    email_queue::EmailQueueSafe,
+    instance::Instance,
+    local_user_keyword_filter::LocalUserKeywordFilter,
+    relay::Relay,
+    slur_filter_entry::{SlurFilterAction, SlurFilterEntry},
+  },
   ListingType,
   SearchType,
   SortType,
@@ -27,6 +35,8 @@ use lemmy_db_views_moderator::structs::{
   AdminPurgePostView,
   ModAddCommunityView,
   ModAddView,
+  ModApproveCommentView,
+  ModApprovePostView,
   ModBanFromCommunityView,
   ModBanView,
   ModHideCommunityView,
@@ -102,6 +112,8 @@ pub struct GetModlogResponse {
   pub admin_purged_posts: Vec<AdminPurgePostView>,
   pub admin_purged_comments: Vec<AdminPurgeCommentView>,
   pub hidden_communities: Vec<ModHideCommunityView>,
+  pub approved_posts: Vec<ModApprovePostView>,
+  pub approved_comments: Vec<ModApproveCommentView>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -172,6 +184,7 @@ pub struct MyUserInfo {
   pub moderates: Vec<CommunityModeratorView>,
   pub community_blocks: Vec<CommunityBlockView>,
   pub person_blocks: Vec<PersonBlockView>,
+  pub keyword_filters: Vec<LocalUserKeywordFilter>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -184,6 +197,11 @@ pub struct FederatedInstances {
   pub linked: Vec<String>,
   pub allowed: Option<Vec<String>>,
   pub blocked: Option<Vec<String>>,
+  /// Instances which couldn't be reached for several days, and only receive occasional activities.
+  /// Only returned to admins.
+  pub dead: Option<Vec<String>>,
+  /// All known remote instances, with their software and delivery health. Only returned to admins.
+  pub instances: Option<Vec<Instance>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -255,3 +273,106 @@ pub struct GetUnreadRegistrationApplicationCount {
 pub struct GetUnreadRegistrationApplicationCountResponse {
   pub registration_applications: i64,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ListSlurFilterEntries {
+  pub auth: Sensitive<String>,
+}
+
+/// The pattern is a case insensitive regex.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreateSlurFilterEntry {
+  pub pattern: String,
+  pub action: SlurFilterAction,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EditSlurFilterEntry {
+  pub id: SlurFilterEntryId,
+  pub pattern: String,
+  pub action: SlurFilterAction,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DeleteSlurFilterEntry {
+  pub id: SlurFilterEntryId,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SlurFilterEntriesResponse {
+  pub entries: Vec<SlurFilterEntry>,
+}
+
+/// Emails which couldn't be sent, even after retrying.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ListFailedEmails {
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ListFailedEmailsResponse {
+  pub emails: Vec<EmailQueueSafe>,
+}
+
+/// Outgoing activities which are still waiting to be sent, or which couldn't be sent even after
+/// retrying.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ListActivityDeliveries {
+  pub failed_only: Option<bool>,
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ListRelays {
+  pub auth: Sensitive<String>,
+}
+
+/// Subscribe the instance to a relay. This is either the actor of a LitePub relay, or the inbox
+/// of a Mastodon style relay (ending with `/inbox`).
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AddRelay {
+  pub relay_url: String,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RemoveRelay {
+  pub id: RelayId,
+  pub auth: Sensitive<String>,
+}
+
+/// Subscribed relays. A relay only forwards posts after it accepted the subscription.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RelaysResponse {
+  pub relays: Vec<Relay>,
+}
+
+/// Replace the keys of a local person or community, or of all local actors including the
+/// instance actor if neither is given. The new keys are sent to followers and known instances.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RotateKeys {
+  pub person_id: Option<PersonId>,
+  pub community_id: Option<CommunityId>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RotateKeysResponse {
+  /// Number of actors whose keys were rotated. When rotating all keys, this happens in the
+  /// background after the response.
+  pub rotated: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ListActivityDeliveriesResponse {
+  pub pending_count: i64,
+  pub failed_count: i64,
+  pub deliveries: Vec<ActivityDelivery>,
+}
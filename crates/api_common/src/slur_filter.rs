@@ -0,0 +1,180 @@
+use crate::utils::blocking;
+use lemmy_db_schema::{
+  source::slur_filter_entry::{SlurFilterAction, SlurFilterEntry},
+  utils::DbPool,
+};
+use lemmy_utils::{
+  error::LemmyError,
+  utils::{check_slurs, remove_slurs},
+};
+use once_cell::sync::Lazy;
+use regex::{Regex, RegexBuilder};
+use std::sync::{Arc, RwLock};
+use tracing::warn;
+
+/// The slur filter entries from the database, compiled on first use. Cleared whenever an admin
+/// changes the entries.
+static SLUR_FILTER: Lazy<RwLock<Option<Arc<SlurFilter>>>> = Lazy::new(|| RwLock::new(None));
+
+const MAX_PATTERN_LENGTH: usize = 1000;
+
+/// The compiled slur filter entries, with all patterns of an action combined into one regex.
+#[derive(Debug, Default)]
+pub struct SlurFilter {
+  reject: Option<Regex>,
+  replace: Option<Regex>,
+  hold: Option<Regex>,
+}
+
+impl SlurFilter {
+  fn new(entries: &[SlurFilterEntry]) -> Self {
+    let combine = |action: SlurFilterAction| {
+      let patterns = entries
+        .iter()
+        .filter(|e| e.action == action)
+        // Entries are validated when they are saved, so this should never happen
+        .filter(|e| match build_slur_regex(&e.pattern) {
+          Ok(_) => true,
+          Err(err) => {
+            warn!("Skipping invalid slur filter entry {:?}: {}", e.id, err);
+            false
+          }
+        })
+        .map(|e| format!("(?:{})", e.pattern))
+        .collect::<Vec<_>>();
+      if patterns.is_empty() {
+        None
+      } else {
+        build_slur_regex(&patterns.join("|")).ok()
+      }
+    };
+    SlurFilter {
+      reject: combine(SlurFilterAction::Reject),
+      replace: combine(SlurFilterAction::Replace),
+      hold: combine(SlurFilterAction::Hold),
+    }
+  }
+
+  /// For names which can't be altered, like usernames. Any match is rejected.
+  pub fn check_name(&self, text: &str) -> Result<(), LemmyError> {
+    check_slurs(text, &self.reject)?;
+    check_slurs(text, &self.replace)?;
+    check_slurs(text, &self.hold)
+  }
+
+  /// Rejects the text or replaces slurs in it. Also returns true if the content needs to be held
+  /// for approval by a mod.
+  pub fn apply(&self, text: &str) -> Result<(String, bool), LemmyError> {
+    check_slurs(text, &self.reject)?;
+    let text = remove_slurs(text, &self.replace);
+    let hold = self
+      .hold
+      .as_ref()
+      .map(|r| r.is_match(&text))
+      .unwrap_or(false);
+    Ok((text, hold))
+  }
+
+  pub fn apply_opt(&self, text: &Option<String>) -> Result<(Option<String>, bool), LemmyError> {
+    match text {
+      Some(t) => self.apply(t).map(|(t, hold)| (Some(t), hold)),
+      None => Ok((None, false)),
+    }
+  }
+
+  /// For content which can't be held for approval, like private messages. Holds are rejected.
+  pub fn apply_without_hold(&self, text: &str) -> Result<String, LemmyError> {
+    check_slurs(text, &self.hold)?;
+    Ok(self.apply(text)?.0)
+  }
+
+  pub fn apply_without_hold_opt(
+    &self,
+    text: &Option<String>,
+  ) -> Result<Option<String>, LemmyError> {
+    text
+      .as_ref()
+      .map(|t| self.apply_without_hold(t))
+      .transpose()
+  }
+}
+
+/// Returns the slur filter entries from the database, compiled and cached.
+#[tracing::instrument(skip_all)]
+pub async fn slur_filter(pool: &DbPool) -> Result<Arc<SlurFilter>, LemmyError> {
+  if let Some(filter) = SLUR_FILTER.read().expect("read slur filter").as_ref() {
+    return Ok(filter.clone());
+  }
+  let entries = blocking(pool, SlurFilterEntry::list).await??;
+  let filter = Arc::new(SlurFilter::new(&entries));
+  *SLUR_FILTER.write().expect("write slur filter") = Some(filter.clone());
+  Ok(filter)
+}
+
+/// Needs to be called after the slur filter entries were changed.
+pub fn clear_slur_filter_cache() {
+  *SLUR_FILTER.write().expect("write slur filter") = None;
+}
+
+fn build_slur_regex(pattern: &str) -> Result<Regex, regex::Error> {
+  RegexBuilder::new(pattern).case_insensitive(true).build()
+}
+
+/// Checks that a pattern submitted by an admin is valid.
+pub fn validate_slur_filter_pattern(pattern: &str) -> Result<(), LemmyError> {
+  if pattern.is_empty() || pattern.chars().count() > MAX_PATTERN_LENGTH {
+    return Err(LemmyError::from_message("invalid_slur_filter_entry"));
+  }
+  build_slur_regex(pattern).map_err(|e| LemmyError::from_error_message(e, "invalid_regex"))?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_db_schema::{newtypes::SlurFilterEntryId, utils::naive_now};
+
+  fn entry(pattern: &str, action: SlurFilterAction) -> SlurFilterEntry {
+    SlurFilterEntry {
+      id: SlurFilterEntryId::default(),
+      pattern: pattern.to_string(),
+      action,
+      published: naive_now(),
+      updated: None,
+    }
+  }
+
+  #[test]
+  fn test_slur_filter() {
+    let filter = SlurFilter::new(&[
+      entry("spam(mer)?", SlurFilterAction::Reject),
+      entry("darn", SlurFilterAction::Replace),
+      entry("heck", SlurFilterAction::Replace),
+      entry("crypto scam", SlurFilterAction::Hold),
+      entry("(unclosed", SlurFilterAction::Reject),
+    ]);
+
+    assert!(filter.apply("what a SPAMMER").is_err());
+    let (replaced, hold) = filter.apply("some Darn and heck").unwrap();
+    assert_eq!("some *removed* and *removed*", replaced);
+    assert!(!hold);
+    let (_, hold) = filter.apply("new Crypto Scam").unwrap();
+    assert!(hold);
+    assert!(filter.apply_without_hold("new crypto scam").is_err());
+    assert_eq!(
+      "*removed*",
+      filter.apply_without_hold("darn").unwrap().as_str()
+    );
+
+    assert!(filter.check_name("darn").is_err());
+    assert!(filter.check_name("crypto scam").is_err());
+    assert!(filter.check_name("nice_name").is_ok());
+  }
+
+  #[test]
+  fn test_validate_slur_filter_pattern() {
+    assert!(validate_slur_filter_pattern("").is_err());
+    assert!(validate_slur_filter_pattern("(unclosed").is_err());
+    assert!(validate_slur_filter_pattern("bad(word)?").is_ok());
+  }
+}
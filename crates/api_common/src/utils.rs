@@ -1,12 +1,16 @@
 use crate::{request::purge_image_from_pictrs, sensitive::Sensitive, site::FederatedInstances};
 use chrono::NaiveDateTime;
+use diesel::PgConnection;
 use lemmy_db_schema::{
   impls::person::is_banned,
-  newtypes::{CommunityId, LocalUserId, PersonId, PostId},
+  newtypes::{CommunityId, DbUrl, LocalUserId, PersonId, PostId},
   source::{
     comment::Comment,
-    community::Community,
+    community::{Community, CommunityFollower, CommunityVisibility},
+    email_queue::{EmailQueue, EmailQueueForm},
     email_verification::{EmailVerification, EmailVerificationForm},
+    instance::Instance,
+    local_user::EmailDigestFrequency,
     password_reset_request::PasswordResetRequest,
     person::Person,
     person_block::PersonBlock,
@@ -16,21 +20,24 @@ use lemmy_db_schema::{
     site::Site,
   },
   traits::{Crud, Readable},
-  utils::DbPool,
+  utils::{DbPool, FETCH_LIMIT_MAX},
+  CommentSortType,
   ListingType,
 };
 use lemmy_db_views::{
   comment_view::CommentQuery,
+  private_message_view::PrivateMessageQuery,
   structs::{LocalUserSettingsView, LocalUserView},
 };
-use lemmy_db_views_actor::structs::{
-  CommunityModeratorView,
-  CommunityPersonBanView,
-  CommunityView,
+use lemmy_db_views_actor::{
+  comment_reply_view::CommentReplyQuery,
+  person_mention_view::PersonMentionQuery,
+  post_notification_view::PostNotificationQuery,
+  structs::{CommunityModeratorView, CommunityPersonBanView, CommunityView},
 };
 use lemmy_utils::{
-  claims::Claims,
-  email::{send_email, translations::Lang},
+  claims::{Claims, UnsubscribeClaims},
+  email::translations::Lang,
   error::LemmyError,
   settings::structs::Settings,
   utils::generate_random_string,
@@ -244,6 +251,39 @@ pub async fn check_community_deleted_or_removed(
   }
 }
 
+/// Content of private communities is only visible to approved followers, mods and admins.
+#[tracing::instrument(skip_all)]
+pub async fn check_community_visible(
+  community_id: CommunityId,
+  person: Option<&Person>,
+  pool: &DbPool,
+) -> Result<(), LemmyError> {
+  let community = blocking(pool, move |conn| Community::read(conn, community_id))
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_community"))?;
+  if community.visibility != CommunityVisibility::Private {
+    return Ok(());
+  }
+  let person = match person {
+    Some(p) if p.admin => return Ok(()),
+    Some(p) => p,
+    None => return Err(LemmyError::from_message("community_is_private")),
+  };
+  let person_id = person.id;
+  let visible = blocking(pool, move |conn| {
+    Ok(
+      CommunityFollower::is_approved_follower(conn, community_id, person_id)?
+        || CommunityView::is_mod_or_admin(conn, person_id, community_id),
+    ) as Result<bool, LemmyError>
+  })
+  .await??;
+  if visible {
+    Ok(())
+  } else {
+    Err(LemmyError::from_message("community_is_private"))
+  }
+}
+
 pub fn check_post_deleted_or_removed(post: &Post) -> Result<(), LemmyError> {
   if post.deleted || post.removed {
     Err(LemmyError::from_message("deleted"))
@@ -299,13 +339,16 @@ pub async fn check_private_instance(
 pub async fn build_federated_instances(
   pool: &DbPool,
   settings: &Settings,
+  is_admin: bool,
 ) -> Result<Option<FederatedInstances>, LemmyError> {
   let federation_config = &settings.federation;
   let hostname = &settings.hostname;
   let federation = federation_config.to_owned();
   if federation.enabled {
-    let distinct_communities = blocking(pool, move |conn| {
-      Community::distinct_federated_communities(conn)
+    let (distinct_communities, instances) = blocking(pool, move |conn| {
+      let distinct_communities = Community::distinct_federated_communities(conn)?;
+      let instances = Instance::list(conn)?;
+      Ok((distinct_communities, instances)) as Result<(Vec<DbUrl>, Vec<Instance>), LemmyError>
     })
     .await??;
 
@@ -329,10 +372,24 @@ pub async fn build_federated_instances(
     linked.sort_unstable();
     linked.dedup();
 
+    // The delivery health of other instances is only for admins
+    let (dead, instances) = if is_admin {
+      let dead = instances
+        .iter()
+        .filter(|i| i.is_dead())
+        .map(|i| i.domain.clone())
+        .collect();
+      (Some(dead), Some(instances))
+    } else {
+      (None, None)
+    };
+
     Ok(Some(FederatedInstances {
       linked,
       allowed,
       blocked,
+      dead,
+      instances,
     }))
   } else {
     Ok(None)
@@ -366,30 +423,232 @@ pub fn honeypot_check(honeypot: &Option<String>) -> Result<(), LemmyError> {
   }
 }
 
-pub fn send_email_to_user(
+/// Adds an email to the queue, from which it is sent by a background task. This way slow or
+/// unavailable smtp servers don't hold up api requests, and failed emails are retried.
+pub async fn queue_email(
+  subject: &str,
+  to_email: &str,
+  to_name: &str,
+  html: &str,
+  unsubscribe_url: Option<String>,
+  pool: &DbPool,
+  settings: &Settings,
+) -> Result<(), LemmyError> {
+  if settings.email.is_none() {
+    return Err(LemmyError::from_message("no_email_setup"));
+  }
+  let form = EmailQueueForm {
+    to_email: to_email.to_string(),
+    to_name: to_name.to_string(),
+    subject: subject.to_string(),
+    html: html.to_string(),
+    unsubscribe_url,
+  };
+  blocking(pool, move |conn| EmailQueue::enqueue(conn, &form))
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_queue_email"))?;
+  Ok(())
+}
+
+/// Link which disables notification emails for the user, without having to log in
+pub fn unsubscribe_url(
+  local_user_id: LocalUserId,
+  secret: &Secret,
+  settings: &Settings,
+) -> Result<String, LemmyError> {
+  let token = UnsubscribeClaims::jwt(local_user_id.0, &secret.jwt_secret, &settings.hostname)?;
+  Ok(format!(
+    "{}/api/v3/user/unsubscribe?token={}",
+    settings.get_protocol_and_hostname(),
+    token
+  ))
+}
+
+pub async fn send_email_to_user(
   local_user_view: &LocalUserView,
   subject: &str,
   body: &str,
+  pool: &DbPool,
+  secret: &Secret,
   settings: &Settings,
 ) {
-  if local_user_view.person.banned || !local_user_view.local_user.send_notifications_to_email {
+  if local_user_view.person.banned
+    || !local_user_view.local_user.send_notifications_to_email
+    || local_user_view.local_user.email_digest_frequency != EmailDigestFrequency::Immediate
+  {
     return;
   }
 
   if let Some(user_email) = &local_user_view.local_user.email {
-    match send_email(
+    let unsubscribe_url = match unsubscribe_url(local_user_view.local_user.id, secret, settings) {
+      Ok(url) => url,
+      Err(e) => {
+        warn!("{}", e);
+        return;
+      }
+    };
+    match queue_email(
       subject,
       user_email,
       &local_user_view.person.name,
       body,
+      Some(unsubscribe_url),
+      pool,
       settings,
-    ) {
+    )
+    .await
+    {
       Ok(_o) => _o,
       Err(e) => warn!("{}", e),
     };
   }
 }
 
+/// Subject and body of the email about a new post in a community which the user subscribed to.
+/// These aren't translated yet.
+pub fn post_notification_email(
+  post: &Post,
+  creator_name: &str,
+  community_name: &str,
+  settings: &Settings,
+) -> (String, String) {
+  let post_link = format!("{}/post/{}", settings.get_protocol_and_hostname(), post.id);
+  let subject = format!("New post in {} by {}", community_name, creator_name);
+  let body = format!(
+    "<h1>{}</h1><br><div>{} - {}</div><br><a href=\"{}\">post</a>",
+    post.name,
+    creator_name,
+    post.body.as_deref().unwrap_or_default(),
+    post_link
+  );
+  (subject, body)
+}
+
+/// Queues a single email with all notifications of the user which were published after `since`
+/// and are still unread. Returns false if there was nothing to send.
+pub fn queue_email_digest(
+  conn: &PgConnection,
+  local_user_id: LocalUserId,
+  since: NaiveDateTime,
+  secret: &Secret,
+  settings: &Settings,
+) -> Result<bool, LemmyError> {
+  let local_user_view = LocalUserView::read(conn, local_user_id)?;
+  let user_email = match &local_user_view.local_user.email {
+    Some(e) if !local_user_view.person.banned => e,
+    _ => return Ok(false),
+  };
+  let person_id = local_user_view.person.id;
+  let lang = get_user_lang(&local_user_view);
+  let inbox_link = format!("{}/inbox", settings.get_protocol_and_hostname());
+
+  // (published, subject, body) of each notification
+  let mut notifications: Vec<(NaiveDateTime, String, String)> = vec![];
+  let mentions = PersonMentionQuery::builder()
+    .conn(conn)
+    .recipient_id(Some(person_id))
+    .my_person_id(Some(person_id))
+    .sort(Some(CommentSortType::New))
+    .unread_only(Some(true))
+    .limit(Some(FETCH_LIMIT_MAX))
+    .build()
+    .list()?;
+  for m in mentions
+    .iter()
+    .filter(|m| m.person_mention.published > since)
+  {
+    notifications.push((
+      m.person_mention.published,
+      lang.notification_mentioned_by_subject(&m.creator.name),
+      lang.notification_mentioned_by_body(&m.comment.content, &inbox_link, &m.creator.name),
+    ));
+  }
+  let replies = CommentReplyQuery::builder()
+    .conn(conn)
+    .recipient_id(Some(person_id))
+    .my_person_id(Some(person_id))
+    .sort(Some(CommentSortType::New))
+    .unread_only(Some(true))
+    .limit(Some(FETCH_LIMIT_MAX))
+    .build()
+    .list()?;
+  for r in replies.iter().filter(|r| r.comment_reply.published > since) {
+    let (subject, body) = if r.comment.parent_comment_id().is_some() {
+      (
+        lang.notification_comment_reply_subject(&r.creator.name),
+        lang.notification_comment_reply_body(&r.comment.content, &inbox_link, &r.creator.name),
+      )
+    } else {
+      (
+        lang.notification_post_reply_subject(&r.creator.name),
+        lang.notification_post_reply_body(&r.comment.content, &inbox_link, &r.creator.name),
+      )
+    };
+    notifications.push((r.comment_reply.published, subject, body));
+  }
+  let messages = PrivateMessageQuery::builder()
+    .conn(conn)
+    .recipient_id(person_id)
+    .unread_only(Some(true))
+    .limit(Some(FETCH_LIMIT_MAX))
+    .build()
+    .list()?;
+  for m in messages
+    .iter()
+    .filter(|m| m.private_message.recipient_id == person_id && m.private_message.published > since)
+  {
+    notifications.push((
+      m.private_message.published,
+      lang.notification_private_message_subject(&m.creator.name),
+      lang.notification_private_message_body(
+        &inbox_link,
+        &m.private_message.content,
+        &m.creator.name,
+      ),
+    ));
+  }
+
+  let post_notifications = PostNotificationQuery::builder()
+    .conn(conn)
+    .recipient_id(person_id)
+    .unread_only(Some(true))
+    .limit(Some(FETCH_LIMIT_MAX))
+    .build()
+    .list()?;
+  for p in post_notifications
+    .iter()
+    .filter(|p| p.post_notification.published > since)
+  {
+    let (subject, body) =
+      post_notification_email(&p.post, &p.creator.name, &p.community.name, settings);
+    notifications.push((p.post_notification.published, subject, body));
+  }
+
+  if notifications.is_empty() {
+    return Ok(false);
+  }
+  notifications.sort_by_key(|n| std::cmp::Reverse(n.0));
+  // The digest uses the subject of the newest notification. Each translated body starts with its
+  // own heading, so they are simply concatenated.
+  let subject = notifications[0].1.to_owned();
+  let body = notifications.into_iter().map(|n| n.2).collect::<String>();
+
+  let form = EmailQueueForm {
+    to_email: user_email.to_owned(),
+    to_name: local_user_view.person.name.to_owned(),
+    subject,
+    html: body,
+    unsubscribe_url: Some(unsubscribe_url(
+      local_user_view.local_user.id,
+      secret,
+      settings,
+    )?),
+  };
+  EmailQueue::enqueue(conn, &form)
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_queue_email"))?;
+  Ok(true)
+}
+
 pub async fn send_password_reset_email(
   user: &LocalUserView,
   pool: &DbPool,
@@ -412,7 +671,16 @@ pub async fn send_password_reset_email(
   let protocol_and_hostname = settings.get_protocol_and_hostname();
   let reset_link = format!("{}/password_change/{}", protocol_and_hostname, &token);
   let body = &lang.password_reset_body(reset_link, &user.person.name);
-  send_email(subject, email, &user.person.name, body, settings)
+  queue_email(
+    subject,
+    email,
+    &user.person.name,
+    body,
+    None,
+    pool,
+    settings,
+  )
+  .await
 }
 
 /// Send a verification email
@@ -437,20 +705,39 @@ pub async fn send_verification_email(
   let lang = get_user_lang(user);
   let subject = lang.verify_email_subject(&settings.hostname);
   let body = lang.verify_email_body(&settings.hostname, &user.person.name, verify_link);
-  send_email(&subject, new_email, &user.person.name, &body, settings)?;
+  queue_email(
+    &subject,
+    new_email,
+    &user.person.name,
+    &body,
+    None,
+    pool,
+    settings,
+  )
+  .await?;
 
   Ok(())
 }
 
-pub fn send_email_verification_success(
+pub async fn send_email_verification_success(
   user: &LocalUserView,
+  pool: &DbPool,
   settings: &Settings,
 ) -> Result<(), LemmyError> {
   let email = &user.local_user.email.to_owned().expect("email");
   let lang = get_user_lang(user);
   let subject = &lang.email_verified_subject(&user.person.actor_id);
   let body = &lang.email_verified_body();
-  send_email(subject, email, &user.person.name, body, settings)
+  queue_email(
+    subject,
+    email,
+    &user.person.name,
+    body,
+    None,
+    pool,
+    settings,
+  )
+  .await
 }
 
 pub fn get_user_lang(user: &LocalUserView) -> Lang {
@@ -461,15 +748,25 @@ pub fn get_user_lang(user: &LocalUserView) -> Lang {
   })
 }
 
-pub fn send_application_approved_email(
+pub async fn send_application_approved_email(
   user: &LocalUserView,
+  pool: &DbPool,
   settings: &Settings,
 ) -> Result<(), LemmyError> {
   let email = &user.local_user.email.to_owned().expect("email");
   let lang = get_user_lang(user);
   let subject = lang.registration_approved_subject(&user.person.actor_id);
   let body = lang.registration_approved_body(&settings.hostname);
-  send_email(&subject, email, &user.person.name, &body, settings)
+  queue_email(
+    &subject,
+    email,
+    &user.person.name,
+    &body,
+    None,
+    pool,
+    settings,
+  )
+  .await
 }
 
 pub async fn check_registration_application(
@@ -0,0 +1,235 @@
+use crate::sensitive::Sensitive;
+use lemmy_db_schema::{
+  newtypes::{CommunityId, PersonId, WikiPageId, WikiPageRevisionId},
+  source::wiki_page::{WikiPage, WikiPageRevision},
+};
+use lemmy_db_views_actor::structs::{WikiContributorView, WikiPageRevisionView};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GetWikiPage {
+  pub community_id: CommunityId,
+  pub name: String,
+  pub auth: Option<Sensitive<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WikiPageResponse {
+  pub wiki_page: WikiPage,
+  /// Whether you are allowed to edit the wiki of this community
+  pub can_edit: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ListWikiPages {
+  pub community_id: CommunityId,
+  pub auth: Option<Sensitive<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ListWikiPagesResponse {
+  pub wiki_pages: Vec<WikiPage>,
+  pub can_edit: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CreateWikiPage {
+  pub community_id: CommunityId,
+  /// Used in the url of the page. Only lowercase letters, numbers, `-` and `_` are allowed.
+  pub name: String,
+  pub title: String,
+  pub body: String,
+  /// Description of the edit, shown in the revision history
+  pub summary: Option<String>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct EditWikiPage {
+  pub id: WikiPageId,
+  pub title: Option<String>,
+  pub body: String,
+  pub summary: Option<String>,
+  pub auth: Sensitive<String>,
+}
+
+/// Only mods can delete wiki pages
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DeleteWikiPage {
+  pub id: WikiPageId,
+  pub deleted: bool,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ListWikiPageRevisions {
+  pub id: WikiPageId,
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Option<Sensitive<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ListWikiPageRevisionsResponse {
+  /// Newest first
+  pub revisions: Vec<WikiPageRevisionView>,
+}
+
+/// Compares a revision with an older one of the same page. Without `compare_to`, it is compared
+/// with the revision right before it.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GetWikiPageDiff {
+  pub revision_id: WikiPageRevisionId,
+  pub compare_to: Option<WikiPageRevisionId>,
+  pub auth: Option<Sensitive<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WikiPageDiffResponse {
+  /// None if the revision is the first one of the page
+  pub from: Option<WikiPageRevision>,
+  pub to: WikiPageRevision,
+  pub diff: Vec<WikiDiffLine>,
+}
+
+/// Makes the content of an old revision the current one, by storing it as a new revision
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RevertWikiPage {
+  pub revision_id: WikiPageRevisionId,
+  pub summary: Option<String>,
+  pub auth: Sensitive<String>,
+}
+
+/// Lets a person edit the wiki of a community without being a mod
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AddWikiContributor {
+  pub community_id: CommunityId,
+  pub person_id: PersonId,
+  pub added: bool,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ListWikiContributors {
+  pub community_id: CommunityId,
+  pub auth: Option<Sensitive<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WikiContributorsResponse {
+  pub contributors: Vec<WikiContributorView>,
+}
+
+/// A single line of a diff between two wiki page revisions
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", content = "line")]
+pub enum WikiDiffLine {
+  Unchanged(String),
+  Added(String),
+  Removed(String),
+}
+
+/// Above this many line comparisons, the changed part is shown as removed and added as a whole
+const MAX_DIFF_COMPARISONS: usize = 4_000_000;
+
+/// Line based diff of two texts, using the longest common subsequence of their lines.
+pub fn diff_lines(old: &str, new: &str) -> Vec<WikiDiffLine> {
+  let old = old.lines().collect::<Vec<&str>>();
+  let new = new.lines().collect::<Vec<&str>>();
+
+  // The common start and end don't need to go through the quadratic part
+  let prefix = old
+    .iter()
+    .zip(new.iter())
+    .take_while(|(a, b)| a == b)
+    .count();
+  let suffix = old[prefix..]
+    .iter()
+    .rev()
+    .zip(new[prefix..].iter().rev())
+    .take_while(|(a, b)| a == b)
+    .count();
+  let old_mid = &old[prefix..old.len() - suffix];
+  let new_mid = &new[prefix..new.len() - suffix];
+
+  let mut diff = old[..prefix]
+    .iter()
+    .map(|l| WikiDiffLine::Unchanged(l.to_string()))
+    .collect::<Vec<_>>();
+
+  if old_mid.len() * new_mid.len() > MAX_DIFF_COMPARISONS {
+    diff.extend(old_mid.iter().map(|l| WikiDiffLine::Removed(l.to_string())));
+    diff.extend(new_mid.iter().map(|l| WikiDiffLine::Added(l.to_string())));
+  } else {
+    // lcs[i][j] is the length of the longest common subsequence of old_mid[i..] and new_mid[j..]
+    let mut lcs = vec![vec![0usize; new_mid.len() + 1]; old_mid.len() + 1];
+    for i in (0..old_mid.len()).rev() {
+      for j in (0..new_mid.len()).rev() {
+        lcs[i][j] = if old_mid[i] == new_mid[j] {
+          lcs[i + 1][j + 1] + 1
+        } else {
+          lcs[i + 1][j].max(lcs[i][j + 1])
+        };
+      }
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < old_mid.len() && j < new_mid.len() {
+      if old_mid[i] == new_mid[j] {
+        diff.push(WikiDiffLine::Unchanged(old_mid[i].to_string()));
+        i += 1;
+        j += 1;
+      } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+        diff.push(WikiDiffLine::Removed(old_mid[i].to_string()));
+        i += 1;
+      } else {
+        diff.push(WikiDiffLine::Added(new_mid[j].to_string()));
+        j += 1;
+      }
+    }
+    diff.extend(
+      old_mid[i..]
+        .iter()
+        .map(|l| WikiDiffLine::Removed(l.to_string())),
+    );
+    diff.extend(
+      new_mid[j..]
+        .iter()
+        .map(|l| WikiDiffLine::Added(l.to_string())),
+    );
+  }
+
+  diff.extend(
+    old[old.len() - suffix..]
+      .iter()
+      .map(|l| WikiDiffLine::Unchanged(l.to_string())),
+  );
+  diff
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::wiki::{diff_lines, WikiDiffLine::*};
+
+  #[test]
+  fn test_diff_lines() {
+    let old = "# Rules\nBe nice\nNo spam\nHave fun";
+    let new = "# Rules\nBe very nice\nNo spam\nNo memes\nHave fun";
+    assert_eq!(
+      vec![
+        Unchanged("# Rules".to_string()),
+        Removed("Be nice".to_string()),
+        Added("Be very nice".to_string()),
+        Unchanged("No spam".to_string()),
+        Added("No memes".to_string()),
+        Unchanged("Have fun".to_string()),
+      ],
+      diff_lines(old, new)
+    );
+    assert_eq!(vec![Added("text".to_string())], diff_lines("", "text"));
+    assert_eq!(vec![Removed("text".to_string())], diff_lines("text", ""));
+    assert_eq!(
+      vec![Unchanged("same".to_string())],
+      diff_lines("same", "same")
+    );
+  }
+}
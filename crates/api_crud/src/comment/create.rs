@@ -1,11 +1,19 @@
 use crate::PerformCrud;
 use actix_web::web::Data;
 use lemmy_api_common::{
+  automod::{
+    check_comment_automod,
+    is_held_for_approval,
+    remove_comment_by_automod,
+    AutomodOutcome,
+  },
   comment::{CommentResponse, CreateComment},
+  slur_filter::slur_filter,
   utils::{
     blocking,
     check_community_ban,
     check_community_deleted_or_removed,
+    check_community_visible,
     check_post_deleted_or_removed,
     get_local_user_view_from_jwt,
     get_post,
@@ -21,6 +29,7 @@ use lemmy_db_schema::{
   source::{
     comment::{Comment, CommentForm, CommentLike, CommentLikeForm},
     comment_reply::CommentReply,
+    community::Community,
     person_mention::PersonMention,
   },
   traits::{Crud, Likeable},
@@ -52,6 +61,9 @@ impl PerformCrud for CreateComment {
 
     let content_slurs_removed =
       remove_slurs(&data.content.to_owned(), &context.settings().slur_regex());
+    let (content_slurs_removed, slur_filter_held) = slur_filter(context.pool())
+      .await?
+      .apply(&content_slurs_removed)?;
 
     // Check for a community ban
     let post_id = data.post_id;
@@ -59,6 +71,7 @@ impl PerformCrud for CreateComment {
     let community_id = post.community_id;
 
     check_community_ban(local_user_view.person.id, community_id, context.pool()).await?;
+    check_community_visible(community_id, Some(&local_user_view.person), context.pool()).await?;
     check_community_deleted_or_removed(community_id, context.pool()).await?;
     check_post_deleted_or_removed(&post)?;
 
@@ -67,6 +80,27 @@ impl PerformCrud for CreateComment {
       return Err(LemmyError::from_message("locked"));
     }
 
+    let automod_outcome = check_comment_automod(
+      &content_slurs_removed,
+      false,
+      &local_user_view.person,
+      community_id,
+      context.pool(),
+    )
+    .await?;
+    let community = blocking(context.pool(), move |conn| {
+      Community::read(conn, community_id)
+    })
+    .await??;
+    let pending = is_held_for_approval(
+      &automod_outcome,
+      slur_filter_held,
+      &community,
+      local_user_view.person.id,
+      context.pool(),
+    )
+    .await?;
+
     // Fetch the parent, if it exists
     let parent_opt = if let Some(parent_id) = data.parent_id {
       blocking(context.pool(), move |conn| Comment::read(conn, parent_id))
@@ -88,6 +122,7 @@ impl PerformCrud for CreateComment {
       content: content_slurs_removed,
       post_id: data.post_id,
       creator_id: local_user_view.person.id,
+      pending: Some(pending),
       ..CommentForm::default()
     };
 
@@ -116,18 +151,32 @@ impl PerformCrud for CreateComment {
       .await?
       .map_err(|e| e.with_message("couldnt_create_comment"))?;
 
+    // Comments which are removed by automod or held for approval don't send out notifications, and
+    // are not federated
+    let removed_by_automod = if let AutomodOutcome::Remove { reason } = automod_outcome {
+      remove_comment_by_automod(inserted_comment_id, reason, context.pool()).await?;
+      true
+    } else {
+      false
+    };
+    let hidden = removed_by_automod || pending;
+
     // Scan the comment for user mentions, add those rows
     let post_id = post.id;
-    let mentions = scrape_text_for_mentions(&comment_form.content);
-    let recipient_ids = send_local_notifs(
-      mentions,
-      &updated_comment,
-      &local_user_view.person,
-      &post,
-      true,
-      context,
-    )
-    .await?;
+    let recipient_ids = if hidden {
+      Vec::new()
+    } else {
+      let mentions = scrape_text_for_mentions(&comment_form.content);
+      send_local_notifs(
+        mentions,
+        &updated_comment,
+        &local_user_view.person,
+        &post,
+        true,
+        context,
+      )
+      .await?
+    };
 
     // You like your own comment by default
     let like_form = CommentLikeForm {
@@ -142,15 +191,17 @@ impl PerformCrud for CreateComment {
       .await?
       .map_err(|e| LemmyError::from_error_message(e, "couldnt_like_comment"))?;
 
-    let apub_comment: ApubComment = updated_comment.into();
-    CreateOrUpdateComment::send(
-      apub_comment.clone(),
-      &local_user_view.person.clone().into(),
-      CreateOrUpdateType::Create,
-      context,
-      &mut 0,
-    )
-    .await?;
+    if !hidden {
+      let apub_comment: ApubComment = updated_comment.into();
+      CreateOrUpdateComment::send(
+        apub_comment.clone(),
+        &local_user_view.person.clone().into(),
+        CreateOrUpdateType::Create,
+        context,
+        &mut 0,
+      )
+      .await?;
+    }
 
     // If its a reply, mark the parent as read
     if let Some(parent) = parent_opt {
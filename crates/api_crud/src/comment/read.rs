@@ -2,8 +2,14 @@ use crate::PerformCrud;
 use actix_web::web::Data;
 use lemmy_api_common::{
   comment::{CommentResponse, GetComment},
-  utils::{blocking, check_private_instance, get_local_user_view_from_jwt_opt},
+  utils::{
+    blocking,
+    check_community_visible,
+    check_private_instance,
+    get_local_user_view_from_jwt_opt,
+  },
 };
+use lemmy_db_schema::source::community::CommunityVisibility;
 use lemmy_db_views::structs::CommentView;
 use lemmy_utils::{error::LemmyError, ConnectionId};
 use lemmy_websocket::LemmyContext;
@@ -25,7 +31,7 @@ impl PerformCrud for GetComment {
 
     check_private_instance(&local_user_view, context.pool()).await?;
 
-    let person_id = local_user_view.map(|u| u.person.id);
+    let person_id = local_user_view.as_ref().map(|u| u.person.id);
     let id = data.id;
     let comment_view = blocking(context.pool(), move |conn| {
       CommentView::read(conn, id, person_id)
@@ -33,6 +39,18 @@ impl PerformCrud for GetComment {
     .await?
     .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_comment"))?;
 
+    // Comments in local-only communities are only visible to logged in users
+    if comment_view.community.visibility == CommunityVisibility::LocalOnly && person_id.is_none() {
+      return Err(LemmyError::from_message("couldnt_find_comment"));
+    }
+    check_community_visible(
+      comment_view.community.id,
+      local_user_view.as_ref().map(|u| &u.person),
+      context.pool(),
+    )
+    .await
+    .map_err(|e| e.with_message("couldnt_find_comment"))?;
+
     Ok(Self::Response {
       comment_view,
       form_id: None,
@@ -1,6 +1,13 @@
 use actix_web::web::Data;
 use lemmy_api_common::{
+  automod::{
+    check_comment_automod,
+    is_edit_held_for_approval,
+    remove_comment_by_automod,
+    AutomodOutcome,
+  },
   comment::{CommentResponse, EditComment},
+  slur_filter::slur_filter,
   utils::{
     blocking,
     check_community_ban,
@@ -66,35 +73,72 @@ impl PerformCrud for EditComment {
     // Do the update
     let content_slurs_removed =
       remove_slurs(&data.content.to_owned(), &context.settings().slur_regex());
+    let (content_slurs_removed, slur_filter_held) = slur_filter(context.pool())
+      .await?
+      .apply(&content_slurs_removed)?;
+    let community_id = orig_comment.community.id;
+    let automod_outcome = check_comment_automod(
+      &content_slurs_removed,
+      true,
+      &local_user_view.person,
+      community_id,
+      context.pool(),
+    )
+    .await?;
+    // Edits matching automod rules or the slur filter may need to be approved again
+    let hold = is_edit_held_for_approval(
+      &automod_outcome,
+      slur_filter_held,
+      community_id,
+      local_user_view.person.id,
+      context.pool(),
+    )
+    .await?;
     let comment_id = data.comment_id;
     let updated_comment = blocking(context.pool(), move |conn| {
+      if hold {
+        Comment::update_pending(conn, comment_id, true)?;
+      }
       Comment::update_content(conn, comment_id, &content_slurs_removed)
     })
     .await?
     .map_err(|e| LemmyError::from_error_message(e, "couldnt_update_comment"))?;
+    let removed_by_automod = if let AutomodOutcome::Remove { reason } = automod_outcome {
+      remove_comment_by_automod(comment_id, reason, context.pool()).await?;
+      true
+    } else {
+      false
+    };
 
-    // Do the mentions / recipients
-    let updated_comment_content = updated_comment.content.to_owned();
-    let mentions = scrape_text_for_mentions(&updated_comment_content);
-    let recipient_ids = send_local_notifs(
-      mentions,
-      &updated_comment,
-      &local_user_view.person,
-      &orig_comment.post,
-      false,
-      context,
-    )
-    .await?;
+    // Comments which are held for approval or removed by automod don't send out notifications,
+    // and are not federated
+    let recipient_ids = if updated_comment.pending || removed_by_automod {
+      Vec::new()
+    } else {
+      // Do the mentions / recipients
+      let updated_comment_content = updated_comment.content.to_owned();
+      let mentions = scrape_text_for_mentions(&updated_comment_content);
+      let recipient_ids = send_local_notifs(
+        mentions,
+        &updated_comment,
+        &local_user_view.person,
+        &orig_comment.post,
+        false,
+        context,
+      )
+      .await?;
 
-    // Send the apub update
-    CreateOrUpdateComment::send(
-      updated_comment.into(),
-      &local_user_view.person.into(),
-      CreateOrUpdateType::Update,
-      context,
-      &mut 0,
-    )
-    .await?;
+      // Send the apub update
+      CreateOrUpdateComment::send(
+        updated_comment.into(),
+        &local_user_view.person.into(),
+        CreateOrUpdateType::Update,
+        context,
+        &mut 0,
+      )
+      .await?;
+      recipient_ids
+    };
 
     send_comment_ws_message(
       data.comment_id,
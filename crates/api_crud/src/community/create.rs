@@ -3,6 +3,7 @@ use activitypub_federation::core::{object_id::ObjectId, signatures::generate_act
 use actix_web::web::Data;
 use lemmy_api_common::{
   community::{CommunityResponse, CreateCommunity},
+  slur_filter::slur_filter,
   utils::{blocking, get_local_user_view_from_jwt, is_admin},
 };
 use lemmy_apub::{
@@ -60,11 +61,15 @@ impl PerformCrud for CreateCommunity {
     // Check to make sure the icon and banners are urls
     let icon = diesel_option_overwrite_to_url(&data.icon)?;
     let banner = diesel_option_overwrite_to_url(&data.banner)?;
-    let description = diesel_option_overwrite(&data.description);
 
     check_slurs(&data.name, &context.settings().slur_regex())?;
     check_slurs(&data.title, &context.settings().slur_regex())?;
     check_slurs_opt(&data.description, &context.settings().slur_regex())?;
+    let slur_filter = slur_filter(context.pool()).await?;
+    slur_filter.check_name(&data.name)?;
+    let title = slur_filter.apply_without_hold(&data.title)?;
+    let description = slur_filter.apply_without_hold_opt(&data.description)?;
+    let description = diesel_option_overwrite(&description);
 
     if !is_valid_actor_name(&data.name, context.settings().actor_name_max_length) {
       return Err(LemmyError::from_message("invalid_community_name"));
@@ -87,7 +92,7 @@ impl PerformCrud for CreateCommunity {
 
     let community_form = CommunityForm {
       name: data.name.to_owned(),
-      title: data.title.to_owned(),
+      title,
       description,
       icon,
       banner,
@@ -99,6 +104,8 @@ impl PerformCrud for CreateCommunity {
       inbox_url: Some(generate_inbox_url(&community_actor_id)?),
       shared_inbox_url: Some(Some(generate_shared_inbox_url(&community_actor_id)?)),
       posting_restricted_to_mods: data.posting_restricted_to_mods,
+      require_approval: data.require_approval,
+      visibility: data.visibility,
       ..CommunityForm::default()
     };
 
@@ -2,6 +2,7 @@ use crate::PerformCrud;
 use actix_web::web::Data;
 use lemmy_api_common::{
   community::{CommunityResponse, EditCommunity},
+  slur_filter::slur_filter,
   utils::{blocking, get_local_user_view_from_jwt},
 };
 use lemmy_apub::protocol::activities::community::update::UpdateCommunity;
@@ -31,10 +32,13 @@ impl PerformCrud for EditCommunity {
 
     let icon = diesel_option_overwrite_to_url(&data.icon)?;
     let banner = diesel_option_overwrite_to_url(&data.banner)?;
-    let description = diesel_option_overwrite(&data.description);
 
     check_slurs_opt(&data.title, &context.settings().slur_regex())?;
     check_slurs_opt(&data.description, &context.settings().slur_regex())?;
+    let slur_filter = slur_filter(context.pool()).await?;
+    let title = slur_filter.apply_without_hold_opt(&data.title)?;
+    let description = slur_filter.apply_without_hold_opt(&data.description)?;
+    let description = diesel_option_overwrite(&description);
 
     // Verify its a mod (only mods can edit it)
     let community_id = data.community_id;
@@ -55,12 +59,14 @@ impl PerformCrud for EditCommunity {
 
     let community_form = CommunityForm {
       name: read_community.name,
-      title: data.title.to_owned().unwrap_or(read_community.title),
+      title: title.unwrap_or(read_community.title),
       description,
       icon,
       banner,
       nsfw: data.nsfw,
       posting_restricted_to_mods: data.posting_restricted_to_mods,
+      require_approval: data.require_approval,
+      visibility: data.visibility,
       updated: Some(naive_now()),
       ..CommunityForm::default()
     };
@@ -6,6 +6,7 @@ use serde::Deserialize;
 
 mod comment;
 mod community;
+mod multi_community;
 mod post;
 mod private_message;
 mod site;
@@ -81,6 +82,23 @@ pub async fn match_websocket_operation_crud(
       do_websocket_operation::<RemoveCommunity>(context, id, op, data).await
     }
 
+    // Multi community ops
+    UserOperationCrud::CreateMultiCommunity => {
+      do_websocket_operation::<CreateMultiCommunity>(context, id, op, data).await
+    }
+    UserOperationCrud::GetMultiCommunity => {
+      do_websocket_operation::<GetMultiCommunity>(context, id, op, data).await
+    }
+    UserOperationCrud::ListMultiCommunities => {
+      do_websocket_operation::<ListMultiCommunities>(context, id, op, data).await
+    }
+    UserOperationCrud::EditMultiCommunity => {
+      do_websocket_operation::<EditMultiCommunity>(context, id, op, data).await
+    }
+    UserOperationCrud::DeleteMultiCommunity => {
+      do_websocket_operation::<DeleteMultiCommunity>(context, id, op, data).await
+    }
+
     // Post ops
     UserOperationCrud::CreatePost => {
       do_websocket_operation::<CreatePost>(context, id, op, data).await
@@ -94,6 +112,9 @@ pub async fn match_websocket_operation_crud(
     UserOperationCrud::RemovePost => {
       do_websocket_operation::<RemovePost>(context, id, op, data).await
     }
+    UserOperationCrud::CrosspostPost => {
+      do_websocket_operation::<CrosspostPost>(context, id, op, data).await
+    }
 
     // Comment ops
     UserOperationCrud::CreateComment => {
@@ -0,0 +1,69 @@
+use crate::{
+  multi_community::{check_multi_community_name, check_multi_community_size},
+  PerformCrud,
+};
+use actix_web::web::Data;
+use lemmy_api_common::{
+  community::{CreateMultiCommunity, MultiCommunityResponse},
+  slur_filter::slur_filter,
+  utils::{blocking, get_local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::multi_community::{MultiCommunity, MultiCommunityForm},
+  utils::diesel_option_overwrite,
+};
+use lemmy_db_views_actor::structs::MultiCommunityView;
+use lemmy_utils::{
+  error::LemmyError,
+  utils::{check_slurs, check_slurs_opt},
+  ConnectionId,
+};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl PerformCrud for CreateMultiCommunity {
+  type Response = MultiCommunityResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<MultiCommunityResponse, LemmyError> {
+    let data: &CreateMultiCommunity = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    check_multi_community_name(&data.name)?;
+    check_multi_community_size(&data.community_ids)?;
+    check_slurs(&data.name, &context.settings().slur_regex())?;
+    check_slurs_opt(&data.description, &context.settings().slur_regex())?;
+    let slur_filter = slur_filter(context.pool()).await?;
+    let name = slur_filter.apply_without_hold(data.name.trim())?;
+    let description = slur_filter.apply_without_hold_opt(&data.description)?;
+
+    let form = MultiCommunityForm {
+      creator_id: local_user_view.person.id,
+      name,
+      description: diesel_option_overwrite(&description),
+      public: data.public,
+      updated: None,
+    };
+    let community_ids = data.community_ids.to_owned();
+    let multi_community = blocking(context.pool(), move |conn| {
+      MultiCommunity::create_with_communities(conn, &form, &community_ids)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_create_multi_community"))?;
+
+    let multi_community_id = multi_community.id;
+    let multi_community_view = blocking(context.pool(), move |conn| {
+      MultiCommunityView::read(conn, multi_community_id)
+    })
+    .await??;
+
+    Ok(MultiCommunityResponse {
+      multi_community_view,
+    })
+  }
+}
@@ -0,0 +1,44 @@
+use crate::{multi_community::check_multi_community_creator, PerformCrud};
+use actix_web::web::Data;
+use lemmy_api_common::{
+  community::{DeleteMultiCommunity, MultiCommunityResponse},
+  utils::{blocking, get_local_user_view_from_jwt},
+};
+use lemmy_db_schema::{source::multi_community::MultiCommunity, traits::Crud};
+use lemmy_db_views_actor::structs::MultiCommunityView;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl PerformCrud for DeleteMultiCommunity {
+  type Response = MultiCommunityResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<MultiCommunityResponse, LemmyError> {
+    let data: &DeleteMultiCommunity = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let multi_community_id = data.id;
+    let multi_community_view = blocking(context.pool(), move |conn| {
+      MultiCommunityView::read(conn, multi_community_id)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_multi_community"))?;
+    check_multi_community_creator(&multi_community_view.multi_community, &local_user_view)?;
+
+    blocking(context.pool(), move |conn| {
+      MultiCommunity::delete(conn, multi_community_id)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_update_multi_community"))?;
+
+    Ok(MultiCommunityResponse {
+      multi_community_view,
+    })
+  }
+}
@@ -0,0 +1,47 @@
+use crate::PerformCrud;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  community::{ListMultiCommunities, ListMultiCommunitiesResponse},
+  utils::{blocking, check_private_instance, get_local_user_view_from_jwt_opt},
+};
+use lemmy_db_views_actor::structs::MultiCommunityView;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl PerformCrud for ListMultiCommunities {
+  type Response = ListMultiCommunitiesResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<ListMultiCommunitiesResponse, LemmyError> {
+    let data: &ListMultiCommunities = self;
+    let local_user_view =
+      get_local_user_view_from_jwt_opt(data.auth.as_ref(), context.pool(), context.secret())
+        .await?;
+
+    check_private_instance(&local_user_view, context.pool()).await?;
+
+    let page = data.page;
+    let limit = data.limit;
+    let multi_communities = if data.mine.unwrap_or(false) {
+      let person_id = local_user_view
+        .map(|l| l.person.id)
+        .ok_or_else(|| LemmyError::from_message("not_logged_in"))?;
+      blocking(context.pool(), move |conn| {
+        MultiCommunityView::for_creator(conn, person_id)
+      })
+      .await??
+    } else {
+      blocking(context.pool(), move |conn| {
+        MultiCommunityView::list_public(conn, page, limit)
+      })
+      .await??
+    };
+
+    Ok(ListMultiCommunitiesResponse { multi_communities })
+  }
+}
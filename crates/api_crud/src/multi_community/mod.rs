@@ -0,0 +1,40 @@
+use lemmy_db_schema::{newtypes::CommunityId, source::multi_community::MultiCommunity};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyError;
+
+mod create;
+mod delete;
+mod list;
+mod read;
+mod update;
+
+/// The most communities a single feed can combine
+const MAX_MULTI_COMMUNITY_COMMUNITIES: usize = 50;
+
+fn check_multi_community_name(name: &str) -> Result<(), LemmyError> {
+  let len = name.trim().chars().count();
+  if len == 0 || len > 100 {
+    Err(LemmyError::from_message("invalid_multi_community_name"))
+  } else {
+    Ok(())
+  }
+}
+
+fn check_multi_community_size(community_ids: &[CommunityId]) -> Result<(), LemmyError> {
+  if community_ids.len() > MAX_MULTI_COMMUNITY_COMMUNITIES {
+    Err(LemmyError::from_message("too_many_communities_in_feed"))
+  } else {
+    Ok(())
+  }
+}
+
+fn check_multi_community_creator(
+  multi_community: &MultiCommunity,
+  local_user_view: &LocalUserView,
+) -> Result<(), LemmyError> {
+  if multi_community.creator_id != local_user_view.person.id {
+    Err(LemmyError::from_message("no_multi_community_edit_allowed"))
+  } else {
+    Ok(())
+  }
+}
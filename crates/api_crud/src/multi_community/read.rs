@@ -0,0 +1,47 @@
+use crate::PerformCrud;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  community::{GetMultiCommunity, MultiCommunityResponse},
+  utils::{blocking, check_private_instance, get_local_user_view_from_jwt_opt},
+};
+use lemmy_db_views_actor::structs::MultiCommunityView;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl PerformCrud for GetMultiCommunity {
+  type Response = MultiCommunityResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<MultiCommunityResponse, LemmyError> {
+    let data: &GetMultiCommunity = self;
+    let local_user_view =
+      get_local_user_view_from_jwt_opt(data.auth.as_ref(), context.pool(), context.secret())
+        .await?;
+
+    check_private_instance(&local_user_view, context.pool()).await?;
+
+    let multi_community_id = data.id;
+    let multi_community_view = blocking(context.pool(), move |conn| {
+      MultiCommunityView::read(conn, multi_community_id)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_multi_community"))?;
+
+    // Private feeds look the same as missing ones to everyone but their creator
+    let person_id = local_user_view.map(|l| l.person.id);
+    if !multi_community_view.multi_community.public
+      && Some(multi_community_view.multi_community.creator_id) != person_id
+    {
+      return Err(LemmyError::from_message("couldnt_find_multi_community"));
+    }
+
+    Ok(MultiCommunityResponse {
+      multi_community_view,
+    })
+  }
+}
@@ -0,0 +1,92 @@
+use crate::{
+  multi_community::{
+    check_multi_community_creator,
+    check_multi_community_name,
+    check_multi_community_size,
+  },
+  PerformCrud,
+};
+use actix_web::web::Data;
+use lemmy_api_common::{
+  community::{EditMultiCommunity, MultiCommunityResponse},
+  slur_filter::slur_filter,
+  utils::{blocking, get_local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::multi_community::{MultiCommunity, MultiCommunityForm},
+  traits::Crud,
+  utils::{diesel_option_overwrite, naive_now},
+};
+use lemmy_db_views_actor::structs::MultiCommunityView;
+use lemmy_utils::{
+  error::LemmyError,
+  utils::{check_slurs, check_slurs_opt},
+  ConnectionId,
+};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl PerformCrud for EditMultiCommunity {
+  type Response = MultiCommunityResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<MultiCommunityResponse, LemmyError> {
+    let data: &EditMultiCommunity = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let multi_community_id = data.id;
+    let orig = blocking(context.pool(), move |conn| {
+      MultiCommunity::read(conn, multi_community_id)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_multi_community"))?;
+    check_multi_community_creator(&orig, &local_user_view)?;
+
+    let slur_filter = slur_filter(context.pool()).await?;
+    let name = if let Some(name) = &data.name {
+      check_multi_community_name(name)?;
+      check_slurs(name, &context.settings().slur_regex())?;
+      slur_filter.apply_without_hold(name.trim())?
+    } else {
+      orig.name
+    };
+    check_slurs_opt(&data.description, &context.settings().slur_regex())?;
+    let description = slur_filter.apply_without_hold_opt(&data.description)?;
+
+    if let Some(community_ids) = data.community_ids.to_owned() {
+      check_multi_community_size(&community_ids)?;
+      blocking(context.pool(), move |conn| {
+        MultiCommunity::set_communities(conn, multi_community_id, &community_ids)
+      })
+      .await?
+      .map_err(|e| LemmyError::from_error_message(e, "couldnt_update_multi_community"))?;
+    }
+
+    let form = MultiCommunityForm {
+      creator_id: orig.creator_id,
+      name,
+      description: diesel_option_overwrite(&description),
+      public: data.public,
+      updated: Some(naive_now()),
+    };
+    blocking(context.pool(), move |conn| {
+      MultiCommunity::update(conn, multi_community_id, &form)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_update_multi_community"))?;
+
+    let multi_community_view = blocking(context.pool(), move |conn| {
+      MultiCommunityView::read(conn, multi_community_id)
+    })
+    .await??;
+
+    Ok(MultiCommunityResponse {
+      multi_community_view,
+    })
+  }
+}
@@ -1,12 +1,15 @@
 use crate::PerformCrud;
 use actix_web::web::Data;
 use lemmy_api_common::{
+  automod::{check_post_automod, remove_post_by_automod, AutomodOutcome},
   post::{CreatePost, PostResponse},
   request::fetch_site_data,
+  slur_filter::slur_filter,
   utils::{
     blocking,
     check_community_ban,
     check_community_deleted_or_removed,
+    check_community_visible,
     get_local_user_view_from_jwt,
     honeypot_check,
     mark_post_as_read,
@@ -22,6 +25,7 @@ use lemmy_db_schema::{
   source::{
     community::Community,
     post::{Post, PostForm, PostLike, PostLikeForm},
+    thread_subscription::{ThreadSubscription, ThreadSubscriptionForm},
   },
   traits::{Crud, Likeable},
   utils::diesel_option_overwrite,
@@ -29,10 +33,14 @@ use lemmy_db_schema::{
 use lemmy_db_views_actor::structs::CommunityView;
 use lemmy_utils::{
   error::LemmyError,
-  utils::{check_slurs, check_slurs_opt, clean_url_params, is_valid_post_title},
+  utils::{canonicalize_url, check_slurs, check_slurs_opt, clean_url_params, is_valid_post_title},
   ConnectionId,
 };
-use lemmy_websocket::{send::send_post_ws_message, LemmyContext, UserOperationCrud};
+use lemmy_websocket::{
+  send::{send_post_notifs, send_post_ws_message},
+  LemmyContext,
+  UserOperationCrud,
+};
 use tracing::{warn, Instrument};
 use url::Url;
 use webmention::{Webmention, WebmentionError};
@@ -55,34 +63,73 @@ impl PerformCrud for CreatePost {
     check_slurs(&data.name, slur_regex)?;
     check_slurs_opt(&data.body, slur_regex)?;
     honeypot_check(&data.honeypot)?;
+    let slur_filter = slur_filter(context.pool()).await?;
+    let (name, name_held) = slur_filter.apply(&data.name)?;
+    let (filtered_body, body_held) = slur_filter.apply_opt(&data.body)?;
 
     let data_url = data.url.as_ref();
     let url = Some(data_url.map(clean_url_params).map(Into::into)); // TODO no good way to handle a "clear"
-    let body = diesel_option_overwrite(&data.body);
+    let url_canonical = Some(data_url.map(canonicalize_url));
+    let body = diesel_option_overwrite(&filtered_body);
 
-    if !is_valid_post_title(&data.name) {
+    if !is_valid_post_title(&name) {
       return Err(LemmyError::from_message("invalid_post_title"));
     }
 
     check_community_ban(local_user_view.person.id, data.community_id, context.pool()).await?;
+    check_community_visible(
+      data.community_id,
+      Some(&local_user_view.person),
+      context.pool(),
+    )
+    .await?;
     check_community_deleted_or_removed(data.community_id, context.pool()).await?;
 
+    // Crossposts always point to the original post, never to another crosspost
+    let crosspost_of = if let Some(crosspost_of) = data.crosspost_of {
+      let original = blocking(context.pool(), move |conn| Post::read(conn, crosspost_of))
+        .await?
+        .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_post"))?;
+      if original.deleted || original.removed || original.pending {
+        return Err(LemmyError::from_message("couldnt_find_post"));
+      }
+      if original.community_id == data.community_id {
+        return Err(LemmyError::from_message("cant_crosspost_to_same_community"));
+      }
+      Some(original.crosspost_of.unwrap_or(original.id))
+    } else {
+      None
+    };
+
     let community_id = data.community_id;
     let community = blocking(context.pool(), move |conn| {
       Community::read(conn, community_id)
     })
     .await??;
-    if community.posting_restricted_to_mods {
-      let community_id = data.community_id;
-      let is_mod = blocking(context.pool(), move |conn| {
-        CommunityView::is_mod_or_admin(conn, local_user_view.local_user.person_id, community_id)
-      })
-      .await?;
-      if !is_mod {
-        return Err(LemmyError::from_message("only_mods_can_post_in_community"));
-      }
+    let person_id = local_user_view.person.id;
+    let is_mod = blocking(context.pool(), move |conn| {
+      CommunityView::is_mod_or_admin(conn, person_id, community_id)
+    })
+    .await?;
+    if community.posting_restricted_to_mods && !is_mod {
+      return Err(LemmyError::from_message("only_mods_can_post_in_community"));
     }
 
+    let automod_outcome = check_post_automod(
+      &name,
+      &filtered_body,
+      data_url,
+      false,
+      &local_user_view.person,
+      community_id,
+      context.pool(),
+    )
+    .await?;
+    let slur_filter_held = (name_held || body_held) && !is_mod;
+    let pending = automod_outcome == AutomodOutcome::Hold
+      || slur_filter_held
+      || (community.require_approval && !is_mod);
+
     // Fetch post links and pictrs cached image
     let (metadata_res, thumbnail_url) =
       fetch_site_data(context.client(), context.settings(), data_url).await;
@@ -91,8 +138,10 @@ impl PerformCrud for CreatePost {
       .unwrap_or_default();
 
     let post_form = PostForm {
-      name: data.name.trim().to_owned(),
+      name: name.trim().to_owned(),
       url,
+      url_canonical,
+      crosspost_of,
       body,
       community_id: data.community_id,
       creator_id: local_user_view.person.id,
@@ -101,6 +150,7 @@ impl PerformCrud for CreatePost {
       embed_description,
       embed_video_url,
       thumbnail_url: Some(thumbnail_url),
+      pending: Some(pending),
       ..PostForm::default()
     };
 
@@ -148,6 +198,36 @@ impl PerformCrud for CreatePost {
     // Mark the post as read
     mark_post_as_read(person_id, post_id, context.pool()).await?;
 
+    // The creator gets notified about all comments in their post
+    let subscription_form = ThreadSubscriptionForm {
+      person_id,
+      post_id,
+      comment_id: None,
+    };
+    let subscribe = move |conn: &'_ _| ThreadSubscription::subscribe(conn, &subscription_form);
+    blocking(context.pool(), subscribe)
+      .await?
+      .map_err(|e| LemmyError::from_error_message(e, "couldnt_subscribe_to_thread"))?;
+
+    // Posts which are removed by automod or held for approval are only visible to the creator and
+    // mods, so they are not federated
+    let removed_by_automod = if let AutomodOutcome::Remove { reason } = automod_outcome {
+      remove_post_by_automod(post_id, reason, context.pool()).await?;
+      true
+    } else {
+      false
+    };
+    if removed_by_automod || pending {
+      return send_post_ws_message(
+        post_id,
+        UserOperationCrud::CreatePost,
+        websocket_id,
+        Some(person_id),
+        context,
+      )
+      .await;
+    }
+
     if let Some(url) = &updated_post.url {
       let mut webmention =
         Webmention::new::<Url>(updated_post.ap_id.clone().into(), url.clone().into())?;
@@ -163,6 +243,8 @@ impl PerformCrud for CreatePost {
       }
     }
 
+    send_post_notifs(updated_post.clone(), context);
+
     let apub_post: ApubPost = updated_post.into();
     CreateOrUpdatePost::send(
       apub_post.clone(),
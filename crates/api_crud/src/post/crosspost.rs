@@ -0,0 +1,64 @@
+use crate::PerformCrud;
+use actix_web::web::Data;
+use lemmy_api_common::{
+  post::{CreatePost, CrosspostPost, PostResponse},
+  utils::{blocking, check_community_visible, get_local_user_view_from_jwt},
+};
+use lemmy_db_views::structs::PostView;
+use lemmy_utils::{error::LemmyError, ConnectionId};
+use lemmy_websocket::LemmyContext;
+
+#[async_trait::async_trait(?Send)]
+impl PerformCrud for CrosspostPost {
+  type Response = PostResponse;
+
+  #[tracing::instrument(skip(context, websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    websocket_id: Option<ConnectionId>,
+  ) -> Result<PostResponse, LemmyError> {
+    let data: &CrosspostPost = self;
+
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    // Only posts which the user can see in GetPost can be crossposted
+    let post_id = data.post_id;
+    let person_id = local_user_view.person.id;
+    let original = blocking(context.pool(), move |conn| {
+      PostView::read(conn, post_id, Some(person_id))
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_post"))?;
+    check_community_visible(
+      original.community.id,
+      Some(&local_user_view.person),
+      context.pool(),
+    )
+    .await
+    .map_err(|e| e.with_message("couldnt_find_post"))?;
+    let original_hidden = original.post.removed
+      || original.post.deleted
+      || original.post.pending
+      || original.community.removed
+      || original.community.deleted;
+    if original_hidden {
+      return Err(LemmyError::from_message("couldnt_find_post"));
+    }
+    let original = original.post;
+
+    // The new post goes through all the regular checks for creating a post
+    let create_post = CreatePost {
+      name: original.name,
+      community_id: data.community_id,
+      url: original.url.map(Into::into),
+      body: original.body,
+      honeypot: None,
+      nsfw: Some(original.nsfw),
+      crosspost_of: Some(original.id),
+      auth: data.auth.clone(),
+    };
+    create_post.perform(context, websocket_id).await
+  }
+}
@@ -10,7 +10,11 @@ use lemmy_api_common::{
   },
 };
 use lemmy_apub::{fetcher::resolve_actor_identifier, objects::community::ApubCommunity};
-use lemmy_db_schema::{source::community::Community, traits::DeleteableOrRemoveable};
+use lemmy_db_schema::{
+  source::{community::Community, multi_community::MultiCommunity},
+  traits::{Crud, DeleteableOrRemoveable},
+  ListingType,
+};
 use lemmy_db_views::post_view::PostQuery;
 use lemmy_utils::{error::LemmyError, ConnectionId};
 use lemmy_websocket::LemmyContext;
@@ -43,7 +47,21 @@ impl PerformCrud for GetPosts {
       .map(|t| t.local_user.show_read_posts);
 
     let sort = data.sort;
-    let listing_type = listing_type_with_site_default(data.type_, context.pool()).await?;
+    let multi_community_id = data.multi_community_id;
+    let listing_type = if let Some(multi_community_id) = multi_community_id {
+      let multi_community = blocking(context.pool(), move |conn| {
+        MultiCommunity::read(conn, multi_community_id)
+      })
+      .await?
+      .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_multi_community"))?;
+      if !multi_community.public && Some(multi_community.creator_id) != person_id {
+        return Err(LemmyError::from_message("couldnt_find_multi_community"));
+      }
+      // A feed can combine local and remote communities, regardless of the listing type
+      ListingType::All
+    } else {
+      listing_type_with_site_default(data.type_, context.pool()).await?
+    };
 
     let page = data.page;
     let limit = data.limit;
@@ -68,6 +86,7 @@ impl PerformCrud for GetPosts {
         .show_read_posts(show_read_posts)
         .community_id(community_id)
         .community_actor_id(community_actor_id)
+        .multi_community_id(multi_community_id)
         .saved_only(saved_only)
         .my_person_id(person_id)
         .page(page)
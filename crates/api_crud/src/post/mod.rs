@@ -1,4 +1,5 @@
 mod create;
+mod crosspost;
 mod delete;
 mod list;
 mod read;
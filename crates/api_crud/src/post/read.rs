@@ -2,17 +2,26 @@ use crate::PerformCrud;
 use actix_web::web::Data;
 use lemmy_api_common::{
   post::{GetPost, GetPostResponse},
-  utils::{blocking, check_private_instance, get_local_user_view_from_jwt_opt, mark_post_as_read},
+  utils::{
+    blocking,
+    check_community_visible,
+    check_private_instance,
+    get_local_user_view_from_jwt_opt,
+    mark_post_as_read,
+  },
 };
 use lemmy_db_schema::{
-  source::comment::Comment,
+  source::{comment::Comment, community::CommunityVisibility},
   traits::{Crud, DeleteableOrRemoveable},
+  ListingType,
 };
-use lemmy_db_views::structs::PostView;
+use lemmy_db_views::{post_view::PostQuery, structs::PostView};
 use lemmy_db_views_actor::structs::{CommunityModeratorView, CommunityView};
 use lemmy_utils::{error::LemmyError, ConnectionId};
 use lemmy_websocket::{messages::GetPostUsersOnline, LemmyContext};
 
+const OTHER_DISCUSSIONS_LIMIT: i64 = 20;
+
 #[async_trait::async_trait(?Send)]
 impl PerformCrud for GetPost {
   type Response = GetPostResponse;
@@ -30,7 +39,11 @@ impl PerformCrud for GetPost {
 
     check_private_instance(&local_user_view, context.pool()).await?;
 
-    let person_id = local_user_view.map(|u| u.person.id);
+    let person_id = local_user_view.as_ref().map(|u| u.person.id);
+    let show_nsfw = local_user_view.as_ref().map(|u| u.local_user.show_nsfw);
+    let show_bot_accounts = local_user_view
+      .as_ref()
+      .map(|u| u.local_user.show_bot_accounts);
 
     // I'd prefer fetching the post_view by a comment join, but it adds a lot of boilerplate
     let post_id = if let Some(id) = data.id {
@@ -50,6 +63,34 @@ impl PerformCrud for GetPost {
     .await?
     .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_post"))?;
 
+    // Pending posts are only visible to their creator and to mods
+    if post_view.post.pending && person_id != Some(post_view.post.creator_id) {
+      let community_id = post_view.community.id;
+      let is_mod_or_admin = blocking(context.pool(), move |conn| {
+        person_id
+          .map(|person_id| CommunityView::is_mod_or_admin(conn, person_id, community_id))
+          .unwrap_or(false)
+      })
+      .await?;
+      if !is_mod_or_admin {
+        return Err(LemmyError::from_message("couldnt_find_post"));
+      }
+    }
+
+    // Posts of local-only communities are only visible to logged in users
+    if post_view.community.visibility == CommunityVisibility::LocalOnly && person_id.is_none() {
+      return Err(LemmyError::from_message("couldnt_find_post"));
+    }
+
+    // Posts of private communities are only visible to approved followers and to mods
+    check_community_visible(
+      post_view.community.id,
+      local_user_view.as_ref().map(|u| &u.person),
+      context.pool(),
+    )
+    .await
+    .map_err(|e| e.with_message("couldnt_find_post"))?;
+
     // Mark the post as read
     let post_id = post_view.post.id;
     if let Some(person_id) = person_id {
@@ -80,6 +121,22 @@ impl PerformCrud for GetPost {
     })
     .await??;
 
+    // Crossposts and other posts of the same link, in local and remote communities
+    let post = post_view.post.to_owned();
+    let other_discussions = blocking(context.pool(), move |conn| {
+      PostQuery::builder()
+        .conn(conn)
+        .listing_type(Some(ListingType::All))
+        .other_discussions_of(Some(&post))
+        .my_person_id(person_id)
+        .show_nsfw(show_nsfw)
+        .show_bot_accounts(show_bot_accounts)
+        .limit(Some(OTHER_DISCUSSIONS_LIMIT))
+        .build()
+        .list()
+    })
+    .await??;
+
     let online = context
       .chat_server()
       .send(GetPostUsersOnline { post_id })
@@ -92,6 +149,7 @@ impl PerformCrud for GetPost {
       community_view,
       moderators,
       online,
+      other_discussions,
     })
   }
 }
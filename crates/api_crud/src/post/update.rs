@@ -1,7 +1,14 @@
 use actix_web::web::Data;
 use lemmy_api_common::{
+  automod::{
+    check_post_automod,
+    is_edit_held_for_approval,
+    remove_post_by_automod,
+    AutomodOutcome,
+  },
   post::{EditPost, PostResponse},
   request::fetch_site_data,
+  slur_filter::slur_filter,
   utils::{
     blocking,
     check_community_ban,
@@ -20,7 +27,7 @@ use lemmy_db_schema::{
 };
 use lemmy_utils::{
   error::LemmyError,
-  utils::{check_slurs_opt, clean_url_params, is_valid_post_title},
+  utils::{canonicalize_url, check_slurs_opt, clean_url_params, is_valid_post_title},
   ConnectionId,
 };
 use lemmy_websocket::{send::send_post_ws_message, LemmyContext, UserOperationCrud};
@@ -46,13 +53,17 @@ impl PerformCrud for EditPost {
     // TODO No good way to handle a clear.
     // Issue link: https://github.com/LemmyNet/lemmy/issues/2287
     let url = Some(data_url.map(clean_url_params).map(Into::into));
-    let body = diesel_option_overwrite(&data.body);
+    let url_canonical = Some(data_url.map(canonicalize_url));
 
     let slur_regex = &context.settings().slur_regex();
     check_slurs_opt(&data.name, slur_regex)?;
     check_slurs_opt(&data.body, slur_regex)?;
+    let slur_filter = slur_filter(context.pool()).await?;
+    let (name, name_held) = slur_filter.apply_opt(&data.name)?;
+    let (filtered_body, body_held) = slur_filter.apply_opt(&data.body)?;
+    let body = diesel_option_overwrite(&filtered_body);
 
-    if let Some(name) = &data.name {
+    if let Some(name) = &name {
       if !is_valid_post_title(name) {
         return Err(LemmyError::from_message("invalid_post_title"));
       }
@@ -74,8 +85,29 @@ impl PerformCrud for EditPost {
       return Err(LemmyError::from_message("no_post_edit_allowed"));
     }
 
+    let community_id = orig_post.community_id;
+    let automod_outcome = check_post_automod(
+      name.as_ref().unwrap_or(&orig_post.name),
+      &filtered_body.or_else(|| orig_post.body.to_owned()),
+      data_url,
+      true,
+      &local_user_view.person,
+      community_id,
+      context.pool(),
+    )
+    .await?;
+    // Edits matching automod rules or the slur filter may need to be approved again
+    let held = is_edit_held_for_approval(
+      &automod_outcome,
+      name_held || body_held,
+      community_id,
+      local_user_view.person.id,
+      context.pool(),
+    )
+    .await?;
+    let pending = if held { Some(true) } else { None };
+
     // Fetch post links and Pictrs cached image
-    let data_url = data.url.as_ref();
     let (metadata_res, thumbnail_url) =
       fetch_site_data(context.client(), context.settings(), data_url).await;
     let (embed_title, embed_description, embed_video_url) = metadata_res
@@ -85,8 +117,9 @@ impl PerformCrud for EditPost {
     let post_form = PostForm {
       creator_id: orig_post.creator_id.to_owned(),
       community_id: orig_post.community_id,
-      name: data.name.to_owned().unwrap_or(orig_post.name),
+      name: name.unwrap_or(orig_post.name),
       url,
+      url_canonical,
       body,
       nsfw: data.nsfw,
       updated: Some(naive_now()),
@@ -94,6 +127,7 @@ impl PerformCrud for EditPost {
       embed_description,
       embed_video_url,
       thumbnail_url: Some(thumbnail_url),
+      pending,
       ..PostForm::default()
     };
 
@@ -114,15 +148,23 @@ impl PerformCrud for EditPost {
         return Err(LemmyError::from_error_message(e, err_type));
       }
     };
+    let removed_by_automod = if let AutomodOutcome::Remove { reason } = automod_outcome {
+      remove_post_by_automod(updated_post.id, reason, context.pool()).await?;
+      true
+    } else {
+      false
+    };
 
-    // Send apub update
-    CreateOrUpdatePost::send(
-      updated_post.into(),
-      &local_user_view.person.clone().into(),
-      CreateOrUpdateType::Update,
-      context,
-    )
-    .await?;
+    // Send apub update, unless the post is held for approval or was removed by automod
+    if !updated_post.pending && !removed_by_automod {
+      CreateOrUpdatePost::send(
+        updated_post.into(),
+        &local_user_view.person.clone().into(),
+        CreateOrUpdateType::Update,
+        context,
+      )
+      .await?;
+    }
 
     send_post_ws_message(
       data.post_id,
@@ -2,6 +2,7 @@ use crate::PerformCrud;
 use actix_web::web::Data;
 use lemmy_api_common::{
   person::{CreatePrivateMessage, PrivateMessageResponse},
+  slur_filter::slur_filter,
   utils::{
     blocking,
     check_person_block,
@@ -24,7 +25,11 @@ use lemmy_db_schema::{
 };
 use lemmy_db_views::structs::LocalUserView;
 use lemmy_utils::{error::LemmyError, utils::remove_slurs, ConnectionId};
-use lemmy_websocket::{send::send_pm_ws_message, LemmyContext, UserOperationCrud};
+use lemmy_websocket::{
+  send::{send_pm_push_notification, send_pm_ws_message},
+  LemmyContext,
+  UserOperationCrud,
+};
 
 #[async_trait::async_trait(?Send)]
 impl PerformCrud for CreatePrivateMessage {
@@ -42,6 +47,9 @@ impl PerformCrud for CreatePrivateMessage {
 
     let content_slurs_removed =
       remove_slurs(&data.content.to_owned(), &context.settings().slur_regex());
+    let content_slurs_removed = slur_filter(context.pool())
+      .await?
+      .apply_without_hold(&content_slurs_removed)?;
 
     check_person_block(local_user_view.person.id, data.recipient_id, context.pool()).await?;
 
@@ -119,8 +127,12 @@ impl PerformCrud for CreatePrivateMessage {
           &content_slurs_removed,
           &local_recipient.person.name,
         ),
+        context.pool(),
+        context.secret(),
         context.settings(),
-      );
+      )
+      .await;
+      send_pm_push_notification(&res.private_message_view, context).await?;
     }
 
     Ok(res)
@@ -2,6 +2,7 @@ use crate::PerformCrud;
 use actix_web::web::Data;
 use lemmy_api_common::{
   person::{EditPrivateMessage, PrivateMessageResponse},
+  slur_filter::slur_filter,
   utils::{blocking, get_local_user_view_from_jwt},
 };
 use lemmy_apub::protocol::activities::{
@@ -38,6 +39,9 @@ impl PerformCrud for EditPrivateMessage {
 
     // Doing the update
     let content_slurs_removed = remove_slurs(&data.content, &context.settings().slur_regex());
+    let content_slurs_removed = slur_filter(context.pool())
+      .await?
+      .apply_without_hold(&content_slurs_removed)?;
     let private_message_id = data.private_message_id;
     let updated_private_message = blocking(context.pool(), move |conn| {
       PrivateMessage::update_content(conn, private_message_id, &content_slurs_removed)
@@ -2,6 +2,7 @@ use crate::PerformCrud;
 use activitypub_federation::core::signatures::generate_actor_keypair;
 use actix_web::web::Data;
 use lemmy_api_common::{
+  automod::create_automod_person,
   site::{CreateSite, SiteResponse},
   utils::{blocking, get_local_user_view_from_jwt, is_admin, site_description_length_check},
 };
@@ -84,6 +85,12 @@ impl PerformCrud for CreateSite {
       .await?
       .map_err(|e| LemmyError::from_error_message(e, "site_already_exists"))?;
 
+    let protocol_and_hostname = context.settings().get_protocol_and_hostname();
+    blocking(context.pool(), move |conn| {
+      create_automod_person(conn, &protocol_and_hostname)
+    })
+    .await??;
+
     let site_view = blocking(context.pool(), SiteView::read_local).await??;
 
     Ok(SiteResponse { site_view })
@@ -5,6 +5,7 @@ use lemmy_api_common::{
   site::{CreateSite, GetSite, GetSiteResponse, MyUserInfo},
   utils::{blocking, build_federated_instances, get_local_user_settings_view_from_jwt_opt},
 };
+use lemmy_db_schema::source::local_user_keyword_filter::LocalUserKeywordFilter;
 use lemmy_db_views::structs::SiteView;
 use lemmy_db_views_actor::structs::{
   CommunityBlockView,
@@ -109,18 +110,31 @@ impl PerformCrud for GetSite {
       .await?
       .map_err(|e| LemmyError::from_error_message(e, "system_err_login"))?;
 
+      let local_user_id = local_user_view.local_user.id;
+      let keyword_filters = blocking(context.pool(), move |conn| {
+        LocalUserKeywordFilter::list_for_local_user(conn, local_user_id)
+      })
+      .await?
+      .map_err(|e| LemmyError::from_error_message(e, "system_err_login"))?;
+
       Some(MyUserInfo {
         local_user_view,
         follows,
         moderates,
         community_blocks,
         person_blocks,
+        keyword_filters,
       })
     } else {
       None
     };
 
-    let federated_instances = build_federated_instances(context.pool(), context.settings()).await?;
+    let is_admin = my_user
+      .as_ref()
+      .map(|u| u.local_user_view.person.admin)
+      .unwrap_or(false);
+    let federated_instances =
+      build_federated_instances(context.pool(), context.settings(), is_admin).await?;
 
     Ok(GetSiteResponse {
       site_view,
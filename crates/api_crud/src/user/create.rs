@@ -3,6 +3,7 @@ use activitypub_federation::core::signatures::generate_actor_keypair;
 use actix_web::web::Data;
 use lemmy_api_common::{
   person::{LoginResponse, Register},
+  slur_filter::slur_filter,
   utils::{blocking, honeypot_check, password_length_check, send_verification_email},
 };
 use lemmy_apub::{
@@ -100,6 +101,9 @@ impl PerformCrud for Register {
     }
 
     check_slurs(&data.username, &context.settings().slur_regex())?;
+    slur_filter(context.pool())
+      .await?
+      .check_name(&data.username)?;
 
     let actor_keypair = generate_actor_keypair()?;
     if !is_valid_actor_name(&data.username, context.settings().actor_name_max_length) {
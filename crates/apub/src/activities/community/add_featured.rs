@@ -0,0 +1,122 @@
+use crate::{
+  activities::{
+    community::{
+      announce::GetCommunity,
+      get_community_from_featured_url,
+      receive_featured_change,
+      send_activity_in_community,
+      verify_featured_target,
+    },
+    generate_activity_id,
+    verify_is_public,
+    verify_mod_action,
+    verify_person_in_community,
+  },
+  activity_lists::AnnouncableActivities,
+  generate_featured_url,
+  objects::{community::ApubCommunity, person::ApubPerson, post::ApubPost},
+  protocol::activities::community::add_featured::AddFeatured,
+  ActorType,
+};
+use activitypub_federation::{
+  core::object_id::ObjectId,
+  data::Data,
+  traits::{ActivityHandler, ApubObject},
+};
+use activitystreams_kinds::{activity::AddType, public};
+use lemmy_utils::error::LemmyError;
+use lemmy_websocket::LemmyContext;
+use url::Url;
+
+impl AddFeatured {
+  #[tracing::instrument(skip_all)]
+  pub async fn send(
+    community: &ApubCommunity,
+    post: ApubPost,
+    actor: &ApubPerson,
+    context: &LemmyContext,
+  ) -> Result<(), LemmyError> {
+    let id = generate_activity_id(
+      AddType::Add,
+      &context.settings().get_protocol_and_hostname(),
+    )?;
+    let add = AddFeatured {
+      actor: ObjectId::new(actor.actor_id()),
+      to: vec![public()],
+      object: post.into_apub(context).await?,
+      target: generate_featured_url(&community.actor_id)?.into(),
+      cc: vec![community.actor_id()],
+      kind: AddType::Add,
+      id: id.clone(),
+      unparsed: Default::default(),
+    };
+
+    let activity = AnnouncableActivities::AddFeatured(Box::new(add));
+    send_activity_in_community(activity, actor, community, vec![], context).await
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ActivityHandler for AddFeatured {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    &self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    verify_is_public(&self.to, &self.cc)?;
+    let community = self.get_community(context, request_counter).await?;
+    verify_person_in_community(&self.actor, &community, context, request_counter).await?;
+    verify_mod_action(
+      &self.actor,
+      self.object.id.inner(),
+      &community,
+      context,
+      request_counter,
+    )
+    .await?;
+    verify_featured_target(&self.target, &community)?;
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn receive(
+    self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    let community = self.get_community(context, request_counter).await?;
+    receive_featured_change(
+      &self.actor,
+      &self.object.id,
+      &community,
+      true,
+      context,
+      request_counter,
+    )
+    .await
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl GetCommunity for AddFeatured {
+  #[tracing::instrument(skip_all)]
+  async fn get_community(
+    &self,
+    context: &LemmyContext,
+    request_counter: &mut i32,
+  ) -> Result<ApubCommunity, LemmyError> {
+    get_community_from_featured_url(&self.target, context, request_counter).await
+  }
+}
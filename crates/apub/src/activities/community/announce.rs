@@ -1,7 +1,8 @@
 use crate::{
-  activities::{generate_activity_id, send_lemmy_activity, verify_is_public},
+  activities::{community_audience, generate_activity_id, send_lemmy_activity, verify_visibility},
   activity_lists::AnnouncableActivities,
   insert_activity,
+  local_instance,
   objects::community::ApubCommunity,
   protocol::{
     activities::{community::announce::AnnounceActivity, CreateOrUpdateType},
@@ -10,7 +11,9 @@ use crate::{
   ActorType,
 };
 use activitypub_federation::{core::object_id::ObjectId, data::Data, traits::ActivityHandler};
-use activitystreams_kinds::{activity::AnnounceType, public};
+use activitystreams_kinds::activity::AnnounceType;
+use lemmy_api_common::utils::blocking;
+use lemmy_db_schema::source::{community::CommunityVisibility, relay::Relay};
 use lemmy_utils::error::LemmyError;
 use lemmy_websocket::LemmyContext;
 use tracing::debug;
@@ -33,7 +36,7 @@ impl AnnounceActivity {
   ) -> Result<AnnounceActivity, LemmyError> {
     Ok(AnnounceActivity {
       actor: ObjectId::new(community.actor_id()),
-      to: vec![public()],
+      to: vec![community_audience(community)],
       object: IdOrNestedObject::NestedObject(object),
       cc: vec![community.followers_url.clone().into()],
       kind: AnnounceType::Announce,
@@ -52,8 +55,9 @@ impl AnnounceActivity {
     context: &LemmyContext,
   ) -> Result<(), LemmyError> {
     let announce = AnnounceActivity::new(object.clone(), community, context)?;
-    let inboxes = community.get_follower_inboxes(context).await?;
-    send_lemmy_activity(context, announce, community, inboxes.clone(), false).await?;
+    let mut inboxes = community.get_follower_inboxes(context).await?;
+    let sensitive = community.visibility != CommunityVisibility::Public;
+    send_lemmy_activity(context, announce, community, inboxes.clone(), sensitive).await?;
 
     // Pleroma and Mastodon can't handle activities like Announce/Create/Page. So for
     // compatibility, we also send Announce/Page so that they can follow Lemmy communities.
@@ -62,8 +66,13 @@ impl AnnounceActivity {
       CreateOrUpdatePost(c) if c.kind == CreateOrUpdateType::Create => Page(c.object),
       _ => return Ok(()),
     };
+    // Subscribed relays forward new public posts to other instances
+    if community.visibility == CommunityVisibility::Public {
+      let relay_inboxes = blocking(context.pool(), Relay::list_accepted_inboxes).await??;
+      inboxes.extend(relay_inboxes.into_iter().map(Into::into));
+    }
     let announce_compat = AnnounceActivity::new(object, community, context)?;
-    send_lemmy_activity(context, announce_compat, community, inboxes, false).await?;
+    send_lemmy_activity(context, announce_compat, community, inboxes, sensitive).await?;
     Ok(())
   }
 }
@@ -84,10 +93,14 @@ impl ActivityHandler for AnnounceActivity {
   #[tracing::instrument(skip_all)]
   async fn verify(
     &self,
-    _context: &Data<LemmyContext>,
-    _request_counter: &mut i32,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
-    verify_is_public(&self.to, &self.cc)?;
+    let community = self
+      .actor
+      .dereference(context, local_instance(context), request_counter)
+      .await?;
+    verify_visibility(&self.to, &self.cc, &community)?;
     Ok(())
   }
 
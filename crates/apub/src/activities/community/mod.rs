@@ -1,18 +1,30 @@
 use crate::{
   activities::send_lemmy_activity,
   activity_lists::AnnouncableActivities,
+  generate_featured_url,
   local_instance,
-  objects::community::ApubCommunity,
+  objects::{community::ApubCommunity, person::ApubPerson, post::ApubPost},
   protocol::activities::community::announce::AnnounceActivity,
   ActorType,
 };
 use activitypub_federation::{core::object_id::ObjectId, traits::Actor};
+use lemmy_api_common::utils::blocking;
+use lemmy_db_schema::{
+  source::{
+    community::CommunityVisibility,
+    moderator::{ModStickyPost, ModStickyPostForm},
+    post::Post,
+  },
+  traits::Crud,
+};
 use lemmy_utils::error::LemmyError;
 use lemmy_websocket::LemmyContext;
 use url::Url;
 
+pub mod add_featured;
 pub mod add_mod;
 pub mod announce;
+pub mod remove_featured;
 pub mod remove_mod;
 pub mod report;
 pub mod update;
@@ -28,8 +40,14 @@ pub(crate) async fn send_activity_in_community<ActorT>(
 where
   ActorT: Actor + ActorType,
 {
+  // Local-only communities don't federate at all
+  if community.visibility == CommunityVisibility::LocalOnly {
+    return Ok(());
+  }
   inboxes.push(community.shared_inbox_or_inbox());
-  send_lemmy_activity(context, activity.clone(), actor, inboxes, false).await?;
+  // Activities of private communities are only served over http to their recipients
+  let sensitive = community.visibility != CommunityVisibility::Public;
+  send_lemmy_activity(context, activity.clone(), actor, inboxes, sensitive).await?;
 
   if community.local {
     AnnounceActivity::send(activity, community, context).await?;
@@ -49,3 +67,61 @@ async fn get_community_from_moderators_url(
     .dereference(context, local_instance(context), request_counter)
     .await
 }
+
+#[tracing::instrument(skip_all)]
+async fn get_community_from_featured_url(
+  featured: &Url,
+  context: &LemmyContext,
+  request_counter: &mut i32,
+) -> Result<ApubCommunity, LemmyError> {
+  let community_id = Url::parse(&featured.to_string().replace("/featured", ""))?;
+  ObjectId::new(community_id)
+    .dereference(context, local_instance(context), request_counter)
+    .await
+}
+
+fn verify_featured_target(target: &Url, community: &ApubCommunity) -> Result<(), LemmyError> {
+  if target != &generate_featured_url(&community.actor_id)?.into() {
+    return Err(LemmyError::from_message("Unkown target url"));
+  }
+  Ok(())
+}
+
+/// Stickies or unstickies a post which was added to or removed from the featured collection of
+/// its community, and writes the mod log.
+#[tracing::instrument(skip_all)]
+async fn receive_featured_change(
+  actor: &ObjectId<ApubPerson>,
+  post_id: &ObjectId<ApubPost>,
+  community: &ApubCommunity,
+  stickied: bool,
+  context: &LemmyContext,
+  request_counter: &mut i32,
+) -> Result<(), LemmyError> {
+  let post = post_id
+    .dereference(context, local_instance(context), request_counter)
+    .await?;
+  if post.community_id != community.id {
+    return Err(LemmyError::from_message("Post is in a different community"));
+  }
+  // The sticky may already have been applied by a `Page` update from the same mod action
+  if post.stickied == stickied {
+    return Ok(());
+  }
+  let actor = actor
+    .dereference(context, local_instance(context), request_counter)
+    .await?;
+
+  let post_id = post.id;
+  let form = ModStickyPostForm {
+    mod_person_id: actor.id,
+    post_id,
+    stickied: Some(stickied),
+  };
+  blocking(context.pool(), move |conn| {
+    Post::update_stickied(conn, post_id, stickied)?;
+    ModStickyPost::create(conn, &form)
+  })
+  .await??;
+  Ok(())
+}
@@ -18,7 +18,7 @@ use activitypub_federation::{
   traits::{ActivityHandler, ApubObject},
 };
 use activitystreams_kinds::{activity::UpdateType, public};
-use lemmy_api_common::utils::blocking;
+use lemmy_api_common::{slur_filter::slur_filter, utils::blocking};
 use lemmy_db_schema::{
   source::community::{Community, CommunityForm},
   traits::Crud,
@@ -101,7 +101,8 @@ impl ActivityHandler for UpdateCommunity {
   ) -> Result<(), LemmyError> {
     let community = self.get_community(context, request_counter).await?;
 
-    let updated_community = self.object.into_form();
+    let slur_filter = slur_filter(context.pool()).await?;
+    let updated_community = self.object.into_form(&slur_filter)?;
     let cf = CommunityForm {
       name: updated_community.name,
       title: updated_community.title,
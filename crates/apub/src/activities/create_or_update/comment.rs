@@ -2,15 +2,20 @@ use crate::{
   activities::{
     check_community_deleted_or_removed,
     community::{announce::GetCommunity, send_activity_in_community},
-    create_or_update::get_comment_notif_recipients,
+    create_or_update::{announce_approved_remote_object, get_comment_notif_recipients},
     generate_activity_id,
-    verify_is_public,
     verify_person_in_community,
+    verify_visibility,
   },
   activity_lists::AnnouncableActivities,
   local_instance,
   mentions::MentionOrValue,
-  objects::{comment::ApubComment, community::ApubCommunity, person::ApubPerson},
+  objects::{
+    comment::ApubComment,
+    community::ApubCommunity,
+    person::ApubPerson,
+    read_from_string_or_source,
+  },
   protocol::activities::{create_or_update::comment::CreateOrUpdateComment, CreateOrUpdateType},
   ActorType,
 };
@@ -20,12 +25,21 @@ use activitypub_federation::{
   traits::{ActivityHandler, Actor, ApubObject},
   utils::verify_domains_match,
 };
-use activitystreams_kinds::public;
-use lemmy_api_common::utils::{blocking, check_post_deleted_or_removed};
+use lemmy_api_common::{
+  automod::{
+    check_comment_automod,
+    is_held_for_approval,
+    remove_comment_by_automod,
+    AutomodOutcome,
+  },
+  slur_filter::slur_filter,
+  utils::{blocking, check_post_deleted_or_removed},
+};
 use lemmy_db_schema::{
   source::{
-    comment::{CommentLike, CommentLikeForm},
+    comment::{Comment, CommentLike, CommentLikeForm},
     community::Community,
+    person::Person,
     post::Post,
   },
   traits::{Crud, Likeable},
@@ -35,6 +49,29 @@ use lemmy_websocket::{send::send_comment_ws_message, LemmyContext, UserOperation
 use url::Url;
 
 impl CreateOrUpdateComment {
+  pub(crate) async fn new(
+    comment: ApubComment,
+    actor: &ApubPerson,
+    kind: CreateOrUpdateType,
+    context: &LemmyContext,
+  ) -> Result<CreateOrUpdateComment, LemmyError> {
+    let id = generate_activity_id(
+      kind.clone(),
+      &context.settings().get_protocol_and_hostname(),
+    )?;
+    let note = comment.into_apub(context).await?;
+    Ok(CreateOrUpdateComment {
+      actor: ObjectId::new(actor.actor_id()),
+      to: note.to.clone(),
+      cc: note.cc.clone(),
+      tag: note.tag.clone(),
+      object: note,
+      kind,
+      id,
+      unparsed: Default::default(),
+    })
+  }
+
   #[tracing::instrument(skip(comment, actor, kind, context))]
   pub async fn send(
     comment: ApubComment,
@@ -53,22 +90,7 @@ impl CreateOrUpdateComment {
     .await??
     .into();
 
-    let id = generate_activity_id(
-      kind.clone(),
-      &context.settings().get_protocol_and_hostname(),
-    )?;
-    let note = comment.into_apub(context).await?;
-
-    let create_or_update = CreateOrUpdateComment {
-      actor: ObjectId::new(actor.actor_id()),
-      to: vec![public()],
-      cc: note.cc.clone(),
-      tag: note.tag.clone(),
-      object: note,
-      kind,
-      id: id.clone(),
-      unparsed: Default::default(),
-    };
+    let create_or_update = CreateOrUpdateComment::new(comment, actor, kind, context).await?;
 
     let tagged_users: Vec<ObjectId<ApubPerson>> = create_or_update
       .tag
@@ -94,6 +116,42 @@ impl CreateOrUpdateComment {
     let activity = AnnouncableActivities::CreateOrUpdateComment(create_or_update);
     send_activity_in_community(activity, actor, &community, inboxes, context).await
   }
+
+  /// Federates a comment which was held for approval, once a mod approved it. Comments by remote
+  /// users were already sent to the community, so only their original activity needs to be
+  /// announced.
+  #[tracing::instrument(skip_all)]
+  pub async fn send_approved(
+    comment: ApubComment,
+    context: &LemmyContext,
+  ) -> Result<(), LemmyError> {
+    let creator_id = comment.creator_id;
+    let post_id = comment.post_id;
+    let (creator, community) = blocking(context.pool(), move |conn| {
+      let creator = Person::read(conn, creator_id)?;
+      let post = Post::read(conn, post_id)?;
+      let community = Community::read(conn, post.community_id)?;
+      Ok((creator, community)) as Result<(Person, Community), LemmyError>
+    })
+    .await??;
+    let creator: ApubPerson = creator.into();
+    let community: ApubCommunity = community.into();
+
+    if creator.local {
+      CreateOrUpdateComment::send(
+        comment,
+        &creator,
+        CreateOrUpdateType::Create,
+        context,
+        &mut 0,
+      )
+      .await
+    } else if community.local {
+      announce_approved_remote_object(comment.ap_id.clone(), &community, context).await
+    } else {
+      Ok(())
+    }
+  }
 }
 
 #[async_trait::async_trait(?Send)]
@@ -115,9 +173,9 @@ impl ActivityHandler for CreateOrUpdateComment {
     context: &Data<LemmyContext>,
     request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
-    verify_is_public(&self.to, &self.cc)?;
     let post = self.object.get_parents(context, request_counter).await?.0;
     let community = self.get_community(context, request_counter).await?;
+    verify_visibility(&self.to, &self.cc, &community)?;
 
     verify_person_in_community(&self.actor, &community, context, request_counter).await?;
     verify_domains_match(self.actor.inner(), self.object.id.inner())?;
@@ -134,6 +192,34 @@ impl ActivityHandler for CreateOrUpdateComment {
     context: &Data<LemmyContext>,
     request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
+    // Automod rules and approval only apply to new comments in local communities
+    let community = self.get_community(context, request_counter).await?;
+    let (automod_outcome, pending) = if self.kind == CreateOrUpdateType::Create && community.local {
+      let creator = self
+        .actor
+        .dereference(context, local_instance(context), request_counter)
+        .await?;
+      let content = read_from_string_or_source(
+        &self.object.content,
+        &self.object.media_type,
+        &self.object.source,
+      );
+      let outcome =
+        check_comment_automod(&content, false, &creator, community.id, context.pool()).await?;
+      let (_, slur_filter_held) = slur_filter(context.pool()).await?.apply(&content)?;
+      let pending = is_held_for_approval(
+        &outcome,
+        slur_filter_held,
+        &community,
+        creator.id,
+        context.pool(),
+      )
+      .await?;
+      (outcome, pending)
+    } else {
+      (AutomodOutcome::Allow, false)
+    };
+
     let comment = ApubComment::from_apub(self.object, context, request_counter).await?;
 
     // author likes their own comment by default
@@ -148,15 +234,29 @@ impl ActivityHandler for CreateOrUpdateComment {
     })
     .await??;
 
-    let do_send_email = self.kind == CreateOrUpdateType::Create;
-    let recipients = get_comment_notif_recipients(
-      &self.actor,
-      &comment,
-      do_send_email,
-      context,
-      request_counter,
-    )
-    .await?;
+    if pending {
+      let comment_id = comment.id;
+      blocking(context.pool(), move |conn| {
+        Comment::update_pending(conn, comment_id, true)
+      })
+      .await??;
+    }
+    let recipients = if let AutomodOutcome::Remove { reason } = automod_outcome {
+      remove_comment_by_automod(comment.id, reason, context.pool()).await?;
+      Vec::new()
+    } else if pending {
+      Vec::new()
+    } else {
+      let do_send_email = self.kind == CreateOrUpdateType::Create;
+      get_comment_notif_recipients(
+        &self.actor,
+        &comment,
+        do_send_email,
+        context,
+        request_counter,
+      )
+      .await?
+    };
     let notif_type = match self.kind {
       CreateOrUpdateType::Create => UserOperationCrud::CreateComment,
       CreateOrUpdateType::Update => UserOperationCrud::EditComment,
@@ -1,18 +1,53 @@
-use crate::{local_instance, objects::person::ApubPerson};
+use crate::{
+  activity_lists::AnnouncableActivities,
+  local_instance,
+  objects::{community::ApubCommunity, person::ApubPerson},
+  protocol::activities::community::announce::AnnounceActivity,
+};
 use activitypub_federation::core::object_id::ObjectId;
 use lemmy_api_common::utils::blocking;
 use lemmy_db_schema::{
-  newtypes::LocalUserId,
-  source::{comment::Comment, post::Post},
+  newtypes::{DbUrl, LocalUserId},
+  source::{activity::Activity, comment::Comment, post::Post},
   traits::Crud,
 };
 use lemmy_utils::{error::LemmyError, utils::scrape_text_for_mentions};
 use lemmy_websocket::{send::send_local_notifs, LemmyContext};
+use tracing::warn;
 
 pub mod comment;
 pub mod post;
 pub mod private_message;
 
+/// Announces the Create activity which a remote user sent to a local community, after the post or
+/// comment was approved. The original activity is announced as is, because only the remote
+/// instance can sign for its user.
+#[tracing::instrument(skip_all)]
+async fn announce_approved_remote_object(
+  object_id: DbUrl,
+  community: &ApubCommunity,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  let object_id_ = object_id.clone();
+  let create = blocking(context.pool(), move |conn| {
+    Activity::read_received_create(conn, &object_id_)
+  })
+  .await??;
+  match create {
+    Some(create) => {
+      let activity: AnnouncableActivities = serde_json::from_value(create.data)?;
+      AnnounceActivity::send(activity, community, context).await
+    }
+    None => {
+      warn!(
+        "Original activity for approved object {} not found",
+        object_id
+      );
+      Ok(())
+    }
+  }
+}
+
 #[tracing::instrument(skip_all)]
 async fn get_comment_notif_recipients(
   actor: &ObjectId<ApubPerson>,
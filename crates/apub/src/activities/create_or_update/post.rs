@@ -2,13 +2,21 @@ use crate::{
   activities::{
     check_community_deleted_or_removed,
     community::{announce::GetCommunity, send_activity_in_community},
+    community_audience,
+    create_or_update::announce_approved_remote_object,
     generate_activity_id,
-    verify_is_public,
     verify_mod_action,
     verify_person_in_community,
+    verify_visibility,
   },
   activity_lists::AnnouncableActivities,
-  objects::{community::ApubCommunity, person::ApubPerson, post::ApubPost},
+  local_instance,
+  objects::{
+    community::ApubCommunity,
+    person::ApubPerson,
+    post::ApubPost,
+    read_from_string_or_source_opt,
+  },
   protocol::activities::{create_or_update::post::CreateOrUpdatePost, CreateOrUpdateType},
   ActorType,
 };
@@ -18,17 +26,33 @@ use activitypub_federation::{
   traits::{ActivityHandler, ApubObject},
   utils::{verify_domains_match, verify_urls_match},
 };
-use activitystreams_kinds::public;
-use lemmy_api_common::utils::blocking;
+use lemmy_api_common::{
+  automod::{
+    check_post_automod,
+    is_edit_held_for_approval,
+    is_held_for_approval,
+    remove_post_by_automod,
+    AutomodOutcome,
+  },
+  slur_filter::slur_filter,
+  utils::blocking,
+};
 use lemmy_db_schema::{
   source::{
-    community::Community,
-    post::{PostLike, PostLikeForm},
+    community::{Community, CommunityVisibility},
+    person::Person,
+    person_follower::PersonFollower,
+    post::{Post, PostLike, PostLikeForm},
   },
   traits::{Crud, Likeable},
 };
 use lemmy_utils::error::LemmyError;
-use lemmy_websocket::{send::send_post_ws_message, LemmyContext, UserOperationCrud};
+use lemmy_websocket::{
+  send::{send_post_notifs, send_post_ws_message},
+  LemmyContext,
+  UserOperationCrud,
+};
+use std::ops::Deref;
 use url::Url;
 
 impl CreateOrUpdatePost {
@@ -45,7 +69,7 @@ impl CreateOrUpdatePost {
     )?;
     Ok(CreateOrUpdatePost {
       actor: ObjectId::new(actor.actor_id()),
-      to: vec![public()],
+      to: vec![community_audience(community)],
       object: post.into_apub(context).await?,
       cc: vec![community.actor_id()],
       kind,
@@ -68,9 +92,48 @@ impl CreateOrUpdatePost {
     .await??
     .into();
 
+    // Remote followers of the author also receive the post, this is how eg Mastodon users see it.
+    // They aren't approved by the community, so they only get posts of public communities.
+    let follower_inboxes = if community.visibility == CommunityVisibility::Public {
+      let person_id = actor.id;
+      blocking(context.pool(), move |conn| {
+        PersonFollower::list_remote_follower_inboxes(conn, person_id)
+      })
+      .await??
+      .into_iter()
+      .map(Into::into)
+      .collect()
+    } else {
+      vec![]
+    };
+
     let create_or_update = CreateOrUpdatePost::new(post, actor, &community, kind, context).await?;
     let activity = AnnouncableActivities::CreateOrUpdatePost(Box::new(create_or_update));
-    send_activity_in_community(activity, actor, &community, vec![], context).await
+    send_activity_in_community(activity, actor, &community, follower_inboxes, context).await
+  }
+
+  /// Federates a post which was held for approval, once a mod approved it. Posts by remote users
+  /// were already sent to the community, so only their original activity needs to be announced.
+  #[tracing::instrument(skip_all)]
+  pub async fn send_approved(post: ApubPost, context: &LemmyContext) -> Result<(), LemmyError> {
+    let creator_id = post.creator_id;
+    let community_id = post.community_id;
+    let (creator, community) = blocking(context.pool(), move |conn| {
+      let creator = Person::read(conn, creator_id)?;
+      let community = Community::read(conn, community_id)?;
+      Ok((creator, community)) as Result<(Person, Community), LemmyError>
+    })
+    .await??;
+    let creator: ApubPerson = creator.into();
+    let community: ApubCommunity = community.into();
+
+    if creator.local {
+      CreateOrUpdatePost::send(post, &creator, CreateOrUpdateType::Create, context).await
+    } else if community.local {
+      announce_approved_remote_object(post.ap_id.clone(), &community, context).await
+    } else {
+      Ok(())
+    }
   }
 }
 
@@ -93,8 +156,8 @@ impl ActivityHandler for CreateOrUpdatePost {
     context: &Data<LemmyContext>,
     request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
-    verify_is_public(&self.to, &self.cc)?;
     let community = self.get_community(context, request_counter).await?;
+    verify_visibility(&self.to, &self.cc, &community)?;
     verify_person_in_community(&self.actor, &community, context, request_counter).await?;
     check_community_deleted_or_removed(&community)?;
 
@@ -141,6 +204,57 @@ impl ActivityHandler for CreateOrUpdatePost {
     context: &Data<LemmyContext>,
     request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
+    // Automod rules and approval only apply to posts in local communities, and not to mod actions
+    let community = self.get_community(context, request_counter).await?;
+    let is_edit = self.kind == CreateOrUpdateType::Update;
+    let check_automod = community.local && !(is_edit && self.object.is_mod_action(context).await?);
+    let (automod_outcome, pending) = if check_automod {
+      let creator = self
+        .actor
+        .dereference(context, local_instance(context), request_counter)
+        .await?;
+      let body = read_from_string_or_source_opt(
+        &self.object.content,
+        &self.object.media_type,
+        &self.object.source,
+      );
+      let outcome = check_post_automod(
+        &self.object.name,
+        &body,
+        self.object.link_url().as_ref(),
+        is_edit,
+        &creator,
+        community.id,
+        context.pool(),
+      )
+      .await?;
+      let slur_filter = slur_filter(context.pool()).await?;
+      let slur_filter_held =
+        slur_filter.apply(&self.object.name)?.1 || slur_filter.apply_opt(&body)?.1;
+      let pending = if is_edit {
+        is_edit_held_for_approval(
+          &outcome,
+          slur_filter_held,
+          community.id,
+          creator.id,
+          context.pool(),
+        )
+        .await?
+      } else {
+        is_held_for_approval(
+          &outcome,
+          slur_filter_held,
+          &community,
+          creator.id,
+          context.pool(),
+        )
+        .await?
+      };
+      (outcome, pending)
+    } else {
+      (AutomodOutcome::Allow, false)
+    };
+
     let post = ApubPost::from_apub(self.object, context, request_counter).await?;
 
     // author likes their own post by default
@@ -154,6 +268,24 @@ impl ActivityHandler for CreateOrUpdatePost {
     })
     .await??;
 
+    let removed_by_automod = if let AutomodOutcome::Remove { reason } = automod_outcome {
+      remove_post_by_automod(post.id, reason, context.pool()).await?;
+      true
+    } else {
+      false
+    };
+    if pending {
+      let post_id = post.id;
+      blocking(context.pool(), move |conn| {
+        Post::update_pending(conn, post_id, true)
+      })
+      .await??;
+    }
+
+    if self.kind == CreateOrUpdateType::Create && !removed_by_automod && !pending {
+      send_post_notifs(post.deref().clone(), context);
+    }
+
     let notif_type = match self.kind {
       CreateOrUpdateType::Create => UserOperationCrud::CreatePost,
       CreateOrUpdateType::Update => UserOperationCrud::EditPost,
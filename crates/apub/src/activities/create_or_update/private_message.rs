@@ -16,7 +16,11 @@ use activitypub_federation::{
 use lemmy_api_common::utils::blocking;
 use lemmy_db_schema::{source::person::Person, traits::Crud};
 use lemmy_utils::error::LemmyError;
-use lemmy_websocket::{send::send_pm_ws_message, LemmyContext, UserOperationCrud};
+use lemmy_websocket::{
+  send::{send_pm_push_notification, send_pm_ws_message},
+  LemmyContext,
+  UserOperationCrud,
+};
 use url::Url;
 
 impl CreateOrUpdatePrivateMessage {
@@ -89,7 +93,10 @@ impl ActivityHandler for CreateOrUpdatePrivateMessage {
       CreateOrUpdateType::Create => UserOperationCrud::CreatePrivateMessage,
       CreateOrUpdateType::Update => UserOperationCrud::EditPrivateMessage,
     };
-    send_pm_ws_message(private_message.id, notif_type, None, context).await?;
+    let res = send_pm_ws_message(private_message.id, notif_type, None, context).await?;
+    if self.kind == CreateOrUpdateType::Create {
+      send_pm_push_notification(&res.private_message_view, context).await?;
+    }
 
     Ok(())
   }
@@ -1,7 +1,9 @@
 use crate::{
   activities::{generate_activity_id, send_lemmy_activity},
+  fetcher::user_or_community::UserOrCommunity,
   local_instance,
-  protocol::activities::following::{accept::AcceptFollowCommunity, follow::FollowCommunity},
+  objects::{community::ApubCommunity, person::ApubPerson},
+  protocol::activities::following::{accept::AcceptFollow, follow::Follow},
   ActorType,
 };
 use activitypub_federation::{
@@ -12,28 +14,35 @@ use activitypub_federation::{
 };
 use activitystreams_kinds::activity::AcceptType;
 use lemmy_api_common::{community::CommunityResponse, utils::blocking};
-use lemmy_db_schema::{source::community::CommunityFollower, traits::Followable};
+use lemmy_db_schema::{
+  source::{community::CommunityFollower, person_follower::PersonFollower},
+  traits::Followable,
+};
 use lemmy_db_views::structs::LocalUserView;
 use lemmy_db_views_actor::structs::CommunityView;
 use lemmy_utils::error::LemmyError;
 use lemmy_websocket::{messages::SendUserRoomMessage, LemmyContext, UserOperation};
 use url::Url;
 
-impl AcceptFollowCommunity {
+impl AcceptFollow {
   #[tracing::instrument(skip_all)]
   pub async fn send(
-    follow: FollowCommunity,
+    follow: Follow,
     context: &LemmyContext,
     request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
-    let community = follow.object.dereference_local(context).await?;
+    let object = follow.object.dereference_local(context).await?;
     let person = follow
       .actor
       .clone()
       .dereference(context, local_instance(context), request_counter)
       .await?;
-    let accept = AcceptFollowCommunity {
-      actor: ObjectId::new(community.actor_id()),
+    let object_id = match &object {
+      UserOrCommunity::User(p) => p.actor_id(),
+      UserOrCommunity::Community(c) => c.actor_id(),
+    };
+    let accept = AcceptFollow {
+      actor: ObjectId::new(object_id),
       object: follow,
       kind: AcceptType::Accept,
       id: generate_activity_id(
@@ -43,13 +52,27 @@ impl AcceptFollowCommunity {
       unparsed: Default::default(),
     };
     let inbox = vec![person.shared_inbox_or_inbox()];
-    send_lemmy_activity(context, accept, &community, inbox, true).await
+    match object {
+      UserOrCommunity::User(p) => send_lemmy_activity(context, accept, &p, inbox, true).await,
+      UserOrCommunity::Community(c) => send_lemmy_activity(context, accept, &c, inbox, true).await,
+    }
+  }
+
+  /// Accept the follow of a private community by a remote user, after it was approved by a mod.
+  #[tracing::instrument(skip_all)]
+  pub async fn send_approved(
+    follower: &ApubPerson,
+    community: &ApubCommunity,
+    context: &LemmyContext,
+  ) -> Result<(), LemmyError> {
+    let follow = Follow::new(follower, community.actor_id(), context)?;
+    AcceptFollow::send(follow, context, &mut 0).await
   }
 }
 
 /// Handle accepted follows
 #[async_trait::async_trait(?Send)]
-impl ActivityHandler for AcceptFollowCommunity {
+impl ActivityHandler for AcceptFollow {
   type DataType = LemmyContext;
   type Error = LemmyError;
 
@@ -78,7 +101,7 @@ impl ActivityHandler for AcceptFollowCommunity {
     context: &Data<LemmyContext>,
     request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
-    let community = self
+    let actor = self
       .actor
       .dereference(context, local_instance(context), request_counter)
       .await?;
@@ -87,9 +110,22 @@ impl ActivityHandler for AcceptFollowCommunity {
       .actor
       .dereference(context, local_instance(context), request_counter)
       .await?;
+    let person_id = person.id;
+    let community = match actor {
+      UserOrCommunity::Community(c) => c,
+      UserOrCommunity::User(followed) => {
+        // This will throw an error if no follow was requested
+        let followed_id = followed.id;
+        blocking(context.pool(), move |conn| {
+          PersonFollower::follow_accepted(conn, followed_id, person_id)
+        })
+        .await??;
+        return Ok(());
+      }
+    };
+
     // This will throw an error if no follow was requested
     let community_id = community.id;
-    let person_id = person.id;
     blocking(context.pool(), move |conn| {
       CommunityFollower::follow_accepted(conn, community_id, person_id)
     })
@@ -5,9 +5,10 @@ use crate::{
     verify_person,
     verify_person_in_community,
   },
+  fetcher::user_or_community::UserOrCommunity,
   local_instance,
   objects::{community::ApubCommunity, person::ApubPerson},
-  protocol::activities::following::{accept::AcceptFollowCommunity, follow::FollowCommunity},
+  protocol::activities::following::{accept::AcceptFollow, follow::Follow},
   ActorType,
 };
 use activitypub_federation::{
@@ -16,24 +17,28 @@ use activitypub_federation::{
   traits::{ActivityHandler, Actor},
 };
 use activitystreams_kinds::activity::FollowType;
-use lemmy_api_common::utils::blocking;
+use anyhow::anyhow;
+use lemmy_api_common::utils::{blocking, check_person_block};
 use lemmy_db_schema::{
-  source::community::{CommunityFollower, CommunityFollowerForm},
+  source::{
+    community::{CommunityFollower, CommunityFollowerForm, CommunityVisibility},
+    person_follower::{PersonFollower, PersonFollowerForm},
+  },
   traits::Followable,
 };
 use lemmy_utils::error::LemmyError;
 use lemmy_websocket::LemmyContext;
 use url::Url;
 
-impl FollowCommunity {
+impl Follow {
   pub(in crate::activities::following) fn new(
     actor: &ApubPerson,
-    community: &ApubCommunity,
+    object_id: Url,
     context: &LemmyContext,
-  ) -> Result<FollowCommunity, LemmyError> {
-    Ok(FollowCommunity {
+  ) -> Result<Follow, LemmyError> {
+    Ok(Follow {
       actor: ObjectId::new(actor.actor_id()),
-      object: ObjectId::new(community.actor_id()),
+      object: ObjectId::new(object_id),
       kind: FollowType::Follow,
       id: generate_activity_id(
         FollowType::Follow,
@@ -59,14 +64,36 @@ impl FollowCommunity {
     })
     .await?;
 
-    let follow = FollowCommunity::new(actor, community, context)?;
+    let follow = Follow::new(actor, community.actor_id(), context)?;
     let inbox = vec![community.shared_inbox_or_inbox()];
     send_lemmy_activity(context, follow, actor, inbox, true).await
   }
+
+  /// Follow a remote person. The follow stays pending until it is accepted.
+  #[tracing::instrument(skip_all)]
+  pub async fn send_to_person(
+    actor: &ApubPerson,
+    person: &ApubPerson,
+    context: &LemmyContext,
+  ) -> Result<(), LemmyError> {
+    let person_follower_form = PersonFollowerForm {
+      person_id: person.id,
+      follower_id: actor.id,
+      pending: true,
+    };
+    blocking(context.pool(), move |conn| {
+      PersonFollower::follow(conn, &person_follower_form).ok()
+    })
+    .await?;
+
+    let follow = Follow::new(actor, person.actor_id(), context)?;
+    let inbox = vec![person.inbox()];
+    send_lemmy_activity(context, follow, actor, inbox, true).await
+  }
 }
 
 #[async_trait::async_trait(?Send)]
-impl ActivityHandler for FollowCommunity {
+impl ActivityHandler for Follow {
   type DataType = LemmyContext;
   type Error = LemmyError;
 
@@ -85,11 +112,13 @@ impl ActivityHandler for FollowCommunity {
     request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
     verify_person(&self.actor, context, request_counter).await?;
-    let community = self
+    let object = self
       .object
       .dereference(context, local_instance(context), request_counter)
       .await?;
-    verify_person_in_community(&self.actor, &community, context, request_counter).await?;
+    if let UserOrCommunity::Community(community) = object {
+      verify_person_in_community(&self.actor, &community, context, request_counter).await?;
+    }
     Ok(())
   }
 
@@ -99,26 +128,54 @@ impl ActivityHandler for FollowCommunity {
     context: &Data<LemmyContext>,
     request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
-    let person = self
+    let actor = self
       .actor
       .dereference(context, local_instance(context), request_counter)
       .await?;
-    let community = self
+    let object = self
       .object
       .dereference(context, local_instance(context), request_counter)
       .await?;
-    let community_follower_form = CommunityFollowerForm {
-      community_id: community.id,
-      person_id: person.id,
-      pending: false,
-    };
+    match object {
+      UserOrCommunity::Community(community) => {
+        if community.visibility == CommunityVisibility::LocalOnly {
+          return Err(LemmyError::from_message("community_is_local_only"));
+        }
+        // Follows of private communities need to be approved by a mod first
+        let pending = community.visibility == CommunityVisibility::Private;
+        let community_follower_form = CommunityFollowerForm {
+          community_id: community.id,
+          person_id: actor.id,
+          pending,
+        };
 
-    // This will fail if they're already a follower, but ignore the error.
-    blocking(context.pool(), move |conn| {
-      CommunityFollower::follow(conn, &community_follower_form).ok()
-    })
-    .await?;
+        // This will fail if they're already a follower, but ignore the error.
+        blocking(context.pool(), move |conn| {
+          CommunityFollower::follow(conn, &community_follower_form).ok()
+        })
+        .await?;
+        if pending {
+          return Ok(());
+        }
+      }
+      UserOrCommunity::User(person) => {
+        // Only local persons can be followed through this instance, and not by those they blocked
+        if !person.local {
+          return Err(anyhow!("Follow of remote person").into());
+        }
+        check_person_block(actor.id, person.id, context.pool()).await?;
+        let person_follower_form = PersonFollowerForm {
+          person_id: person.id,
+          follower_id: actor.id,
+          pending: false,
+        };
+        blocking(context.pool(), move |conn| {
+          PersonFollower::follow(conn, &person_follower_form)
+        })
+        .await??;
+      }
+    }
 
-    AcceptFollowCommunity::send(self, context, request_counter).await
+    AcceptFollow::send(self, context, request_counter).await
   }
 }
@@ -1,3 +1,4 @@
 pub mod accept;
 pub mod follow;
+pub mod reject;
 pub mod undo_follow;
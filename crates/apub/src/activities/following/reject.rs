@@ -0,0 +1,117 @@
+use crate::{
+  activities::{generate_activity_id, send_lemmy_activity},
+  fetcher::user_or_community::UserOrCommunity,
+  local_instance,
+  objects::{community::ApubCommunity, person::ApubPerson},
+  protocol::activities::following::{follow::Follow, reject::RejectFollow},
+  ActorType,
+};
+use activitypub_federation::{
+  core::object_id::ObjectId,
+  data::Data,
+  traits::{ActivityHandler, Actor},
+  utils::verify_urls_match,
+};
+use activitystreams_kinds::activity::RejectType;
+use lemmy_api_common::utils::blocking;
+use lemmy_db_schema::{
+  source::{
+    community::{CommunityFollower, CommunityFollowerForm},
+    person_follower::{PersonFollower, PersonFollowerForm},
+  },
+  traits::Followable,
+};
+use lemmy_utils::error::LemmyError;
+use lemmy_websocket::LemmyContext;
+use url::Url;
+
+impl RejectFollow {
+  /// Tell a remote user that their follow of a private community was denied by a mod.
+  #[tracing::instrument(skip_all)]
+  pub async fn send(
+    follower: &ApubPerson,
+    community: &ApubCommunity,
+    context: &LemmyContext,
+  ) -> Result<(), LemmyError> {
+    let reject = RejectFollow {
+      actor: ObjectId::new(community.actor_id()),
+      object: Follow::new(follower, community.actor_id(), context)?,
+      kind: RejectType::Reject,
+      id: generate_activity_id(
+        RejectType::Reject,
+        &context.settings().get_protocol_and_hostname(),
+      )?,
+      unparsed: Default::default(),
+    };
+    let inbox = vec![follower.shared_inbox_or_inbox()];
+    send_lemmy_activity(context, reject, community, inbox, true).await
+  }
+}
+
+/// Handle rejected follows, by removing the pending follow
+#[async_trait::async_trait(?Send)]
+impl ActivityHandler for RejectFollow {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    &self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    verify_urls_match(self.actor.inner(), self.object.object.inner())?;
+    self.object.verify(context, request_counter).await?;
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn receive(
+    self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    let actor = self
+      .actor
+      .dereference(context, local_instance(context), request_counter)
+      .await?;
+    let person = self
+      .object
+      .actor
+      .dereference(context, local_instance(context), request_counter)
+      .await?;
+    match actor {
+      UserOrCommunity::Community(community) => {
+        let community_follower_form = CommunityFollowerForm {
+          community_id: community.id,
+          person_id: person.id,
+          pending: true,
+        };
+        blocking(context.pool(), move |conn| {
+          CommunityFollower::unfollow(conn, &community_follower_form)
+        })
+        .await??;
+      }
+      UserOrCommunity::User(followed) => {
+        let person_follower_form = PersonFollowerForm {
+          person_id: followed.id,
+          follower_id: person.id,
+          pending: true,
+        };
+        blocking(context.pool(), move |conn| {
+          PersonFollower::unfollow(conn, &person_follower_form)
+        })
+        .await??;
+      }
+    }
+    Ok(())
+  }
+}
@@ -1,8 +1,9 @@
 use crate::{
   activities::{generate_activity_id, send_lemmy_activity, verify_person},
+  fetcher::user_or_community::UserOrCommunity,
   local_instance,
   objects::{community::ApubCommunity, person::ApubPerson},
-  protocol::activities::following::{follow::FollowCommunity, undo_follow::UndoFollowCommunity},
+  protocol::activities::following::{follow::Follow, undo_follow::UndoFollow},
   ActorType,
 };
 use activitypub_federation::{
@@ -14,22 +15,46 @@ use activitypub_federation::{
 use activitystreams_kinds::activity::UndoType;
 use lemmy_api_common::utils::blocking;
 use lemmy_db_schema::{
-  source::community::{CommunityFollower, CommunityFollowerForm},
+  source::{
+    community::{CommunityFollower, CommunityFollowerForm},
+    person_follower::{PersonFollower, PersonFollowerForm},
+  },
   traits::Followable,
 };
 use lemmy_utils::error::LemmyError;
 use lemmy_websocket::LemmyContext;
 use url::Url;
 
-impl UndoFollowCommunity {
+impl UndoFollow {
   #[tracing::instrument(skip_all)]
   pub async fn send(
     actor: &ApubPerson,
     community: &ApubCommunity,
     context: &LemmyContext,
   ) -> Result<(), LemmyError> {
-    let object = FollowCommunity::new(actor, community, context)?;
-    let undo = UndoFollowCommunity {
+    let object = Follow::new(actor, community.actor_id(), context)?;
+    let inbox = vec![community.shared_inbox_or_inbox()];
+    UndoFollow::send_undo(actor, object, inbox, context).await
+  }
+
+  #[tracing::instrument(skip_all)]
+  pub async fn send_to_person(
+    actor: &ApubPerson,
+    person: &ApubPerson,
+    context: &LemmyContext,
+  ) -> Result<(), LemmyError> {
+    let object = Follow::new(actor, person.actor_id(), context)?;
+    let inbox = vec![person.inbox()];
+    UndoFollow::send_undo(actor, object, inbox, context).await
+  }
+
+  async fn send_undo(
+    actor: &ApubPerson,
+    object: Follow,
+    inbox: Vec<Url>,
+    context: &LemmyContext,
+  ) -> Result<(), LemmyError> {
+    let undo = UndoFollow {
       actor: ObjectId::new(actor.actor_id()),
       object,
       kind: UndoType::Undo,
@@ -39,13 +64,12 @@ impl UndoFollowCommunity {
       )?,
       unparsed: Default::default(),
     };
-    let inbox = vec![community.shared_inbox_or_inbox()];
     send_lemmy_activity(context, undo, actor, inbox, true).await
   }
 }
 
 #[async_trait::async_trait(?Send)]
-impl ActivityHandler for UndoFollowCommunity {
+impl ActivityHandler for UndoFollow {
   type DataType = LemmyContext;
   type Error = LemmyError;
 
@@ -79,23 +103,38 @@ impl ActivityHandler for UndoFollowCommunity {
       .actor
       .dereference(context, local_instance(context), request_counter)
       .await?;
-    let community = self
+    let object = self
       .object
       .object
       .dereference(context, local_instance(context), request_counter)
       .await?;
 
-    let community_follower_form = CommunityFollowerForm {
-      community_id: community.id,
-      person_id: person.id,
-      pending: false,
-    };
+    match object {
+      UserOrCommunity::Community(community) => {
+        let community_follower_form = CommunityFollowerForm {
+          community_id: community.id,
+          person_id: person.id,
+          pending: false,
+        };
 
-    // This will fail if they aren't a follower, but ignore the error.
-    blocking(context.pool(), move |conn| {
-      CommunityFollower::unfollow(conn, &community_follower_form).ok()
-    })
-    .await?;
+        // This will fail if they aren't a follower, but ignore the error.
+        blocking(context.pool(), move |conn| {
+          CommunityFollower::unfollow(conn, &community_follower_form).ok()
+        })
+        .await?;
+      }
+      UserOrCommunity::User(followed) => {
+        let person_follower_form = PersonFollowerForm {
+          person_id: followed.id,
+          follower_id: person.id,
+          pending: false,
+        };
+        blocking(context.pool(), move |conn| {
+          PersonFollower::unfollow(conn, &person_follower_form).ok()
+        })
+        .await?;
+      }
+    }
     Ok(())
   }
 }
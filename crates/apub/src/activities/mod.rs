@@ -1,4 +1,5 @@
 use crate::{
+  delivery_queue::queue_activity_delivery,
   generate_moderators_url,
   insert_activity,
   local_instance,
@@ -14,7 +15,7 @@ use activitypub_federation::{
 use activitystreams_kinds::public;
 use anyhow::anyhow;
 use lemmy_api_common::utils::blocking;
-use lemmy_db_schema::source::community::Community;
+use lemmy_db_schema::source::community::{Community, CommunityVisibility};
 use lemmy_db_views_actor::structs::{CommunityPersonBanView, CommunityView};
 use lemmy_utils::error::LemmyError;
 use lemmy_websocket::LemmyContext;
@@ -29,6 +30,8 @@ pub mod community;
 pub mod create_or_update;
 pub mod deletion;
 pub mod following;
+pub mod relay;
+pub mod update_actor;
 pub mod voting;
 
 /// Checks that the specified Url actually identifies a Person (by fetching it), and that the person
@@ -141,6 +144,33 @@ pub(crate) fn verify_is_public(to: &[Url], cc: &[Url]) -> Result<(), LemmyError>
   Ok(())
 }
 
+/// Content of private communities is only addressed to the followers, not to the public.
+pub(crate) fn community_audience(community: &Community) -> Url {
+  match community.visibility {
+    CommunityVisibility::Public | CommunityVisibility::LocalOnly => public(),
+    CommunityVisibility::Private => community.followers_url.clone().into(),
+  }
+}
+
+pub(crate) fn verify_visibility(
+  to: &[Url],
+  cc: &[Url],
+  community: &Community,
+) -> Result<(), LemmyError> {
+  match community.visibility {
+    CommunityVisibility::Public | CommunityVisibility::LocalOnly => verify_is_public(to, cc),
+    CommunityVisibility::Private => {
+      let followers: Url = community.followers_url.clone().into();
+      if ![to, cc].iter().any(|set| set.contains(&followers)) {
+        return Err(LemmyError::from_message(
+          "Object is not addressed to community followers",
+        ));
+      }
+      Ok(())
+    }
+  }
+}
+
 pub(crate) fn check_community_deleted_or_removed(community: &Community) -> Result<(), LemmyError> {
   if community.deleted || community.removed {
     Err(LemmyError::from_message(
@@ -185,14 +215,19 @@ where
   let object_value = serde_json::to_value(&activity)?;
   insert_activity(activity.id(), object_value, true, sensitive, context.pool()).await?;
 
-  send_activity(
-    activity,
-    actor.get_public_key(),
-    actor.private_key().expect("actor has private key"),
-    inbox,
-    local_instance(context),
-  )
-  .await?;
+  // In debug mode, activities are sent synchronously for easier testing
+  if context.settings().federation.debug {
+    send_activity(
+      activity,
+      actor.get_public_key(),
+      actor.private_key().expect("actor has private key"),
+      inbox,
+      local_instance(context),
+    )
+    .await?;
+  } else {
+    queue_activity_delivery(activity.id(), actor.actor_id(), inbox, context).await?;
+  }
 
   Ok(())
 }
@@ -0,0 +1,55 @@
+use crate::{
+  local_instance,
+  objects::relay::clear_relay_cache,
+  protocol::activities::relay::accept::AcceptRelayFollow,
+};
+use activitypub_federation::{data::Data, traits::ActivityHandler, utils::verify_urls_match};
+use lemmy_api_common::utils::blocking;
+use lemmy_db_schema::source::relay::Relay;
+use lemmy_utils::error::LemmyError;
+use lemmy_websocket::LemmyContext;
+use url::Url;
+
+#[async_trait::async_trait(?Send)]
+impl ActivityHandler for AcceptRelayFollow {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    &self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    let relay = self
+      .actor
+      .dereference(context, local_instance(context), request_counter)
+      .await?;
+    // The relay may only accept the follow which was sent to it
+    verify_urls_match(&relay.follow_id, &self.object.id)?;
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn receive(
+    self,
+    context: &Data<LemmyContext>,
+    _request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    let follow_id = self.object.id.into();
+    blocking(context.pool(), move |conn| {
+      Relay::mark_accepted(conn, &follow_id)
+    })
+    .await??;
+    clear_relay_cache();
+    Ok(())
+  }
+}
@@ -0,0 +1,59 @@
+use crate::{
+  activities::verify_is_public,
+  local_instance,
+  objects::post::ApubPost,
+  protocol::activities::relay::announce::RelayAnnounce,
+};
+use activitypub_federation::{core::object_id::ObjectId, data::Data, traits::ActivityHandler};
+use lemmy_utils::error::LemmyError;
+use lemmy_websocket::LemmyContext;
+use tracing::debug;
+use url::Url;
+
+#[async_trait::async_trait(?Send)]
+impl ActivityHandler for RelayAnnounce {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    &self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    verify_is_public(&self.to, &self.cc)?;
+    let relay = self
+      .actor
+      .dereference(context, local_instance(context), request_counter)
+      .await?;
+    if !relay.accepted {
+      return Err(LemmyError::from_message("Relay didn't accept our follow"));
+    }
+    Ok(())
+  }
+
+  /// Relays forward all kinds of public objects, but only posts in communities can be stored.
+  /// Everything else is ignored.
+  #[tracing::instrument(skip_all)]
+  async fn receive(
+    self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    let post = ObjectId::<ApubPost>::new(self.object.clone())
+      .dereference(context, local_instance(context), request_counter)
+      .await;
+    if let Err(e) = post {
+      debug!("Ignoring object {} from relay: {}", self.object, e);
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,112 @@
+use crate::{
+  activities::{generate_activity_id, send_lemmy_activity},
+  local_instance,
+  objects::{
+    instance::ApubSite,
+    relay::{clear_relay_cache, ApubRelay},
+  },
+  protocol::{activities::relay::follow::RelayFollow, objects::relay::RelayActor},
+  ActorType,
+};
+use activitypub_federation::{
+  core::object_id::ObjectId,
+  data::Data,
+  traits::{ActivityHandler, ApubObject},
+  utils::fetch_object_http,
+};
+use activitystreams_kinds::{activity::FollowType, public};
+use lemmy_api_common::utils::blocking;
+use lemmy_db_schema::source::{
+  relay::{Relay, RelayForm},
+  site::Site,
+};
+use lemmy_utils::error::LemmyError;
+use lemmy_websocket::LemmyContext;
+use url::Url;
+
+impl RelayFollow {
+  pub(in crate::activities::relay) fn new(relay: &Relay, site: &ApubSite) -> RelayFollow {
+    RelayFollow {
+      actor: ObjectId::new(site.actor_id()),
+      object: relay.follow_object.clone().into(),
+      kind: FollowType::Follow,
+      id: relay.follow_id.clone().into(),
+      unparsed: Default::default(),
+    }
+  }
+
+  /// Subscribe the instance actor to a relay. Urls ending with `/inbox` are treated as Mastodon
+  /// style relays, anything else has to be the actor of a LitePub relay.
+  #[tracing::instrument(skip_all)]
+  pub async fn send(relay_url: Url, context: &LemmyContext) -> Result<Relay, LemmyError> {
+    let site: ApubSite = blocking(context.pool(), Site::read_local_site)
+      .await??
+      .into();
+    let follow_id = generate_activity_id(
+      FollowType::Follow,
+      &context.settings().get_protocol_and_hostname(),
+    )?
+    .into();
+    let form = if relay_url.path().ends_with("/inbox") {
+      RelayForm {
+        inbox_url: relay_url.into(),
+        actor_id: None,
+        public_key: None,
+        follow_id,
+        follow_object: public().into(),
+      }
+    } else {
+      let request_counter = &mut 0;
+      let actor: RelayActor =
+        fetch_object_http(&relay_url, local_instance(context), request_counter).await?;
+      ApubRelay::verify(&actor, &relay_url, context, request_counter).await?;
+      RelayForm {
+        inbox_url: actor.inbox.into(),
+        actor_id: Some(actor.id.clone().into()),
+        public_key: Some(actor.public_key.public_key_pem),
+        follow_id,
+        follow_object: actor.id.into(),
+      }
+    };
+    let relay = blocking(context.pool(), move |conn| Relay::upsert(conn, &form))
+      .await?
+      .map_err(|e| LemmyError::from_error_message(e, "couldnt_add_relay"))?;
+    clear_relay_cache();
+
+    let follow = RelayFollow::new(&relay, &site);
+    let inbox = vec![relay.inbox_url.clone().into()];
+    send_lemmy_activity(context, follow, &site, inbox, true).await?;
+    Ok(relay)
+  }
+}
+
+/// Only sent, relays never follow us
+#[async_trait::async_trait(?Send)]
+impl ActivityHandler for RelayFollow {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  async fn verify(
+    &self,
+    _context: &Data<LemmyContext>,
+    _request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    Err(LemmyError::from_message("Relay follows can't be received"))
+  }
+
+  async fn receive(
+    self,
+    _context: &Data<LemmyContext>,
+    _request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    unimplemented!()
+  }
+}
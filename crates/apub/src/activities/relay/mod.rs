@@ -0,0 +1,4 @@
+pub mod accept;
+pub mod announce;
+pub mod follow;
+pub mod undo_follow;
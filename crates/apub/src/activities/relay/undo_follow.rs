@@ -0,0 +1,73 @@
+use crate::{
+  activities::{generate_activity_id, send_lemmy_activity},
+  objects::{instance::ApubSite, relay::clear_relay_cache},
+  protocol::activities::relay::{follow::RelayFollow, undo_follow::UndoRelayFollow},
+  ActorType,
+};
+use activitypub_federation::{core::object_id::ObjectId, data::Data, traits::ActivityHandler};
+use activitystreams_kinds::activity::UndoType;
+use lemmy_api_common::utils::blocking;
+use lemmy_db_schema::source::{relay::Relay, site::Site};
+use lemmy_utils::error::LemmyError;
+use lemmy_websocket::LemmyContext;
+use url::Url;
+
+impl UndoRelayFollow {
+  /// Unsubscribe from the relay and forget about it
+  #[tracing::instrument(skip_all)]
+  pub async fn send(relay: &Relay, context: &LemmyContext) -> Result<(), LemmyError> {
+    let site: ApubSite = blocking(context.pool(), Site::read_local_site)
+      .await??
+      .into();
+    let undo = UndoRelayFollow {
+      actor: ObjectId::new(site.actor_id()),
+      object: RelayFollow::new(relay, &site),
+      kind: UndoType::Undo,
+      id: generate_activity_id(
+        UndoType::Undo,
+        &context.settings().get_protocol_and_hostname(),
+      )?,
+      unparsed: Default::default(),
+    };
+    let inbox = vec![relay.inbox_url.clone().into()];
+    send_lemmy_activity(context, undo, &site, inbox, true).await?;
+
+    let relay_id = relay.id;
+    blocking(context.pool(), move |conn| Relay::delete(conn, relay_id))
+      .await?
+      .map_err(|e| LemmyError::from_error_message(e, "couldnt_remove_relay"))?;
+    clear_relay_cache();
+    Ok(())
+  }
+}
+
+/// Only sent, relays never follow us
+#[async_trait::async_trait(?Send)]
+impl ActivityHandler for UndoRelayFollow {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  async fn verify(
+    &self,
+    _context: &Data<LemmyContext>,
+    _request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    Err(LemmyError::from_message("Relay follows can't be received"))
+  }
+
+  async fn receive(
+    self,
+    _context: &Data<LemmyContext>,
+    _request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    unimplemented!()
+  }
+}
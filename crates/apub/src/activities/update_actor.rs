@@ -0,0 +1,147 @@
+use crate::{
+  activities::{generate_activity_id, send_lemmy_activity},
+  fetcher::signed_fetch::{PersonOrGroupOrInstance, SignedFetchActor},
+  local_instance,
+  objects::{
+    community::ApubCommunity,
+    instance::{remote_instance_inboxes, ApubSite},
+    person::ApubPerson,
+  },
+  protocol::activities::update_actor::UpdateActor,
+  ActorType,
+};
+use activitypub_federation::{
+  core::object_id::ObjectId,
+  data::Data,
+  traits::{ActivityHandler, Actor, ApubObject},
+  utils::verify_urls_match,
+};
+use activitystreams_kinds::{activity::UpdateType, public};
+use itertools::Itertools;
+use lemmy_api_common::utils::blocking;
+use lemmy_db_schema::{
+  newtypes::DbUrl,
+  source::{
+    community::{Community, CommunityVisibility},
+    person_follower::PersonFollower,
+  },
+};
+use lemmy_utils::error::LemmyError;
+use lemmy_websocket::LemmyContext;
+use url::Url;
+
+impl UpdateActor {
+  /// Announces the current key of a local person to its remote followers, and to the instances of
+  /// remote communities where the person is active.
+  #[tracing::instrument(skip_all)]
+  pub async fn send_person(person: ApubPerson, context: &LemmyContext) -> Result<(), LemmyError> {
+    let person_id = person.id;
+    let (follower_inboxes, communities) = blocking(context.pool(), move |conn| {
+      let follower_inboxes = PersonFollower::list_remote_follower_inboxes(conn, person_id)?;
+      let communities = Community::list_remote_for_person(conn, person_id)?;
+      Ok((follower_inboxes, communities)) as Result<(Vec<DbUrl>, Vec<Community>), LemmyError>
+    })
+    .await??;
+    let inboxes = follower_inboxes
+      .into_iter()
+      .map(Into::into)
+      .chain(
+        communities
+          .into_iter()
+          .map(|c| ApubCommunity::from(c).shared_inbox_or_inbox()),
+      )
+      .collect();
+    let object = PersonOrGroupOrInstance::Person(person.clone().into_apub(context).await?);
+    UpdateActor::send(object, &person, inboxes, context).await
+  }
+
+  /// Announces the current key of a local community to its remote followers, whose instances are
+  /// the ones with a copy of it. Local-only communities aren't federated, so nothing is sent for
+  /// them.
+  #[tracing::instrument(skip_all)]
+  pub async fn send_community(
+    community: ApubCommunity,
+    context: &LemmyContext,
+  ) -> Result<(), LemmyError> {
+    if community.visibility == CommunityVisibility::LocalOnly {
+      return Ok(());
+    }
+    let inboxes = community.get_follower_inboxes(context).await?;
+    let object = PersonOrGroupOrInstance::Group(community.clone().into_apub(context).await?);
+    UpdateActor::send(object, &community, inboxes, context).await
+  }
+
+  /// Announces the current key of the local instance actor to all known instances.
+  #[tracing::instrument(skip_all)]
+  pub async fn send_site(site: ApubSite, context: &LemmyContext) -> Result<(), LemmyError> {
+    let inboxes = remote_instance_inboxes(context.pool()).await?;
+    let object = PersonOrGroupOrInstance::Instance(site.clone().into_apub(context).await?);
+    UpdateActor::send(object, &site, inboxes, context).await
+  }
+
+  async fn send<ActorT>(
+    object: PersonOrGroupOrInstance,
+    actor: &ActorT,
+    inboxes: Vec<Url>,
+    context: &LemmyContext,
+  ) -> Result<(), LemmyError>
+  where
+    ActorT: Actor + ActorType,
+  {
+    let id = generate_activity_id(
+      UpdateType::Update,
+      &context.settings().get_protocol_and_hostname(),
+    )?;
+    let update = UpdateActor {
+      actor: ObjectId::new(actor.actor_id()),
+      to: vec![public()],
+      object: Box::new(object),
+      cc: vec![],
+      kind: UpdateType::Update,
+      id,
+      unparsed: Default::default(),
+    };
+    let inboxes = inboxes.into_iter().unique().collect();
+    send_lemmy_activity(context, update, actor, inboxes, false).await
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ActivityHandler for UpdateActor {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    &self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    verify_urls_match(self.actor.inner(), self.object.id())?;
+    SignedFetchActor::verify(&self.object, self.actor.inner(), context, request_counter).await?;
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn receive(
+    self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    let actor = self
+      .actor
+      .dereference(context, local_instance(context), request_counter)
+      .await?;
+    // The embedded key isn't trusted, the current key is always fetched from the actor's origin
+    SignedFetchActor::refetch_key(self.actor.inner(), actor.public_key(), context).await?;
+    Ok(())
+  }
+}
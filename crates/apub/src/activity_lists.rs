@@ -5,8 +5,10 @@ use crate::{
     activities::{
       block::{block_user::BlockUser, undo_block_user::UndoBlockUser},
       community::{
+        add_featured::AddFeatured,
         add_mod::AddMod,
         announce::AnnounceActivity,
+        remove_featured::RemoveFeatured,
         remove_mod::RemoveMod,
         report::Report,
         update::UpdateCommunity,
@@ -18,10 +20,12 @@ use crate::{
       },
       deletion::{delete::Delete, delete_user::DeleteUser, undo_delete::UndoDelete},
       following::{
-        accept::AcceptFollowCommunity,
-        follow::FollowCommunity,
-        undo_follow::UndoFollowCommunity,
+        accept::AcceptFollow,
+        follow::Follow,
+        reject::RejectFollow,
+        undo_follow::UndoFollow,
       },
+      relay::{accept::AcceptRelayFollow, announce::RelayAnnounce},
       voting::{undo_vote::UndoVote, vote::Vote},
     },
     objects::page::Page,
@@ -52,8 +56,8 @@ pub enum SharedInboxActivities {
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum GroupInboxActivities {
-  FollowCommunity(FollowCommunity),
-  UndoFollowCommunity(UndoFollowCommunity),
+  Follow(Follow),
+  UndoFollow(UndoFollow),
   AnnouncableActivities(Box<AnnouncableActivities>),
   Report(Report),
 }
@@ -62,7 +66,11 @@ pub enum GroupInboxActivities {
 #[serde(untagged)]
 #[activity_handler(LemmyContext, LemmyError)]
 pub enum PersonInboxActivities {
-  AcceptFollowCommunity(AcceptFollowCommunity),
+  AcceptFollow(AcceptFollow),
+  RejectFollow(RejectFollow),
+  /// Follows of local persons, eg by Mastodon users
+  Follow(Follow),
+  UndoFollow(UndoFollow),
   /// Some activities can also be sent from user to user, eg a comment with mentions
   AnnouncableActivities(AnnouncableActivities),
   CreateOrUpdatePrivateMessage(CreateOrUpdatePrivateMessage),
@@ -86,6 +94,8 @@ pub enum AnnouncableActivities {
   UndoBlockUser(UndoBlockUser),
   AddMod(AddMod),
   RemoveMod(RemoveMod),
+  AddFeatured(Box<AddFeatured>),
+  RemoveFeatured(Box<RemoveFeatured>),
   // For compatibility with Pleroma/Mastodon (send only)
   Page(Page),
 }
@@ -100,6 +110,15 @@ pub enum SiteInboxActivities {
   DeleteUser(DeleteUser),
 }
 
+/// Activities sent by relays which the instance actor follows
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+#[activity_handler(LemmyContext, LemmyError)]
+pub enum RelayInboxActivities {
+  AcceptRelayFollow(AcceptRelayFollow),
+  RelayAnnounce(RelayAnnounce),
+}
+
 #[async_trait::async_trait(?Send)]
 impl GetCommunity for AnnouncableActivities {
   #[tracing::instrument(skip(self, context))]
@@ -121,12 +140,39 @@ impl GetCommunity for AnnouncableActivities {
       UndoBlockUser(a) => a.get_community(context, request_counter).await?,
       AddMod(a) => a.get_community(context, request_counter).await?,
       RemoveMod(a) => a.get_community(context, request_counter).await?,
+      AddFeatured(a) => a.get_community(context, request_counter).await?,
+      RemoveFeatured(a) => a.get_community(context, request_counter).await?,
       Page(_) => unimplemented!(),
     };
     Ok(community)
   }
 }
 
+impl AnnouncableActivities {
+  /// Returns true if this creates or updates a post or comment which is removed or held for
+  /// approval locally, so that it isn't announced to other instances.
+  async fn object_is_hidden(&self, context: &LemmyContext) -> bool {
+    use AnnouncableActivities::*;
+    match self {
+      CreateOrUpdatePost(a) => a
+        .object
+        .id
+        .dereference_local(context)
+        .await
+        .map(|p| p.removed || p.pending)
+        .unwrap_or(false),
+      CreateOrUpdateComment(a) => a
+        .object
+        .id
+        .dereference_local(context)
+        .await
+        .map(|c| c.removed || c.pending)
+        .unwrap_or(false),
+      _ => false,
+    }
+  }
+}
+
 impl Id for AnnouncableActivities {
   fn object_id(&self) -> &Url {
     ActivityHandler::id(self)
@@ -141,8 +187,8 @@ impl ActivityHandler for GroupInboxActivities {
 
   fn id(&self) -> &Url {
     match self {
-      GroupInboxActivities::FollowCommunity(a) => a.id(),
-      GroupInboxActivities::UndoFollowCommunity(a) => a.id(),
+      GroupInboxActivities::Follow(a) => a.id(),
+      GroupInboxActivities::UndoFollow(a) => a.id(),
       GroupInboxActivities::AnnouncableActivities(a) => a.object_id(),
       GroupInboxActivities::Report(a) => a.id(),
     }
@@ -150,8 +196,8 @@ impl ActivityHandler for GroupInboxActivities {
 
   fn actor(&self) -> &Url {
     match self {
-      GroupInboxActivities::FollowCommunity(a) => a.actor(),
-      GroupInboxActivities::UndoFollowCommunity(a) => a.actor(),
+      GroupInboxActivities::Follow(a) => a.actor(),
+      GroupInboxActivities::UndoFollow(a) => a.actor(),
       GroupInboxActivities::AnnouncableActivities(a) => a.actor(),
       GroupInboxActivities::Report(a) => a.actor(),
     }
@@ -163,8 +209,8 @@ impl ActivityHandler for GroupInboxActivities {
     request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
     match self {
-      GroupInboxActivities::FollowCommunity(a) => a.verify(data, request_counter).await,
-      GroupInboxActivities::UndoFollowCommunity(a) => a.verify(data, request_counter).await,
+      GroupInboxActivities::Follow(a) => a.verify(data, request_counter).await,
+      GroupInboxActivities::UndoFollow(a) => a.verify(data, request_counter).await,
       GroupInboxActivities::AnnouncableActivities(a) => a.verify(data, request_counter).await,
       GroupInboxActivities::Report(a) => a.verify(data, request_counter).await,
     }
@@ -176,8 +222,8 @@ impl ActivityHandler for GroupInboxActivities {
     request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
     match self {
-      GroupInboxActivities::FollowCommunity(a) => a.receive(data, request_counter).await,
-      GroupInboxActivities::UndoFollowCommunity(a) => a.receive(data, request_counter).await,
+      GroupInboxActivities::Follow(a) => a.receive(data, request_counter).await,
+      GroupInboxActivities::UndoFollow(a) => a.receive(data, request_counter).await,
       GroupInboxActivities::AnnouncableActivities(activity) => {
         activity.clone().receive(data, request_counter).await?;
 
@@ -185,7 +231,9 @@ impl ActivityHandler for GroupInboxActivities {
         // community, but looks identical to Delete/Post or Delete/Comment which are in a community.
         let community = activity.get_community(data, &mut 0).await;
         if let Ok(community) = community {
-          if community.local {
+          // Content which was removed by automod or held for approval on creation is not
+          // announced
+          if community.local && !activity.object_is_hidden(data).await {
             let actor_id = ObjectId::new(activity.actor().clone());
             verify_person_in_community(&actor_id, &community, data, &mut 0).await?;
             AnnounceActivity::send(*activity, &community, data).await?;
@@ -0,0 +1,136 @@
+use crate::{
+  collections::CommunityContext,
+  generate_featured_url,
+  local_instance,
+  objects::post::ApubPost,
+  protocol::collections::group_featured::GroupFeatured,
+};
+use activitypub_federation::{traits::ApubObject, utils::verify_domains_match};
+use activitystreams_kinds::collection::OrderedCollectionType;
+use chrono::NaiveDateTime;
+use futures::future::join_all;
+use lemmy_api_common::utils::blocking;
+use lemmy_db_schema::source::post::Post;
+use lemmy_utils::error::LemmyError;
+use url::Url;
+
+/// Maximum number of posts which are received from the featured collection of a remote community
+const FEATURED_POST_LIMIT: usize = 20;
+
+#[derive(Clone, Debug)]
+pub(crate) struct ApubCommunityFeatured(Vec<ApubPost>);
+
+#[async_trait::async_trait(?Send)]
+impl ApubObject for ApubCommunityFeatured {
+  type DataType = CommunityContext;
+  type ApubType = GroupFeatured;
+  type DbType = ();
+  type Error = LemmyError;
+
+  fn last_refreshed_at(&self) -> Option<NaiveDateTime> {
+    None
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn read_from_apub_id(
+    _object_id: Url,
+    data: &Self::DataType,
+  ) -> Result<Option<Self>, LemmyError> {
+    // Only read from database if its a local community, otherwise fetch over http
+    if data.0.local {
+      let community_id = data.0.id;
+      let posts = blocking(data.1.pool(), move |conn| {
+        Post::list_featured_for_community(conn, community_id)
+      })
+      .await??;
+      Ok(Some(ApubCommunityFeatured(
+        posts.into_iter().map(Into::into).collect(),
+      )))
+    } else {
+      Ok(None)
+    }
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn delete(self, _data: &Self::DataType) -> Result<(), LemmyError> {
+    // do nothing (it gets deleted automatically with the community)
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn into_apub(self, data: &Self::DataType) -> Result<Self::ApubType, LemmyError> {
+    let mut ordered_items = vec![];
+    for post in self.0 {
+      ordered_items.push(post.into_apub(&data.1).await?);
+    }
+    Ok(GroupFeatured {
+      r#type: OrderedCollectionType::OrderedCollection,
+      id: generate_featured_url(&data.0.actor_id)?.into(),
+      total_items: ordered_items.len() as i32,
+      ordered_items,
+    })
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    group_featured: &GroupFeatured,
+    expected_domain: &Url,
+    _data: &Self::DataType,
+    _request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    verify_domains_match(expected_domain, &group_featured.id)?;
+    Ok(())
+  }
+
+  /// Stores the posts in the collection as stickied, and unstickies all other posts of the
+  /// community.
+  #[tracing::instrument(skip_all)]
+  async fn from_apub(
+    apub: Self::ApubType,
+    data: &Self::DataType,
+    _request_counter: &mut i32,
+  ) -> Result<Self, LemmyError> {
+    let community_id = data.0.id;
+    let collection_id = apub.id;
+    let pages = apub.ordered_items.into_iter().take(FEATURED_POST_LIMIT);
+
+    // Each post is received separately, with its own request counter. Errors in one of them are
+    // ignored, so that the rest of the collection is still received. Embedded posts from other
+    // instances can't be trusted, so those are fetched from their origin instead.
+    let posts = join_all(pages.map(|page| async {
+      let mut request_counter = 0;
+      let page_id = page.id.inner().clone();
+      if page_id.domain() == collection_id.domain() {
+        ApubPost::verify(&page, &page_id, &data.1, &mut request_counter).await?;
+        ApubPost::from_apub(page, &data.1, &mut request_counter).await
+      } else {
+        page
+          .id
+          .dereference(&data.1, local_instance(&data.1), &mut request_counter)
+          .await
+      }
+    }))
+    .await
+    .into_iter()
+    .flatten()
+    .filter(|post| post.community_id == community_id)
+    .collect::<Vec<ApubPost>>();
+
+    let featured_ids: Vec<_> = posts.iter().map(|p| p.id).collect();
+    blocking(data.1.pool(), move |conn| {
+      for post in Post::list_featured_for_community(conn, community_id)? {
+        if !featured_ids.contains(&post.id) {
+          Post::update_stickied(conn, post.id, false)?;
+        }
+      }
+      for post_id in featured_ids {
+        Post::update_stickied(conn, post_id, true)?;
+      }
+      Ok(()) as Result<(), LemmyError>
+    })
+    .await??;
+
+    // This return value is unused, so just set an empty vec
+    Ok(ApubCommunityFeatured(Vec::new()))
+  }
+}
@@ -1,6 +1,7 @@
 use crate::{
   activity_lists::AnnouncableActivities,
   collections::CommunityContext,
+  generate_outbox_page_url,
   generate_outbox_url,
   objects::post::ApubPost,
   protocol::{
@@ -17,12 +18,20 @@ use activitystreams_kinds::collection::OrderedCollectionType;
 use chrono::NaiveDateTime;
 use futures::future::join_all;
 use lemmy_api_common::utils::blocking;
-use lemmy_db_schema::source::post::Post;
+use lemmy_db_schema::{aggregates::structs::CommunityAggregates, source::post::Post};
 use lemmy_utils::error::LemmyError;
+use lemmy_websocket::LemmyContext;
 use url::Url;
 
+/// Number of posts in the outbox itself, and in each of its pages
+pub(crate) const OUTBOX_POST_LIMIT: i64 = 20;
+
 #[derive(Clone, Debug)]
-pub(crate) struct ApubCommunityOutbox(Vec<ApubPost>);
+pub(crate) struct ApubCommunityOutbox {
+  posts: Vec<ApubPost>,
+  /// First page of the outbox history, only set for remote communities
+  pub(crate) first: Option<Url>,
+}
 
 #[async_trait::async_trait(?Send)]
 impl ApubObject for ApubCommunityOutbox {
@@ -42,14 +51,14 @@ impl ApubObject for ApubCommunityOutbox {
     // Only read from database if its a local community, otherwise fetch over http
     if data.0.local {
       let community_id = data.0.id;
-      let post_list: Vec<ApubPost> = blocking(data.1.pool(), move |conn| {
-        Post::list_for_community(conn, community_id)
+      let posts: Vec<ApubPost> = blocking(data.1.pool(), move |conn| {
+        Post::list_for_community(conn, community_id, None, Some(OUTBOX_POST_LIMIT))
       })
       .await??
       .into_iter()
       .map(Into::into)
       .collect();
-      Ok(Some(ApubCommunityOutbox(post_list)))
+      Ok(Some(ApubCommunityOutbox { posts, first: None }))
     } else {
       Ok(None)
     }
@@ -63,17 +72,24 @@ impl ApubObject for ApubCommunityOutbox {
   #[tracing::instrument(skip_all)]
   async fn into_apub(self, data: &Self::DataType) -> Result<Self::ApubType, LemmyError> {
     let mut ordered_items = vec![];
-    for post in self.0 {
+    for post in self.posts {
       let page = post.into_apub(&data.1).await?;
       let announcable = AnnouncableActivities::Page(page);
       let announce = AnnounceActivity::new(announcable, &data.0, &data.1)?;
       ordered_items.push(announce);
     }
 
+    let community_id = data.0.id;
+    let counts = blocking(data.1.pool(), move |conn| {
+      CommunityAggregates::read(conn, community_id)
+    })
+    .await??;
+    let outbox_url = generate_outbox_url(&data.0.actor_id)?;
     Ok(GroupOutbox {
       r#type: OrderedCollectionType::OrderedCollection,
-      id: generate_outbox_url(&data.0.actor_id)?.into(),
-      total_items: ordered_items.len() as i32,
+      id: outbox_url.clone().into(),
+      total_items: (counts.posts + counts.comments) as i32,
+      first: Some(generate_outbox_page_url(&outbox_url, 1)?.into()),
       ordered_items,
     })
   }
@@ -86,6 +102,9 @@ impl ApubObject for ApubCommunityOutbox {
     _request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
     verify_domains_match(expected_domain, &group_outbox.id)?;
+    if let Some(first) = &group_outbox.first {
+      verify_domains_match(expected_domain, first)?;
+    }
     Ok(())
   }
 
@@ -96,31 +115,38 @@ impl ApubObject for ApubCommunityOutbox {
     _request_counter: &mut i32,
   ) -> Result<Self, LemmyError> {
     let mut outbox_activities = apub.ordered_items;
-    if outbox_activities.len() > 20 {
-      outbox_activities = outbox_activities[0..20].to_vec();
+    if outbox_activities.len() > OUTBOX_POST_LIMIT as usize {
+      outbox_activities = outbox_activities[0..OUTBOX_POST_LIMIT as usize].to_vec();
     }
+    receive_outbox_items(outbox_activities, &data.1).await;
 
-    // We intentionally ignore errors here. This is because the outbox might contain posts from old
-    // Lemmy versions, or from other software which we cant parse. In that case, we simply skip the
-    // item and only parse the ones that work.
-    let data = Data::new(data.1.clone());
-    // process items in parallel, to avoid long delay from fetch_site_metadata() and other processing
-    join_all(outbox_activities.into_iter().map(|activity| {
-      async {
-        // use separate request counter for each item, otherwise there will be problems with
-        // parallel processing
-        let request_counter = &mut 0;
-        let verify = activity.verify(&data, request_counter).await;
-        if verify.is_ok() {
-          activity.receive(&data, request_counter).await.ok();
-        }
-      }
-    }))
-    .await;
-
-    // This return value is unused, so just set an empty vec
-    Ok(ApubCommunityOutbox(Vec::new()))
+    // The posts are unused, so just set an empty vec
+    Ok(ApubCommunityOutbox {
+      posts: Vec::new(),
+      first: apub.first,
+    })
   }
 
   type DbType = ();
 }
+
+/// Receives the announced activities from a community outbox or one of its pages.
+pub(crate) async fn receive_outbox_items(items: Vec<AnnounceActivity>, context: &LemmyContext) {
+  // We intentionally ignore errors here. This is because the outbox might contain posts from old
+  // Lemmy versions, or from other software which we cant parse. In that case, we simply skip the
+  // item and only parse the ones that work.
+  let data = Data::new(context.clone());
+  // process items in parallel, to avoid long delay from fetch_site_metadata() and other processing
+  join_all(items.into_iter().map(|activity| {
+    async {
+      // use separate request counter for each item, otherwise there will be problems with
+      // parallel processing
+      let request_counter = &mut 0;
+      let verify = activity.verify(&data, request_counter).await;
+      if verify.is_ok() {
+        activity.receive(&data, request_counter).await.ok();
+      }
+    }
+  }))
+  .await;
+}
@@ -0,0 +1,161 @@
+use crate::{
+  activity_lists::AnnouncableActivities,
+  collections::{
+    community_outbox::{receive_outbox_items, OUTBOX_POST_LIMIT},
+    CommunityContext,
+  },
+  generate_outbox_page_url,
+  generate_outbox_url,
+  objects::{comment::ApubComment, person::ApubPerson, post::ApubPost},
+  protocol::{
+    activities::{
+      community::announce::AnnounceActivity,
+      create_or_update::comment::CreateOrUpdateComment,
+      CreateOrUpdateType,
+    },
+    collections::group_outbox_page::GroupOutboxPage,
+  },
+};
+use activitypub_federation::{traits::ApubObject, utils::verify_domains_match};
+use activitystreams_kinds::collection::OrderedCollectionPageType;
+use chrono::NaiveDateTime;
+use lemmy_api_common::utils::blocking;
+use lemmy_db_schema::{
+  source::{comment::Comment, person::Person, post::Post},
+  traits::Crud,
+};
+use lemmy_utils::error::LemmyError;
+use url::Url;
+
+/// Number of comments in each page of the outbox
+const OUTBOX_COMMENT_LIMIT: i64 = 50;
+
+/// A page of the community outbox. Each page contains the posts and comments at the same offset,
+/// so that walking all pages returns the full history of the community.
+#[derive(Clone, Debug)]
+pub(crate) struct ApubCommunityOutboxPage {
+  page: i64,
+  posts: Vec<ApubPost>,
+  comments: Vec<(ApubComment, ApubPerson)>,
+  /// Next page of a remote outbox, if any
+  pub(crate) next: Option<Url>,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ApubObject for ApubCommunityOutboxPage {
+  type DataType = CommunityContext;
+  type ApubType = GroupOutboxPage;
+  type DbType = ();
+  type Error = LemmyError;
+
+  fn last_refreshed_at(&self) -> Option<NaiveDateTime> {
+    None
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn read_from_apub_id(
+    object_id: Url,
+    data: &Self::DataType,
+  ) -> Result<Option<Self>, LemmyError> {
+    // Only read from database if its a local community, otherwise fetch over http
+    if !data.0.local {
+      return Ok(None);
+    }
+    let page = object_id
+      .query_pairs()
+      .find(|(key, _)| key == "page")
+      .and_then(|(_, value)| value.parse::<i64>().ok())
+      .unwrap_or(1);
+    let community_id = data.0.id;
+    let (posts, comments) = blocking(data.1.pool(), move |conn| {
+      let posts =
+        Post::list_for_community(conn, community_id, Some(page), Some(OUTBOX_POST_LIMIT))?;
+      let comments =
+        Comment::list_for_community(conn, community_id, Some(page), Some(OUTBOX_COMMENT_LIMIT))?
+          .into_iter()
+          .map(|comment| {
+            let creator = Person::read(conn, comment.creator_id)?;
+            Ok((comment.into(), creator.into()))
+          })
+          .collect::<Result<Vec<(ApubComment, ApubPerson)>, LemmyError>>()?;
+      Ok((posts, comments)) as Result<(Vec<Post>, Vec<(ApubComment, ApubPerson)>), LemmyError>
+    })
+    .await??;
+    Ok(Some(ApubCommunityOutboxPage {
+      page,
+      posts: posts.into_iter().map(Into::into).collect(),
+      comments,
+      next: None,
+    }))
+  }
+
+  async fn delete(self, _data: &Self::DataType) -> Result<(), LemmyError> {
+    // do nothing (it gets deleted automatically with the community)
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn into_apub(self, data: &Self::DataType) -> Result<Self::ApubType, LemmyError> {
+    let has_next = self.posts.len() as i64 == OUTBOX_POST_LIMIT
+      || self.comments.len() as i64 == OUTBOX_COMMENT_LIMIT;
+
+    let mut ordered_items = vec![];
+    for post in self.posts {
+      let page = post.into_apub(&data.1).await?;
+      let announcable = AnnouncableActivities::Page(page);
+      ordered_items.push(AnnounceActivity::new(announcable, &data.0, &data.1)?);
+    }
+    for (comment, creator) in self.comments {
+      let create =
+        CreateOrUpdateComment::new(comment, &creator, CreateOrUpdateType::Create, &data.1).await?;
+      let announcable = AnnouncableActivities::CreateOrUpdateComment(create);
+      ordered_items.push(AnnounceActivity::new(announcable, &data.0, &data.1)?);
+    }
+
+    let outbox_url = generate_outbox_url(&data.0.actor_id)?;
+    let next = if has_next {
+      Some(generate_outbox_page_url(&outbox_url, self.page + 1)?.into())
+    } else {
+      None
+    };
+    Ok(GroupOutboxPage {
+      r#type: OrderedCollectionPageType::OrderedCollectionPage,
+      id: generate_outbox_page_url(&outbox_url, self.page)?.into(),
+      part_of: outbox_url.into(),
+      next,
+      ordered_items,
+    })
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    outbox_page: &GroupOutboxPage,
+    expected_domain: &Url,
+    _context: &CommunityContext,
+    _request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    verify_domains_match(expected_domain, &outbox_page.id)?;
+    verify_domains_match(expected_domain, &outbox_page.part_of)?;
+    if let Some(next) = &outbox_page.next {
+      verify_domains_match(expected_domain, next)?;
+    }
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn from_apub(
+    apub: Self::ApubType,
+    data: &Self::DataType,
+    _request_counter: &mut i32,
+  ) -> Result<Self, LemmyError> {
+    receive_outbox_items(apub.ordered_items, &data.1).await;
+
+    // The items are unused, only the link to the next page is needed
+    Ok(ApubCommunityOutboxPage {
+      page: 0,
+      posts: Vec::new(),
+      comments: Vec::new(),
+      next: apub.next,
+    })
+  }
+}
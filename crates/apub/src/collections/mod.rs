@@ -2,8 +2,10 @@ use lemmy_websocket::LemmyContext;
 
 use crate::objects::community::ApubCommunity;
 
+pub(crate) mod community_featured;
 pub(crate) mod community_moderators;
 pub(crate) mod community_outbox;
+pub(crate) mod community_outbox_page;
 
 /// Put community in the data, so we dont have to read it again from the database.
 pub(crate) struct CommunityContext(pub ApubCommunity, pub LemmyContext);
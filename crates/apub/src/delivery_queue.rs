@@ -0,0 +1,243 @@
+use crate::check_apub_id_valid;
+use activitypub_federation::APUB_JSON_CONTENT_TYPE;
+use actix_web::rt::{spawn, time::sleep};
+use http_signature_normalization_reqwest::prelude::{Config, SignExt};
+use itertools::Itertools;
+use lemmy_api_common::utils::blocking;
+use lemmy_db_schema::{
+  newtypes::DbUrl,
+  source::{
+    activity::Activity,
+    activity_delivery::{ActivityDelivery, ActivityDeliveryForm},
+    community::Community,
+    instance::Instance,
+    person::Person,
+    site::Site,
+  },
+  traits::{ApubActor, Crud},
+};
+use lemmy_utils::error::LemmyError;
+use lemmy_websocket::LemmyContext;
+use once_cell::sync::Lazy;
+use openssl::{hash::MessageDigest, pkey::PKey, sign::Signer};
+use reqwest::StatusCode;
+use sha2::{Digest, Sha256};
+use std::{
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+  },
+  time::Duration,
+};
+use tracing::{error, info, warn};
+use url::Url;
+
+static HTTP_SIG_CONFIG: Lazy<Config> = Lazy::new(Config::new);
+
+/// After this many failed attempts, a delivery is marked as failed and not retried anymore. With
+/// the backoff in `ActivityDelivery::mark_attempt_failed`, this takes about a day.
+const MAX_DELIVERY_ATTEMPTS: i32 = 12;
+/// How often the queue is checked for deliveries which are due
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Stores a delivery for each of the inboxes, which is then sent by `run_delivery_queue`. Local
+/// and blocked inboxes are skipped, as well as dead instances which were probed recently.
+pub(crate) async fn queue_activity_delivery(
+  activity_id: &Url,
+  actor_id: Url,
+  inboxes: Vec<Url>,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  let settings = context.settings();
+  let local_hostname = settings.get_hostname_without_port()?;
+  let inboxes: Vec<DbUrl> = inboxes
+    .into_iter()
+    .unique()
+    .filter(|inbox| inbox.domain() != Some(&local_hostname))
+    .filter(|inbox| check_apub_id_valid(inbox, settings).is_ok())
+    .map(Into::into)
+    .collect();
+  if inboxes.is_empty() {
+    return Ok(());
+  }
+
+  let activity_id: DbUrl = activity_id.clone().into();
+  let actor_id: DbUrl = actor_id.into();
+  blocking(context.pool(), move |conn| {
+    let suppressed = Instance::list_suppressed_domains(conn)?;
+    let activity = Activity::read_from_apub_id(conn, &activity_id)?;
+    let forms: Vec<ActivityDeliveryForm> = inboxes
+      .into_iter()
+      .filter(|inbox| {
+        let domain = inbox.domain().unwrap_or_default();
+        !suppressed.iter().any(|s| s == domain)
+      })
+      .map(|inbox| ActivityDeliveryForm {
+        activity_id: activity.id,
+        inbox,
+        actor_id: actor_id.clone(),
+      })
+      .collect();
+    ActivityDelivery::enqueue(conn, &forms)
+  })
+  .await??;
+  Ok(())
+}
+
+/// Sends queued activities in the background, with at most `federation.worker_count` requests at
+/// the same time. As the queue is stored in the database, pending deliveries are resumed after a
+/// restart.
+pub async fn run_delivery_queue(context: LemmyContext) {
+  let max_parallel = context.settings().federation.worker_count as usize;
+  let in_flight = Arc::new(AtomicUsize::new(0));
+  loop {
+    let free_workers = max_parallel.saturating_sub(in_flight.load(Ordering::SeqCst)) as i64;
+    if free_workers > 0 {
+      let due = blocking(context.pool(), move |conn| {
+        ActivityDelivery::claim_due(conn, free_workers)
+      })
+      .await;
+      match due {
+        Ok(Ok(deliveries)) => {
+          for delivery in deliveries {
+            in_flight.fetch_add(1, Ordering::SeqCst);
+            let context = context.clone();
+            let in_flight = in_flight.clone();
+            spawn(async move {
+              deliver(delivery, &context).await;
+              in_flight.fetch_sub(1, Ordering::SeqCst);
+            });
+          }
+        }
+        Ok(Err(e)) => error!("Failed to read activity delivery queue: {}", e),
+        Err(e) => error!("Failed to read activity delivery queue: {}", e),
+      }
+    }
+    sleep(QUEUE_POLL_INTERVAL).await;
+  }
+}
+
+/// Sends a single delivery, and removes it from the queue or schedules a retry. Network errors,
+/// server errors and rate limits are retried, other client errors like a bad signature or a gone
+/// inbox won't change on retry and fail right away. The result is also recorded for the receiving
+/// instance: it counts as reachable if it answered with anything other than a server error.
+async fn deliver(delivery: ActivityDelivery, context: &LemmyContext) {
+  let result = send_delivery(&delivery, context).await;
+  let domain = delivery.inbox.domain().unwrap_or_default().to_string();
+  let updated = blocking(context.pool(), move |conn| {
+    let reachable = match &result {
+      Ok((status, _)) => !status.is_server_error(),
+      Err(_) => false,
+    };
+    if reachable {
+      Instance::mark_delivery_success(conn, &domain)?;
+    } else {
+      Instance::mark_delivery_failed(conn, &domain)?;
+    }
+    match result {
+      Ok((status, _)) if status.is_success() => {
+        ActivityDelivery::delete(conn, delivery.id)?;
+      }
+      Ok((status, text)) => {
+        let error = format!(
+          "Send to {} failed with status {}: {}",
+          delivery.inbox, status, text
+        );
+        warn!("{}", error);
+        if is_retryable(status) {
+          ActivityDelivery::mark_attempt_failed(conn, &delivery, &error, MAX_DELIVERY_ATTEMPTS)?;
+        } else {
+          ActivityDelivery::mark_failed(conn, &delivery, &error)?;
+        }
+      }
+      Err(e) => {
+        warn!("{}", e);
+        ActivityDelivery::mark_attempt_failed(
+          conn,
+          &delivery,
+          &e.to_string(),
+          MAX_DELIVERY_ATTEMPTS,
+        )?;
+      }
+    }
+    Ok(()) as Result<(), LemmyError>
+  })
+  .await;
+  match updated {
+    Ok(Ok(())) => {}
+    Ok(Err(e)) => error!("Failed to update activity delivery queue: {}", e),
+    Err(e) => error!("Failed to update activity delivery queue: {}", e),
+  }
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+  !status.is_client_error()
+    || status == StatusCode::REQUEST_TIMEOUT
+    || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Signs the activity with the key of the sending actor, and posts it to the inbox. Returns the
+/// response status, and the response body if it isn't a success.
+async fn send_delivery(
+  delivery: &ActivityDelivery,
+  context: &LemmyContext,
+) -> Result<(StatusCode, String), LemmyError> {
+  let activity_id = delivery.activity_id;
+  let actor_id = delivery.actor_id.clone();
+  let (activity, private_key) = blocking(context.pool(), move |conn| {
+    let activity = Activity::read(conn, activity_id)?;
+    let private_key = read_private_key(conn, &actor_id)?;
+    Ok((activity, private_key)) as Result<(Activity, String), LemmyError>
+  })
+  .await??;
+  info!("Sending {} to {}", activity.ap_id, delivery.inbox);
+
+  let inbox: Url = delivery.inbox.clone().into();
+  let mut host = inbox.domain().unwrap_or_default().to_string();
+  if let Some(port) = inbox.port() {
+    host = format!("{}:{}", host, port);
+  }
+  let key_id = format!("{}#main-key", delivery.actor_id);
+  let request = context
+    .client()
+    .post(inbox.as_str())
+    .timeout(DELIVERY_TIMEOUT)
+    .header("content-type", APUB_JSON_CONTENT_TYPE)
+    .header("host", host)
+    .signature_with_digest(
+      HTTP_SIG_CONFIG.clone(),
+      key_id,
+      Sha256::new(),
+      serde_json::to_string(&activity.data)?,
+      move |signing_string| {
+        let private_key = PKey::private_key_from_pem(private_key.as_bytes())?;
+        let mut signer = Signer::new(MessageDigest::sha256(), &private_key)?;
+        signer.update(signing_string.as_bytes())?;
+        Ok(base64::encode(signer.sign_to_vec()?)) as Result<_, anyhow::Error>
+      },
+    )
+    .await?;
+  let response = context.client().execute(request).await?;
+  let status = response.status();
+  let text = if status.is_success() {
+    String::new()
+  } else {
+    response.text().await.unwrap_or_default()
+  };
+  Ok((status, text))
+}
+
+/// Activities can be sent by users, communities and the instance actor.
+fn read_private_key(conn: &diesel::PgConnection, actor_id: &DbUrl) -> Result<String, LemmyError> {
+  let private_key = if let Some(person) = Person::read_from_apub_id(conn, actor_id)? {
+    person.private_key
+  } else if let Some(community) = Community::read_from_apub_id(conn, actor_id)? {
+    community.private_key
+  } else if let Some(site) = Site::read_from_apub_id(conn, actor_id.clone().into())? {
+    site.private_key
+  } else {
+    None
+  };
+  private_key.ok_or_else(|| LemmyError::from_message("couldnt_find_private_key"))
+}
@@ -0,0 +1,120 @@
+use crate::{
+  collections::{
+    community_outbox::ApubCommunityOutbox,
+    community_outbox_page::ApubCommunityOutboxPage,
+    CommunityContext,
+  },
+  generate_outbox_url,
+  local_instance,
+  objects::community::ApubCommunity,
+};
+use activitypub_federation::core::object_id::ObjectId;
+use lemmy_db_schema::newtypes::CommunityId;
+use lemmy_utils::error::LemmyError;
+use lemmy_websocket::LemmyContext;
+use once_cell::sync::Lazy;
+use std::{collections::HashSet, sync::Mutex};
+use tracing::{info, warn};
+use url::Url;
+
+/// At most this many outbox pages are imported for a community, which is 2000 posts with the
+/// page size of Lemmy
+const MAX_BACKFILL_PAGES: usize = 100;
+/// The import is stopped after this many pages in a row couldn't be fetched
+const MAX_FAILED_PAGES_IN_ROW: usize = 3;
+
+/// Remote communities whose history is currently being imported
+static BACKFILLS_IN_PROGRESS: Lazy<Mutex<HashSet<CommunityId>>> =
+  Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Starts importing the full history of a remote community in the background.
+pub fn spawn_community_backfill(
+  community: ApubCommunity,
+  context: LemmyContext,
+) -> Result<(), LemmyError> {
+  if community.local {
+    return Err(LemmyError::from_message("cant_backfill_local_community"));
+  }
+  let community_id = community.id;
+  if !BACKFILLS_IN_PROGRESS
+    .lock()
+    .expect("mutex poison error")
+    .insert(community_id)
+  {
+    return Err(LemmyError::from_message("community_backfill_in_progress"));
+  }
+
+  actix_web::rt::spawn(async move {
+    let actor_id = community.actor_id.clone();
+    match backfill_community(community, &context).await {
+      Ok(pages) => info!("Imported {} outbox pages of community {}", pages, actor_id),
+      Err(e) => warn!("Failed to import history of community {}: {}", actor_id, e),
+    }
+    BACKFILLS_IN_PROGRESS
+      .lock()
+      .expect("mutex poison error")
+      .remove(&community_id);
+  });
+  Ok(())
+}
+
+/// Walks the pages of the community outbox, up to `MAX_BACKFILL_PAGES`, and receives the posts and
+/// comments in them. Each page gets its own request counter, so `http_fetch_retry_limit` applies
+/// to a single page and the objects it references, the same as for an incoming activity. A page
+/// which fails is skipped, if the url of the following page can be guessed. Returns the number of
+/// imported pages.
+async fn backfill_community(
+  community: ApubCommunity,
+  context: &LemmyContext,
+) -> Result<usize, LemmyError> {
+  let outbox_url = generate_outbox_url(&community.actor_id)?;
+  let data = CommunityContext(community, context.clone());
+  let outbox: ApubCommunityOutbox = ObjectId::new(outbox_url)
+    .dereference(&data, local_instance(context), &mut 0)
+    .await?;
+
+  let mut visited = HashSet::new();
+  let mut imported = 0;
+  let mut failed_in_row = 0;
+  let mut next = outbox.first;
+  while let Some(page_url) = next {
+    // Protect against outboxes which link back to an earlier page
+    if visited.len() >= MAX_BACKFILL_PAGES || !visited.insert(page_url.clone()) {
+      break;
+    }
+    let page: Result<ApubCommunityOutboxPage, LemmyError> = ObjectId::new(page_url.clone())
+      .dereference(&data, local_instance(context), &mut 0)
+      .await;
+    next = match page {
+      Ok(page) => {
+        imported += 1;
+        failed_in_row = 0;
+        page.next
+      }
+      Err(e) => {
+        warn!("Failed to import outbox page {}: {}", page_url, e);
+        failed_in_row += 1;
+        if failed_in_row >= MAX_FAILED_PAGES_IN_ROW {
+          break;
+        }
+        following_page_url(&page_url)
+      }
+    };
+  }
+  Ok(imported)
+}
+
+/// Lemmy numbers its outbox pages with the `page` query parameter, so the page after a failed one
+/// can still be found. Other software links its pages differently, then there is no next page.
+fn following_page_url(page_url: &Url) -> Option<Url> {
+  let page = page_url
+    .query_pairs()
+    .find(|(key, _)| key == "page")
+    .and_then(|(_, value)| value.parse::<i64>().ok())?;
+  let mut next = page_url.clone();
+  next
+    .query_pairs_mut()
+    .clear()
+    .append_pair("page", &(page + 1).to_string());
+  Some(next)
+}
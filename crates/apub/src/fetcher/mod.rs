@@ -6,8 +6,11 @@ use lemmy_db_schema::traits::ApubActor;
 use lemmy_utils::error::LemmyError;
 use lemmy_websocket::LemmyContext;
 
+pub mod community_backfill;
+pub(crate) mod nodeinfo;
 pub mod post_or_comment;
 pub mod search;
+pub mod signed_fetch;
 pub mod user_or_community;
 pub mod webfinger;
 
@@ -0,0 +1,148 @@
+use crate::check_apub_id_valid_with_strictness;
+use activitypub_federation::utils::verify_domains_match;
+use lemmy_api_common::utils::blocking;
+use lemmy_db_schema::{newtypes::InstanceId, source::instance::Instance};
+use lemmy_utils::error::LemmyError;
+use lemmy_websocket::LemmyContext;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use std::{
+  collections::HashMap,
+  sync::Mutex,
+  time::{Duration, Instant},
+};
+use tracing::{debug, warn};
+use url::Url;
+
+const NODEINFO_TIMEOUT: Duration = Duration::from_secs(10);
+/// How often nodeinfo is fetched again for instances whose software is still unknown
+const NODEINFO_RETRY_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// When nodeinfo was last fetched for instances whose software is unknown
+static NODEINFO_ATTEMPTS: Lazy<Mutex<HashMap<InstanceId, Instant>>> =
+  Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Deserialize, Debug)]
+struct NodeInfoWellKnown {
+  links: Vec<NodeInfoWellKnownLink>,
+}
+
+#[derive(Deserialize, Debug)]
+struct NodeInfoWellKnownLink {
+  rel: String,
+  href: Url,
+}
+
+/// Only the parts of nodeinfo which are stored, the same for schema 2.0 and 2.1
+#[derive(Deserialize, Debug)]
+struct NodeInfo {
+  software: NodeInfoSoftware,
+}
+
+#[derive(Deserialize, Debug)]
+struct NodeInfoSoftware {
+  name: String,
+  version: Option<String>,
+}
+
+/// Returns the id of the instance which the actor belongs to, creating it if necessary. As long as
+/// the software of the instance is unknown, it is fetched from nodeinfo in the background, at most
+/// once per `NODEINFO_RETRY_INTERVAL`.
+#[tracing::instrument(skip_all)]
+pub(crate) async fn read_or_create_instance(
+  actor_id: &Url,
+  context: &LemmyContext,
+) -> Result<InstanceId, LemmyError> {
+  let domain = actor_id
+    .domain()
+    .ok_or_else(|| LemmyError::from_message("invalid_domain"))?
+    .to_string();
+  let (instance, _) = blocking(context.pool(), move |conn| {
+    Instance::read_or_create(conn, &domain)
+  })
+  .await??;
+  if instance.software.is_none() && should_fetch_nodeinfo(instance.id) {
+    let actor_id = actor_id.clone();
+    let context = context.clone();
+    let instance_id = instance.id;
+    actix_web::rt::spawn(async move {
+      if let Err(e) = update_instance_software(instance_id, &actor_id, &context).await {
+        warn!("Failed to fetch nodeinfo for {}: {}", actor_id, e);
+      }
+    });
+  }
+  Ok(instance.id)
+}
+
+fn should_fetch_nodeinfo(instance_id: InstanceId) -> bool {
+  let mut attempts = NODEINFO_ATTEMPTS.lock().expect("mutex poison error");
+  let now = Instant::now();
+  let retry = attempts
+    .get(&instance_id)
+    .map(|last| now.duration_since(*last) > NODEINFO_RETRY_INTERVAL)
+    .unwrap_or(true);
+  if retry {
+    attempts.insert(instance_id, now);
+  }
+  retry
+}
+
+async fn update_instance_software(
+  instance_id: InstanceId,
+  actor_id: &Url,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  let software = fetch_nodeinfo_software(actor_id, context).await?;
+  blocking(context.pool(), move |conn| {
+    Instance::update_software(conn, instance_id, Some(software.name), software.version)
+  })
+  .await??;
+  NODEINFO_ATTEMPTS
+    .lock()
+    .expect("mutex poison error")
+    .remove(&instance_id);
+  Ok(())
+}
+
+/// Follows the link in `/.well-known/nodeinfo` to the nodeinfo document of the actor's instance
+async fn fetch_nodeinfo_software(
+  actor_id: &Url,
+  context: &LemmyContext,
+) -> Result<NodeInfoSoftware, LemmyError> {
+  let mut well_known_url = actor_id.clone();
+  well_known_url.set_path("/.well-known/nodeinfo");
+  well_known_url.set_query(None);
+  well_known_url.set_fragment(None);
+  debug!("Fetching nodeinfo from {}", well_known_url);
+
+  let well_known: NodeInfoWellKnown = context
+    .client()
+    .get(well_known_url.as_str())
+    .timeout(NODEINFO_TIMEOUT)
+    .send()
+    .await?
+    .json()
+    .await?;
+  let link = well_known
+    .links
+    .into_iter()
+    .filter(|l| {
+      l.rel
+        .starts_with("http://nodeinfo.diaspora.software/ns/schema/")
+    })
+    // Prefer the newest schema version
+    .max_by(|a, b| a.rel.cmp(&b.rel))
+    .ok_or_else(|| LemmyError::from_message("nodeinfo_not_found"))?;
+  // Don't let other instances make us send requests to arbitrary urls
+  verify_domains_match(&link.href, actor_id)?;
+  check_apub_id_valid_with_strictness(&link.href, false, context.settings())?;
+  let nodeinfo: NodeInfo = context
+    .client()
+    .get(link.href.as_str())
+    .timeout(NODEINFO_TIMEOUT)
+    .send()
+    .await?
+    .json()
+    .await?;
+  Ok(nodeinfo.software)
+}
@@ -0,0 +1,295 @@
+use crate::{
+  local_instance,
+  objects::{community::ApubCommunity, instance::ApubSite, person::ApubPerson},
+  protocol::objects::{group::Group, instance::Instance, person::Person},
+};
+use activitypub_federation::{
+  traits::{Actor, ApubObject},
+  utils::{fetch_object_http, verify_urls_match},
+  APUB_JSON_CONTENT_TYPE,
+};
+use anyhow::anyhow;
+use chrono::{NaiveDateTime, Utc};
+use http::{header::HeaderValue, Method};
+use http_signature_normalization::Config;
+use lemmy_api_common::utils::blocking;
+use lemmy_db_schema::{
+  source::{
+    actor_key_rotation::{ActorKeyRotation, ActorKeyRotationForm},
+    site::Site,
+  },
+  utils::{naive_now, DbPool},
+};
+use lemmy_utils::error::LemmyError;
+use lemmy_websocket::LemmyContext;
+use once_cell::sync::Lazy;
+use openssl::{hash::MessageDigest, pkey::PKey, sign::Signer};
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, sync::RwLock};
+use task_local_extensions::Extensions;
+use url::Url;
+
+static HTTP_SIG_CONFIG: Lazy<Config> = Lazy::new(|| Config::new().mastodon_compat());
+
+/// Key id and private key of the instance actor, so that the site doesn't have to be read for
+/// every fetch. Cleared when the key is rotated.
+static SITE_KEY: Lazy<RwLock<Option<SiteKey>>> = Lazy::new(|| RwLock::new(None));
+
+#[derive(Clone)]
+struct SiteKey {
+  key_id: String,
+  private_key: String,
+}
+
+/// Needs to be called after the key of the instance actor changed, so that fetches are signed
+/// with the new key.
+pub fn clear_site_key_cache() {
+  if let Ok(mut site_key) = SITE_KEY.write() {
+    *site_key = None;
+  }
+}
+
+/// Signs outgoing ActivityPub GET requests with the key of the instance actor, for remote
+/// instances which require authorized fetch or serve private communities. Other requests are
+/// passed through unchanged. Only used if `signed_fetch` is enabled.
+pub struct SignedFetchMiddleware {
+  pub pool: DbPool,
+}
+
+#[async_trait::async_trait]
+impl Middleware for SignedFetchMiddleware {
+  async fn handle(
+    &self,
+    mut req: Request,
+    extensions: &mut Extensions,
+    next: Next<'_>,
+  ) -> reqwest_middleware::Result<Response> {
+    let is_apub_fetch = req.method() == Method::GET
+      && req
+        .headers()
+        .get("accept")
+        .map(|a| a == APUB_JSON_CONTENT_TYPE)
+        .unwrap_or(false);
+    if is_apub_fetch {
+      if let Some(site_key) = self.site_key().await? {
+        let date = Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+        req.headers_mut().insert(
+          "date",
+          HeaderValue::from_str(&date).map_err(|e| anyhow!("{}", e))?,
+        );
+        let signature = sign_fetch(&req, site_key.key_id, site_key.private_key)?;
+        req.headers_mut().insert("signature", signature);
+      }
+    }
+    next.run(req, extensions).await
+  }
+}
+
+impl SignedFetchMiddleware {
+  async fn site_key(&self) -> Result<Option<SiteKey>, anyhow::Error> {
+    let cached = SITE_KEY.read().map_err(|e| anyhow!("{}", e))?.clone();
+    if cached.is_some() {
+      return Ok(cached);
+    }
+    let site = blocking(&self.pool, Site::read_local_site)
+      .await
+      .map_err(|e| anyhow!("{}", e))??;
+    let site_key = site.private_key.map(|private_key| SiteKey {
+      key_id: format!("{}#main-key", site.actor_id),
+      private_key,
+    });
+    *SITE_KEY.write().map_err(|e| anyhow!("{}", e))? = site_key.clone();
+    Ok(site_key)
+  }
+}
+
+/// Builds the signature header for a GET request, which has no body and so no digest.
+pub(crate) fn sign_fetch(
+  req: &Request,
+  key_id: String,
+  private_key: String,
+) -> Result<HeaderValue, anyhow::Error> {
+  let mut headers = BTreeMap::new();
+  for (name, value) in req.headers() {
+    headers.insert(name.to_string(), value.to_str()?.to_string());
+  }
+  let url = req.url();
+  let mut host = url.host_str().unwrap_or_default().to_string();
+  if let Some(port) = url.port() {
+    host = format!("{}:{}", host, port);
+  }
+  headers.insert("host".to_string(), host);
+  let path_and_query = match url.query() {
+    Some(query) => format!("{}?{}", url.path(), query),
+    None => url.path().to_string(),
+  };
+
+  let signed = HTTP_SIG_CONFIG
+    .begin_sign(req.method().as_str(), &path_and_query, headers)?
+    .sign(key_id, |signing_string| {
+      let private_key = PKey::private_key_from_pem(private_key.as_bytes())?;
+      let mut signer = Signer::new(MessageDigest::sha256(), &private_key)?;
+      signer.update(signing_string.as_bytes())?;
+      Ok(base64::encode(signer.sign_to_vec()?)) as Result<_, anyhow::Error>
+    })?;
+  Ok(HeaderValue::from_str(&signed.signature_header())?)
+}
+
+/// Any actor which can sign a fetch: a user, a community or an instance actor.
+#[derive(Clone, Debug)]
+pub enum SignedFetchActor {
+  User(ApubPerson),
+  Community(ApubCommunity),
+  Site(ApubSite),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum PersonOrGroupOrInstance {
+  Person(Person),
+  Group(Group),
+  Instance(Instance),
+}
+
+impl PersonOrGroupOrInstance {
+  pub(crate) fn id(&self) -> &Url {
+    match self {
+      PersonOrGroupOrInstance::Person(p) => p.id.inner(),
+      PersonOrGroupOrInstance::Group(g) => g.id.inner(),
+      PersonOrGroupOrInstance::Instance(i) => i.id.inner(),
+    }
+  }
+}
+
+impl SignedFetchActor {
+  /// Fetches a remote actor again, regardless of when it was last refreshed, to get its current
+  /// key. If the key changed, the previous key is kept so that signatures which were made before
+  /// the rotation are still accepted for a while.
+  #[tracing::instrument(skip_all)]
+  pub(crate) async fn refetch_key(
+    actor_id: &Url,
+    previous_key: &str,
+    context: &LemmyContext,
+  ) -> Result<Self, LemmyError> {
+    let request_counter = &mut 0;
+    let apub: PersonOrGroupOrInstance =
+      fetch_object_http(actor_id, local_instance(context), request_counter).await?;
+    verify_urls_match(actor_id, apub.id())?;
+    SignedFetchActor::verify(&apub, actor_id, context, request_counter).await?;
+    let actor = SignedFetchActor::from_apub(apub, context, request_counter).await?;
+    if actor.public_key() != previous_key {
+      let form = ActorKeyRotationForm {
+        actor_id: actor_id.clone().into(),
+        previous_public_key: previous_key.to_string(),
+        rotated_at: naive_now(),
+      };
+      blocking(context.pool(), move |conn| {
+        ActorKeyRotation::upsert(conn, &form)
+      })
+      .await??;
+    }
+    Ok(actor)
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ApubObject for SignedFetchActor {
+  type DataType = LemmyContext;
+  type ApubType = PersonOrGroupOrInstance;
+  type DbType = ();
+  type Error = LemmyError;
+
+  fn last_refreshed_at(&self) -> Option<NaiveDateTime> {
+    Some(match self {
+      SignedFetchActor::User(p) => p.last_refreshed_at,
+      SignedFetchActor::Community(c) => c.last_refreshed_at,
+      SignedFetchActor::Site(s) => s.last_refreshed_at,
+    })
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn read_from_apub_id(
+    object_id: Url,
+    data: &Self::DataType,
+  ) -> Result<Option<Self>, LemmyError> {
+    if let Some(p) = ApubPerson::read_from_apub_id(object_id.clone(), data).await? {
+      return Ok(Some(SignedFetchActor::User(p)));
+    }
+    if let Some(c) = ApubCommunity::read_from_apub_id(object_id.clone(), data).await? {
+      return Ok(Some(SignedFetchActor::Community(c)));
+    }
+    Ok(
+      ApubSite::read_from_apub_id(object_id, data)
+        .await?
+        .map(SignedFetchActor::Site),
+    )
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn delete(self, data: &Self::DataType) -> Result<(), LemmyError> {
+    match self {
+      SignedFetchActor::User(p) => p.delete(data).await,
+      SignedFetchActor::Community(c) => c.delete(data).await,
+      SignedFetchActor::Site(s) => s.delete(data).await,
+    }
+  }
+
+  async fn into_apub(self, _data: &Self::DataType) -> Result<Self::ApubType, LemmyError> {
+    unimplemented!()
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    apub: &Self::ApubType,
+    expected_domain: &Url,
+    data: &Self::DataType,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    match apub {
+      PersonOrGroupOrInstance::Person(a) => {
+        ApubPerson::verify(a, expected_domain, data, request_counter).await
+      }
+      PersonOrGroupOrInstance::Group(a) => {
+        ApubCommunity::verify(a, expected_domain, data, request_counter).await
+      }
+      PersonOrGroupOrInstance::Instance(a) => {
+        ApubSite::verify(a, expected_domain, data, request_counter).await
+      }
+    }
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn from_apub(
+    apub: Self::ApubType,
+    data: &Self::DataType,
+    request_counter: &mut i32,
+  ) -> Result<Self, LemmyError> {
+    Ok(match apub {
+      PersonOrGroupOrInstance::Person(p) => {
+        SignedFetchActor::User(ApubPerson::from_apub(p, data, request_counter).await?)
+      }
+      PersonOrGroupOrInstance::Group(g) => {
+        SignedFetchActor::Community(ApubCommunity::from_apub(g, data, request_counter).await?)
+      }
+      PersonOrGroupOrInstance::Instance(i) => {
+        SignedFetchActor::Site(ApubSite::from_apub(i, data, request_counter).await?)
+      }
+    })
+  }
+}
+
+impl Actor for SignedFetchActor {
+  fn public_key(&self) -> &str {
+    match self {
+      SignedFetchActor::User(p) => p.public_key(),
+      SignedFetchActor::Community(c) => c.public_key(),
+      SignedFetchActor::Site(s) => s.public_key(),
+    }
+  }
+
+  fn inbox(&self) -> Url {
+    unimplemented!()
+  }
+}
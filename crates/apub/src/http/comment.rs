@@ -1,12 +1,21 @@
 use crate::{
-  http::{create_apub_response, create_apub_tombstone_response},
+  http::{
+    create_apub_response,
+    create_apub_tombstone_response,
+    verify_community_fetch,
+    verify_signed_fetch,
+  },
   objects::comment::ApubComment,
 };
 use activitypub_federation::traits::ApubObject;
-use actix_web::{web, web::Path, HttpResponse};
+use actix_web::{web, web::Path, HttpRequest, HttpResponse};
 use diesel::result::Error::NotFound;
 use lemmy_api_common::utils::blocking;
-use lemmy_db_schema::{newtypes::CommentId, source::comment::Comment, traits::Crud};
+use lemmy_db_schema::{
+  newtypes::CommentId,
+  source::{comment::Comment, community::Community, post::Post},
+  traits::Crud,
+};
 use lemmy_utils::error::LemmyError;
 use lemmy_websocket::LemmyContext;
 use serde::Deserialize;
@@ -19,16 +28,25 @@ pub(crate) struct CommentQuery {
 /// Return the ActivityPub json representation of a local comment over HTTP.
 #[tracing::instrument(skip_all)]
 pub(crate) async fn get_apub_comment(
+  request: HttpRequest,
   info: Path<CommentQuery>,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
+  verify_signed_fetch(&request, &context).await?;
   let id = CommentId(info.comment_id.parse::<i32>()?);
-  let comment: ApubComment = blocking(context.pool(), move |conn| Comment::read(conn, id))
-    .await??
-    .into();
-  if !comment.local {
+  let (comment, community) = blocking(context.pool(), move |conn| {
+    let comment = Comment::read(conn, id)?;
+    let post = Post::read(conn, comment.post_id)?;
+    let community = Community::read(conn, post.community_id)?;
+    Ok((comment, community)) as Result<(Comment, Community), LemmyError>
+  })
+  .await??;
+  // Content which is held for approval is only published once a mod approves it
+  if !comment.local || comment.pending {
     return Err(NotFound.into());
   }
+  verify_community_fetch(&request, &community, &context).await?;
+  let comment: ApubComment = comment.into();
 
   if !comment.deleted {
     Ok(create_apub_response(&comment.into_apub(&**context).await?))
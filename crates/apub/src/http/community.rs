@@ -1,12 +1,22 @@
 use crate::{
   activity_lists::GroupInboxActivities,
   collections::{
+    community_featured::ApubCommunityFeatured,
     community_moderators::ApubCommunityModerators,
     community_outbox::ApubCommunityOutbox,
+    community_outbox_page::ApubCommunityOutboxPage,
     CommunityContext,
   },
+  generate_featured_url,
+  generate_outbox_page_url,
   generate_outbox_url,
-  http::{create_apub_response, create_apub_tombstone_response, receive_lemmy_activity},
+  http::{
+    create_apub_response,
+    create_apub_tombstone_response,
+    receive_lemmy_activity,
+    verify_community_fetch,
+    verify_signed_fetch,
+  },
   local_instance,
   objects::{community::ApubCommunity, person::ApubPerson},
   protocol::collections::group_followers::GroupFollowers,
@@ -17,8 +27,12 @@ use activitypub_federation::{
   traits::ApubObject,
 };
 use actix_web::{web, HttpRequest, HttpResponse};
+use diesel::result::Error::NotFound;
 use lemmy_api_common::utils::blocking;
-use lemmy_db_schema::{source::community::Community, traits::ApubActor};
+use lemmy_db_schema::{
+  source::community::{Community, CommunityVisibility},
+  traits::ApubActor,
+};
 use lemmy_utils::error::LemmyError;
 use lemmy_websocket::LemmyContext;
 use serde::Deserialize;
@@ -31,14 +45,17 @@ pub(crate) struct CommunityQuery {
 /// Return the ActivityPub json representation of a local community over HTTP.
 #[tracing::instrument(skip_all)]
 pub(crate) async fn get_apub_community_http(
+  request: HttpRequest,
   info: web::Path<CommunityQuery>,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
+  verify_signed_fetch(&request, &context).await?;
   let community: ApubCommunity = blocking(context.pool(), move |conn| {
     Community::read_from_name(conn, &info.community_name, true)
   })
   .await??
   .into();
+  check_community_federated(&community)?;
 
   if !community.deleted {
     let apub = community.into_apub(&**context).await?;
@@ -62,45 +79,94 @@ pub async fn community_inbox(
 
 /// Returns an empty followers collection, only populating the size (for privacy).
 pub(crate) async fn get_apub_community_followers(
+  request: HttpRequest,
   info: web::Path<CommunityQuery>,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
+  verify_signed_fetch(&request, &context).await?;
   let community = blocking(context.pool(), move |conn| {
     Community::read_from_name(conn, &info.community_name, false)
   })
   .await??;
+  check_community_federated(&community)?;
   let followers = GroupFollowers::new(community, &context).await?;
   Ok(create_apub_response(&followers))
 }
 
-/// Returns the community outbox, which is populated by a maximum of 20 posts (but no other
-/// activites like votes or comments).
+#[derive(Deserialize)]
+pub(crate) struct CommunityOutboxQuery {
+  page: Option<i64>,
+}
+
+/// Returns the community outbox, which is populated by a maximum of 20 posts. The full history of
+/// posts and comments is available in pages, starting from `first`.
 pub(crate) async fn get_apub_community_outbox(
+  request: HttpRequest,
   info: web::Path<CommunityQuery>,
+  query: web::Query<CommunityOutboxQuery>,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
+  verify_signed_fetch(&request, &context).await?;
   let community = blocking(context.pool(), move |conn| {
     Community::read_from_name(conn, &info.community_name, false)
   })
   .await??;
-  let id = ObjectId::new(generate_outbox_url(&community.actor_id)?);
+  verify_community_fetch(&request, &community, &context).await?;
+  let outbox_url = generate_outbox_url(&community.actor_id)?;
   let outbox_data = CommunityContext(community.into(), context.get_ref().clone());
-  let outbox: ApubCommunityOutbox = id
-    .dereference(&outbox_data, local_instance(&context), &mut 0)
+  if let Some(page) = query.page {
+    let id = ObjectId::new(generate_outbox_page_url(&outbox_url, page)?);
+    let outbox_page: ApubCommunityOutboxPage = id
+      .dereference(&outbox_data, local_instance(&context), &mut 0)
+      .await?;
+    Ok(create_apub_response(
+      &outbox_page.into_apub(&outbox_data).await?,
+    ))
+  } else {
+    let outbox: ApubCommunityOutbox = ObjectId::new(outbox_url)
+      .dereference(&outbox_data, local_instance(&context), &mut 0)
+      .await?;
+    Ok(create_apub_response(&outbox.into_apub(&outbox_data).await?))
+  }
+}
+
+/// Returns the stickied posts of the community
+#[tracing::instrument(skip_all)]
+pub(crate) async fn get_apub_community_featured(
+  request: HttpRequest,
+  info: web::Path<CommunityQuery>,
+  context: web::Data<LemmyContext>,
+) -> Result<HttpResponse, LemmyError> {
+  verify_signed_fetch(&request, &context).await?;
+  let community: ApubCommunity = blocking(context.pool(), move |conn| {
+    Community::read_from_name(conn, &info.community_name, false)
+  })
+  .await??
+  .into();
+  verify_community_fetch(&request, &community, &context).await?;
+  let id = ObjectId::new(generate_featured_url(&community.actor_id)?);
+  let featured_data = CommunityContext(community, context.get_ref().clone());
+  let featured: ApubCommunityFeatured = id
+    .dereference(&featured_data, local_instance(&context), &mut 0)
     .await?;
-  Ok(create_apub_response(&outbox.into_apub(&outbox_data).await?))
+  Ok(create_apub_response(
+    &featured.into_apub(&featured_data).await?,
+  ))
 }
 
 #[tracing::instrument(skip_all)]
 pub(crate) async fn get_apub_community_moderators(
+  request: HttpRequest,
   info: web::Path<CommunityQuery>,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
+  verify_signed_fetch(&request, &context).await?;
   let community: ApubCommunity = blocking(context.pool(), move |conn| {
     Community::read_from_name(conn, &info.community_name, false)
   })
   .await??
   .into();
+  check_community_federated(&community)?;
   let id = ObjectId::new(generate_outbox_url(&community.actor_id)?);
   let outbox_data = CommunityContext(community, context.get_ref().clone());
   let moderators: ApubCommunityModerators = id
@@ -110,3 +176,11 @@ pub(crate) async fn get_apub_community_moderators(
     &moderators.into_apub(&outbox_data).await?,
   ))
 }
+
+/// Local-only communities aren't exposed to other instances at all
+fn check_community_federated(community: &Community) -> Result<(), LemmyError> {
+  if community.visibility == CommunityVisibility::LocalOnly {
+    return Err(NotFound.into());
+  }
+  Ok(())
+}
@@ -1,23 +1,40 @@
 use crate::{
-  activity_lists::SharedInboxActivities,
-  fetcher::user_or_community::UserOrCommunity,
+  activity_lists::{RelayInboxActivities, SharedInboxActivities},
+  check_apub_id_valid_with_strictness,
+  fetcher::{signed_fetch::SignedFetchActor, user_or_community::UserOrCommunity},
   insert_activity,
   local_instance,
-  protocol::objects::tombstone::Tombstone,
+  objects::{
+    relay::{subscribed_relays, ApubRelay},
+    verify_is_remote_object,
+  },
+  protocol::{activities::update_actor::UpdateActor, objects::tombstone::Tombstone},
   CONTEXT,
 };
 use activitypub_federation::{
-  core::inbox::receive_activity,
+  core::{object_id::ObjectId, signatures::verify_signature},
   data::Data,
   deser::context::WithContext,
   traits::{ActivityHandler, Actor, ApubObject},
+  utils::verify_domains_match,
   APUB_JSON_CONTENT_TYPE,
 };
 use actix_web::{web, HttpRequest, HttpResponse};
+use chrono::Duration;
+use diesel::result::Error::NotFound;
 use http::StatusCode;
+use http_signature_normalization_actix::Config;
 use lemmy_api_common::utils::blocking;
-use lemmy_db_schema::source::activity::Activity;
-use lemmy_utils::error::LemmyError;
+use lemmy_db_schema::{
+  source::{
+    activity::Activity,
+    actor_key_rotation::ActorKeyRotation,
+    community::{Community, CommunityVisibility},
+  },
+  utils::naive_now,
+};
+use lemmy_db_views_actor::structs::CommunityFollowerView;
+use lemmy_utils::{error::LemmyError, settings::structs::Settings};
 use lemmy_websocket::LemmyContext;
 use once_cell::sync::OnceCell;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -32,6 +49,11 @@ mod person;
 mod post;
 pub mod routes;
 pub mod site;
+mod wiki_page;
+
+/// How long signatures made with the previous key of a remote actor are still accepted after the
+/// actor rotated its key, for activities which were already on their way.
+const KEY_ROTATION_OVERLAP_HOURS: i64 = 24;
 
 #[tracing::instrument(skip_all)]
 pub async fn shared_inbox(
@@ -39,9 +61,63 @@ pub async fn shared_inbox(
   payload: String,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
+  if is_relay_activity(&payload, &context).await? {
+    return receive_lemmy_activity::<WithContext<RelayInboxActivities>, ApubRelay>(
+      request, payload, context,
+    )
+    .await;
+  }
+  if is_actor_update(&payload) {
+    return receive_lemmy_activity::<WithContext<UpdateActor>, SignedFetchActor>(
+      request, payload, context,
+    )
+    .await;
+  }
   receive_lemmy_activity::<SharedInboxActivities, UserOrCommunity>(request, payload, context).await
 }
 
+/// Relays are neither persons nor communities, so their activities need to be received with a
+/// different actor type. They are recognized by the actor id of a subscribed relay, or by the inbox
+/// domain if the relay hasn't told us its actor yet (before it accepted our follow).
+pub(crate) async fn is_relay_activity(
+  payload: &str,
+  context: &LemmyContext,
+) -> Result<bool, LemmyError> {
+  #[derive(Deserialize)]
+  struct ActivityActor {
+    actor: Url,
+  }
+  let actor = match serde_json::from_str::<ActivityActor>(payload) {
+    Ok(a) => a.actor,
+    Err(_) => return Ok(false),
+  };
+  let relays = subscribed_relays(context).await?;
+  Ok(relays.iter().any(|r| match &r.actor_id {
+    Some(actor_id) => **actor_id == actor,
+    None => r.inbox_url.domain() == actor.domain(),
+  }))
+}
+
+/// Updates which persons, communities or instance actors send about themselves, for example after
+/// a key rotation. They are recognized by the actor being the updated object.
+pub(crate) fn is_actor_update(payload: &str) -> bool {
+  #[derive(Deserialize)]
+  struct ObjectWithId {
+    id: Url,
+  }
+  #[derive(Deserialize)]
+  struct ActorUpdate {
+    #[serde(rename = "type")]
+    kind: String,
+    actor: Url,
+    object: ObjectWithId,
+  }
+  match serde_json::from_str::<ActorUpdate>(payload) {
+    Ok(u) => u.kind == "Update" && u.actor == u.object.id,
+    Err(_) => false,
+  }
+}
+
 pub async fn receive_lemmy_activity<Activity, ActorT>(
   request: HttpRequest,
   payload: String,
@@ -66,15 +142,82 @@ where
   }
   info!("Received activity {}", payload);
 
+  verify_domains_match(activity.id(), activity.actor())?;
+  check_apub_id_valid_with_strictness(activity.id(), false, context.settings())?;
+  verify_is_remote_object(activity.id(), context.settings())?;
+
   static DATA: OnceCell<Data<LemmyContext>> = OnceCell::new();
   let data = DATA.get_or_init(|| Data::new(context.get_ref().clone()));
-  receive_activity::<Activity, ActorT, LemmyContext>(
-    request,
-    activity,
-    local_instance(&context),
-    data,
+  let request_counter = &mut 0;
+  let actor = ObjectId::<ActorT>::new(activity.actor().clone())
+    .dereference(data, local_instance(&context), request_counter)
+    .await?;
+  // Otherwise anyone holding a leaked key could replace the rotated key with their own
+  let allow_previous_key = !is_actor_update(&payload);
+  verify_actor_signature(
+    &request,
+    activity.actor(),
+    &actor,
+    allow_previous_key,
+    &context,
   )
-  .await
+  .await?;
+
+  debug!("Verifying activity {}", activity.id().to_string());
+  activity.verify(data, request_counter).await?;
+  debug!("Receiving activity {}", activity.id().to_string());
+  activity.receive(data, request_counter).await?;
+  Ok(HttpResponse::Ok().finish())
+}
+
+/// Verifies the http signature of a request with the stored key of the actor. After a key
+/// rotation, the previous key is accepted for a short time if `allow_previous_key` is set. If
+/// neither matches, the actor may have rotated its key without us receiving the update, so its
+/// current key is fetched once.
+#[tracing::instrument(skip_all)]
+async fn verify_actor_signature<ActorT>(
+  request: &HttpRequest,
+  actor_id: &Url,
+  actor: &ActorT,
+  allow_previous_key: bool,
+  context: &LemmyContext,
+) -> Result<(), LemmyError>
+where
+  ActorT: ApubObject + Actor,
+{
+  let err = match verify_signature(request, actor.public_key()) {
+    Ok(()) => return Ok(()),
+    Err(e) => e,
+  };
+
+  if allow_previous_key {
+    let id = actor_id.clone().into();
+    let since = naive_now() - Duration::hours(KEY_ROTATION_OVERLAP_HOURS);
+    let previous_key = blocking(context.pool(), move |conn| {
+      ActorKeyRotation::read_previous_key(conn, &id, since)
+    })
+    .await??;
+    if let Some(previous_key) = previous_key {
+      if verify_signature(request, &previous_key).is_ok() {
+        return Ok(());
+      }
+    }
+  }
+
+  // Don't refetch actors over and over because of invalid signatures
+  let recently_refreshed = actor
+    .last_refreshed_at()
+    .map(|r| r > naive_now() - Duration::minutes(1))
+    .unwrap_or(false);
+  if !recently_refreshed {
+    if let Ok(refetched) =
+      SignedFetchActor::refetch_key(actor_id, actor.public_key(), context).await
+    {
+      return verify_signature(request, refetched.public_key())
+        .map_err(|e| LemmyError::from_error_message(e, "invalid_signature"));
+    }
+  }
+  Err(LemmyError::from_error_message(err, "invalid_signature"))
 }
 
 /// Convert the data to json and turn it into an HTTP Response with the correct ActivityPub
@@ -102,6 +245,83 @@ fn create_apub_tombstone_response<T: Into<Url>>(id: T) -> HttpResponse {
     .json(WithContext::new(tombstone, CONTEXT.deref().clone()))
 }
 
+/// With secure mode enabled, only serve ActivityPub GET requests which are signed by an actor
+/// from an allowed instance.
+#[tracing::instrument(skip_all)]
+pub(crate) async fn verify_signed_fetch(
+  request: &HttpRequest,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  if !context.settings().federation.signed_fetch {
+    return Ok(());
+  }
+  signed_fetch_actor(request, context).await?;
+  Ok(())
+}
+
+/// Content of private communities can only be fetched by instances which have an approved
+/// follower of the community, regardless of the signed fetch setting. Content of local-only
+/// communities can't be fetched at all.
+#[tracing::instrument(skip_all)]
+pub(crate) async fn verify_community_fetch(
+  request: &HttpRequest,
+  community: &Community,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  match community.visibility {
+    CommunityVisibility::Public => return Ok(()),
+    CommunityVisibility::LocalOnly => return Err(NotFound.into()),
+    CommunityVisibility::Private => {}
+  }
+  let signer = signed_fetch_actor(request, context).await?;
+  let community_id = community.id;
+  let followers = blocking(context.pool(), move |conn| {
+    CommunityFollowerView::for_community(conn, community_id)
+  })
+  .await??;
+  let has_follower = followers
+    .iter()
+    .any(|f| !f.follower.local && f.follower.actor_id.domain() == signer.domain());
+  if !has_follower {
+    return Err(LemmyError::from_message("community_is_private"));
+  }
+  Ok(())
+}
+
+/// Verifies the http signature of a fetch, and returns the id of the actor who signed it.
+async fn signed_fetch_actor(
+  request: &HttpRequest,
+  context: &LemmyContext,
+) -> Result<Url, LemmyError> {
+  let actor_id = signed_fetch_actor_id(request, context.settings())?;
+  let actor = ObjectId::<SignedFetchActor>::new(actor_id.clone())
+    .dereference(context, local_instance(context), &mut 0)
+    .await?;
+  verify_actor_signature(request, &actor_id, &actor, true, context).await?;
+  Ok(actor_id)
+}
+
+/// Reads the actor id from the key id of the signature, and checks that fetches from its instance
+/// are allowed. The signature itself isn't verified yet.
+fn signed_fetch_actor_id(request: &HttpRequest, settings: &Settings) -> Result<Url, LemmyError> {
+  let unverified = Config::new()
+    .begin_verify(
+      request.method(),
+      request.uri().path_and_query(),
+      request.headers().clone(),
+    )
+    .map_err(|e| LemmyError::from_error_message(e, "missing_signature"))?;
+  let mut actor_id = Url::parse(unverified.key_id())
+    .map_err(|e| LemmyError::from_error_message(e, "invalid_signature"))?;
+  actor_id.set_fragment(None);
+  // Checked first, because the instance checks expect a domain
+  if actor_id.domain().is_none() {
+    return Err(LemmyError::from_message("invalid_signature"));
+  }
+  check_apub_id_valid_with_strictness(&actor_id, false, settings)?;
+  Ok(actor_id)
+}
+
 #[derive(Deserialize)]
 pub struct ActivityQuery {
   type_: String,
@@ -111,9 +331,11 @@ pub struct ActivityQuery {
 /// Return the ActivityPub json representation of a local activity over HTTP.
 #[tracing::instrument(skip_all)]
 pub(crate) async fn get_activity(
+  request: HttpRequest,
   info: web::Path<ActivityQuery>,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
+  verify_signed_fetch(&request, &context).await?;
   let settings = context.settings();
   let activity_id = Url::parse(&format!(
     "{}/activities/{}/{}",
@@ -134,3 +356,104 @@ pub(crate) async fn get_activity(
     Ok(create_json_apub_response(activity.data))
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{fetcher::signed_fetch::sign_fetch, objects::tests::init_context};
+  use activitypub_federation::core::signatures::generate_actor_keypair;
+  use actix_web::test::TestRequest;
+  use chrono::Utc;
+  use http::header::HeaderValue;
+  use lemmy_db_schema::{
+    source::person::{Person, PersonForm},
+    traits::Crud,
+  };
+  use lemmy_utils::settings::SETTINGS;
+  use serial_test::serial;
+
+  /// Builds a GET request to the local instance, signed the same way as our own fetches.
+  fn signed_request(path: &str, key_id: &str, private_key: String) -> HttpRequest {
+    let host = SETTINGS.hostname.clone();
+    let url = Url::parse(&format!("https://{}{}", host, path)).unwrap();
+    let mut req = reqwest::Request::new(reqwest::Method::GET, url);
+    let date = Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+    req
+      .headers_mut()
+      .insert("date", HeaderValue::from_str(&date).unwrap());
+    let signature = sign_fetch(&req, key_id.to_string(), private_key).unwrap();
+    TestRequest::get()
+      .uri(path)
+      .insert_header(("host", host))
+      .insert_header(("date", date))
+      .insert_header(("signature", signature.to_str().unwrap()))
+      .to_http_request()
+  }
+
+  #[test]
+  fn test_signed_fetch_unsigned() {
+    let request = TestRequest::get().uri("/c/main").to_http_request();
+    assert!(signed_fetch_actor_id(&request, &SETTINGS).is_err());
+  }
+
+  #[test]
+  fn test_signed_fetch_invalid_key_id() {
+    let keypair = generate_actor_keypair().unwrap();
+    let mut settings = SETTINGS.to_owned();
+    settings.federation.blocked_instances = Some(vec!["blocked.example".to_string()]);
+
+    let blocked = signed_request(
+      "/c/main",
+      "https://blocked.example/u/alice#main-key",
+      keypair.private_key.clone(),
+    );
+    assert!(signed_fetch_actor_id(&blocked, &settings).is_err());
+
+    let without_domain = signed_request(
+      "/c/main",
+      "https://127.0.0.1/u/alice#main-key",
+      keypair.private_key.clone(),
+    );
+    assert!(signed_fetch_actor_id(&without_domain, &settings).is_err());
+
+    let allowed = signed_request(
+      "/c/main",
+      "https://allowed.example/u/alice#main-key",
+      keypair.private_key,
+    );
+    assert_eq!(
+      "https://allowed.example/u/alice",
+      signed_fetch_actor_id(&allowed, &settings).unwrap().as_str()
+    );
+  }
+
+  #[actix_rt::test]
+  #[serial]
+  async fn test_signed_fetch_actor() {
+    let context = init_context();
+    let conn = context.pool().get().unwrap();
+    let keypair = generate_actor_keypair().unwrap();
+    let actor_id = Url::parse("https://remote.example/u/alice").unwrap();
+    let person_form = PersonForm {
+      name: "signed_fetch_alice".into(),
+      actor_id: Some(actor_id.clone().into()),
+      public_key: Some(keypair.public_key),
+      local: Some(false),
+      last_refreshed_at: Some(naive_now()),
+      ..PersonForm::default()
+    };
+    let person = Person::create(&conn, &person_form).unwrap();
+    let key_id = format!("{}#main-key", actor_id);
+
+    let request = signed_request("/c/main", &key_id, keypair.private_key);
+    let signer = signed_fetch_actor(&request, &context).await.unwrap();
+    assert_eq!(actor_id, signer);
+
+    // Signed with another key, which the recently refreshed actor doesn't have
+    let other_keypair = generate_actor_keypair().unwrap();
+    let request = signed_request("/c/main", &key_id, other_keypair.private_key);
+    assert!(signed_fetch_actor(&request, &context).await.is_err());
+
+    Person::delete(&conn, person.id).unwrap();
+  }
+}
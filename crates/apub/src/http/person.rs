@@ -1,10 +1,16 @@
 use crate::{
   activity_lists::PersonInboxActivities,
-  fetcher::user_or_community::UserOrCommunity,
+  fetcher::{signed_fetch::SignedFetchActor, user_or_community::UserOrCommunity},
   generate_outbox_url,
-  http::{create_apub_response, create_apub_tombstone_response, receive_lemmy_activity},
+  http::{
+    create_apub_response,
+    create_apub_tombstone_response,
+    is_actor_update,
+    receive_lemmy_activity,
+    verify_signed_fetch,
+  },
   objects::person::ApubPerson,
-  protocol::collections::empty_outbox::EmptyOutbox,
+  protocol::{activities::update_actor::UpdateActor, collections::empty_outbox::EmptyOutbox},
 };
 use activitypub_federation::{deser::context::WithContext, traits::ApubObject};
 use actix_web::{web, HttpRequest, HttpResponse};
@@ -22,9 +28,11 @@ pub struct PersonQuery {
 /// Return the ActivityPub json representation of a local person over HTTP.
 #[tracing::instrument(skip_all)]
 pub(crate) async fn get_apub_person_http(
+  request: HttpRequest,
   info: web::Path<PersonQuery>,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
+  verify_signed_fetch(&request, &context).await?;
   let user_name = info.into_inner().user_name;
   // TODO: this needs to be able to read deleted persons, so that it can send tombstones
   let person: ApubPerson = blocking(context.pool(), move |conn| {
@@ -48,6 +56,13 @@ pub async fn person_inbox(
   payload: String,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
+  // Followers without a shared inbox receive key rotations of the followed person here
+  if is_actor_update(&payload) {
+    return receive_lemmy_activity::<WithContext<UpdateActor>, SignedFetchActor>(
+      request, payload, context,
+    )
+    .await;
+  }
   receive_lemmy_activity::<WithContext<PersonInboxActivities>, UserOrCommunity>(
     request, payload, context,
   )
@@ -56,9 +71,11 @@ pub async fn person_inbox(
 
 #[tracing::instrument(skip_all)]
 pub(crate) async fn get_apub_person_outbox(
+  request: HttpRequest,
   info: web::Path<PersonQuery>,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
+  verify_signed_fetch(&request, &context).await?;
   let person = blocking(context.pool(), move |conn| {
     Person::read_from_name(conn, &info.user_name, false)
   })
@@ -1,12 +1,21 @@
 use crate::{
-  http::{create_apub_response, create_apub_tombstone_response},
+  http::{
+    create_apub_response,
+    create_apub_tombstone_response,
+    verify_community_fetch,
+    verify_signed_fetch,
+  },
   objects::post::ApubPost,
 };
 use activitypub_federation::traits::ApubObject;
-use actix_web::{web, HttpResponse};
+use actix_web::{web, HttpRequest, HttpResponse};
 use diesel::result::Error::NotFound;
 use lemmy_api_common::utils::blocking;
-use lemmy_db_schema::{newtypes::PostId, source::post::Post, traits::Crud};
+use lemmy_db_schema::{
+  newtypes::PostId,
+  source::{community::Community, post::Post},
+  traits::Crud,
+};
 use lemmy_utils::error::LemmyError;
 use lemmy_websocket::LemmyContext;
 use serde::Deserialize;
@@ -19,16 +28,24 @@ pub(crate) struct PostQuery {
 /// Return the ActivityPub json representation of a local post over HTTP.
 #[tracing::instrument(skip_all)]
 pub(crate) async fn get_apub_post(
+  request: HttpRequest,
   info: web::Path<PostQuery>,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
+  verify_signed_fetch(&request, &context).await?;
   let id = PostId(info.post_id.parse::<i32>()?);
-  let post: ApubPost = blocking(context.pool(), move |conn| Post::read(conn, id))
-    .await??
-    .into();
-  if !post.local {
+  let (post, community) = blocking(context.pool(), move |conn| {
+    let post = Post::read(conn, id)?;
+    let community = Community::read(conn, post.community_id)?;
+    Ok((post, community)) as Result<(Post, Community), LemmyError>
+  })
+  .await??;
+  // Content which is held for approval is only published once a mod approves it
+  if !post.local || post.pending {
     return Err(NotFound.into());
   }
+  verify_community_fetch(&request, &community, &context).await?;
+  let post: ApubPost = post.into();
 
   if !post.deleted {
     Ok(create_apub_response(&post.into_apub(&context).await?))
@@ -2,6 +2,7 @@ use crate::http::{
   comment::get_apub_comment,
   community::{
     community_inbox,
+    get_apub_community_featured,
     get_apub_community_followers,
     get_apub_community_http,
     get_apub_community_moderators,
@@ -12,6 +13,7 @@ use crate::http::{
   post::get_apub_post,
   shared_inbox,
   site::{get_apub_site_http, get_apub_site_inbox, get_apub_site_outbox},
+  wiki_page::get_apub_wiki_page,
 };
 use actix_web::{
   guard::{Guard, GuardContext},
@@ -45,6 +47,14 @@ pub fn config(cfg: &mut web::ServiceConfig, settings: &Settings) {
         "/c/{community_name}/moderators",
         web::get().to(get_apub_community_moderators),
       )
+      .route(
+        "/c/{community_name}/featured",
+        web::get().to(get_apub_community_featured),
+      )
+      .route(
+        "/c/{community_name}/wiki/{page_name}",
+        web::get().to(get_apub_wiki_page),
+      )
       .route("/u/{user_name}", web::get().to(get_apub_person_http))
       .route(
         "/u/{user_name}/outbox",
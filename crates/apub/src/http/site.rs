@@ -1,8 +1,15 @@
 use crate::{
-  activity_lists::SiteInboxActivities,
-  http::{create_apub_response, receive_lemmy_activity},
-  objects::{instance::ApubSite, person::ApubPerson},
-  protocol::collections::empty_outbox::EmptyOutbox,
+  activity_lists::{RelayInboxActivities, SiteInboxActivities},
+  fetcher::signed_fetch::SignedFetchActor,
+  http::{
+    create_apub_response,
+    is_actor_update,
+    is_relay_activity,
+    receive_lemmy_activity,
+    verify_signed_fetch,
+  },
+  objects::{instance::ApubSite, person::ApubPerson, relay::ApubRelay},
+  protocol::{activities::update_actor::UpdateActor, collections::empty_outbox::EmptyOutbox},
 };
 use activitypub_federation::{deser::context::WithContext, traits::ApubObject};
 use actix_web::{web, HttpRequest, HttpResponse};
@@ -25,8 +32,10 @@ pub(crate) async fn get_apub_site_http(
 
 #[tracing::instrument(skip_all)]
 pub(crate) async fn get_apub_site_outbox(
+  request: HttpRequest,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
+  verify_signed_fetch(&request, &context).await?;
   let outbox_id = format!(
     "{}/site_outbox",
     context.settings().get_protocol_and_hostname()
@@ -41,6 +50,19 @@ pub async fn get_apub_site_inbox(
   payload: String,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
+  // Relays which don't use the shared inbox deliver here
+  if is_relay_activity(&payload, &context).await? {
+    return receive_lemmy_activity::<WithContext<RelayInboxActivities>, ApubRelay>(
+      request, payload, context,
+    )
+    .await;
+  }
+  if is_actor_update(&payload) {
+    return receive_lemmy_activity::<WithContext<UpdateActor>, SignedFetchActor>(
+      request, payload, context,
+    )
+    .await;
+  }
   receive_lemmy_activity::<WithContext<SiteInboxActivities>, ApubPerson>(request, payload, context)
     .await
 }
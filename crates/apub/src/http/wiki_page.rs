@@ -0,0 +1,53 @@
+use crate::{
+  http::{
+    create_apub_response,
+    create_apub_tombstone_response,
+    verify_community_fetch,
+    verify_signed_fetch,
+  },
+  objects::wiki_page::ApubWikiPage,
+};
+use activitypub_federation::traits::ApubObject;
+use actix_web::{web, HttpRequest, HttpResponse};
+use diesel::result::Error::NotFound;
+use lemmy_api_common::utils::blocking;
+use lemmy_db_schema::{
+  source::{community::Community, wiki_page::WikiPage},
+  traits::ApubActor,
+};
+use lemmy_utils::error::LemmyError;
+use lemmy_websocket::LemmyContext;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub(crate) struct WikiPageQuery {
+  community_name: String,
+  page_name: String,
+}
+
+/// Return the ActivityPub json representation of a local community wiki page over HTTP.
+#[tracing::instrument(skip_all)]
+pub(crate) async fn get_apub_wiki_page(
+  request: HttpRequest,
+  info: web::Path<WikiPageQuery>,
+  context: web::Data<LemmyContext>,
+) -> Result<HttpResponse, LemmyError> {
+  verify_signed_fetch(&request, &context).await?;
+  let (community, wiki_page) = blocking(context.pool(), move |conn| {
+    let community = Community::read_from_name(conn, &info.community_name, false)?;
+    let wiki_page = WikiPage::read_by_name(conn, community.id, &info.page_name)?;
+    Ok((community, wiki_page)) as Result<(Community, WikiPage), LemmyError>
+  })
+  .await??;
+  verify_community_fetch(&request, &community, &context).await?;
+  let wiki_page: ApubWikiPage = wiki_page.into();
+  if !wiki_page.local {
+    return Err(NotFound.into());
+  }
+
+  if !wiki_page.deleted {
+    Ok(create_apub_response(&wiki_page.into_apub(&context).await?))
+  } else {
+    Ok(create_apub_tombstone_response(wiki_page.ap_id.clone()))
+  }
+}
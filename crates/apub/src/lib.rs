@@ -20,6 +20,7 @@ use url::{ParseError, Url};
 pub mod activities;
 pub(crate) mod activity_lists;
 pub(crate) mod collections;
+pub mod delivery_queue;
 pub mod fetcher;
 pub mod http;
 pub(crate) mod mentions;
@@ -63,7 +64,7 @@ fn local_instance(context: &LemmyContext) -> &'static LocalInstance {
 /// post/comment in a local community.
 #[tracing::instrument(skip(settings))]
 fn check_apub_id_valid(apub_id: &Url, settings: &Settings) -> Result<(), &'static str> {
-  let domain = apub_id.domain().expect("apud id has domain").to_string();
+  let domain = apub_id.domain().ok_or("Url has no domain")?.to_string();
   let local_instance = settings
     .get_hostname_without_port()
     .expect("local hostname is valid");
@@ -185,10 +186,22 @@ pub fn generate_outbox_url(actor_id: &DbUrl) -> Result<DbUrl, ParseError> {
   Ok(Url::parse(&format!("{}/outbox", actor_id))?.into())
 }
 
+pub fn generate_outbox_page_url(outbox_url: &DbUrl, page: i64) -> Result<DbUrl, ParseError> {
+  Ok(Url::parse(&format!("{}?page={}", outbox_url, page))?.into())
+}
+
+pub fn generate_wiki_page_url(community_id: &DbUrl, name: &str) -> Result<DbUrl, ParseError> {
+  Ok(Url::parse(&format!("{}/wiki/{}", community_id, name))?.into())
+}
+
 fn generate_moderators_url(community_id: &DbUrl) -> Result<DbUrl, LemmyError> {
   Ok(Url::parse(&format!("{}/moderators", community_id))?.into())
 }
 
+fn generate_featured_url(community_id: &DbUrl) -> Result<DbUrl, LemmyError> {
+  Ok(Url::parse(&format!("{}/featured", community_id))?.into())
+}
+
 /// Store a sent or received activity in the database, for logging purposes. These records are not
 /// persistent.
 #[tracing::instrument(skip(pool))]
@@ -1,5 +1,5 @@
 use crate::{
-  activities::{verify_is_public, verify_person_in_community},
+  activities::{community_audience, verify_person_in_community, verify_visibility},
   check_apub_id_valid_with_strictness,
   local_instance,
   mentions::collect_non_local_mentions,
@@ -13,9 +13,9 @@ use activitypub_federation::{
   traits::ApubObject,
   utils::verify_domains_match,
 };
-use activitystreams_kinds::{object::NoteType, public};
+use activitystreams_kinds::object::NoteType;
 use chrono::NaiveDateTime;
-use lemmy_api_common::utils::blocking;
+use lemmy_api_common::{slur_filter::slur_filter, utils::blocking};
 use lemmy_db_schema::{
   source::{
     comment::{Comment, CommentForm},
@@ -105,14 +105,19 @@ impl ApubObject for ApubComment {
     } else {
       ObjectId::<PostOrComment>::new(post.ap_id)
     };
-    let maa =
-      collect_non_local_mentions(&self, ObjectId::new(community.actor_id), context, &mut 0).await?;
+    let maa = collect_non_local_mentions(
+      &self,
+      ObjectId::new(community.actor_id.clone()),
+      context,
+      &mut 0,
+    )
+    .await?;
 
     let note = Note {
       r#type: NoteType::Note,
       id: ObjectId::new(self.ap_id.clone()),
       attributed_to: ObjectId::new(creator.actor_id),
-      to: vec![public()],
+      to: vec![community_audience(&community)],
       cc: maa.ccs,
       content: markdown_to_html(&self.content),
       media_type: Some(MediaTypeMarkdownOrHtml::Html),
@@ -135,13 +140,13 @@ impl ApubObject for ApubComment {
   ) -> Result<(), LemmyError> {
     verify_domains_match(note.id.inner(), expected_domain)?;
     verify_domains_match(note.attributed_to.inner(), note.id.inner())?;
-    verify_is_public(&note.to, &note.cc)?;
     let (post, _) = note.get_parents(context, request_counter).await?;
     let community_id = post.community_id;
     let community = blocking(context.pool(), move |conn| {
       Community::read(conn, community_id)
     })
     .await??;
+    verify_visibility(&note.to, &note.cc, &community)?;
     check_apub_id_valid_with_strictness(note.id.inner(), community.local, context.settings())?;
     verify_is_remote_object(note.id.inner(), context.settings())?;
     verify_person_in_community(
@@ -174,6 +179,10 @@ impl ApubObject for ApubComment {
 
     let content = read_from_string_or_source(&note.content, &note.media_type, &note.source);
     let content_slurs_removed = remove_slurs(&content, &context.settings().slur_regex());
+    // Holds are handled when receiving the activity
+    let (content_slurs_removed, _) = slur_filter(context.pool())
+      .await?
+      .apply(&content_slurs_removed)?;
 
     let form = CommentForm {
       creator_id: creator.id,
@@ -185,6 +194,7 @@ impl ApubObject for ApubComment {
       deleted: None,
       ap_id: Some(note.id.into()),
       local: Some(false),
+      pending: None,
     };
     let parent_comment_path = parent_comment.map(|t| t.0.path);
     let comment = blocking(context.pool(), move |conn| {
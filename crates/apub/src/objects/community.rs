@@ -1,6 +1,8 @@
 use crate::{
   check_apub_id_valid_with_strictness,
   collections::{community_moderators::ApubCommunityModerators, CommunityContext},
+  fetcher::nodeinfo::read_or_create_instance,
+  generate_featured_url,
   generate_moderators_url,
   generate_outbox_url,
   local_instance,
@@ -19,8 +21,11 @@ use activitypub_federation::{
 use activitystreams_kinds::actor::GroupType;
 use chrono::NaiveDateTime;
 use itertools::Itertools;
-use lemmy_api_common::utils::blocking;
-use lemmy_db_schema::{source::community::Community, traits::ApubActor};
+use lemmy_api_common::{slur_filter::slur_filter, utils::blocking};
+use lemmy_db_schema::{
+  source::community::{Community, CommunityForm, CommunityVisibility},
+  traits::ApubActor,
+};
 use lemmy_db_views_actor::structs::CommunityFollowerView;
 use lemmy_utils::{
   error::LemmyError,
@@ -98,6 +103,7 @@ impl ApubObject for ApubCommunity {
       )),
       inbox: self.inbox_url.clone().into(),
       outbox: ObjectId::new(generate_outbox_url(&self.actor_id)?),
+      featured: Some(ObjectId::new(generate_featured_url(&self.actor_id)?)),
       followers: self.followers_url.clone().into(),
       endpoints: self.shared_inbox_url.clone().map(|s| Endpoints {
         shared_inbox: s.into(),
@@ -106,6 +112,7 @@ impl ApubObject for ApubCommunity {
       published: Some(convert_datetime(self.published)),
       updated: self.updated.map(convert_datetime),
       posting_restricted_to_mods: Some(self.posting_restricted_to_mods),
+      manually_approves_followers: Some(self.visibility == CommunityVisibility::Private),
     };
     Ok(group)
   }
@@ -127,9 +134,14 @@ impl ApubObject for ApubCommunity {
     context: &LemmyContext,
     request_counter: &mut i32,
   ) -> Result<ApubCommunity, LemmyError> {
-    let form = Group::into_form(group.clone());
+    let instance_id = read_or_create_instance(group.id.inner(), context).await?;
+    let slur_filter = slur_filter(context.pool()).await?;
+    let form = CommunityForm {
+      instance_id: Some(Some(instance_id)),
+      ..Group::into_form(group.clone(), &slur_filter)?
+    };
 
-    // Fetching mods and outbox is not necessary for Lemmy to work, so ignore errors. Besides,
+    // Fetching mods, outbox and featured posts is not necessary for Lemmy to work, so ignore errors. Besides,
     // we need to ignore these errors so that tests can work entirely offline.
     let community: ApubCommunity =
       blocking(context.pool(), move |conn| Community::upsert(conn, &form))
@@ -152,6 +164,14 @@ impl ApubObject for ApubCommunity {
         .ok();
     }
 
+    if let Some(featured) = &group.featured {
+      featured
+        .dereference(&outbox_data, local_instance(context), request_counter)
+        .await
+        .map_err(|e| debug!("{}", e))
+        .ok();
+    }
+
     fetch_instance_actor_for_object(community.actor_id(), context, request_counter).await;
 
     Ok(community)
@@ -227,6 +247,7 @@ pub(crate) mod tests {
     let mut json: Group = file_to_json_object("assets/lemmy/objects/group.json").unwrap();
     // change these links so they dont fetch over the network
     json.moderators = None;
+    json.featured = None;
     json.outbox =
       ObjectId::new(Url::parse("https://enterprise.lemmy.ml/c/tenforward/not_outbox").unwrap());
 
@@ -1,9 +1,13 @@
 use crate::{
   check_apub_id_valid_with_strictness,
+  fetcher::nodeinfo::read_or_create_instance,
   local_instance,
   objects::read_from_string_or_source_opt,
   protocol::{
-    objects::instance::{Instance, InstanceType},
+    objects::{
+      instance::{Instance, InstanceType},
+      Endpoints,
+    },
     ImageObject,
     Source,
   },
@@ -18,7 +22,10 @@ use activitypub_federation::{
 use chrono::NaiveDateTime;
 use lemmy_api_common::utils::blocking;
 use lemmy_db_schema::{
-  source::site::{Site, SiteForm},
+  source::{
+    instance::Instance as DbInstance,
+    site::{Site, SiteForm},
+  },
   utils::{naive_now, DbPool},
 };
 use lemmy_utils::{
@@ -76,7 +83,7 @@ impl ApubObject for ApubSite {
   }
 
   #[tracing::instrument(skip_all)]
-  async fn into_apub(self, _data: &Self::DataType) -> Result<Self::ApubType, LemmyError> {
+  async fn into_apub(self, data: &Self::DataType) -> Result<Self::ApubType, LemmyError> {
     let instance = Instance {
       kind: InstanceType::Service,
       id: ObjectId::new(self.actor_id()),
@@ -89,6 +96,12 @@ impl ApubObject for ApubSite {
       image: self.banner.clone().map(ImageObject::new),
       inbox: self.inbox_url.clone().into(),
       outbox: Url::parse(&format!("{}/site_outbox", self.actor_id))?,
+      endpoints: Some(Endpoints {
+        shared_inbox: Url::parse(&format!(
+          "{}/inbox",
+          data.settings().get_protocol_and_hostname()
+        ))?,
+      }),
       public_key: self.get_public_key(),
       published: convert_datetime(self.published),
       updated: self.updated.map(convert_datetime),
@@ -118,6 +131,7 @@ impl ApubObject for ApubSite {
     data: &Self::DataType,
     _request_counter: &mut i32,
   ) -> Result<Self, LemmyError> {
+    let instance_id = read_or_create_instance(apub.id.inner(), data).await?;
     let site_form = SiteForm {
       name: apub.name.clone(),
       sidebar: Some(read_from_string_or_source_opt(
@@ -133,6 +147,7 @@ impl ApubObject for ApubSite {
       last_refreshed_at: Some(naive_now()),
       inbox_url: Some(apub.inbox.clone().into()),
       public_key: Some(apub.public_key.public_key_pem.clone()),
+      instance_id: Some(Some(instance_id)),
       ..SiteForm::default()
     };
     let site = blocking(data.pool(), move |conn| Site::upsert(conn, &site_form)).await??;
@@ -184,10 +199,21 @@ pub(in crate::objects) async fn fetch_instance_actor_for_object(
   }
 }
 
+/// Inboxes of all known remote instances, which are also added to the instance table so that
+/// their delivery health is tracked.
 pub(crate) async fn remote_instance_inboxes(pool: &DbPool) -> Result<Vec<Url>, LemmyError> {
+  let sites = blocking(pool, |conn| {
+    let sites = Site::read_remote_sites(conn)?;
+    let domains: Vec<String> = sites
+      .iter()
+      .filter_map(|s| s.actor_id.domain().map(ToString::to_string))
+      .collect();
+    DbInstance::upsert_domains(conn, &domains)?;
+    Ok(sites) as Result<Vec<Site>, LemmyError>
+  })
+  .await??;
   Ok(
-    blocking(pool, Site::read_remote_sites)
-      .await??
+    sites
       .into_iter()
       .map(|s| ApubSite::from(s).shared_inbox_or_inbox())
       .collect(),
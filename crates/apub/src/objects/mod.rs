@@ -11,6 +11,8 @@ pub mod instance;
 pub mod person;
 pub mod post;
 pub mod private_message;
+pub mod relay;
+pub mod wiki_page;
 
 pub(crate) fn read_from_string_or_source(
   content: &str,
@@ -109,6 +111,8 @@ pub(crate) mod tests {
     let secret = Secret {
       id: 0,
       jwt_secret: "".to_string(),
+      vapid_private_key: None,
+      vapid_public_key: None,
     };
     let db_url = match get_database_url_from_env() {
       Ok(url) => url,
@@ -1,5 +1,6 @@
 use crate::{
   check_apub_id_valid_with_strictness,
+  fetcher::nodeinfo::read_or_create_instance,
   generate_outbox_url,
   objects::{instance::fetch_instance_actor_for_object, read_from_string_or_source_opt},
   protocol::{
@@ -18,7 +19,7 @@ use activitypub_federation::{
   utils::verify_domains_match,
 };
 use chrono::NaiveDateTime;
-use lemmy_api_common::utils::blocking;
+use lemmy_api_common::{slur_filter::slur_filter, utils::blocking};
 use lemmy_db_schema::{
   source::person::{Person as DbPerson, PersonForm},
   traits::ApubActor,
@@ -127,6 +128,9 @@ impl ApubObject for ApubPerson {
     check_slurs_opt(&person.name, slur_regex)?;
     let bio = read_from_string_or_source_opt(&person.summary, &None, &person.source);
     check_slurs_opt(&bio, slur_regex)?;
+    slur_filter(context.pool())
+      .await?
+      .check_name(&person.preferred_username)?;
     Ok(())
   }
 
@@ -136,9 +140,18 @@ impl ApubObject for ApubPerson {
     context: &LemmyContext,
     request_counter: &mut i32,
   ) -> Result<ApubPerson, LemmyError> {
+    let instance_id = read_or_create_instance(person.id.inner(), context).await?;
+    // Rejects the display name and bio, or replaces slurs in them
+    let slur_filter = slur_filter(context.pool()).await?;
+    let display_name = slur_filter.apply_without_hold_opt(&person.name)?;
+    let bio = slur_filter.apply_without_hold_opt(&read_from_string_or_source_opt(
+      &person.summary,
+      &None,
+      &person.source,
+    ))?;
     let person_form = PersonForm {
       name: person.preferred_username,
-      display_name: Some(person.name),
+      display_name: Some(display_name),
       banned: None,
       ban_expires: None,
       deleted: None,
@@ -147,11 +160,7 @@ impl ApubObject for ApubPerson {
       published: person.published.map(|u| u.naive_local()),
       updated: person.updated.map(|u| u.naive_local()),
       actor_id: Some(person.id.into()),
-      bio: Some(read_from_string_or_source_opt(
-        &person.summary,
-        &None,
-        &person.source,
-      )),
+      bio: Some(bio),
       local: Some(false),
       admin: Some(false),
       bot_account: Some(person.kind == UserTypes::Service),
@@ -161,6 +170,7 @@ impl ApubObject for ApubPerson {
       inbox_url: Some(person.inbox.into()),
       shared_inbox_url: Some(person.endpoints.map(|e| e.shared_inbox.into())),
       matrix_user_id: Some(person.matrix_user_id),
+      instance_id: Some(Some(instance_id)),
     };
     let person = blocking(context.pool(), move |conn| {
       DbPerson::upsert(conn, &person_form)
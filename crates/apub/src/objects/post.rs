@@ -1,5 +1,5 @@
 use crate::{
-  activities::{verify_is_public, verify_person_in_community},
+  activities::{community_audience, verify_person_in_community, verify_visibility},
   check_apub_id_valid_with_strictness,
   local_instance,
   objects::{read_from_string_or_source_opt, verify_is_remote_object},
@@ -15,9 +15,8 @@ use activitypub_federation::{
   traits::ApubObject,
   utils::verify_domains_match,
 };
-use activitystreams_kinds::public;
 use chrono::NaiveDateTime;
-use lemmy_api_common::{request::fetch_site_data, utils::blocking};
+use lemmy_api_common::{request::fetch_site_data, slur_filter::slur_filter, utils::blocking};
 use lemmy_db_schema::{
   self,
   source::{
@@ -30,7 +29,7 @@ use lemmy_db_schema::{
 };
 use lemmy_utils::{
   error::LemmyError,
-  utils::{check_slurs, convert_datetime, markdown_to_html, remove_slurs},
+  utils::{canonicalize_url, check_slurs, convert_datetime, markdown_to_html, remove_slurs},
 };
 use lemmy_websocket::LemmyContext;
 use std::ops::Deref;
@@ -103,7 +102,10 @@ impl ApubObject for ApubPost {
       kind: PageType::Page,
       id: ObjectId::new(self.ap_id.clone()),
       attributed_to: AttributedTo::Lemmy(ObjectId::new(creator.actor_id)),
-      to: vec![community.actor_id.into(), public()],
+      to: vec![
+        community.actor_id.clone().into(),
+        community_audience(&community),
+      ],
       cc: vec![],
       name: self.name.clone(),
       content: self.body.as_ref().map(|b| markdown_to_html(b)),
@@ -139,8 +141,9 @@ impl ApubObject for ApubPost {
     check_apub_id_valid_with_strictness(page.id.inner(), community.local, context.settings())?;
     verify_person_in_community(&page.creator()?, &community, context, request_counter).await?;
     check_slurs(&page.name, &context.settings().slur_regex())?;
+    slur_filter(context.pool()).await?.apply(&page.name)?;
     verify_domains_match(page.creator()?.inner(), page.id.inner())?;
-    verify_is_public(&page.to, &page.cc)?;
+    verify_visibility(&page.to, &page.cc, &community)?;
     Ok(())
   }
 
@@ -157,16 +160,8 @@ impl ApubObject for ApubPost {
     let community = page.extract_community(context, request_counter).await?;
 
     let form = if !page.is_mod_action(context).await? {
-      let url = if let Some(attachment) = page.attachment.first() {
-        // url as sent by Lemmy (new)
-        Some(attachment.href.clone())
-      } else if page.kind == PageType::Video {
-        // we cant display videos directly, so insert a link to external video page
-        Some(page.id.inner().clone())
-      } else {
-        // url sent by lemmy (old)
-        page.url
-      };
+      let url = page.link_url();
+      let url_canonical = url.as_ref().map(canonicalize_url);
       let (metadata_res, thumbnail_url) = if let Some(url) = &url {
         fetch_site_data(context.client(), context.settings(), Some(url)).await
       } else {
@@ -175,14 +170,17 @@ impl ApubObject for ApubPost {
       let (embed_title, embed_description, embed_video_url) = metadata_res
         .map(|u| (Some(u.title), Some(u.description), Some(u.embed_video_url)))
         .unwrap_or_default();
-      let body_slurs_removed =
-        read_from_string_or_source_opt(&page.content, &page.media_type, &page.source)
-          .map(|s| Some(remove_slurs(&s, &context.settings().slur_regex())));
+      // Holds are handled when receiving the activity
+      let slur_filter = slur_filter(context.pool()).await?;
+      let (name, _) = slur_filter.apply(&page.name)?;
+      let body = read_from_string_or_source_opt(&page.content, &page.media_type, &page.source)
+        .map(|s| remove_slurs(&s, &context.settings().slur_regex()));
+      let (body_slurs_removed, _) = slur_filter.apply_opt(&body)?;
 
       PostForm {
-        name: page.name.clone(),
+        name,
         url: Some(url.map(Into::into)),
-        body: body_slurs_removed,
+        body: Some(body_slurs_removed),
         creator_id: creator.id,
         community_id: community.id,
         removed: None,
@@ -198,6 +196,9 @@ impl ApubObject for ApubPost {
         thumbnail_url: Some(thumbnail_url),
         ap_id: Some(page.id.clone().into()),
         local: Some(false),
+        pending: None,
+        crosspost_of: None,
+        url_canonical: Some(url_canonical),
       }
     } else {
       // if is mod action, only update locked/stickied fields, nothing else
@@ -14,7 +14,7 @@ use activitypub_federation::{
   utils::verify_domains_match,
 };
 use chrono::NaiveDateTime;
-use lemmy_api_common::utils::blocking;
+use lemmy_api_common::{slur_filter::slur_filter, utils::blocking};
 use lemmy_db_schema::{
   source::{
     person::Person,
@@ -132,11 +132,15 @@ impl ApubObject for ApubPrivateMessage {
     let recipient = note.to[0]
       .dereference(context, local_instance(context), request_counter)
       .await?;
+    let content = read_from_string_or_source(&note.content, &None, &note.source);
+    let content = slur_filter(context.pool())
+      .await?
+      .apply_without_hold(&content)?;
 
     let form = PrivateMessageForm {
       creator_id: creator.id,
       recipient_id: recipient.id,
-      content: read_from_string_or_source(&note.content, &None, &note.source),
+      content,
       published: note.published.map(|u| u.naive_local()),
       updated: note.updated.map(|u| u.naive_local()),
       deleted: None,
@@ -0,0 +1,138 @@
+use crate::{check_apub_id_valid_with_strictness, protocol::objects::relay::RelayActor};
+use activitypub_federation::{
+  traits::{Actor, ApubObject},
+  utils::verify_domains_match,
+};
+use chrono::NaiveDateTime;
+use lemmy_api_common::utils::blocking;
+use lemmy_db_schema::{newtypes::DbUrl, source::relay::Relay};
+use lemmy_utils::error::LemmyError;
+use lemmy_websocket::LemmyContext;
+use once_cell::sync::Lazy;
+use std::{ops::Deref, sync::Mutex};
+use url::Url;
+
+/// Subscribed relays, so that incoming activities can be checked against them without reading the
+/// database. Cleared whenever a relay is added, changed or removed.
+static SUBSCRIBED_RELAYS: Lazy<Mutex<Option<Vec<Relay>>>> = Lazy::new(|| Mutex::new(None));
+
+/// Returns all subscribed relays, from the cache if possible.
+pub(crate) async fn subscribed_relays(context: &LemmyContext) -> Result<Vec<Relay>, LemmyError> {
+  if let Some(relays) = SUBSCRIBED_RELAYS
+    .lock()
+    .expect("mutex poison error")
+    .as_ref()
+  {
+    return Ok(relays.clone());
+  }
+  let relays = blocking(context.pool(), Relay::list).await??;
+  *SUBSCRIBED_RELAYS.lock().expect("mutex poison error") = Some(relays.clone());
+  Ok(relays)
+}
+
+/// Needs to be called after any change to the relay table.
+pub(crate) fn clear_relay_cache() {
+  *SUBSCRIBED_RELAYS.lock().expect("mutex poison error") = None;
+}
+
+/// A subscribed relay. Relays which we don't follow are never stored.
+#[derive(Clone, Debug)]
+pub struct ApubRelay(Relay);
+
+impl Deref for ApubRelay {
+  type Target = Relay;
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
+
+impl From<Relay> for ApubRelay {
+  fn from(r: Relay) -> Self {
+    ApubRelay(r)
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ApubObject for ApubRelay {
+  type DataType = LemmyContext;
+  type ApubType = RelayActor;
+  type DbType = Relay;
+  type Error = LemmyError;
+
+  fn last_refreshed_at(&self) -> Option<NaiveDateTime> {
+    Some(self.last_refreshed_at)
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn read_from_apub_id(
+    object_id: Url,
+    data: &Self::DataType,
+  ) -> Result<Option<Self>, LemmyError> {
+    Ok(
+      blocking(data.pool(), move |conn| {
+        Relay::read_from_actor_id(conn, &object_id.into())
+      })
+      .await??
+      .map(Into::into),
+    )
+  }
+
+  /// The relay is gone (eg returned 410), so there is nothing left to subscribe to.
+  #[tracing::instrument(skip_all)]
+  async fn delete(self, data: &Self::DataType) -> Result<(), LemmyError> {
+    let relay_id = self.id;
+    blocking(data.pool(), move |conn| Relay::delete(conn, relay_id))
+      .await?
+      .map_err(|e| LemmyError::from_error_message(e, "couldnt_remove_relay"))?;
+    clear_relay_cache();
+    Ok(())
+  }
+
+  async fn into_apub(self, _data: &Self::DataType) -> Result<Self::ApubType, LemmyError> {
+    unimplemented!()
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    apub: &Self::ApubType,
+    expected_domain: &Url,
+    data: &Self::DataType,
+    _request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    check_apub_id_valid_with_strictness(apub.id.inner(), false, data.settings())?;
+    verify_domains_match(expected_domain, apub.id.inner())?;
+    verify_domains_match(apub.id.inner(), &apub.inbox)?;
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn from_apub(
+    apub: Self::ApubType,
+    data: &Self::DataType,
+    _request_counter: &mut i32,
+  ) -> Result<Self, LemmyError> {
+    let actor_id: DbUrl = apub.id.into();
+    let mut inboxes: Vec<DbUrl> = vec![apub.inbox.into()];
+    if let Some(endpoints) = apub.endpoints {
+      inboxes.push(endpoints.shared_inbox.into());
+    }
+    let public_key = apub.public_key.public_key_pem;
+    let relay = blocking(data.pool(), move |conn| {
+      Relay::update_actor(conn, &actor_id, &inboxes, &public_key)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "relay_not_subscribed"))?;
+    clear_relay_cache();
+    Ok(relay.into())
+  }
+}
+
+impl Actor for ApubRelay {
+  fn public_key(&self) -> &str {
+    self.public_key.as_deref().unwrap_or_default()
+  }
+
+  fn inbox(&self) -> Url {
+    self.inbox_url.clone().into()
+  }
+}
@@ -0,0 +1,213 @@
+use crate::{
+  activities::verify_is_public,
+  check_apub_id_valid_with_strictness,
+  local_instance,
+  objects::{read_from_string_or_source, verify_is_remote_object},
+  protocol::{objects::article::Article, Source},
+};
+use activitypub_federation::{
+  core::object_id::ObjectId,
+  deser::values::MediaTypeMarkdownOrHtml,
+  traits::ApubObject,
+  utils::verify_domains_match,
+};
+use activitystreams_kinds::{object::ArticleType, public};
+use anyhow::anyhow;
+use chrono::NaiveDateTime;
+use lemmy_api_common::{slur_filter::slur_filter, utils::blocking};
+use lemmy_db_schema::{
+  source::{
+    community::Community,
+    wiki_page::{WikiPage, WikiPageForm},
+  },
+  traits::Crud,
+};
+use lemmy_utils::{
+  error::LemmyError,
+  utils::{check_slurs, convert_datetime, markdown_to_html},
+};
+use lemmy_websocket::LemmyContext;
+use std::ops::Deref;
+use url::Url;
+
+#[derive(Clone, Debug)]
+pub struct ApubWikiPage(WikiPage);
+
+impl Deref for ApubWikiPage {
+  type Target = WikiPage;
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
+
+impl From<WikiPage> for ApubWikiPage {
+  fn from(w: WikiPage) -> Self {
+    ApubWikiPage(w)
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ApubObject for ApubWikiPage {
+  type DataType = LemmyContext;
+  type ApubType = Article;
+  type DbType = WikiPage;
+  type Error = LemmyError;
+
+  fn last_refreshed_at(&self) -> Option<NaiveDateTime> {
+    None
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn read_from_apub_id(
+    object_id: Url,
+    context: &LemmyContext,
+  ) -> Result<Option<Self>, LemmyError> {
+    Ok(
+      blocking(context.pool(), move |conn| {
+        WikiPage::read_from_apub_id(conn, object_id)
+      })
+      .await??
+      .map(Into::into),
+    )
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn delete(self, context: &LemmyContext) -> Result<(), LemmyError> {
+    if !self.deleted {
+      blocking(context.pool(), move |conn| {
+        WikiPage::update_deleted(conn, self.id, true)
+      })
+      .await??;
+    }
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn into_apub(self, context: &LemmyContext) -> Result<Article, LemmyError> {
+    let community_id = self.community_id;
+    let community = blocking(context.pool(), move |conn| {
+      Community::read(conn, community_id)
+    })
+    .await??;
+
+    let article = Article {
+      kind: ArticleType::Article,
+      id: ObjectId::new(self.ap_id.clone()),
+      attributed_to: ObjectId::new(community.actor_id),
+      to: vec![public()],
+      name: self.title.clone(),
+      content: markdown_to_html(&self.body),
+      media_type: Some(MediaTypeMarkdownOrHtml::Html),
+      source: Some(Source::new(self.body.clone())),
+      published: Some(convert_datetime(self.published)),
+      updated: self.updated.map(convert_datetime),
+    };
+    Ok(article)
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    article: &Article,
+    expected_domain: &Url,
+    context: &LemmyContext,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    verify_domains_match(article.id.inner(), expected_domain)?;
+    verify_domains_match(article.attributed_to.inner(), article.id.inner())?;
+    verify_is_remote_object(article.id.inner(), context.settings())?;
+    let community = article
+      .attributed_to
+      .dereference(context, local_instance(context), request_counter)
+      .await?;
+    check_apub_id_valid_with_strictness(article.id.inner(), community.local, context.settings())?;
+    verify_is_public(&article.to, &[])?;
+    check_slurs(&article.name, &context.settings().slur_regex())?;
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn from_apub(
+    article: Article,
+    context: &LemmyContext,
+    request_counter: &mut i32,
+  ) -> Result<ApubWikiPage, LemmyError> {
+    let community = article
+      .attributed_to
+      .dereference(context, local_instance(context), request_counter)
+      .await?;
+    // The page name is the last part of its url, like for local pages
+    let name = article
+      .id
+      .inner()
+      .path_segments()
+      .and_then(|mut s| s.next_back())
+      .filter(|n| !n.is_empty())
+      .ok_or_else(|| anyhow!("wiki page id has no name"))?
+      .to_string();
+    let body = read_from_string_or_source(&article.content, &article.media_type, &article.source);
+    let slur_filter = slur_filter(context.pool()).await?;
+
+    let form = WikiPageForm {
+      community_id: community.id,
+      name,
+      title: slur_filter.apply_without_hold(&article.name)?,
+      body: slur_filter.apply_without_hold(&body)?,
+      ap_id: article.id.into(),
+      local: Some(false),
+      deleted: Some(false),
+      published: article.published.map(|u| u.naive_local()),
+      updated: article.updated.map(|u| u.naive_local()),
+    };
+    let wiki_page = blocking(context.pool(), move |conn| WikiPage::upsert(conn, &form)).await??;
+    Ok(wiki_page.into())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    objects::{
+      community::tests::parse_lemmy_community,
+      instance::tests::parse_lemmy_instance,
+      tests::init_context,
+    },
+    protocol::tests::file_to_json_object,
+  };
+  use lemmy_db_schema::source::site::Site;
+  use serial_test::serial;
+
+  #[actix_rt::test]
+  #[serial]
+  async fn test_parse_lemmy_wiki_page() {
+    let context = init_context();
+    let site = parse_lemmy_instance(&context).await;
+    let community = parse_lemmy_community(&context).await;
+
+    let json = file_to_json_object("assets/lemmy/objects/article.json").unwrap();
+    let url = Url::parse("https://enterprise.lemmy.ml/c/tenforward/wiki/rules").unwrap();
+    let mut request_counter = 0;
+    ApubWikiPage::verify(&json, &url, &context, &mut request_counter)
+      .await
+      .unwrap();
+    let wiki_page = ApubWikiPage::from_apub(json, &context, &mut request_counter)
+      .await
+      .unwrap();
+
+    assert_eq!(wiki_page.ap_id, url.into());
+    assert_eq!(wiki_page.community_id, community.id);
+    assert_eq!(wiki_page.name, "rules");
+    assert_eq!(wiki_page.title, "Rules of Ten Forward");
+    assert_eq!(wiki_page.body, "1. No phasers\n2. Be nice to Guinan");
+    assert!(!wiki_page.local);
+    assert_eq!(request_counter, 0);
+
+    let article = wiki_page.clone().into_apub(&context).await.unwrap();
+    assert_eq!(article.attributed_to.inner(), &*community.actor_id);
+    assert_eq!(article.source.unwrap().content, wiki_page.body);
+
+    WikiPage::delete(&context.pool().get().unwrap(), wiki_page.id).unwrap();
+    Community::delete(&context.pool().get().unwrap(), community.id).unwrap();
+    Site::delete(&context.pool().get().unwrap(), site.id).unwrap();
+  }
+}
@@ -0,0 +1,28 @@
+use crate::{
+  objects::person::ApubPerson,
+  protocol::{objects::page::Page, Unparsed},
+};
+use activitypub_federation::{core::object_id::ObjectId, deser::helpers::deserialize_one_or_many};
+use activitystreams_kinds::activity::AddType;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Adds a post to the featured collection of a community. The post is embedded, to distinguish
+/// this from `AddMod`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddFeatured {
+  pub(crate) actor: ObjectId<ApubPerson>,
+  #[serde(deserialize_with = "deserialize_one_or_many")]
+  pub(crate) to: Vec<Url>,
+  pub(crate) object: Page,
+  pub(crate) target: Url,
+  #[serde(deserialize_with = "deserialize_one_or_many")]
+  pub(crate) cc: Vec<Url>,
+  #[serde(rename = "type")]
+  pub(crate) kind: AddType,
+  pub(crate) id: Url,
+
+  #[serde(flatten)]
+  pub(crate) unparsed: Unparsed,
+}
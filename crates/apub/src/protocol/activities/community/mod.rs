@@ -1,5 +1,7 @@
+pub mod add_featured;
 pub mod add_mod;
 pub mod announce;
+pub mod remove_featured;
 pub mod remove_mod;
 pub mod report;
 pub mod update;
@@ -8,8 +10,10 @@ pub mod update;
 mod tests {
   use crate::protocol::{
     activities::community::{
+      add_featured::AddFeatured,
       add_mod::AddMod,
       announce::AnnounceActivity,
+      remove_featured::RemoveFeatured,
       remove_mod::RemoveMod,
       report::Report,
       update::UpdateCommunity,
@@ -28,6 +32,13 @@ mod tests {
     test_parse_lemmy_item::<RemoveMod>("assets/lemmy/activities/community/remove_mod.json")
       .unwrap();
 
+    test_parse_lemmy_item::<AddFeatured>("assets/lemmy/activities/community/add_featured.json")
+      .unwrap();
+    test_parse_lemmy_item::<RemoveFeatured>(
+      "assets/lemmy/activities/community/remove_featured.json",
+    )
+    .unwrap();
+
     test_parse_lemmy_item::<UpdateCommunity>(
       "assets/lemmy/activities/community/update_community.json",
     )
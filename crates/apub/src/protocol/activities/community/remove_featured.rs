@@ -0,0 +1,28 @@
+use crate::{
+  objects::person::ApubPerson,
+  protocol::{objects::page::Page, Unparsed},
+};
+use activitypub_federation::{core::object_id::ObjectId, deser::helpers::deserialize_one_or_many};
+use activitystreams_kinds::activity::RemoveType;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Removes a post from the featured collection of a community. The post is embedded, to distinguish
+/// this from `RemoveMod`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveFeatured {
+  pub(crate) actor: ObjectId<ApubPerson>,
+  #[serde(deserialize_with = "deserialize_one_or_many")]
+  pub(crate) to: Vec<Url>,
+  pub(crate) object: Page,
+  pub(crate) target: Url,
+  #[serde(deserialize_with = "deserialize_one_or_many")]
+  pub(crate) cc: Vec<Url>,
+  #[serde(rename = "type")]
+  pub(crate) kind: RemoveType,
+  pub(crate) id: Url,
+
+  #[serde(flatten)]
+  pub(crate) unparsed: Unparsed,
+}
@@ -1,6 +1,6 @@
 use crate::{
-  objects::community::ApubCommunity,
-  protocol::{activities::following::follow::FollowCommunity, Unparsed},
+  fetcher::user_or_community::UserOrCommunity,
+  protocol::{activities::following::follow::Follow, Unparsed},
 };
 use activitypub_federation::core::object_id::ObjectId;
 use activitystreams_kinds::activity::AcceptType;
@@ -9,9 +9,9 @@ use url::Url;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct AcceptFollowCommunity {
-  pub(crate) actor: ObjectId<ApubCommunity>,
-  pub(crate) object: FollowCommunity,
+pub struct AcceptFollow {
+  pub(crate) actor: ObjectId<UserOrCommunity>,
+  pub(crate) object: Follow,
   #[serde(rename = "type")]
   pub(crate) kind: AcceptType,
   pub(crate) id: Url,
@@ -1,5 +1,6 @@
 use crate::{
-  objects::{community::ApubCommunity, person::ApubPerson},
+  fetcher::user_or_community::UserOrCommunity,
+  objects::person::ApubPerson,
   protocol::Unparsed,
 };
 use activitypub_federation::core::object_id::ObjectId;
@@ -7,11 +8,12 @@ use activitystreams_kinds::activity::FollowType;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+/// Follow of a community, or of a person (which is also used by Mastodon and similar software).
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct FollowCommunity {
+pub struct Follow {
   pub(crate) actor: ObjectId<ApubPerson>,
-  pub(crate) object: ObjectId<ApubCommunity>,
+  pub(crate) object: ObjectId<UserOrCommunity>,
   #[serde(rename = "type")]
   pub(crate) kind: FollowType,
   pub(crate) id: Url,
@@ -1,27 +1,26 @@
-pub(crate) mod accept;
+pub mod accept;
 pub mod follow;
+pub mod reject;
 pub mod undo_follow;
 
 #[cfg(test)]
 mod tests {
   use crate::protocol::{
     activities::following::{
-      accept::AcceptFollowCommunity,
-      follow::FollowCommunity,
-      undo_follow::UndoFollowCommunity,
+      accept::AcceptFollow,
+      follow::Follow,
+      reject::RejectFollow,
+      undo_follow::UndoFollow,
     },
     tests::test_parse_lemmy_item,
   };
 
   #[test]
   fn test_parse_lemmy_accept_follow() {
-    test_parse_lemmy_item::<FollowCommunity>("assets/lemmy/activities/following/follow.json")
+    test_parse_lemmy_item::<Follow>("assets/lemmy/activities/following/follow.json").unwrap();
+    test_parse_lemmy_item::<AcceptFollow>("assets/lemmy/activities/following/accept.json").unwrap();
+    test_parse_lemmy_item::<RejectFollow>("assets/lemmy/activities/following/reject.json").unwrap();
+    test_parse_lemmy_item::<UndoFollow>("assets/lemmy/activities/following/undo_follow.json")
       .unwrap();
-    test_parse_lemmy_item::<AcceptFollowCommunity>("assets/lemmy/activities/following/accept.json")
-      .unwrap();
-    test_parse_lemmy_item::<UndoFollowCommunity>(
-      "assets/lemmy/activities/following/undo_follow.json",
-    )
-    .unwrap();
   }
 }
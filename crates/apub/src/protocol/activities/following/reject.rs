@@ -0,0 +1,21 @@
+use crate::{
+  fetcher::user_or_community::UserOrCommunity,
+  protocol::{activities::following::follow::Follow, Unparsed},
+};
+use activitypub_federation::core::object_id::ObjectId;
+use activitystreams_kinds::activity::RejectType;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectFollow {
+  pub(crate) actor: ObjectId<UserOrCommunity>,
+  pub(crate) object: Follow,
+  #[serde(rename = "type")]
+  pub(crate) kind: RejectType,
+  pub(crate) id: Url,
+
+  #[serde(flatten)]
+  pub(crate) unparsed: Unparsed,
+}
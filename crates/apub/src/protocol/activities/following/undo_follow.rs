@@ -1,6 +1,6 @@
 use crate::{
   objects::person::ApubPerson,
-  protocol::{activities::following::follow::FollowCommunity, Unparsed},
+  protocol::{activities::following::follow::Follow, Unparsed},
 };
 use activitypub_federation::core::object_id::ObjectId;
 use activitystreams_kinds::activity::UndoType;
@@ -9,9 +9,9 @@ use url::Url;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct UndoFollowCommunity {
+pub struct UndoFollow {
   pub(crate) actor: ObjectId<ApubPerson>,
-  pub(crate) object: FollowCommunity,
+  pub(crate) object: Follow,
   #[serde(rename = "type")]
   pub(crate) kind: UndoType,
   pub(crate) id: Url,
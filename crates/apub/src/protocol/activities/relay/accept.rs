@@ -0,0 +1,21 @@
+use crate::{
+  objects::relay::ApubRelay,
+  protocol::{activities::relay::follow::RelayFollow, Unparsed},
+};
+use activitypub_federation::core::object_id::ObjectId;
+use activitystreams_kinds::activity::AcceptType;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AcceptRelayFollow {
+  pub(crate) actor: ObjectId<ApubRelay>,
+  pub(crate) object: RelayFollow,
+  #[serde(rename = "type")]
+  pub(crate) kind: AcceptType,
+  pub(crate) id: Url,
+
+  #[serde(flatten)]
+  pub(crate) unparsed: Unparsed,
+}
@@ -0,0 +1,24 @@
+use crate::{objects::relay::ApubRelay, protocol::Unparsed};
+use activitypub_federation::{core::object_id::ObjectId, deser::helpers::deserialize_one_or_many};
+use activitystreams_kinds::activity::AnnounceType;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Public object from another instance, forwarded by a relay. The object is only referenced by
+/// id and needs to be fetched from its origin.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayAnnounce {
+  pub(crate) actor: ObjectId<ApubRelay>,
+  #[serde(deserialize_with = "deserialize_one_or_many", default)]
+  pub(crate) to: Vec<Url>,
+  pub(crate) object: Url,
+  #[serde(deserialize_with = "deserialize_one_or_many", default)]
+  pub(crate) cc: Vec<Url>,
+  #[serde(rename = "type")]
+  pub(crate) kind: AnnounceType,
+  pub(crate) id: Url,
+
+  #[serde(flatten)]
+  pub(crate) unparsed: Unparsed,
+}
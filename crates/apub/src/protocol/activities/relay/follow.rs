@@ -0,0 +1,20 @@
+use crate::{objects::instance::ApubSite, protocol::Unparsed};
+use activitypub_federation::core::object_id::ObjectId;
+use activitystreams_kinds::activity::FollowType;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Subscription of the instance actor to a relay. LitePub relays are followed directly, while
+/// Mastodon style relays expect the public collection as object.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayFollow {
+  pub(crate) actor: ObjectId<ApubSite>,
+  pub(crate) object: Url,
+  #[serde(rename = "type")]
+  pub(crate) kind: FollowType,
+  pub(crate) id: Url,
+
+  #[serde(flatten)]
+  pub(crate) unparsed: Unparsed,
+}
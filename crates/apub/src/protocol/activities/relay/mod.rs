@@ -0,0 +1,30 @@
+pub mod accept;
+pub mod announce;
+pub mod follow;
+pub mod undo_follow;
+
+#[cfg(test)]
+mod tests {
+  use crate::protocol::{
+    activities::relay::{
+      accept::AcceptRelayFollow,
+      announce::RelayAnnounce,
+      follow::RelayFollow,
+      undo_follow::UndoRelayFollow,
+    },
+    tests::{test_json, test_parse_lemmy_item},
+  };
+
+  #[test]
+  fn test_parse_lemmy_relay_follow() {
+    test_parse_lemmy_item::<RelayFollow>("assets/lemmy/activities/relay/follow.json").unwrap();
+    test_parse_lemmy_item::<UndoRelayFollow>("assets/lemmy/activities/relay/undo_follow.json")
+      .unwrap();
+  }
+
+  #[test]
+  fn test_parse_pleroma_relay_activities() {
+    test_json::<AcceptRelayFollow>("assets/pleroma/activities/relay_accept.json").unwrap();
+    test_json::<RelayAnnounce>("assets/pleroma/activities/relay_announce.json").unwrap();
+  }
+}
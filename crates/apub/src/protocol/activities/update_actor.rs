@@ -0,0 +1,38 @@
+use crate::{
+  fetcher::signed_fetch::{PersonOrGroupOrInstance, SignedFetchActor},
+  protocol::Unparsed,
+};
+use activitypub_federation::{core::object_id::ObjectId, deser::helpers::deserialize_one_or_many};
+use activitystreams_kinds::activity::UpdateType;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Sent by a person, community or instance actor about itself, for example after its key was
+/// rotated. Unlike [`UpdateCommunity`](crate::protocol::activities::community::update::UpdateCommunity)
+/// the actor and the object are the same.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateActor {
+  pub(crate) actor: ObjectId<SignedFetchActor>,
+  #[serde(deserialize_with = "deserialize_one_or_many")]
+  pub(crate) to: Vec<Url>,
+  pub(crate) object: Box<PersonOrGroupOrInstance>,
+  #[serde(deserialize_with = "deserialize_one_or_many", default)]
+  pub(crate) cc: Vec<Url>,
+  #[serde(rename = "type")]
+  pub(crate) kind: UpdateType,
+  pub(crate) id: Url,
+
+  #[serde(flatten)]
+  pub(crate) unparsed: Unparsed,
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::protocol::{activities::update_actor::UpdateActor, tests::test_parse_lemmy_item};
+
+  #[test]
+  fn test_parse_lemmy_update_actor() {
+    test_parse_lemmy_item::<UpdateActor>("assets/lemmy/activities/update_actor.json").unwrap();
+  }
+}
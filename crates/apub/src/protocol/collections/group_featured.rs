@@ -0,0 +1,14 @@
+use crate::protocol::objects::page::Page;
+use activitystreams_kinds::collection::OrderedCollectionType;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Stickied posts of a community, the same as featured posts in Mastodon
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupFeatured {
+  pub(crate) r#type: OrderedCollectionType,
+  pub(crate) id: Url,
+  pub(crate) total_items: i32,
+  pub(crate) ordered_items: Vec<Page>,
+}
@@ -9,5 +9,9 @@ pub struct GroupOutbox {
   pub(crate) r#type: OrderedCollectionType,
   pub(crate) id: Url,
   pub(crate) total_items: i32,
+  /// First page of the full outbox history. Missing in older Lemmy versions.
+  pub(crate) first: Option<Url>,
+  /// The latest posts. Other software may only provide them in the pages.
+  #[serde(default)]
   pub(crate) ordered_items: Vec<AnnounceActivity>,
 }
@@ -0,0 +1,15 @@
+use crate::protocol::activities::community::announce::AnnounceActivity;
+use activitystreams_kinds::collection::OrderedCollectionPageType;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// A single page of the community outbox, containing announced posts and comments.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupOutboxPage {
+  pub(crate) r#type: OrderedCollectionPageType,
+  pub(crate) id: Url,
+  pub(crate) part_of: Url,
+  pub(crate) next: Option<Url>,
+  pub(crate) ordered_items: Vec<AnnounceActivity>,
+}
@@ -1,16 +1,20 @@
 pub(crate) mod empty_outbox;
+pub(crate) mod group_featured;
 pub(crate) mod group_followers;
 pub(crate) mod group_moderators;
 pub(crate) mod group_outbox;
+pub(crate) mod group_outbox_page;
 
 #[cfg(test)]
 mod tests {
   use crate::protocol::{
     collections::{
       empty_outbox::EmptyOutbox,
+      group_featured::GroupFeatured,
       group_followers::GroupFollowers,
       group_moderators::GroupModerators,
       group_outbox::GroupOutbox,
+      group_outbox_page::GroupOutboxPage,
     },
     tests::test_parse_lemmy_item,
   };
@@ -21,9 +25,20 @@ mod tests {
       .unwrap();
     let outbox =
       test_parse_lemmy_item::<GroupOutbox>("assets/lemmy/collections/group_outbox.json").unwrap();
-    assert_eq!(outbox.ordered_items.len() as i32, outbox.total_items);
+    assert_eq!(2, outbox.ordered_items.len());
+    assert_eq!(3, outbox.total_items);
+    assert!(outbox.first.is_some());
+    let outbox_page =
+      test_parse_lemmy_item::<GroupOutboxPage>("assets/lemmy/collections/group_outbox_page.json")
+        .unwrap();
+    assert_eq!(2, outbox_page.ordered_items.len());
+    assert!(outbox_page.next.is_some());
     test_parse_lemmy_item::<GroupModerators>("assets/lemmy/collections/group_moderators.json")
       .unwrap();
+    let featured =
+      test_parse_lemmy_item::<GroupFeatured>("assets/lemmy/collections/group_featured.json")
+        .unwrap();
+    assert_eq!(1, featured.ordered_items.len());
     test_parse_lemmy_item::<EmptyOutbox>("assets/lemmy/collections/person_outbox.json").unwrap();
   }
 }
@@ -0,0 +1,37 @@
+use crate::{
+  objects::{community::ApubCommunity, wiki_page::ApubWikiPage},
+  protocol::Source,
+};
+use activitypub_federation::{
+  core::object_id::ObjectId,
+  deser::{
+    helpers::{deserialize_one_or_many, deserialize_skip_error},
+    values::MediaTypeMarkdownOrHtml,
+  },
+};
+use activitystreams_kinds::object::ArticleType;
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use url::Url;
+
+/// A community wiki page. Unlike posts these are attributed to the community, as they can be
+/// edited by many people.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Article {
+  #[serde(rename = "type")]
+  pub(crate) kind: ArticleType,
+  pub(crate) id: ObjectId<ApubWikiPage>,
+  pub(crate) attributed_to: ObjectId<ApubCommunity>,
+  #[serde(deserialize_with = "deserialize_one_or_many")]
+  pub(crate) to: Vec<Url>,
+  pub(crate) name: String,
+  pub(crate) content: String,
+  pub(crate) media_type: Option<MediaTypeMarkdownOrHtml>,
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  pub(crate) source: Option<Source>,
+  pub(crate) published: Option<DateTime<FixedOffset>>,
+  pub(crate) updated: Option<DateTime<FixedOffset>>,
+}
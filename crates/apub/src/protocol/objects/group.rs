@@ -1,6 +1,7 @@
 use crate::{
   check_apub_id_valid_with_strictness,
   collections::{
+    community_featured::ApubCommunityFeatured,
     community_moderators::ApubCommunityModerators,
     community_outbox::ApubCommunityOutbox,
   },
@@ -14,7 +15,11 @@ use activitypub_federation::{
 };
 use activitystreams_kinds::actor::GroupType;
 use chrono::{DateTime, FixedOffset};
-use lemmy_db_schema::{source::community::CommunityForm, utils::naive_now};
+use lemmy_api_common::slur_filter::{slur_filter, SlurFilter};
+use lemmy_db_schema::{
+  source::community::{CommunityForm, CommunityVisibility},
+  utils::naive_now,
+};
 use lemmy_utils::{
   error::LemmyError,
   utils::{check_slurs, check_slurs_opt},
@@ -51,7 +56,11 @@ pub struct Group {
   pub(crate) moderators: Option<ObjectId<ApubCommunityModerators>>,
   // lemmy extension
   pub(crate) posting_restricted_to_mods: Option<bool>,
+  /// set for private communities, whose posts are only visible to approved followers
+  pub(crate) manually_approves_followers: Option<bool>,
   pub(crate) outbox: ObjectId<ApubCommunityOutbox>,
+  /// stickied posts
+  pub(crate) featured: Option<ObjectId<ApubCommunityFeatured>>,
   pub(crate) endpoints: Option<Endpoints>,
   pub(crate) published: Option<DateTime<FixedOffset>>,
   pub(crate) updated: Option<DateTime<FixedOffset>>,
@@ -71,18 +80,25 @@ impl Group {
     check_slurs_opt(&self.name, slur_regex)?;
     let description = read_from_string_or_source_opt(&self.summary, &None, &self.source);
     check_slurs_opt(&description, slur_regex)?;
+    slur_filter(context.pool())
+      .await?
+      .check_name(&self.preferred_username)?;
     Ok(())
   }
 
-  pub(crate) fn into_form(self) -> CommunityForm {
-    CommunityForm {
+  /// The slur filter is applied to title and description, which rejects them or replaces slurs.
+  pub(crate) fn into_form(self, slur_filter: &SlurFilter) -> Result<CommunityForm, LemmyError> {
+    let title =
+      slur_filter.apply_without_hold(self.name.as_ref().unwrap_or(&self.preferred_username))?;
+    let description = slur_filter.apply_without_hold_opt(&read_from_string_or_source_opt(
+      &self.summary,
+      &None,
+      &self.source,
+    ))?;
+    Ok(CommunityForm {
       name: self.preferred_username.clone(),
-      title: self.name.unwrap_or(self.preferred_username),
-      description: Some(read_from_string_or_source_opt(
-        &self.summary,
-        &None,
-        &self.source,
-      )),
+      title,
+      description: Some(description),
       removed: None,
       published: self.published.map(|u| u.naive_local()),
       updated: self.updated.map(|u| u.naive_local()),
@@ -100,6 +116,13 @@ impl Group {
       inbox_url: Some(self.inbox.into()),
       shared_inbox_url: Some(self.endpoints.map(|e| e.shared_inbox.into())),
       posting_restricted_to_mods: self.posting_restricted_to_mods,
-    }
+      require_approval: None,
+      instance_id: None,
+      visibility: Some(if self.manually_approves_followers.unwrap_or(false) {
+        CommunityVisibility::Private
+      } else {
+        CommunityVisibility::Public
+      }),
+    })
   }
 }
@@ -1,6 +1,6 @@
 use crate::{
   objects::instance::ApubSite,
-  protocol::{ImageObject, Source},
+  protocol::{objects::Endpoints, ImageObject, Source},
 };
 use activitypub_federation::{
   core::{object_id::ObjectId, signatures::PublicKey},
@@ -29,6 +29,8 @@ pub struct Instance {
   pub(crate) inbox: Url,
   /// mandatory field in activitypub, lemmy currently serves an empty outbox
   pub(crate) outbox: Url,
+  /// Relays deliver to the shared inbox
+  pub(crate) endpoints: Option<Endpoints>,
   pub(crate) public_key: PublicKey,
 
   // sidebar
@@ -1,12 +1,14 @@
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+pub(crate) mod article;
 pub(crate) mod chat_message;
 pub(crate) mod group;
 pub(crate) mod instance;
 pub(crate) mod note;
 pub(crate) mod page;
 pub(crate) mod person;
+pub(crate) mod relay;
 pub(crate) mod tombstone;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -19,12 +21,14 @@ pub struct Endpoints {
 mod tests {
   use crate::protocol::{
     objects::{
+      article::Article,
       chat_message::ChatMessage,
       group::Group,
       instance::Instance,
       note::Note,
       page::Page,
       person::Person,
+      relay::RelayActor,
       tombstone::Tombstone,
     },
     tests::{test_json, test_parse_lemmy_item},
@@ -39,6 +43,7 @@ mod tests {
     test_parse_lemmy_item::<Note>("assets/lemmy/objects/note.json").unwrap();
     test_parse_lemmy_item::<ChatMessage>("assets/lemmy/objects/chat_message.json").unwrap();
     test_parse_lemmy_item::<Tombstone>("assets/lemmy/objects/tombstone.json").unwrap();
+    test_parse_lemmy_item::<Article>("assets/lemmy/objects/article.json").unwrap();
   }
 
   #[test]
@@ -46,6 +51,7 @@ mod tests {
     test_json::<Person>("assets/pleroma/objects/person.json").unwrap();
     test_json::<Note>("assets/pleroma/objects/note.json").unwrap();
     test_json::<ChatMessage>("assets/pleroma/objects/chat_message.json").unwrap();
+    test_json::<RelayActor>("assets/pleroma/objects/relay.json").unwrap();
   }
 
   #[test]
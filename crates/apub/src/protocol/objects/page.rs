@@ -87,6 +87,19 @@ pub(crate) struct AttributedToPeertube {
 }
 
 impl Page {
+  /// The link of the post, taken from the attachment as sent by Lemmy, or from the deprecated
+  /// `url` field as sent by older versions.
+  pub(crate) fn link_url(&self) -> Option<Url> {
+    if let Some(attachment) = self.attachment.first() {
+      Some(attachment.href.clone())
+    } else if self.kind == PageType::Video {
+      // we cant display videos directly, so insert a link to external video page
+      Some(self.id.inner().clone())
+    } else {
+      self.url.clone()
+    }
+  }
+
   /// Only mods can change the post's stickied/locked status. So if either of these is changed from
   /// the current value, it is a mod action and needs to be verified as such.
   ///
@@ -0,0 +1,25 @@
+use crate::{objects::relay::ApubRelay, protocol::objects::Endpoints};
+use activitypub_federation::core::{object_id::ObjectId, signatures::PublicKey};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use url::Url;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+pub enum RelayType {
+  Application,
+  Service,
+}
+
+/// Actor of a LitePub or Mastodon style relay. Only the fields needed for subscribing and for
+/// verifying signatures are parsed.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayActor {
+  #[serde(rename = "type")]
+  pub(crate) kind: RelayType,
+  pub(crate) id: ObjectId<ApubRelay>,
+  pub(crate) inbox: Url,
+  pub(crate) endpoints: Option<Endpoints>,
+  pub(crate) public_key: PublicKey,
+}
@@ -69,6 +69,22 @@ impl Activity {
     activity.filter(ap_id.eq(object_id)).first::<Self>(conn)
   }
 
+  /// The Create activity which a remote instance sent us for the given post or comment, if it is
+  /// still stored.
+  pub fn read_received_create(
+    conn: &PgConnection,
+    object_id: &DbUrl,
+  ) -> Result<Option<Activity>, Error> {
+    use crate::schema::activity::dsl::*;
+    activity
+      .filter(local.eq(false))
+      .filter(sql("data->>'type' = 'Create'"))
+      .filter(sql("data->'object'->>'id' = ").bind::<sql_types::Text, _>(object_id))
+      .order_by(published.desc())
+      .first::<Self>(conn)
+      .optional()
+  }
+
   pub fn delete_olds(conn: &PgConnection) -> Result<usize, Error> {
     use crate::schema::activity::dsl::*;
     diesel::delete(activity.filter(published.lt(now - 6.months()))).execute(conn)
@@ -151,4 +167,36 @@ mod tests {
     assert_eq!(expected_activity, read_activity_by_apub_id);
     assert_eq!(expected_activity, inserted_activity);
   }
+
+  #[test]
+  #[serial]
+  fn test_read_received_create() {
+    let conn = establish_unpooled_connection();
+
+    let object_id: DbUrl = Url::parse("https://remote.example/post/7").unwrap().into();
+    let create_id: DbUrl = Url::parse("https://remote.example/activities/create/1")
+      .unwrap()
+      .into();
+    let create_json: Value = serde_json::from_str(
+      r#"{
+    "id": "https://remote.example/activities/create/1",
+    "type": "Create",
+    "actor": "https://remote.example/u/alice",
+    "object": {"id": "https://remote.example/post/7", "type": "Page"}
+    }"#,
+    )
+    .unwrap();
+    assert!(Activity::insert(&conn, create_id.clone(), create_json, false, false).unwrap());
+
+    let read = Activity::read_received_create(&conn, &object_id)
+      .unwrap()
+      .unwrap();
+    assert_eq!(create_id, read.ap_id);
+    let other_id: DbUrl = Url::parse("https://remote.example/post/8").unwrap().into();
+    assert!(Activity::read_received_create(&conn, &other_id)
+      .unwrap()
+      .is_none());
+
+    Activity::delete(&conn, read.id).unwrap();
+  }
 }
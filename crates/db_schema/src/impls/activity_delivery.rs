@@ -0,0 +1,206 @@
+use crate::{
+  newtypes::ActivityDeliveryId,
+  schema::activity_delivery::dsl::*,
+  source::activity_delivery::{ActivityDelivery, ActivityDeliveryForm},
+  utils::{limit_and_offset, naive_now},
+};
+use chrono::Duration;
+use diesel::{dsl::*, result::Error, *};
+
+/// Retries are spaced out exponentially, but at most this far apart
+const MAX_RETRY_DELAY_SECONDS: i64 = 6 * 60 * 60;
+/// Claimed deliveries aren't handed out again for this long, in case the sender died before
+/// recording the result
+const CLAIM_SECONDS: i64 = 10 * 60;
+
+impl ActivityDelivery {
+  pub fn enqueue(conn: &PgConnection, forms: &[ActivityDeliveryForm]) -> Result<usize, Error> {
+    insert_into(activity_delivery).values(forms).execute(conn)
+  }
+
+  /// Claims deliveries which should be sent now, oldest first, by moving their next attempt into
+  /// the future. Rows which another server process is claiming at the same time are skipped, so
+  /// that each delivery is only sent once.
+  pub fn claim_due(conn: &PgConnection, limit: i64) -> Result<Vec<Self>, Error> {
+    conn.transaction(|| {
+      let due = activity_delivery
+        .select(id)
+        .filter(failed.eq(false))
+        .filter(next_attempt.le(naive_now()))
+        .order_by(next_attempt)
+        .limit(limit)
+        .for_update()
+        .skip_locked()
+        .load::<ActivityDeliveryId>(conn)?;
+      diesel::update(activity_delivery.filter(id.eq_any(due)))
+        .set(next_attempt.eq(naive_now() + Duration::seconds(CLAIM_SECONDS)))
+        .get_results::<Self>(conn)
+    })
+  }
+
+  /// Pending or failed deliveries, newest first
+  pub fn list(
+    conn: &PgConnection,
+    failed_only: bool,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    activity_delivery
+      .filter(failed.eq(failed_only))
+      .order_by(published.desc())
+      .limit(limit)
+      .offset(offset)
+      .load::<Self>(conn)
+  }
+
+  /// Returns the number of pending and of failed deliveries
+  pub fn count(conn: &PgConnection) -> Result<(i64, i64), Error> {
+    let count_failed = |f| {
+      activity_delivery
+        .filter(failed.eq(f))
+        .select(count(id))
+        .first::<i64>(conn)
+    };
+    Ok((count_failed(false)?, count_failed(true)?))
+  }
+
+  /// Called once the activity was delivered successfully
+  pub fn delete(conn: &PgConnection, delivery_id: ActivityDeliveryId) -> Result<usize, Error> {
+    diesel::delete(activity_delivery.find(delivery_id)).execute(conn)
+  }
+
+  /// Gives up on the delivery right away, eg because the inbox rejected the activity
+  pub fn mark_failed(
+    conn: &PgConnection,
+    delivery: &ActivityDelivery,
+    error: &str,
+  ) -> Result<Self, Error> {
+    diesel::update(activity_delivery.find(delivery.id))
+      .set((
+        attempts.eq(delivery.attempts + 1),
+        last_error.eq(error),
+        failed.eq(true),
+      ))
+      .get_result::<Self>(conn)
+  }
+
+  /// Schedules the next attempt with exponential backoff, or marks the delivery as failed once
+  /// `max_attempts` is reached. Other deliveries to the same inbox are held back until the next
+  /// attempt, so that an unreachable inbox isn't hit with lots of requests.
+  pub fn mark_attempt_failed(
+    conn: &PgConnection,
+    delivery: &ActivityDelivery,
+    error: &str,
+    max_attempts: i32,
+  ) -> Result<Self, Error> {
+    let new_attempts = delivery.attempts + 1;
+    let delay_seconds = 3_i64
+      .saturating_pow(new_attempts as u32)
+      .min(MAX_RETRY_DELAY_SECONDS);
+    let retry_at = naive_now() + Duration::seconds(delay_seconds);
+    conn.transaction(|| {
+      diesel::update(
+        activity_delivery
+          .filter(inbox.eq(&delivery.inbox))
+          .filter(failed.eq(false))
+          .filter(next_attempt.lt(retry_at)),
+      )
+      .set(next_attempt.eq(retry_at))
+      .execute(conn)?;
+      diesel::update(activity_delivery.find(delivery.id))
+        .set((
+          attempts.eq(new_attempts),
+          next_attempt.eq(retry_at),
+          last_error.eq(error),
+          failed.eq(new_attempts >= max_attempts),
+        ))
+        .get_result::<Self>(conn)
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    newtypes::DbUrl,
+    schema::activity_delivery,
+    source::{activity::Activity, activity_delivery::*},
+    traits::Crud,
+    utils::{establish_unpooled_connection, naive_now},
+  };
+  use diesel::{ExpressionMethods, RunQueryDsl};
+  use serde_json::json;
+  use serial_test::serial;
+  use url::Url;
+
+  #[test]
+  #[serial]
+  fn test_activity_delivery() {
+    let conn = establish_unpooled_connection();
+
+    let ap_id: DbUrl = Url::parse("https://lemmy-alpha/activities/delivery_test/1")
+      .unwrap()
+      .into();
+    Activity::insert(&conn, ap_id.clone(), json!({}), true, false).unwrap();
+    let activity = Activity::read_from_apub_id(&conn, &ap_id).unwrap();
+
+    let actor: DbUrl = Url::parse("https://lemmy-alpha/u/sender").unwrap().into();
+    let form = |inbox_url: &str| ActivityDeliveryForm {
+      activity_id: activity.id,
+      inbox: Url::parse(inbox_url).unwrap().into(),
+      actor_id: actor.clone(),
+    };
+    let forms = [
+      form("https://lemmy-beta/inbox"),
+      form("https://lemmy-beta/inbox"),
+      form("https://lemmy-gamma/inbox"),
+    ];
+    assert_eq!(3, ActivityDelivery::enqueue(&conn, &forms).unwrap());
+    // Claimed deliveries aren't handed out a second time
+    let claimed = ActivityDelivery::claim_due(&conn, 1).unwrap();
+    assert_eq!(1, claimed.len());
+    assert!(claimed[0].next_attempt > naive_now());
+    assert_eq!(2, ActivityDelivery::claim_due(&conn, 10).unwrap().len());
+    assert!(ActivityDelivery::claim_due(&conn, 10).unwrap().is_empty());
+
+    // Once the claims expire, they are due again
+    diesel::update(activity_delivery::table)
+      .set(activity_delivery::next_attempt.eq(naive_now()))
+      .execute(&conn)
+      .unwrap();
+
+    // A failed attempt holds back all deliveries to the same inbox
+    let beta = ActivityDelivery::list(&conn, false, None, None)
+      .unwrap()
+      .into_iter()
+      .find(|d| d.inbox.to_string() == "https://lemmy-beta/inbox")
+      .unwrap();
+    let delivery = ActivityDelivery::mark_attempt_failed(&conn, &beta, "timeout", 2).unwrap();
+    assert_eq!(1, delivery.attempts);
+    assert!(!delivery.failed);
+    assert!(delivery.next_attempt > naive_now());
+    let due = ActivityDelivery::claim_due(&conn, 10).unwrap();
+    assert_eq!(1, due.len());
+    assert_eq!("https://lemmy-gamma/inbox", due[0].inbox.to_string());
+    assert_eq!((3, 0), ActivityDelivery::count(&conn).unwrap());
+
+    // After too many attempts, it ends up in the failed list
+    let delivery =
+      ActivityDelivery::mark_attempt_failed(&conn, &delivery, "timeout again", 2).unwrap();
+    assert!(delivery.failed);
+    let failed = ActivityDelivery::list(&conn, true, None, None).unwrap();
+    assert_eq!(vec![delivery.clone()], failed);
+    assert_eq!((2, 1), ActivityDelivery::count(&conn).unwrap());
+
+    // Rejected deliveries fail right away
+    let rejected = ActivityDelivery::mark_failed(&conn, &due[0], "400 Bad Request").unwrap();
+    assert!(rejected.failed);
+    assert_eq!((1, 2), ActivityDelivery::count(&conn).unwrap());
+
+    assert_eq!(1, ActivityDelivery::delete(&conn, rejected.id).unwrap());
+    // Deliveries are removed together with the activity
+    Activity::delete(&conn, activity.id).unwrap();
+    assert_eq!((0, 0), ActivityDelivery::count(&conn).unwrap());
+  }
+}
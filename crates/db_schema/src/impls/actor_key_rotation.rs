@@ -0,0 +1,73 @@
+use crate::{
+  newtypes::DbUrl,
+  schema::actor_key_rotation::dsl::*,
+  source::actor_key_rotation::{ActorKeyRotation, ActorKeyRotationForm},
+};
+use diesel::{result::Error, *};
+
+impl ActorKeyRotation {
+  /// Only the key before the latest rotation is kept.
+  pub fn upsert(conn: &PgConnection, form: &ActorKeyRotationForm) -> Result<Self, Error> {
+    insert_into(actor_key_rotation)
+      .values(form)
+      .on_conflict(actor_id)
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+  }
+
+  /// The previous key of the actor, if it was rotated after `since`
+  pub fn read_previous_key(
+    conn: &PgConnection,
+    object_id: &DbUrl,
+    since: chrono::NaiveDateTime,
+  ) -> Result<Option<String>, Error> {
+    actor_key_rotation
+      .filter(actor_id.eq(object_id))
+      .filter(rotated_at.gt(since))
+      .select(previous_public_key)
+      .first::<String>(conn)
+      .optional()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    newtypes::DbUrl,
+    source::actor_key_rotation::{ActorKeyRotation, ActorKeyRotationForm},
+    utils::{establish_unpooled_connection, naive_now},
+  };
+  use chrono::Duration;
+  use diesel::*;
+  use serial_test::serial;
+  use url::Url;
+
+  #[test]
+  #[serial]
+  fn test_previous_key() {
+    let conn = establish_unpooled_connection();
+
+    let actor_id: DbUrl = Url::parse("https://example.com/u/rotated").unwrap().into();
+    let form = |key: &str| ActorKeyRotationForm {
+      actor_id: actor_id.clone(),
+      previous_public_key: key.to_string(),
+      rotated_at: naive_now(),
+    };
+    ActorKeyRotation::upsert(&conn, &form("first")).unwrap();
+    let rotation = ActorKeyRotation::upsert(&conn, &form("second")).unwrap();
+    assert_eq!("second", rotation.previous_public_key);
+
+    let hour_ago = naive_now() - Duration::hours(1);
+    let previous = ActorKeyRotation::read_previous_key(&conn, &actor_id, hour_ago).unwrap();
+    assert_eq!(Some("second".to_string()), previous);
+
+    // The previous key isn't returned anymore once the overlap is over
+    let previous = ActorKeyRotation::read_previous_key(&conn, &actor_id, naive_now()).unwrap();
+    assert_eq!(None, previous);
+
+    delete(crate::schema::actor_key_rotation::table)
+      .execute(&conn)
+      .unwrap();
+  }
+}
@@ -0,0 +1,134 @@
+use crate::{
+  newtypes::{AutomodRuleId, CommunityId},
+  source::automod_rule::*,
+  traits::Crud,
+};
+use diesel::{insert_into, result::Error, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
+
+impl Crud for AutomodRule {
+  type Form = AutomodRuleForm;
+  type IdType = AutomodRuleId;
+  fn create(conn: &PgConnection, form: &Self::Form) -> Result<Self, Error> {
+    use crate::schema::automod_rule::dsl::*;
+    insert_into(automod_rule)
+      .values(form)
+      .get_result::<Self>(conn)
+  }
+
+  fn read(conn: &PgConnection, id_: Self::IdType) -> Result<Self, Error> {
+    use crate::schema::automod_rule::dsl::*;
+    automod_rule.find(id_).first::<Self>(conn)
+  }
+
+  fn update(conn: &PgConnection, id_: Self::IdType, form: &Self::Form) -> Result<Self, Error> {
+    use crate::schema::automod_rule::dsl::*;
+    diesel::update(automod_rule.find(id_))
+      .set(form)
+      .get_result::<Self>(conn)
+  }
+
+  fn delete(conn: &PgConnection, id_: Self::IdType) -> Result<usize, Error> {
+    use crate::schema::automod_rule::dsl::*;
+    diesel::delete(automod_rule.find(id_)).execute(conn)
+  }
+}
+
+impl AutomodRule {
+  pub fn list_for_community(
+    conn: &PgConnection,
+    for_community_id: CommunityId,
+  ) -> Result<Vec<Self>, Error> {
+    use crate::schema::automod_rule::dsl::*;
+    automod_rule
+      .filter(community_id.eq(for_community_id))
+      .order_by(published)
+      .load::<Self>(conn)
+  }
+
+  /// Returns the `AutoLock` rules of all local communities, as `(community_id, days)`. The locks
+  /// of remote communities are up to their own instance.
+  pub fn list_auto_lock(conn: &PgConnection) -> Result<Vec<(CommunityId, i64)>, Error> {
+    use crate::schema::{automod_rule, community};
+    let rules = automod_rule::table
+      .inner_join(community::table)
+      .filter(community::local.eq(true))
+      .select(automod_rule::all_columns)
+      .load::<Self>(conn)?;
+    Ok(
+      rules
+        .into_iter()
+        .filter_map(|r| match r.rule {
+          AutomodRuleType::AutoLock { days } => Some((r.community_id, days)),
+          _ => None,
+        })
+        .collect(),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    source::{
+      automod_rule::*,
+      community::{Community, CommunityForm},
+    },
+    traits::Crud,
+    utils::establish_unpooled_connection,
+  };
+  use serial_test::serial;
+
+  #[test]
+  #[serial]
+  fn test_crud() {
+    let conn = establish_unpooled_connection();
+
+    let new_community = CommunityForm {
+      name: "automod_community".to_string(),
+      title: "nada".to_owned(),
+      public_key: Some("pubkey".to_string()),
+      ..CommunityForm::default()
+    };
+    let inserted_community = Community::create(&conn, &new_community).unwrap();
+
+    let regex_form = AutomodRuleForm {
+      community_id: inserted_community.id,
+      rule: AutomodRuleType::ContentRegex {
+        patterns: vec!["buy now".to_string()],
+        action: AutomodAction::Remove,
+      },
+      updated: None,
+    };
+    let inserted_regex = AutomodRule::create(&conn, &regex_form).unwrap();
+    assert_eq!(regex_form.rule, inserted_regex.rule);
+
+    let lock_form = AutomodRuleForm {
+      community_id: inserted_community.id,
+      rule: AutomodRuleType::AutoLock { days: 30 },
+      updated: None,
+    };
+    let inserted_lock = AutomodRule::create(&conn, &lock_form).unwrap();
+
+    let read_regex = AutomodRule::read(&conn, inserted_regex.id).unwrap();
+    let rules = AutomodRule::list_for_community(&conn, inserted_community.id).unwrap();
+    let auto_lock = AutomodRule::list_auto_lock(&conn).unwrap();
+
+    let updated_form = AutomodRuleForm {
+      community_id: inserted_community.id,
+      rule: AutomodRuleType::MinAccountAge {
+        days: 3,
+        action: AutomodAction::Reject,
+      },
+      updated: None,
+    };
+    let updated_regex = AutomodRule::update(&conn, inserted_regex.id, &updated_form).unwrap();
+    let num_deleted = AutomodRule::delete(&conn, inserted_lock.id).unwrap();
+    Community::delete(&conn, inserted_community.id).unwrap();
+
+    assert_eq!(inserted_regex, read_regex);
+    assert_eq!(vec![inserted_regex, inserted_lock], rules);
+    assert_eq!(vec![(inserted_community.id, 30)], auto_lock);
+    assert_eq!(updated_form.rule, updated_regex.rule);
+    assert_eq!(1, num_deleted);
+  }
+}
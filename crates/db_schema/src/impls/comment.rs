@@ -1,5 +1,5 @@
 use crate::{
-  newtypes::{CommentId, DbUrl, PersonId},
+  newtypes::{CommentId, CommunityId, DbUrl, PersonId},
   source::comment::{
     Comment,
     CommentForm,
@@ -9,13 +9,38 @@ use crate::{
     CommentSavedForm,
   },
   traits::{Crud, DeleteableOrRemoveable, Likeable, Saveable},
-  utils::naive_now,
+  utils::{limit_and_offset, naive_now},
 };
 use diesel::{dsl::*, result::Error, *};
 use diesel_ltree::Ltree;
 use url::Url;
 
 impl Comment {
+  /// Comments which are federated in the community outbox, newest first.
+  pub fn list_for_community(
+    conn: &PgConnection,
+    for_community_id: CommunityId,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    use crate::schema::{comment::dsl::*, post};
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    comment
+      .inner_join(post::table)
+      .filter(post::community_id.eq(for_community_id))
+      .filter(post::deleted.eq(false))
+      .filter(post::removed.eq(false))
+      .filter(deleted.eq(false))
+      .filter(removed.eq(false))
+      .filter(pending.eq(false))
+      .select(comment::all_columns())
+      .order_by(published.desc())
+      .then_order_by(id.desc())
+      .limit(limit)
+      .offset(offset)
+      .load::<Self>(conn)
+  }
+
   pub fn update_ap_id(
     conn: &PgConnection,
     comment_id: CommentId,
@@ -64,6 +89,17 @@ impl Comment {
       .get_result::<Self>(conn)
   }
 
+  pub fn update_pending(
+    conn: &PgConnection,
+    comment_id: CommentId,
+    new_pending: bool,
+  ) -> Result<Self, Error> {
+    use crate::schema::comment::dsl::*;
+    diesel::update(comment.find(comment_id))
+      .set(pending.eq(new_pending))
+      .get_result::<Self>(conn)
+  }
+
   pub fn update_removed_for_creator(
     conn: &PgConnection,
     for_creator_id: PersonId,
@@ -176,6 +212,17 @@ where ca.comment_id = c.id",
       None
     }
   }
+
+  /// Ids of all comments above this one in the tree, starting with the top level comment
+  pub fn ancestor_comment_ids(&self) -> Vec<CommentId> {
+    let mut ltree_split: Vec<&str> = self.path.0.split('.').collect();
+    ltree_split.remove(0); // The first is always 0
+    ltree_split.pop(); // The last is this comment
+    ltree_split
+      .into_iter()
+      .filter_map(|id| id.parse::<i32>().map(CommentId).ok())
+      .collect()
+  }
 }
 
 impl Crud for Comment {
@@ -331,6 +378,7 @@ mod tests {
       updated: None,
       ap_id: inserted_comment.ap_id.to_owned(),
       local: true,
+      pending: false,
     };
 
     let child_comment_form = CommentForm {
@@ -379,6 +427,12 @@ mod tests {
     };
 
     let read_comment = Comment::read(&conn, inserted_comment.id).unwrap();
+    let community_comments =
+      Comment::list_for_community(&conn, inserted_community.id, None, None).unwrap();
+    assert_eq!(
+      vec![inserted_child_comment.id, inserted_comment.id],
+      community_comments.iter().map(|c| c.id).collect::<Vec<_>>()
+    );
     let updated_comment = Comment::update(&conn, inserted_comment.id, &comment_form).unwrap();
     let like_removed = CommentLike::remove(&conn, inserted_person.id, inserted_comment.id).unwrap();
     let saved_removed = CommentSaved::unsave(&conn, &comment_saved_form).unwrap();
@@ -18,6 +18,7 @@ use crate::{
 use diesel::{
   dsl::*,
   result::Error,
+  BoolExpressionMethods,
   ExpressionMethods,
   PgConnection,
   QueryDsl,
@@ -44,6 +45,8 @@ mod safe_type {
     banner,
     hidden,
     posting_restricted_to_mods,
+    require_approval,
+    visibility,
   );
 
   impl ToSafe for Community {
@@ -65,6 +68,8 @@ mod safe_type {
         banner,
         hidden,
         posting_restricted_to_mods,
+        require_approval,
+        visibility,
       )
     }
   }
@@ -125,6 +130,57 @@ impl Community {
       .get_result::<Self>(conn)
   }
 
+  pub fn update_keys(
+    conn: &PgConnection,
+    community_id: CommunityId,
+    new_private_key: &str,
+    new_public_key: &str,
+  ) -> Result<Community, Error> {
+    use crate::schema::community::dsl::*;
+    diesel::update(community.find(community_id))
+      .set((
+        private_key.eq(new_private_key),
+        public_key.eq(new_public_key),
+      ))
+      .get_result::<Self>(conn)
+  }
+
+  pub fn list_local(conn: &PgConnection) -> Result<Vec<Self>, Error> {
+    use crate::schema::community::dsl::*;
+    community
+      .filter(local.eq(true))
+      .filter(deleted.eq(false))
+      .load::<Self>(conn)
+  }
+
+  /// Remote communities which the person follows, or posted or commented in. Their instances
+  /// have a copy of the person.
+  pub fn list_remote_for_person(
+    conn: &PgConnection,
+    for_person_id: PersonId,
+  ) -> Result<Vec<Self>, Error> {
+    use crate::schema::{comment, community, community_follower, post};
+    let followed = community_follower::table
+      .filter(community_follower::person_id.eq(for_person_id))
+      .select(community_follower::community_id);
+    let posted = post::table
+      .filter(post::creator_id.eq(for_person_id))
+      .select(post::community_id);
+    let commented = comment::table
+      .inner_join(post::table)
+      .filter(comment::creator_id.eq(for_person_id))
+      .select(post::community_id);
+    community::table
+      .filter(community::local.eq(false))
+      .filter(
+        community::id
+          .eq_any(followed)
+          .or(community::id.eq_any(posted))
+          .or(community::id.eq_any(commented)),
+      )
+      .load::<Self>(conn)
+  }
+
   pub fn distinct_federated_communities(conn: &PgConnection) -> Result<Vec<DbUrl>, Error> {
     use crate::schema::community::dsl::*;
     community.select(actor_id).distinct().load::<DbUrl>(conn)
@@ -264,6 +320,22 @@ impl CommunityFollower {
       None => SubscribedType::NotSubscribed,
     }
   }
+
+  /// Whether the person follows the community, and the follow isn't waiting for approval
+  pub fn is_approved_follower(
+    conn: &PgConnection,
+    for_community_id: CommunityId,
+    for_person_id: PersonId,
+  ) -> Result<bool, Error> {
+    use crate::schema::community_follower::dsl::*;
+    select(exists(
+      community_follower
+        .filter(community_id.eq(for_community_id))
+        .filter(person_id.eq(for_person_id))
+        .filter(pending.is_null().or(pending.eq(false))),
+    ))
+    .get_result(conn)
+  }
 }
 
 impl Followable for CommunityFollower {
@@ -414,6 +486,9 @@ mod tests {
       shared_inbox_url: None,
       hidden: false,
       posting_restricted_to_mods: false,
+      require_approval: false,
+      instance_id: None,
+      visibility: CommunityVisibility::Public,
     };
 
     let community_follower_form = CommunityFollowerForm {
@@ -0,0 +1,146 @@
+use crate::{
+  newtypes::EmailQueueId,
+  schema::email_queue::dsl::*,
+  source::email_queue::{EmailQueue, EmailQueueForm, EmailQueueSafe},
+  traits::ToSafe,
+  utils::{limit_and_offset, naive_now},
+};
+use chrono::Duration;
+use diesel::{dsl::*, result::Error, *};
+
+mod safe_type {
+  use crate::{schema::email_queue::columns::*, source::email_queue::EmailQueue, traits::ToSafe};
+
+  type Columns = (
+    id,
+    to_email,
+    to_name,
+    subject,
+    attempts,
+    next_attempt,
+    last_error,
+    failed,
+    published,
+  );
+
+  impl ToSafe for EmailQueue {
+    type SafeColumns = Columns;
+    fn safe_columns_tuple() -> Self::SafeColumns {
+      (
+        id,
+        to_email,
+        to_name,
+        subject,
+        attempts,
+        next_attempt,
+        last_error,
+        failed,
+        published,
+      )
+    }
+  }
+}
+
+/// Retries are spaced out exponentially, but at most this far apart
+const MAX_RETRY_DELAY_MINUTES: i64 = 6 * 60;
+
+impl EmailQueue {
+  pub fn enqueue(conn: &PgConnection, form: &EmailQueueForm) -> Result<Self, Error> {
+    insert_into(email_queue)
+      .values(form)
+      .get_result::<Self>(conn)
+  }
+
+  /// Emails which should be sent now, oldest first
+  pub fn list_due(conn: &PgConnection, limit: i64) -> Result<Vec<Self>, Error> {
+    email_queue
+      .filter(failed.eq(false))
+      .filter(next_attempt.le(naive_now()))
+      .order_by(next_attempt)
+      .limit(limit)
+      .load::<Self>(conn)
+  }
+
+  pub fn list_failed(
+    conn: &PgConnection,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<EmailQueueSafe>, Error> {
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    email_queue
+      .select(EmailQueue::safe_columns_tuple())
+      .filter(failed.eq(true))
+      .order_by(published.desc())
+      .limit(limit)
+      .offset(offset)
+      .load::<EmailQueueSafe>(conn)
+  }
+
+  /// Called once the email was sent successfully
+  pub fn delete(conn: &PgConnection, email_id: EmailQueueId) -> Result<usize, Error> {
+    diesel::delete(email_queue.find(email_id)).execute(conn)
+  }
+
+  /// Schedules the next attempt with exponential backoff, or marks the email as failed once
+  /// `max_attempts` is reached.
+  pub fn mark_attempt_failed(
+    conn: &PgConnection,
+    email: &EmailQueue,
+    error: &str,
+    max_attempts: i32,
+  ) -> Result<Self, Error> {
+    let new_attempts = email.attempts + 1;
+    let delay_minutes = 2_i64
+      .saturating_pow(new_attempts as u32)
+      .min(MAX_RETRY_DELAY_MINUTES);
+    diesel::update(email_queue.find(email.id))
+      .set((
+        attempts.eq(new_attempts),
+        next_attempt.eq(naive_now() + Duration::minutes(delay_minutes)),
+        last_error.eq(error),
+        failed.eq(new_attempts >= max_attempts),
+      ))
+      .get_result::<Self>(conn)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    source::email_queue::*,
+    utils::{establish_unpooled_connection, naive_now},
+  };
+  use serial_test::serial;
+
+  #[test]
+  #[serial]
+  fn test_email_queue() {
+    let conn = establish_unpooled_connection();
+
+    let form = EmailQueueForm {
+      to_email: "queued@example.com".to_string(),
+      to_name: "queued".to_string(),
+      subject: "Hi".to_string(),
+      html: "<p>secret link</p>".to_string(),
+      unsubscribe_url: None,
+    };
+    let email = EmailQueue::enqueue(&conn, &form).unwrap();
+    assert!(EmailQueue::list_due(&conn, 10).unwrap().contains(&email));
+
+    // A failed attempt is retried later
+    let email = EmailQueue::mark_attempt_failed(&conn, &email, "timeout", 2).unwrap();
+    assert_eq!(1, email.attempts);
+    assert!(!email.failed);
+    assert!(email.next_attempt > naive_now());
+    assert!(!EmailQueue::list_due(&conn, 10).unwrap().contains(&email));
+
+    // After too many attempts, it ends up in the failed list
+    let email = EmailQueue::mark_attempt_failed(&conn, &email, "timeout again", 2).unwrap();
+    assert!(email.failed);
+    let failed = EmailQueue::list_failed(&conn, None, None).unwrap();
+    let failed_email = failed.iter().find(|e| e.id == email.id).unwrap();
+    assert_eq!(Some("timeout again".to_string()), failed_email.last_error);
+
+    assert_eq!(1, EmailQueue::delete(&conn, email.id).unwrap());
+  }
+}
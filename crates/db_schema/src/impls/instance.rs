@@ -0,0 +1,206 @@
+use crate::{
+  newtypes::InstanceId,
+  schema::instance::dsl::*,
+  source::instance::{Instance, InstanceForm},
+  utils::naive_now,
+};
+use chrono::Duration;
+use diesel::{result::Error, *};
+
+/// An instance which couldn't be reached for this many days is considered dead
+pub const INSTANCE_DEAD_AFTER_DAYS: i64 = 3;
+/// Activities are still sent to dead instances this often, to notice when they come back
+pub const DEAD_INSTANCE_PROBE_INTERVAL_HOURS: i64 = 24;
+
+impl Instance {
+  /// Adds the domains which aren't known yet
+  pub fn upsert_domains(conn: &PgConnection, domains: &[String]) -> Result<usize, Error> {
+    let forms: Vec<InstanceForm> = domains
+      .iter()
+      .map(|d| InstanceForm { domain: d.clone() })
+      .collect();
+    insert_into(instance)
+      .values(forms)
+      .on_conflict(domain)
+      .do_nothing()
+      .execute(conn)
+  }
+
+  /// Returns the instance for the domain and updates when it was last seen. The flag is true if
+  /// the domain wasn't known before.
+  pub fn read_or_create(conn: &PgConnection, domain_: &str) -> Result<(Self, bool), Error> {
+    let form = InstanceForm {
+      domain: domain_.to_string(),
+    };
+    let inserted = insert_into(instance)
+      .values(form)
+      .on_conflict(domain)
+      .do_nothing()
+      .get_result::<Self>(conn)
+      .optional()?;
+    match inserted {
+      Some(inserted) => Ok((inserted, true)),
+      None => {
+        let updated = diesel::update(instance.filter(domain.eq(domain_)))
+          .set(last_seen.eq(naive_now()))
+          .get_result::<Self>(conn)?;
+        Ok((updated, false))
+      }
+    }
+  }
+
+  pub fn update_software(
+    conn: &PgConnection,
+    instance_id: InstanceId,
+    software_: Option<String>,
+    version_: Option<String>,
+  ) -> Result<Self, Error> {
+    diesel::update(instance.find(instance_id))
+      .set((software.eq(software_), version.eq(version_)))
+      .get_result::<Self>(conn)
+  }
+
+  pub fn read_from_domain(conn: &PgConnection, domain_: &str) -> Result<Self, Error> {
+    instance.filter(domain.eq(domain_)).first::<Self>(conn)
+  }
+
+  pub fn list(conn: &PgConnection) -> Result<Vec<Self>, Error> {
+    instance.order_by(domain).load::<Self>(conn)
+  }
+
+  /// Ends a streak of failed deliveries
+  pub fn mark_delivery_success(conn: &PgConnection, domain_: &str) -> Result<Self, Error> {
+    Instance::upsert_domains(conn, &[domain_.to_string()])?;
+    diesel::update(instance.filter(domain.eq(domain_)))
+      .set((
+        last_successful_delivery.eq(naive_now()),
+        failing_since.eq(None::<chrono::NaiveDateTime>),
+      ))
+      .get_result::<Self>(conn)
+  }
+
+  pub fn mark_delivery_failed(conn: &PgConnection, domain_: &str) -> Result<Self, Error> {
+    conn.transaction(|| {
+      Instance::upsert_domains(conn, &[domain_.to_string()])?;
+      diesel::update(
+        instance
+          .filter(domain.eq(domain_))
+          .filter(failing_since.is_null()),
+      )
+      .set(failing_since.eq(naive_now()))
+      .execute(conn)?;
+      diesel::update(instance.filter(domain.eq(domain_)))
+        .set(last_failed_delivery.eq(naive_now()))
+        .get_result::<Self>(conn)
+    })
+  }
+
+  /// Instances which have been failing for `INSTANCE_DEAD_AFTER_DAYS`
+  pub fn list_dead(conn: &PgConnection) -> Result<Vec<Self>, Error> {
+    let dead_since = naive_now() - Duration::days(INSTANCE_DEAD_AFTER_DAYS);
+    instance
+      .filter(failing_since.lt(dead_since))
+      .order_by(domain)
+      .load::<Self>(conn)
+  }
+
+  /// Domains of dead instances which shouldn't receive any activities right now, because they
+  /// were already probed within `DEAD_INSTANCE_PROBE_INTERVAL_HOURS`.
+  pub fn list_suppressed_domains(conn: &PgConnection) -> Result<Vec<String>, Error> {
+    let dead_since = naive_now() - Duration::days(INSTANCE_DEAD_AFTER_DAYS);
+    let probed_since = naive_now() - Duration::hours(DEAD_INSTANCE_PROBE_INTERVAL_HOURS);
+    instance
+      .filter(failing_since.lt(dead_since))
+      .filter(last_failed_delivery.gt(probed_since))
+      .select(domain)
+      .load::<String>(conn)
+  }
+
+  pub fn is_dead(&self) -> bool {
+    self
+      .failing_since
+      .map(|f| f < naive_now() - Duration::days(INSTANCE_DEAD_AFTER_DAYS))
+      .unwrap_or(false)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    schema::instance::dsl::*,
+    source::instance::Instance,
+    utils::{establish_unpooled_connection, naive_now},
+  };
+  use chrono::Duration;
+  use diesel::*;
+  use serial_test::serial;
+
+  #[test]
+  #[serial]
+  fn test_instance_health() {
+    let conn = establish_unpooled_connection();
+
+    let domains = vec!["dead.example".to_string(), "alive.example".to_string()];
+    let all_domains = vec![
+      "dead.example".to_string(),
+      "alive.example".to_string(),
+      "new.example".to_string(),
+    ];
+    assert_eq!(2, Instance::upsert_domains(&conn, &domains).unwrap());
+    assert_eq!(0, Instance::upsert_domains(&conn, &domains).unwrap());
+
+    let failed = Instance::mark_delivery_failed(&conn, "dead.example").unwrap();
+    let first_failure = failed.failing_since.unwrap();
+    let failed = Instance::mark_delivery_failed(&conn, "dead.example").unwrap();
+    assert_eq!(Some(first_failure), failed.failing_since);
+    assert!(!failed.is_dead());
+    assert!(Instance::list_suppressed_domains(&conn).unwrap().is_empty());
+
+    // Failing for longer than the limit
+    diesel::update(instance.find(failed.id))
+      .set(failing_since.eq(naive_now() - Duration::days(10)))
+      .execute(&conn)
+      .unwrap();
+    let dead = Instance::list_dead(&conn).unwrap();
+    assert_eq!(1, dead.len());
+    assert!(dead[0].is_dead());
+    assert_eq!(
+      vec!["dead.example".to_string()],
+      Instance::list_suppressed_domains(&conn).unwrap()
+    );
+
+    // Once the probe is due, activities are sent again
+    diesel::update(instance.find(failed.id))
+      .set(last_failed_delivery.eq(naive_now() - Duration::days(2)))
+      .execute(&conn)
+      .unwrap();
+    assert!(Instance::list_suppressed_domains(&conn).unwrap().is_empty());
+
+    let alive = Instance::mark_delivery_success(&conn, "dead.example").unwrap();
+    assert!(alive.failing_since.is_none());
+    assert!(alive.last_successful_delivery.is_some());
+    assert!(Instance::list_dead(&conn).unwrap().is_empty());
+
+    let alive = Instance::read_from_domain(&conn, "alive.example").unwrap();
+    assert!(alive.last_successful_delivery.is_none());
+
+    let (existing, created) = Instance::read_or_create(&conn, "alive.example").unwrap();
+    assert!(!created);
+    assert!(existing.last_seen >= alive.last_seen);
+    let (new, created) = Instance::read_or_create(&conn, "new.example").unwrap();
+    assert!(created);
+    let new = Instance::update_software(
+      &conn,
+      new.id,
+      Some("lemmy".to_string()),
+      Some("0.16.5".to_string()),
+    )
+    .unwrap();
+    assert_eq!(Some("lemmy".to_string()), new.software);
+    assert_eq!(Some("0.16.5".to_string()), new.version);
+
+    diesel::delete(instance.filter(domain.eq_any(all_domains)))
+      .execute(&conn)
+      .unwrap();
+  }
+}
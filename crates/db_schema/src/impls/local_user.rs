@@ -1,11 +1,12 @@
 use crate::{
   newtypes::LocalUserId,
   schema::local_user::dsl::*,
-  source::local_user::{LocalUser, LocalUserForm},
+  source::local_user::{EmailDigestFrequency, LocalUser, LocalUserForm},
   traits::Crud,
   utils::naive_now,
 };
 use bcrypt::{hash, DEFAULT_COST};
+use chrono::Duration;
 use diesel::{dsl::*, result::Error, *};
 
 mod safe_settings_type {
@@ -33,6 +34,7 @@ mod safe_settings_type {
     show_new_post_notifs,
     email_verified,
     accepted_application,
+    email_digest_frequency,
   );
 
   impl ToSafeSettings for LocalUser {
@@ -58,6 +60,7 @@ mod safe_settings_type {
         show_new_post_notifs,
         email_verified,
         accepted_application,
+        email_digest_frequency,
       )
     }
   }
@@ -96,6 +99,35 @@ impl LocalUser {
       .get_results::<Self>(conn)
   }
 
+  /// Users with the given digest frequency, whose next digest is due at `current_time`
+  pub fn list_for_email_digest(
+    conn: &PgConnection,
+    frequency: EmailDigestFrequency,
+    current_time: chrono::NaiveDateTime,
+  ) -> Result<Vec<Self>, Error> {
+    let interval = match frequency {
+      EmailDigestFrequency::Hourly => Duration::hours(1),
+      EmailDigestFrequency::Daily => Duration::days(1),
+      EmailDigestFrequency::Immediate | EmailDigestFrequency::Off => return Ok(vec![]),
+    };
+    local_user
+      .filter(email_digest_frequency.eq(frequency))
+      .filter(send_notifications_to_email.eq(true))
+      .filter(email.is_not_null())
+      .filter(last_email_digest.le(current_time - interval))
+      .load::<Self>(conn)
+  }
+
+  pub fn update_last_email_digest(
+    conn: &PgConnection,
+    local_user_id: LocalUserId,
+    sent: chrono::NaiveDateTime,
+  ) -> Result<Self, Error> {
+    diesel::update(local_user.find(local_user_id))
+      .set(last_email_digest.eq(sent))
+      .get_result::<Self>(conn)
+  }
+
   pub fn set_all_users_registration_applications_accepted(
     conn: &PgConnection,
   ) -> Result<Vec<Self>, Error> {
@@ -129,3 +161,70 @@ impl Crud for LocalUser {
       .get_result::<Self>(conn)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    source::{
+      local_user::{EmailDigestFrequency, LocalUser, LocalUserForm},
+      person::{Person, PersonForm},
+    },
+    traits::Crud,
+    utils::{establish_unpooled_connection, naive_now},
+  };
+  use chrono::Duration;
+  use serial_test::serial;
+
+  #[test]
+  #[serial]
+  fn test_list_for_email_digest() {
+    let conn = establish_unpooled_connection();
+
+    let new_person = PersonForm {
+      name: "digest_reader".into(),
+      public_key: Some("pubkey".to_string()),
+      ..PersonForm::default()
+    };
+    let inserted_person = Person::create(&conn, &new_person).unwrap();
+
+    let local_user_form = LocalUserForm {
+      person_id: Some(inserted_person.id),
+      password_encrypted: Some("pass".to_string()),
+      email: Some(Some("digest@example.com".to_string())),
+      send_notifications_to_email: Some(true),
+      email_digest_frequency: Some(EmailDigestFrequency::Hourly),
+      ..LocalUserForm::default()
+    };
+    let inserted_local_user = LocalUser::create(&conn, &local_user_form).unwrap();
+    let is_due = |time| {
+      LocalUser::list_for_email_digest(&conn, EmailDigestFrequency::Hourly, time)
+        .unwrap()
+        .iter()
+        .any(|u| u.id == inserted_local_user.id)
+    };
+
+    // The first digest is due an hour after signup
+    assert!(!is_due(naive_now()));
+    assert!(is_due(naive_now() + Duration::minutes(61)));
+
+    // Sending a digest resets the interval
+    LocalUser::update_last_email_digest(
+      &conn,
+      inserted_local_user.id,
+      naive_now() + Duration::minutes(61),
+    )
+    .unwrap();
+    assert!(!is_due(naive_now() + Duration::minutes(90)));
+    assert!(is_due(naive_now() + Duration::minutes(122)));
+
+    // Daily digests are listed separately
+    assert!(
+      !LocalUser::list_for_email_digest(&conn, EmailDigestFrequency::Daily, naive_now())
+        .unwrap()
+        .iter()
+        .any(|u| u.id == inserted_local_user.id)
+    );
+
+    Person::delete(&conn, inserted_person.id).unwrap();
+  }
+}
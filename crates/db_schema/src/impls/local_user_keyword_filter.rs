@@ -0,0 +1,169 @@
+use crate::{
+  newtypes::{LocalUserId, PersonId},
+  source::local_user_keyword_filter::{LocalUserKeywordFilter, LocalUserKeywordFilterForm},
+  utils::functions::regex_match_insensitive,
+};
+use diesel::{
+  dsl::*,
+  result::Error,
+  sql_types::Text,
+  Connection,
+  ExpressionMethods,
+  IntoSql,
+  PgConnection,
+  QueryDsl,
+  RunQueryDsl,
+};
+
+impl LocalUserKeywordFilter {
+  pub fn list_for_local_user(
+    conn: &PgConnection,
+    for_local_user_id: LocalUserId,
+  ) -> Result<Vec<Self>, Error> {
+    use crate::schema::local_user_keyword_filter::dsl::*;
+    local_user_keyword_filter
+      .filter(local_user_id.eq(for_local_user_id))
+      .order_by(id)
+      .load::<Self>(conn)
+  }
+
+  pub fn list_for_person(conn: &PgConnection, for_person_id: PersonId) -> Result<Vec<Self>, Error> {
+    use crate::schema::{local_user, local_user_keyword_filter};
+    local_user_keyword_filter::table
+      .inner_join(local_user::table)
+      .filter(local_user::person_id.eq(for_person_id))
+      .select(local_user_keyword_filter::all_columns)
+      .order_by(local_user_keyword_filter::id)
+      .load::<Self>(conn)
+  }
+
+  /// Replaces all keyword filters of the user with the given ones.
+  pub fn replace(
+    conn: &PgConnection,
+    for_local_user_id: LocalUserId,
+    forms: &[LocalUserKeywordFilterForm],
+  ) -> Result<Vec<Self>, Error> {
+    use crate::schema::local_user_keyword_filter::dsl::*;
+    conn.transaction::<_, Error, _>(|| {
+      delete(local_user_keyword_filter.filter(local_user_id.eq(for_local_user_id)))
+        .execute(conn)?;
+      insert_into(local_user_keyword_filter)
+        .values(forms)
+        .get_results::<Self>(conn)
+    })
+  }
+
+  /// The (case insensitive) Postgres regex to match content against. Plain keywords are escaped,
+  /// the escaping of the regex crate is understood by Postgres too. Returns `None` for regexes
+  /// which aren't safe to run, eg if they were stored before the current limits.
+  pub fn pattern(&self) -> Option<String> {
+    if !self.regex {
+      Some(regex::escape(&self.keyword))
+    } else if is_safe_regex(&self.keyword) {
+      Some(self.keyword.clone())
+    } else {
+      None
+    }
+  }
+
+  /// Rust and Postgres regexes differ a bit, so make sure that Postgres accepts the pattern
+  /// before storing it. Otherwise it would break listing posts for the user.
+  pub fn is_valid_pattern(conn: &PgConnection, pattern: &str) -> bool {
+    is_safe_regex(pattern)
+      && select(regex_match_insensitive(
+        "".into_sql::<Text>(),
+        pattern.to_owned(),
+      ))
+      .get_result::<bool>(conn)
+      .is_ok()
+  }
+}
+
+/// Upper bound for the compiled size of a user regex, in bytes.
+const MAX_REGEX_SIZE: usize = 1 << 16;
+
+/// User regexes are run by Postgres on every listing, so they are restricted to the syntax of the
+/// regex crate. It has no backreferences or lookaround, which are what make Postgres regexes
+/// exponentially slow, and the compiled size is limited.
+fn is_safe_regex(pattern: &str) -> bool {
+  regex::RegexBuilder::new(pattern)
+    .size_limit(MAX_REGEX_SIZE)
+    .build()
+    .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    source::{
+      local_user::{LocalUser, LocalUserForm},
+      local_user_keyword_filter::*,
+      person::*,
+    },
+    traits::Crud,
+    utils::establish_unpooled_connection,
+  };
+  use serial_test::serial;
+
+  #[test]
+  #[serial]
+  fn test_crud() {
+    let conn = establish_unpooled_connection();
+
+    let new_person = PersonForm {
+      name: "keyword_filterer".into(),
+      public_key: Some("pubkey".to_string()),
+      ..PersonForm::default()
+    };
+    let inserted_person = Person::create(&conn, &new_person).unwrap();
+
+    let local_user_form = LocalUserForm {
+      person_id: Some(inserted_person.id),
+      password_encrypted: Some("pass".to_string()),
+      ..LocalUserForm::default()
+    };
+    let inserted_local_user = LocalUser::create(&conn, &local_user_form).unwrap();
+
+    let form = |keyword: &str, regex: bool| LocalUserKeywordFilterForm {
+      local_user_id: inserted_local_user.id,
+      community_id: None,
+      keyword: keyword.to_string(),
+      regex,
+    };
+    LocalUserKeywordFilter::replace(&conn, inserted_local_user.id, &[form("old", false)]).unwrap();
+    let filters = LocalUserKeywordFilter::replace(
+      &conn,
+      inserted_local_user.id,
+      &[form("c++", false), form("^rust(acean)?$", true)],
+    )
+    .unwrap();
+    let read_filters = LocalUserKeywordFilter::list_for_person(&conn, inserted_person.id).unwrap();
+
+    assert_eq!(filters, read_filters);
+    assert_eq!(2, read_filters.len());
+    assert_eq!(Some("c\\+\\+".into()), read_filters[0].pattern());
+    assert_eq!(Some("^rust(acean)?$".into()), read_filters[1].pattern());
+    assert!(LocalUserKeywordFilter::is_valid_pattern(
+      &conn,
+      &read_filters[0].pattern().unwrap()
+    ));
+    assert!(!LocalUserKeywordFilter::is_valid_pattern(
+      &conn,
+      "(unclosed"
+    ));
+    // Backreferences, lookaround and huge repetitions are rejected
+    assert!(!LocalUserKeywordFilter::is_valid_pattern(&conn, "(a+)+\\1"));
+    assert!(!LocalUserKeywordFilter::is_valid_pattern(&conn, "a(?=b)"));
+    assert!(!LocalUserKeywordFilter::is_valid_pattern(
+      &conn,
+      "(\\w{100}){100}"
+    ));
+
+    Person::delete(&conn, inserted_person.id).unwrap();
+    assert!(
+      LocalUserKeywordFilter::list_for_local_user(&conn, inserted_local_user.id)
+        .unwrap()
+        .is_empty()
+    );
+  }
+}
@@ -1,19 +1,33 @@
 pub mod activity;
+pub mod activity_delivery;
+pub mod actor_key_rotation;
+pub mod automod_rule;
 pub mod comment;
 pub mod comment_reply;
 pub mod comment_report;
 pub mod community;
 pub mod community_block;
+pub mod email_queue;
 pub mod email_verification;
+pub mod instance;
 pub mod local_user;
+pub mod local_user_keyword_filter;
 pub mod moderator;
+pub mod multi_community;
 pub mod password_reset_request;
 pub mod person;
 pub mod person_block;
+pub mod person_follower;
 pub mod person_mention;
 pub mod post;
+pub mod post_notification;
 pub mod post_report;
 pub mod private_message;
+pub mod push_subscription;
 pub mod registration_application;
+pub mod relay;
 pub mod secret;
 pub mod site;
+pub mod slur_filter_entry;
+pub mod thread_subscription;
+pub mod wiki_page;
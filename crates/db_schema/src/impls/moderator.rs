@@ -93,6 +93,56 @@ impl Crud for ModRemoveComment {
   }
 }
 
+impl Crud for ModApprovePost {
+  type Form = ModApprovePostForm;
+  type IdType = i32;
+  fn read(conn: &PgConnection, from_id: i32) -> Result<Self, Error> {
+    use crate::schema::mod_approve_post::dsl::*;
+    mod_approve_post.find(from_id).first::<Self>(conn)
+  }
+
+  fn create(conn: &PgConnection, form: &ModApprovePostForm) -> Result<Self, Error> {
+    use crate::schema::mod_approve_post::dsl::*;
+    insert_into(mod_approve_post)
+      .values(form)
+      .get_result::<Self>(conn)
+  }
+
+  fn update(conn: &PgConnection, from_id: i32, form: &ModApprovePostForm) -> Result<Self, Error> {
+    use crate::schema::mod_approve_post::dsl::*;
+    diesel::update(mod_approve_post.find(from_id))
+      .set(form)
+      .get_result::<Self>(conn)
+  }
+}
+
+impl Crud for ModApproveComment {
+  type Form = ModApproveCommentForm;
+  type IdType = i32;
+  fn read(conn: &PgConnection, from_id: i32) -> Result<Self, Error> {
+    use crate::schema::mod_approve_comment::dsl::*;
+    mod_approve_comment.find(from_id).first::<Self>(conn)
+  }
+
+  fn create(conn: &PgConnection, form: &ModApproveCommentForm) -> Result<Self, Error> {
+    use crate::schema::mod_approve_comment::dsl::*;
+    insert_into(mod_approve_comment)
+      .values(form)
+      .get_result::<Self>(conn)
+  }
+
+  fn update(
+    conn: &PgConnection,
+    from_id: i32,
+    form: &ModApproveCommentForm,
+  ) -> Result<Self, Error> {
+    use crate::schema::mod_approve_comment::dsl::*;
+    diesel::update(mod_approve_comment.find(from_id))
+      .set(form)
+      .get_result::<Self>(conn)
+  }
+}
+
 impl Crud for ModRemoveCommunity {
   type Form = ModRemoveCommunityForm;
   type IdType = i32;
@@ -467,6 +517,25 @@ mod tests {
       when_: inserted_mod_sticky_post.when_,
     };
 
+    // approve post
+
+    let mod_approve_post_form = ModApprovePostForm {
+      mod_person_id: inserted_mod.id,
+      post_id: inserted_post.id,
+      reason: None,
+      approved: None,
+    };
+    let inserted_mod_approve_post = ModApprovePost::create(&conn, &mod_approve_post_form).unwrap();
+    let read_mod_approve_post = ModApprovePost::read(&conn, inserted_mod_approve_post.id).unwrap();
+    let expected_mod_approve_post = ModApprovePost {
+      id: inserted_mod_approve_post.id,
+      post_id: inserted_post.id,
+      mod_person_id: inserted_mod.id,
+      reason: None,
+      approved: Some(true),
+      when_: inserted_mod_approve_post.when_,
+    };
+
     // comment
 
     let mod_remove_comment_form = ModRemoveCommentForm {
@@ -488,6 +557,25 @@ mod tests {
       when_: inserted_mod_remove_comment.when_,
     };
 
+    let mod_approve_comment_form = ModApproveCommentForm {
+      mod_person_id: inserted_mod.id,
+      comment_id: inserted_comment.id,
+      reason: Some("looks fine".into()),
+      approved: Some(false),
+    };
+    let inserted_mod_approve_comment =
+      ModApproveComment::create(&conn, &mod_approve_comment_form).unwrap();
+    let read_mod_approve_comment =
+      ModApproveComment::read(&conn, inserted_mod_approve_comment.id).unwrap();
+    let expected_mod_approve_comment = ModApproveComment {
+      id: inserted_mod_approve_comment.id,
+      comment_id: inserted_comment.id,
+      mod_person_id: inserted_mod.id,
+      reason: Some("looks fine".into()),
+      approved: Some(false),
+      when_: inserted_mod_approve_comment.when_,
+    };
+
     // community
 
     let mod_remove_community_form = ModRemoveCommunityForm {
@@ -604,7 +692,9 @@ mod tests {
     assert_eq!(expected_mod_remove_post, read_mod_remove_post);
     assert_eq!(expected_mod_lock_post, read_mod_lock_post);
     assert_eq!(expected_mod_sticky_post, read_mod_sticky_post);
+    assert_eq!(expected_mod_approve_post, read_mod_approve_post);
     assert_eq!(expected_mod_remove_comment, read_mod_remove_comment);
+    assert_eq!(expected_mod_approve_comment, read_mod_approve_comment);
     assert_eq!(expected_mod_remove_community, read_mod_remove_community);
     assert_eq!(expected_mod_ban_from_community, read_mod_ban_from_community);
     assert_eq!(expected_mod_ban, read_mod_ban);
@@ -0,0 +1,200 @@
+use crate::{
+  newtypes::{CommunityId, MultiCommunityId, PersonId},
+  source::multi_community::*,
+  traits::Crud,
+  utils::limit_and_offset,
+};
+use diesel::{
+  insert_into,
+  result::Error,
+  Connection,
+  ExpressionMethods,
+  PgConnection,
+  QueryDsl,
+  RunQueryDsl,
+};
+
+impl Crud for MultiCommunity {
+  type Form = MultiCommunityForm;
+  type IdType = MultiCommunityId;
+  fn create(conn: &PgConnection, form: &Self::Form) -> Result<Self, Error> {
+    use crate::schema::multi_community::dsl::*;
+    insert_into(multi_community)
+      .values(form)
+      .get_result::<Self>(conn)
+  }
+
+  fn read(conn: &PgConnection, id_: Self::IdType) -> Result<Self, Error> {
+    use crate::schema::multi_community::dsl::*;
+    multi_community.find(id_).first::<Self>(conn)
+  }
+
+  fn update(conn: &PgConnection, id_: Self::IdType, form: &Self::Form) -> Result<Self, Error> {
+    use crate::schema::multi_community::dsl::*;
+    diesel::update(multi_community.find(id_))
+      .set(form)
+      .get_result::<Self>(conn)
+  }
+
+  fn delete(conn: &PgConnection, id_: Self::IdType) -> Result<usize, Error> {
+    use crate::schema::multi_community::dsl::*;
+    diesel::delete(multi_community.find(id_)).execute(conn)
+  }
+}
+
+impl MultiCommunity {
+  /// Creates the feed together with its member communities
+  pub fn create_with_communities(
+    conn: &PgConnection,
+    form: &MultiCommunityForm,
+    community_ids: &[CommunityId],
+  ) -> Result<Self, Error> {
+    conn.transaction(|| {
+      let created = Self::create(conn, form)?;
+      Self::set_communities(conn, created.id, community_ids)?;
+      Ok(created)
+    })
+  }
+
+  pub fn list_for_creator(
+    conn: &PgConnection,
+    for_creator_id: PersonId,
+  ) -> Result<Vec<Self>, Error> {
+    use crate::schema::multi_community::dsl::*;
+    multi_community
+      .filter(creator_id.eq(for_creator_id))
+      .order_by(name)
+      .load::<Self>(conn)
+  }
+
+  pub fn list_public(
+    conn: &PgConnection,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    use crate::schema::multi_community::dsl::*;
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    multi_community
+      .filter(public.eq(true))
+      .order_by(published.desc())
+      .limit(limit)
+      .offset(offset)
+      .load::<Self>(conn)
+  }
+
+  pub fn community_ids(
+    conn: &PgConnection,
+    for_multi_community_id: MultiCommunityId,
+  ) -> Result<Vec<CommunityId>, Error> {
+    use crate::schema::multi_community_community::dsl::*;
+    multi_community_community
+      .filter(multi_community_id.eq(for_multi_community_id))
+      .order_by(published)
+      .select(community_id)
+      .load::<CommunityId>(conn)
+  }
+
+  /// Replaces the member communities of the feed
+  pub fn set_communities(
+    conn: &PgConnection,
+    for_multi_community_id: MultiCommunityId,
+    community_ids: &[CommunityId],
+  ) -> Result<Vec<MultiCommunityCommunity>, Error> {
+    use crate::schema::multi_community_community::dsl::*;
+    let forms = community_ids
+      .iter()
+      .map(|c| MultiCommunityCommunityForm {
+        multi_community_id: for_multi_community_id,
+        community_id: *c,
+      })
+      .collect::<Vec<_>>();
+    conn.transaction(|| {
+      diesel::delete(
+        multi_community_community.filter(multi_community_id.eq(for_multi_community_id)),
+      )
+      .execute(conn)?;
+      insert_into(multi_community_community)
+        .values(forms)
+        .on_conflict_do_nothing()
+        .get_results::<MultiCommunityCommunity>(conn)
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    source::{
+      community::{Community, CommunityForm},
+      multi_community::*,
+      person::{Person, PersonForm},
+    },
+    traits::Crud,
+    utils::establish_unpooled_connection,
+  };
+  use serial_test::serial;
+
+  #[test]
+  #[serial]
+  fn test_crud() {
+    let conn = establish_unpooled_connection();
+
+    let new_person = PersonForm {
+      name: "multi_community_creator".into(),
+      public_key: Some("pubkey".to_string()),
+      ..PersonForm::default()
+    };
+    let inserted_person = Person::create(&conn, &new_person).unwrap();
+
+    let new_community = CommunityForm {
+      name: "multi_community_1".to_string(),
+      title: "nada".to_owned(),
+      public_key: Some("pubkey".to_string()),
+      ..CommunityForm::default()
+    };
+    let community_1 = Community::create(&conn, &new_community).unwrap();
+    let new_community = CommunityForm {
+      name: "multi_community_2".to_string(),
+      ..new_community
+    };
+    let community_2 = Community::create(&conn, &new_community).unwrap();
+
+    let form = MultiCommunityForm {
+      creator_id: inserted_person.id,
+      name: "news".to_string(),
+      public: Some(true),
+      ..MultiCommunityForm::default()
+    };
+    let inserted = MultiCommunity::create(&conn, &form).unwrap();
+    assert!(inserted.public);
+
+    MultiCommunity::set_communities(&conn, inserted.id, &[community_1.id, community_2.id]).unwrap();
+    let ids = MultiCommunity::community_ids(&conn, inserted.id).unwrap();
+    assert_eq!(vec![community_1.id, community_2.id], ids);
+
+    MultiCommunity::set_communities(&conn, inserted.id, &[community_2.id]).unwrap();
+    let ids = MultiCommunity::community_ids(&conn, inserted.id).unwrap();
+    assert_eq!(vec![community_2.id], ids);
+
+    let for_creator = MultiCommunity::list_for_creator(&conn, inserted_person.id).unwrap();
+    let public = MultiCommunity::list_public(&conn, None, None).unwrap();
+    assert_eq!(vec![inserted.clone()], for_creator);
+    assert_eq!(vec![inserted.clone()], public);
+
+    let update_form = MultiCommunityForm {
+      public: Some(false),
+      ..form
+    };
+    let updated = MultiCommunity::update(&conn, inserted.id, &update_form).unwrap();
+    assert!(!updated.public);
+    assert!(MultiCommunity::list_public(&conn, None, None)
+      .unwrap()
+      .is_empty());
+
+    let num_deleted = MultiCommunity::delete(&conn, inserted.id).unwrap();
+    Community::delete(&conn, community_1.id).unwrap();
+    Community::delete(&conn, community_2.id).unwrap();
+    Person::delete(&conn, inserted_person.id).unwrap();
+    assert_eq!(1, num_deleted);
+  }
+}
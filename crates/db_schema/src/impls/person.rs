@@ -258,6 +258,27 @@ impl Person {
       .get_result::<Self>(conn)
   }
 
+  pub fn update_keys(
+    conn: &PgConnection,
+    person_id: PersonId,
+    new_private_key: &str,
+    new_public_key: &str,
+  ) -> Result<Self, Error> {
+    diesel::update(person.find(person_id))
+      .set((
+        private_key.eq(new_private_key),
+        public_key.eq(new_public_key),
+      ))
+      .get_result::<Self>(conn)
+  }
+
+  pub fn list_local(conn: &PgConnection) -> Result<Vec<Self>, Error> {
+    person
+      .filter(local.eq(true))
+      .filter(deleted.eq(false))
+      .load::<Self>(conn)
+  }
+
   pub fn leave_admin(conn: &PgConnection, person_id: PersonId) -> Result<Self, Error> {
     diesel::update(person.find(person_id))
       .set(admin.eq(false))
@@ -361,6 +382,7 @@ mod tests {
       shared_inbox_url: None,
       matrix_user_id: None,
       ban_expires: None,
+      instance_id: None,
     };
 
     let read_person = Person::read(&conn, inserted_person.id).unwrap();
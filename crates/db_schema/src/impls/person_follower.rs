@@ -0,0 +1,154 @@
+use crate::{
+  newtypes::{DbUrl, PersonId},
+  source::person_follower::{PersonFollower, PersonFollowerForm},
+};
+use diesel::{dsl::*, result::Error, *};
+
+impl PersonFollower {
+  pub fn follow(conn: &PgConnection, form: &PersonFollowerForm) -> Result<Self, Error> {
+    use crate::schema::person_follower::dsl::*;
+    insert_into(person_follower)
+      .values(form)
+      .on_conflict((follower_id, person_id))
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+  }
+
+  pub fn follow_accepted(
+    conn: &PgConnection,
+    for_person_id: PersonId,
+    for_follower_id: PersonId,
+  ) -> Result<Self, Error> {
+    use crate::schema::person_follower::dsl::*;
+    diesel::update(
+      person_follower
+        .filter(person_id.eq(for_person_id))
+        .filter(follower_id.eq(for_follower_id)),
+    )
+    .set(pending.eq(false))
+    .get_result::<Self>(conn)
+  }
+
+  pub fn unfollow(conn: &PgConnection, form: &PersonFollowerForm) -> Result<usize, Error> {
+    use crate::schema::person_follower::dsl::*;
+    diesel::delete(
+      person_follower
+        .filter(person_id.eq(form.person_id))
+        .filter(follower_id.eq(form.follower_id)),
+    )
+    .execute(conn)
+  }
+
+  pub fn read(
+    conn: &PgConnection,
+    for_person_id: PersonId,
+    for_follower_id: PersonId,
+  ) -> Result<Self, Error> {
+    use crate::schema::person_follower::dsl::*;
+    person_follower
+      .filter(person_id.eq(for_person_id))
+      .filter(follower_id.eq(for_follower_id))
+      .first::<Self>(conn)
+  }
+
+  /// Shared inboxes (or inboxes if there is none) of all remote followers of the person, so that
+  /// new posts can be delivered to them.
+  pub fn list_remote_follower_inboxes(
+    conn: &PgConnection,
+    for_person_id: PersonId,
+  ) -> Result<Vec<DbUrl>, Error> {
+    use crate::schema::{person, person_follower};
+    let inboxes = person_follower::table
+      .inner_join(person::table.on(person_follower::follower_id.eq(person::id)))
+      .filter(person_follower::person_id.eq(for_person_id))
+      .filter(person_follower::pending.eq(false))
+      .filter(person::local.eq(false))
+      .select((person::shared_inbox_url, person::inbox_url))
+      .load::<(Option<DbUrl>, DbUrl)>(conn)?;
+    let mut inboxes: Vec<DbUrl> = inboxes
+      .into_iter()
+      .map(|(shared_inbox, inbox)| shared_inbox.unwrap_or(inbox))
+      .collect();
+    inboxes.sort_by_key(ToString::to_string);
+    inboxes.dedup();
+    Ok(inboxes)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    newtypes::DbUrl,
+    source::{
+      person::{Person, PersonForm},
+      person_follower::*,
+    },
+    traits::Crud,
+    utils::establish_unpooled_connection,
+  };
+  use serial_test::serial;
+
+  #[test]
+  #[serial]
+  fn test_crud() {
+    let conn = establish_unpooled_connection();
+
+    let new_person = PersonForm {
+      name: "follow_alice".into(),
+      public_key: Some("pubkey".to_string()),
+      ..PersonForm::default()
+    };
+    let alice = Person::create(&conn, &new_person).unwrap();
+
+    let new_remote_person = PersonForm {
+      name: "follow_bob".into(),
+      public_key: Some("pubkey".to_string()),
+      actor_id: Some(
+        url::Url::parse("https://example.com/users/follow_bob")
+          .unwrap()
+          .into(),
+      ),
+      inbox_url: Some(
+        url::Url::parse("https://example.com/users/follow_bob/inbox")
+          .unwrap()
+          .into(),
+      ),
+      shared_inbox_url: Some(Some(
+        url::Url::parse("https://example.com/inbox").unwrap().into(),
+      )),
+      local: Some(false),
+      ..PersonForm::default()
+    };
+    let bob = Person::create(&conn, &new_remote_person).unwrap();
+
+    let form = PersonFollowerForm {
+      person_id: alice.id,
+      follower_id: bob.id,
+      pending: true,
+    };
+    let follow = PersonFollower::follow(&conn, &form).unwrap();
+    assert!(follow.pending);
+    // Pending followers don't receive anything yet
+    assert!(
+      PersonFollower::list_remote_follower_inboxes(&conn, alice.id)
+        .unwrap()
+        .is_empty()
+    );
+
+    let accepted = PersonFollower::follow_accepted(&conn, alice.id, bob.id).unwrap();
+    assert_eq!(follow.id, accepted.id);
+    assert!(!accepted.pending);
+    let shared_inbox: DbUrl = url::Url::parse("https://example.com/inbox").unwrap().into();
+    assert_eq!(
+      vec![shared_inbox],
+      PersonFollower::list_remote_follower_inboxes(&conn, alice.id).unwrap()
+    );
+
+    assert_eq!(1, PersonFollower::unfollow(&conn, &form).unwrap());
+    assert!(PersonFollower::read(&conn, alice.id, bob.id).is_err());
+
+    Person::delete(&conn, alice.id).unwrap();
+    Person::delete(&conn, bob.id).unwrap();
+  }
+}
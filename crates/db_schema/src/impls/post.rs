@@ -11,7 +11,7 @@ use crate::{
     PostSavedForm,
   },
   traits::{Crud, DeleteableOrRemoveable, Likeable, Readable, Saveable},
-  utils::{naive_now, FETCH_LIMIT_MAX},
+  utils::{limit_and_offset, naive_now},
 };
 use diesel::{dsl::*, result::Error, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, *};
 use url::Url;
@@ -43,18 +43,40 @@ impl Crud for Post {
 }
 
 impl Post {
+  /// Posts which are federated in the community outbox, newest first.
   pub fn list_for_community(
     conn: &PgConnection,
     the_community_id: CommunityId,
+    page: Option<i64>,
+    limit: Option<i64>,
   ) -> Result<Vec<Self>, Error> {
     use crate::schema::post::dsl::*;
+    let (limit, offset) = limit_and_offset(page, limit)?;
     post
       .filter(community_id.eq(the_community_id))
       .filter(deleted.eq(false))
       .filter(removed.eq(false))
-      .then_order_by(published.desc())
-      .then_order_by(stickied.desc())
-      .limit(FETCH_LIMIT_MAX)
+      .filter(pending.eq(false))
+      .order_by(published.desc())
+      .then_order_by(id.desc())
+      .limit(limit)
+      .offset(offset)
+      .load::<Self>(conn)
+  }
+
+  /// Stickied posts of the community, which are federated in its featured collection.
+  pub fn list_featured_for_community(
+    conn: &PgConnection,
+    the_community_id: CommunityId,
+  ) -> Result<Vec<Self>, Error> {
+    use crate::schema::post::dsl::*;
+    post
+      .filter(community_id.eq(the_community_id))
+      .filter(stickied.eq(true))
+      .filter(deleted.eq(false))
+      .filter(removed.eq(false))
+      .filter(pending.eq(false))
+      .order_by(published.desc())
       .load::<Self>(conn)
   }
 
@@ -128,6 +150,17 @@ impl Post {
       .get_results::<Self>(conn)
   }
 
+  pub fn update_pending(
+    conn: &PgConnection,
+    post_id: PostId,
+    new_pending: bool,
+  ) -> Result<Self, Error> {
+    use crate::schema::post::dsl::*;
+    diesel::update(post.find(post_id))
+      .set(pending.eq(new_pending))
+      .get_result::<Self>(conn)
+  }
+
   pub fn update_locked(
     conn: &PgConnection,
     post_id: PostId,
@@ -139,6 +172,24 @@ impl Post {
       .get_result::<Self>(conn)
   }
 
+  /// Locks all posts in the community which were published more than `days` ago, and returns
+  /// the newly locked posts.
+  pub fn update_locked_older_than(
+    conn: &PgConnection,
+    for_community_id: CommunityId,
+    days: i64,
+  ) -> Result<Vec<Self>, Error> {
+    use crate::schema::post::dsl::*;
+    diesel::update(
+      post
+        .filter(community_id.eq(for_community_id))
+        .filter(locked.eq(false))
+        .filter(published.lt(now - days.days())),
+    )
+    .set(locked.eq(true))
+    .get_results::<Self>(conn)
+  }
+
   pub fn update_stickied(
     conn: &PgConnection,
     post_id: PostId,
@@ -387,6 +438,9 @@ mod tests {
       thumbnail_url: None,
       ap_id: inserted_post.ap_id.to_owned(),
       local: true,
+      pending: false,
+      crosspost_of: None,
+      url_canonical: None,
     };
 
     // Post Like
@@ -437,6 +491,18 @@ mod tests {
     };
 
     let read_post = Post::read(&conn, inserted_post.id).unwrap();
+    let community_posts =
+      Post::list_for_community(&conn, inserted_community.id, None, None).unwrap();
+    assert_eq!(vec![read_post.clone()], community_posts);
+    assert!(
+      Post::list_featured_for_community(&conn, inserted_community.id)
+        .unwrap()
+        .is_empty()
+    );
+    let stickied_post = Post::update_stickied(&conn, inserted_post.id, true).unwrap();
+    let featured = Post::list_featured_for_community(&conn, inserted_community.id).unwrap();
+    assert_eq!(vec![stickied_post], featured);
+    Post::update_stickied(&conn, inserted_post.id, false).unwrap();
     let updated_post = Post::update(&conn, inserted_post.id, &new_post).unwrap();
     let like_removed = PostLike::remove(&conn, inserted_person.id, inserted_post.id).unwrap();
     let saved_removed = PostSaved::unsave(&conn, &post_saved_form).unwrap();
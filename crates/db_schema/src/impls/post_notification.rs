@@ -0,0 +1,223 @@
+use crate::{
+  newtypes::{CommunityId, PersonId, PostNotificationId},
+  source::post_notification::*,
+  traits::Crud,
+};
+use diesel::{dsl::*, result::Error, *};
+
+impl CommunityPostSubscription {
+  pub fn subscribe(
+    conn: &PgConnection,
+    form: &CommunityPostSubscriptionForm,
+  ) -> Result<Self, Error> {
+    use crate::schema::community_post_subscription::dsl::*;
+    insert_into(community_post_subscription)
+      .values(form)
+      .on_conflict((person_id, community_id))
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+  }
+
+  pub fn unsubscribe(
+    conn: &PgConnection,
+    form: &CommunityPostSubscriptionForm,
+  ) -> Result<usize, Error> {
+    use crate::schema::community_post_subscription::dsl::*;
+    delete(
+      community_post_subscription
+        .filter(person_id.eq(form.person_id))
+        .filter(community_id.eq(form.community_id)),
+    )
+    .execute(conn)
+  }
+
+  pub fn is_subscribed(
+    conn: &PgConnection,
+    for_person_id: PersonId,
+    for_community_id: CommunityId,
+  ) -> Result<bool, Error> {
+    use crate::schema::community_post_subscription::dsl::*;
+    select(exists(
+      community_post_subscription
+        .filter(person_id.eq(for_person_id))
+        .filter(community_id.eq(for_community_id)),
+    ))
+    .get_result(conn)
+  }
+
+  pub fn list_subscribers(
+    conn: &PgConnection,
+    for_community_id: CommunityId,
+  ) -> Result<Vec<PersonId>, Error> {
+    use crate::schema::community_post_subscription::dsl::*;
+    community_post_subscription
+      .filter(community_id.eq(for_community_id))
+      .select(person_id)
+      .load::<PersonId>(conn)
+  }
+}
+
+impl Crud for PostNotification {
+  type Form = PostNotificationForm;
+  type IdType = PostNotificationId;
+  fn read(conn: &PgConnection, post_notification_id: PostNotificationId) -> Result<Self, Error> {
+    use crate::schema::post_notification::dsl::*;
+    post_notification
+      .find(post_notification_id)
+      .first::<Self>(conn)
+  }
+
+  fn create(conn: &PgConnection, form: &PostNotificationForm) -> Result<Self, Error> {
+    use crate::schema::post_notification::dsl::*;
+    insert_into(post_notification)
+      .values(form)
+      .get_result::<Self>(conn)
+  }
+
+  fn update(
+    conn: &PgConnection,
+    post_notification_id: PostNotificationId,
+    form: &PostNotificationForm,
+  ) -> Result<Self, Error> {
+    use crate::schema::post_notification::dsl::*;
+    diesel::update(post_notification.find(post_notification_id))
+      .set(form)
+      .get_result::<Self>(conn)
+  }
+}
+
+impl PostNotification {
+  /// Returns None if the recipient was already notified about this post, eg because the same
+  /// activity was received twice.
+  pub fn create_if_new(
+    conn: &PgConnection,
+    form: &PostNotificationForm,
+  ) -> Result<Option<Self>, Error> {
+    use crate::schema::post_notification::dsl::*;
+    insert_into(post_notification)
+      .values(form)
+      .on_conflict_do_nothing()
+      .get_result::<Self>(conn)
+      .optional()
+  }
+
+  pub fn update_read(
+    conn: &PgConnection,
+    post_notification_id: PostNotificationId,
+    new_read: bool,
+  ) -> Result<Self, Error> {
+    use crate::schema::post_notification::dsl::*;
+    diesel::update(post_notification.find(post_notification_id))
+      .set(read.eq(new_read))
+      .get_result::<Self>(conn)
+  }
+
+  pub fn mark_all_as_read(
+    conn: &PgConnection,
+    for_recipient_id: PersonId,
+  ) -> Result<Vec<Self>, Error> {
+    use crate::schema::post_notification::dsl::*;
+    diesel::update(
+      post_notification
+        .filter(recipient_id.eq(for_recipient_id))
+        .filter(read.eq(false)),
+    )
+    .set(read.eq(true))
+    .get_results::<Self>(conn)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    source::{
+      community::{Community, CommunityForm},
+      person::{Person, PersonForm},
+      post::*,
+      post_notification::*,
+    },
+    traits::Crud,
+    utils::establish_unpooled_connection,
+  };
+  use serial_test::serial;
+
+  #[test]
+  #[serial]
+  fn test_crud() {
+    let conn = establish_unpooled_connection();
+
+    let new_person = PersonForm {
+      name: "post_notif_alice".into(),
+      public_key: Some("pubkey".to_string()),
+      ..PersonForm::default()
+    };
+    let inserted_person = Person::create(&conn, &new_person).unwrap();
+
+    let new_community = CommunityForm {
+      name: "post_notif_community".to_string(),
+      title: "nada".to_owned(),
+      public_key: Some("pubkey".to_string()),
+      ..CommunityForm::default()
+    };
+    let inserted_community = Community::create(&conn, &new_community).unwrap();
+
+    let subscription_form = CommunityPostSubscriptionForm {
+      person_id: inserted_person.id,
+      community_id: inserted_community.id,
+    };
+    CommunityPostSubscription::subscribe(&conn, &subscription_form).unwrap();
+    // Subscribing twice is fine
+    CommunityPostSubscription::subscribe(&conn, &subscription_form).unwrap();
+    assert_eq!(
+      vec![inserted_person.id],
+      CommunityPostSubscription::list_subscribers(&conn, inserted_community.id).unwrap()
+    );
+
+    let new_post = PostForm {
+      name: "A test post".into(),
+      creator_id: inserted_person.id,
+      community_id: inserted_community.id,
+      ..PostForm::default()
+    };
+    let inserted_post = Post::create(&conn, &new_post).unwrap();
+
+    let notification_form = PostNotificationForm {
+      recipient_id: inserted_person.id,
+      post_id: inserted_post.id,
+      read: None,
+    };
+    let notification = PostNotification::create_if_new(&conn, &notification_form)
+      .unwrap()
+      .unwrap();
+    assert!(!notification.read);
+    assert_eq!(
+      None,
+      PostNotification::create_if_new(&conn, &notification_form).unwrap()
+    );
+
+    let read_notification = PostNotification::update_read(&conn, notification.id, true).unwrap();
+    assert!(read_notification.read);
+    PostNotification::update_read(&conn, notification.id, false).unwrap();
+    assert_eq!(
+      1,
+      PostNotification::mark_all_as_read(&conn, inserted_person.id)
+        .unwrap()
+        .len()
+    );
+
+    assert_eq!(
+      1,
+      CommunityPostSubscription::unsubscribe(&conn, &subscription_form).unwrap()
+    );
+    assert!(!CommunityPostSubscription::is_subscribed(
+      &conn,
+      inserted_person.id,
+      inserted_community.id
+    )
+    .unwrap());
+
+    Community::delete(&conn, inserted_community.id).unwrap();
+    Person::delete(&conn, inserted_person.id).unwrap();
+  }
+}
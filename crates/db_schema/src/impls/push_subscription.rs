@@ -0,0 +1,128 @@
+use crate::{
+  newtypes::LocalUserId,
+  schema::push_subscription::dsl::*,
+  source::push_subscription::{PushSubscription, PushSubscriptionForm},
+};
+use diesel::{dsl::*, result::Error, *};
+
+impl PushSubscription {
+  /// Creates the subscription, or updates it if the user already registered the endpoint. If
+  /// several accounts are used on the same device, each of them has its own subscription.
+  pub fn upsert(conn: &PgConnection, form: &PushSubscriptionForm) -> Result<Self, Error> {
+    insert_into(push_subscription)
+      .values(form)
+      .on_conflict((local_user_id, endpoint))
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+  }
+
+  pub fn list_for_local_user(
+    conn: &PgConnection,
+    for_local_user_id: LocalUserId,
+  ) -> Result<Vec<Self>, Error> {
+    push_subscription
+      .filter(local_user_id.eq(for_local_user_id))
+      .order_by(published)
+      .load::<Self>(conn)
+  }
+
+  pub fn delete_for_local_user(
+    conn: &PgConnection,
+    for_local_user_id: LocalUserId,
+    for_endpoint: &str,
+  ) -> Result<usize, Error> {
+    delete(
+      push_subscription
+        .filter(local_user_id.eq(for_local_user_id))
+        .filter(endpoint.eq(for_endpoint)),
+    )
+    .execute(conn)
+  }
+
+  /// Used when the push service reports that the subscription doesn't exist anymore
+  pub fn delete_for_endpoint(conn: &PgConnection, for_endpoint: &str) -> Result<usize, Error> {
+    delete(push_subscription.filter(endpoint.eq(for_endpoint))).execute(conn)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    source::{
+      local_user::{LocalUser, LocalUserForm},
+      person::*,
+      push_subscription::*,
+    },
+    traits::Crud,
+    utils::establish_unpooled_connection,
+  };
+  use serial_test::serial;
+
+  #[test]
+  #[serial]
+  fn test_crud() {
+    let conn = establish_unpooled_connection();
+
+    let mut local_users = vec![];
+    for name in ["push_alice", "push_bob"] {
+      let new_person = PersonForm {
+        name: name.into(),
+        public_key: Some("pubkey".to_string()),
+        ..PersonForm::default()
+      };
+      let inserted_person = Person::create(&conn, &new_person).unwrap();
+      let local_user_form = LocalUserForm {
+        person_id: Some(inserted_person.id),
+        password_encrypted: Some("pass".to_string()),
+        ..LocalUserForm::default()
+      };
+      local_users.push(LocalUser::create(&conn, &local_user_form).unwrap());
+    }
+    let (alice, bob) = (&local_users[0], &local_users[1]);
+
+    let mut form = PushSubscriptionForm {
+      local_user_id: alice.id,
+      endpoint: "https://push.example.com/abc".to_string(),
+      p256dh: "p256dh".to_string(),
+      auth: "auth".to_string(),
+      notify_mentions: true,
+      notify_replies: false,
+      notify_private_messages: true,
+      notify_new_posts: true,
+    };
+    let subscription = PushSubscription::upsert(&conn, &form).unwrap();
+    assert!(!subscription.notify_replies);
+    assert_eq!(
+      vec![subscription.clone()],
+      PushSubscription::list_for_local_user(&conn, alice.id).unwrap()
+    );
+
+    // Updating the same endpoint keeps the subscription
+    form.notify_replies = true;
+    let updated = PushSubscription::upsert(&conn, &form).unwrap();
+    assert_eq!(subscription.id, updated.id);
+    assert!(updated.notify_replies);
+
+    // Another account can't take over the endpoint, it gets its own subscription
+    form.local_user_id = bob.id;
+    let other = PushSubscription::upsert(&conn, &form).unwrap();
+    assert_ne!(subscription.id, other.id);
+    assert_eq!(
+      vec![updated],
+      PushSubscription::list_for_local_user(&conn, alice.id).unwrap()
+    );
+
+    assert_eq!(
+      1,
+      PushSubscription::delete_for_local_user(&conn, alice.id, &form.endpoint).unwrap()
+    );
+    assert_eq!(
+      1,
+      PushSubscription::delete_for_endpoint(&conn, &form.endpoint).unwrap()
+    );
+
+    Person::delete(&conn, alice.person_id).unwrap();
+    Person::delete(&conn, bob.person_id).unwrap();
+  }
+}
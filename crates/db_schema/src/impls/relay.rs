@@ -0,0 +1,135 @@
+use crate::{
+  newtypes::{DbUrl, RelayId},
+  schema::relay::dsl::*,
+  source::relay::{Relay, RelayForm},
+  utils::naive_now,
+};
+use diesel::{result::Error, *};
+
+impl Relay {
+  /// Subscribing to the same relay again replaces the previous follow.
+  pub fn upsert(conn: &PgConnection, form: &RelayForm) -> Result<Self, Error> {
+    insert_into(relay)
+      .values(form)
+      .on_conflict(inbox_url)
+      .do_update()
+      .set((form, accepted.eq(false)))
+      .get_result::<Self>(conn)
+  }
+
+  pub fn read(conn: &PgConnection, relay_id: RelayId) -> Result<Self, Error> {
+    relay.find(relay_id).first::<Self>(conn)
+  }
+
+  pub fn read_from_actor_id(conn: &PgConnection, object_id: &DbUrl) -> Result<Option<Self>, Error> {
+    relay
+      .filter(actor_id.eq(object_id))
+      .first::<Self>(conn)
+      .optional()
+  }
+
+  pub fn list(conn: &PgConnection) -> Result<Vec<Self>, Error> {
+    relay.order_by(published).load::<Self>(conn)
+  }
+
+  /// Inboxes of relays which accepted our follow, and receive public posts from local communities
+  pub fn list_accepted_inboxes(conn: &PgConnection) -> Result<Vec<DbUrl>, Error> {
+    relay
+      .filter(accepted.eq(true))
+      .select(inbox_url)
+      .load::<DbUrl>(conn)
+  }
+
+  /// Stores the relay actor. A relay is matched either by its actor id, or by one of its inboxes
+  /// in case it was subscribed through the inbox url.
+  pub fn update_actor(
+    conn: &PgConnection,
+    object_id: &DbUrl,
+    inboxes: &[DbUrl],
+    public_key_: &str,
+  ) -> Result<Self, Error> {
+    diesel::update(
+      relay.filter(
+        actor_id
+          .eq(object_id)
+          .or(inbox_url.eq_any(inboxes).and(actor_id.is_null())),
+      ),
+    )
+    .set((
+      actor_id.eq(object_id),
+      public_key.eq(public_key_),
+      last_refreshed_at.eq(naive_now()),
+    ))
+    .get_result::<Self>(conn)
+  }
+
+  pub fn mark_accepted(conn: &PgConnection, follow_id_: &DbUrl) -> Result<Self, Error> {
+    diesel::update(relay.filter(follow_id.eq(follow_id_)))
+      .set(accepted.eq(true))
+      .get_result::<Self>(conn)
+  }
+
+  pub fn delete(conn: &PgConnection, relay_id: RelayId) -> Result<usize, Error> {
+    diesel::delete(relay.find(relay_id)).execute(conn)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    newtypes::DbUrl,
+    source::relay::{Relay, RelayForm},
+    utils::establish_unpooled_connection,
+  };
+  use serial_test::serial;
+  use url::Url;
+
+  #[test]
+  #[serial]
+  fn test_relay_subscription() {
+    let conn = establish_unpooled_connection();
+
+    let inbox: Url = Url::parse("https://relay.example/inbox").unwrap();
+    let form = RelayForm {
+      inbox_url: inbox.clone().into(),
+      actor_id: None,
+      public_key: None,
+      follow_id: Url::parse("https://lemmy.example/activities/follow/1")
+        .unwrap()
+        .into(),
+      follow_object: Url::parse("https://www.w3.org/ns/activitystreams#Public")
+        .unwrap()
+        .into(),
+    };
+    let pending = Relay::upsert(&conn, &form).unwrap();
+    assert!(!pending.accepted);
+    assert!(Relay::list_accepted_inboxes(&conn).unwrap().is_empty());
+
+    // The relay accepts with its own actor, which is matched by inbox
+    let relay_actor: Url = Url::parse("https://relay.example/actor").unwrap();
+    let updated = Relay::update_actor(
+      &conn,
+      &relay_actor.clone().into(),
+      &[inbox.clone().into()],
+      "key",
+    )
+    .unwrap();
+    assert_eq!(pending.id, updated.id);
+    assert_eq!(Some(relay_actor.clone().into()), updated.actor_id);
+    let read = Relay::read_from_actor_id(&conn, &relay_actor.into())
+      .unwrap()
+      .unwrap();
+    assert_eq!(Some("key".to_string()), read.public_key);
+
+    let accepted = Relay::mark_accepted(&conn, &form.follow_id).unwrap();
+    assert!(accepted.accepted);
+    assert_eq!(
+      vec![Into::<DbUrl>::into(inbox)],
+      Relay::list_accepted_inboxes(&conn).unwrap()
+    );
+    assert_eq!(1, Relay::list(&conn).unwrap().len());
+
+    assert_eq!(1, Relay::delete(&conn, accepted.id).unwrap());
+    assert!(Relay::list(&conn).unwrap().is_empty());
+  }
+}
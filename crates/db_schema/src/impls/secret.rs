@@ -7,6 +7,21 @@ impl Secret {
   pub fn init(conn: &PgConnection) -> Result<Secret, Error> {
     read_secrets(conn)
   }
+
+  /// Stores newly generated Web Push keys
+  pub fn update_vapid_keys(
+    conn: &PgConnection,
+    private_key: &str,
+    public_key: &str,
+  ) -> Result<Secret, Error> {
+    use crate::schema::secret::dsl::*;
+    diesel::update(secret)
+      .set((
+        vapid_private_key.eq(private_key),
+        vapid_public_key.eq(public_key),
+      ))
+      .get_result::<Secret>(conn)
+  }
 }
 
 fn read_secrets(conn: &PgConnection) -> Result<Secret, Error> {
@@ -1,4 +1,8 @@
-use crate::{newtypes::DbUrl, source::site::*, traits::Crud};
+use crate::{
+  newtypes::{DbUrl, PersonId},
+  source::site::*,
+  traits::Crud,
+};
 use diesel::{dsl::*, result::Error, *};
 use url::Url;
 
@@ -55,6 +59,32 @@ impl Site {
     )
   }
 
+  pub fn update_keys(
+    conn: &PgConnection,
+    site_id: i32,
+    new_private_key: &str,
+    new_public_key: &str,
+  ) -> Result<Self, Error> {
+    use crate::schema::site::dsl::*;
+    diesel::update(site.find(site_id))
+      .set((
+        private_key.eq(new_private_key),
+        public_key.eq(new_public_key),
+      ))
+      .get_result::<Self>(conn)
+  }
+
+  pub fn update_automod_person(
+    conn: &PgConnection,
+    site_id: i32,
+    person_id: PersonId,
+  ) -> Result<Self, Error> {
+    use crate::schema::site::dsl::*;
+    diesel::update(site.find(site_id))
+      .set(automod_person_id.eq(person_id))
+      .get_result::<Self>(conn)
+  }
+
   pub fn read_remote_sites(conn: &PgConnection) -> Result<Vec<Self>, Error> {
     use crate::schema::site::dsl::*;
     site.order_by(id).offset(1).get_results::<Self>(conn)
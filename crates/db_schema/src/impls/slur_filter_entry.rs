@@ -0,0 +1,70 @@
+use crate::{newtypes::SlurFilterEntryId, source::slur_filter_entry::*, traits::Crud};
+use diesel::{insert_into, result::Error, PgConnection, QueryDsl, RunQueryDsl};
+
+impl Crud for SlurFilterEntry {
+  type Form = SlurFilterEntryForm;
+  type IdType = SlurFilterEntryId;
+  fn create(conn: &PgConnection, form: &Self::Form) -> Result<Self, Error> {
+    use crate::schema::slur_filter_entry::dsl::*;
+    insert_into(slur_filter_entry)
+      .values(form)
+      .get_result::<Self>(conn)
+  }
+
+  fn read(conn: &PgConnection, id_: Self::IdType) -> Result<Self, Error> {
+    use crate::schema::slur_filter_entry::dsl::*;
+    slur_filter_entry.find(id_).first::<Self>(conn)
+  }
+
+  fn update(conn: &PgConnection, id_: Self::IdType, form: &Self::Form) -> Result<Self, Error> {
+    use crate::schema::slur_filter_entry::dsl::*;
+    diesel::update(slur_filter_entry.find(id_))
+      .set(form)
+      .get_result::<Self>(conn)
+  }
+
+  fn delete(conn: &PgConnection, id_: Self::IdType) -> Result<usize, Error> {
+    use crate::schema::slur_filter_entry::dsl::*;
+    diesel::delete(slur_filter_entry.find(id_)).execute(conn)
+  }
+}
+
+impl SlurFilterEntry {
+  pub fn list(conn: &PgConnection) -> Result<Vec<Self>, Error> {
+    use crate::schema::slur_filter_entry::dsl::*;
+    slur_filter_entry.order_by(id).load::<Self>(conn)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{source::slur_filter_entry::*, traits::Crud, utils::establish_unpooled_connection};
+  use serial_test::serial;
+
+  #[test]
+  #[serial]
+  fn test_crud() {
+    let conn = establish_unpooled_connection();
+
+    let form = SlurFilterEntryForm {
+      pattern: "badword".to_string(),
+      action: SlurFilterAction::Hold,
+      updated: None,
+    };
+    let inserted = SlurFilterEntry::create(&conn, &form).unwrap();
+    assert_eq!(SlurFilterAction::Hold, inserted.action);
+
+    let update_form = SlurFilterEntryForm {
+      action: SlurFilterAction::Replace,
+      ..form
+    };
+    let updated = SlurFilterEntry::update(&conn, inserted.id, &update_form).unwrap();
+    let read = SlurFilterEntry::read(&conn, inserted.id).unwrap();
+    assert_eq!(updated, read);
+    assert_eq!(SlurFilterAction::Replace, read.action);
+    assert!(SlurFilterEntry::list(&conn).unwrap().contains(&read));
+
+    let num_deleted = SlurFilterEntry::delete(&conn, inserted.id).unwrap();
+    assert_eq!(1, num_deleted);
+  }
+}
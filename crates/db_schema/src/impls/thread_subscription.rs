@@ -0,0 +1,283 @@
+use crate::{
+  newtypes::{CommentId, PersonId, PostId},
+  source::{
+    comment::Comment,
+    thread_subscription::{ThreadMute, ThreadMuteForm, ThreadSubscription, ThreadSubscriptionForm},
+  },
+};
+use diesel::{dsl::*, result::Error, *};
+use diesel_ltree::Ltree;
+
+impl ThreadSubscription {
+  /// Subscribes to the thread, removing a mute of the same thread if there is one
+  pub fn subscribe(conn: &PgConnection, form: &ThreadSubscriptionForm) -> Result<Self, Error> {
+    use crate::schema::thread_subscription::dsl::*;
+    conn.transaction(|| {
+      ThreadMute::unmute(conn, form.person_id, form.post_id, form.comment_id)?;
+      let existing = Self::read(conn, form.person_id, form.post_id, form.comment_id)?;
+      match existing {
+        Some(existing) => Ok(existing),
+        None => insert_into(thread_subscription)
+          .values(form)
+          .get_result::<Self>(conn),
+      }
+    })
+  }
+
+  pub fn unsubscribe(
+    conn: &PgConnection,
+    for_person_id: PersonId,
+    for_post_id: PostId,
+    for_comment_id: Option<CommentId>,
+  ) -> Result<usize, Error> {
+    use crate::schema::thread_subscription::dsl::*;
+    let target = thread_subscription
+      .filter(person_id.eq(for_person_id))
+      .filter(post_id.eq(for_post_id));
+    match for_comment_id {
+      Some(for_comment_id) => delete(target.filter(comment_id.eq(for_comment_id))).execute(conn),
+      None => delete(target.filter(comment_id.is_null())).execute(conn),
+    }
+  }
+
+  pub fn read(
+    conn: &PgConnection,
+    for_person_id: PersonId,
+    for_post_id: PostId,
+    for_comment_id: Option<CommentId>,
+  ) -> Result<Option<Self>, Error> {
+    use crate::schema::thread_subscription::dsl::*;
+    let query = thread_subscription
+      .filter(person_id.eq(for_person_id))
+      .filter(post_id.eq(for_post_id))
+      .into_boxed();
+    let query = match for_comment_id {
+      Some(for_comment_id) => query.filter(comment_id.eq(for_comment_id)),
+      None => query.filter(comment_id.is_null()),
+    };
+    query.first::<Self>(conn).optional()
+  }
+
+  /// Everyone subscribed to the post of the comment, or to one of its parent comments
+  pub fn list_subscribers(conn: &PgConnection, comment: &Comment) -> Result<Vec<PersonId>, Error> {
+    use crate::schema::thread_subscription::dsl::*;
+    thread_subscription
+      .filter(post_id.eq(comment.post_id))
+      .filter(
+        comment_id
+          .is_null()
+          .or(comment_id.eq_any(comment.ancestor_comment_ids())),
+      )
+      .select(person_id)
+      .distinct()
+      .load::<PersonId>(conn)
+  }
+}
+
+impl ThreadMute {
+  /// Mutes the thread, removing a subscription of the same thread if there is one
+  pub fn mute(conn: &PgConnection, form: &ThreadMuteForm) -> Result<Self, Error> {
+    use crate::schema::thread_mute::dsl::*;
+    conn.transaction(|| {
+      ThreadSubscription::unsubscribe(conn, form.person_id, form.post_id, form.comment_id)?;
+      let existing = Self::read(conn, form.person_id, form.post_id, form.comment_id)?;
+      match existing {
+        Some(existing) => Ok(existing),
+        None => insert_into(thread_mute)
+          .values(form)
+          .get_result::<Self>(conn),
+      }
+    })
+  }
+
+  pub fn unmute(
+    conn: &PgConnection,
+    for_person_id: PersonId,
+    for_post_id: PostId,
+    for_comment_id: Option<CommentId>,
+  ) -> Result<usize, Error> {
+    use crate::schema::thread_mute::dsl::*;
+    let target = thread_mute
+      .filter(person_id.eq(for_person_id))
+      .filter(post_id.eq(for_post_id));
+    match for_comment_id {
+      Some(for_comment_id) => delete(target.filter(comment_id.eq(for_comment_id))).execute(conn),
+      None => delete(target.filter(comment_id.is_null())).execute(conn),
+    }
+  }
+
+  pub fn read(
+    conn: &PgConnection,
+    for_person_id: PersonId,
+    for_post_id: PostId,
+    for_comment_id: Option<CommentId>,
+  ) -> Result<Option<Self>, Error> {
+    use crate::schema::thread_mute::dsl::*;
+    let query = thread_mute
+      .filter(person_id.eq(for_person_id))
+      .filter(post_id.eq(for_post_id))
+      .into_boxed();
+    let query = match for_comment_id {
+      Some(for_comment_id) => query.filter(comment_id.eq(for_comment_id)),
+      None => query.filter(comment_id.is_null()),
+    };
+    query.first::<Self>(conn).optional()
+  }
+
+  /// Whether the person muted the post of the comment, the comment itself or one of its parents
+  pub fn is_muted(
+    conn: &PgConnection,
+    for_person_id: PersonId,
+    comment: &Comment,
+  ) -> Result<bool, Error> {
+    use crate::schema::thread_mute::dsl::*;
+    let mut comment_ids = comment.ancestor_comment_ids();
+    comment_ids.push(comment.id);
+    select(exists(
+      thread_mute
+        .filter(person_id.eq(for_person_id))
+        .filter(post_id.eq(comment.post_id))
+        .filter(comment_id.is_null().or(comment_id.eq_any(comment_ids))),
+    ))
+    .get_result(conn)
+  }
+
+  /// All muted threads of the person, with the path of the muted comment for comment threads
+  pub fn list_for_person(
+    conn: &PgConnection,
+    for_person_id: PersonId,
+  ) -> Result<Vec<(PostId, Option<Ltree>)>, Error> {
+    use crate::schema::{comment, thread_mute};
+    thread_mute::table
+      .left_join(comment::table)
+      .filter(thread_mute::person_id.eq(for_person_id))
+      .select((thread_mute::post_id, comment::path.nullable()))
+      .load::<(PostId, Option<Ltree>)>(conn)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    source::{
+      comment::*,
+      community::{Community, CommunityForm},
+      person::{Person, PersonForm},
+      post::*,
+      thread_subscription::*,
+    },
+    traits::Crud,
+    utils::establish_unpooled_connection,
+  };
+  use serial_test::serial;
+
+  #[test]
+  #[serial]
+  fn test_subscribe_and_mute() {
+    let conn = establish_unpooled_connection();
+
+    let mut persons = vec![];
+    for name in ["thread_alice", "thread_bob"] {
+      let new_person = PersonForm {
+        name: name.into(),
+        public_key: Some("pubkey".to_string()),
+        ..PersonForm::default()
+      };
+      persons.push(Person::create(&conn, &new_person).unwrap());
+    }
+    let (alice, bob) = (&persons[0], &persons[1]);
+
+    let new_community = CommunityForm {
+      name: "thread_community".to_string(),
+      title: "nada".to_owned(),
+      public_key: Some("pubkey".to_string()),
+      ..CommunityForm::default()
+    };
+    let inserted_community = Community::create(&conn, &new_community).unwrap();
+
+    let new_post = PostForm {
+      name: "A test post".into(),
+      creator_id: alice.id,
+      community_id: inserted_community.id,
+      ..PostForm::default()
+    };
+    let inserted_post = Post::create(&conn, &new_post).unwrap();
+
+    let comment_form = CommentForm {
+      content: "A test comment".into(),
+      creator_id: alice.id,
+      post_id: inserted_post.id,
+      ..CommentForm::default()
+    };
+    let parent = Comment::create(&conn, &comment_form, None).unwrap();
+    let child = Comment::create(&conn, &comment_form, Some(&parent.path)).unwrap();
+    let other = Comment::create(&conn, &comment_form, None).unwrap();
+    assert_eq!(vec![parent.id], child.ancestor_comment_ids());
+
+    // Alice follows the whole post, Bob only the first comment thread
+    let alice_form = ThreadSubscriptionForm {
+      person_id: alice.id,
+      post_id: inserted_post.id,
+      comment_id: None,
+    };
+    let alice_subscription = ThreadSubscription::subscribe(&conn, &alice_form).unwrap();
+    let bob_form = ThreadSubscriptionForm {
+      person_id: bob.id,
+      post_id: inserted_post.id,
+      comment_id: Some(parent.id),
+    };
+    ThreadSubscription::subscribe(&conn, &bob_form).unwrap();
+
+    // Subscribing twice keeps the existing subscription
+    assert_eq!(
+      alice_subscription.id,
+      ThreadSubscription::subscribe(&conn, &alice_form)
+        .unwrap()
+        .id
+    );
+
+    let mut subscribers = ThreadSubscription::list_subscribers(&conn, &child).unwrap();
+    subscribers.sort_by_key(|p| p.0);
+    assert_eq!(vec![alice.id, bob.id], subscribers);
+    assert_eq!(
+      vec![alice.id],
+      ThreadSubscription::list_subscribers(&conn, &other).unwrap()
+    );
+
+    // Muting replaces the subscription
+    let mute_form = ThreadMuteForm {
+      person_id: bob.id,
+      post_id: inserted_post.id,
+      comment_id: Some(parent.id),
+    };
+    ThreadMute::mute(&conn, &mute_form).unwrap();
+    assert_eq!(
+      vec![alice.id],
+      ThreadSubscription::list_subscribers(&conn, &child).unwrap()
+    );
+    assert!(ThreadMute::is_muted(&conn, bob.id, &parent).unwrap());
+    assert!(ThreadMute::is_muted(&conn, bob.id, &child).unwrap());
+    assert!(!ThreadMute::is_muted(&conn, bob.id, &other).unwrap());
+    assert!(!ThreadMute::is_muted(&conn, alice.id, &child).unwrap());
+    assert_eq!(
+      vec![(inserted_post.id, Some(parent.path.to_owned()))],
+      ThreadMute::list_for_person(&conn, bob.id).unwrap()
+    );
+
+    assert_eq!(
+      1,
+      ThreadMute::unmute(&conn, bob.id, inserted_post.id, Some(parent.id)).unwrap()
+    );
+    assert_eq!(
+      1,
+      ThreadSubscription::unsubscribe(&conn, alice.id, inserted_post.id, None).unwrap()
+    );
+    assert!(ThreadSubscription::list_subscribers(&conn, &child)
+      .unwrap()
+      .is_empty());
+
+    Community::delete(&conn, inserted_community.id).unwrap();
+    Person::delete(&conn, alice.id).unwrap();
+    Person::delete(&conn, bob.id).unwrap();
+  }
+}
@@ -0,0 +1,282 @@
+use crate::{
+  newtypes::{CommunityId, DbUrl, PersonId, WikiPageId, WikiPageRevisionId},
+  source::wiki_page::*,
+  traits::Crud,
+  utils::naive_now,
+};
+use diesel::{dsl::*, result::Error, *};
+use url::Url;
+
+impl Crud for WikiPage {
+  type Form = WikiPageForm;
+  type IdType = WikiPageId;
+  fn create(conn: &PgConnection, form: &Self::Form) -> Result<Self, Error> {
+    use crate::schema::wiki_page::dsl::*;
+    insert_into(wiki_page).values(form).get_result::<Self>(conn)
+  }
+
+  fn read(conn: &PgConnection, id_: Self::IdType) -> Result<Self, Error> {
+    use crate::schema::wiki_page::dsl::*;
+    wiki_page.find(id_).first::<Self>(conn)
+  }
+
+  fn update(conn: &PgConnection, id_: Self::IdType, form: &Self::Form) -> Result<Self, Error> {
+    use crate::schema::wiki_page::dsl::*;
+    diesel::update(wiki_page.find(id_))
+      .set(form)
+      .get_result::<Self>(conn)
+  }
+
+  fn delete(conn: &PgConnection, id_: Self::IdType) -> Result<usize, Error> {
+    use crate::schema::wiki_page::dsl::*;
+    diesel::delete(wiki_page.find(id_)).execute(conn)
+  }
+}
+
+impl WikiPage {
+  /// Creates a local page, together with its first revision
+  pub fn create_with_revision(
+    conn: &PgConnection,
+    form: &WikiPageForm,
+    for_editor_id: PersonId,
+    for_summary: Option<String>,
+  ) -> Result<Self, Error> {
+    conn.transaction(|| {
+      let page = Self::create(conn, form)?;
+      let revision_form = WikiPageRevisionForm {
+        wiki_page_id: page.id,
+        editor_id: for_editor_id,
+        title: page.title.clone(),
+        body: page.body.clone(),
+        summary: for_summary,
+      };
+      WikiPageRevision::create(conn, &revision_form)?;
+      Ok(page)
+    })
+  }
+
+  /// Stores a new revision, and makes it the current content of the page
+  pub fn edit(conn: &PgConnection, revision_form: &WikiPageRevisionForm) -> Result<Self, Error> {
+    use crate::schema::wiki_page::dsl::*;
+    conn.transaction(|| {
+      WikiPageRevision::create(conn, revision_form)?;
+      diesel::update(wiki_page.find(revision_form.wiki_page_id))
+        .set((
+          title.eq(&revision_form.title),
+          body.eq(&revision_form.body),
+          updated.eq(naive_now()),
+        ))
+        .get_result::<Self>(conn)
+    })
+  }
+
+  pub fn update_deleted(
+    conn: &PgConnection,
+    page_id: WikiPageId,
+    new_deleted: bool,
+  ) -> Result<Self, Error> {
+    use crate::schema::wiki_page::dsl::*;
+    diesel::update(wiki_page.find(page_id))
+      .set((deleted.eq(new_deleted), updated.eq(naive_now())))
+      .get_result::<Self>(conn)
+  }
+
+  pub fn read_by_name(
+    conn: &PgConnection,
+    for_community_id: CommunityId,
+    page_name: &str,
+  ) -> Result<Self, Error> {
+    use crate::schema::wiki_page::dsl::*;
+    wiki_page
+      .filter(community_id.eq(for_community_id))
+      .filter(name.eq(page_name))
+      .first::<Self>(conn)
+  }
+
+  pub fn list_for_community(
+    conn: &PgConnection,
+    for_community_id: CommunityId,
+  ) -> Result<Vec<Self>, Error> {
+    use crate::schema::wiki_page::dsl::*;
+    wiki_page
+      .filter(community_id.eq(for_community_id))
+      .filter(deleted.eq(false))
+      .order_by(title)
+      .load::<Self>(conn)
+  }
+
+  pub fn upsert(conn: &PgConnection, form: &WikiPageForm) -> Result<Self, Error> {
+    use crate::schema::wiki_page::dsl::*;
+    insert_into(wiki_page)
+      .values(form)
+      .on_conflict(ap_id)
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+  }
+
+  pub fn read_from_apub_id(conn: &PgConnection, object_id: Url) -> Result<Option<Self>, Error> {
+    use crate::schema::wiki_page::dsl::*;
+    let object_id: DbUrl = object_id.into();
+    wiki_page
+      .filter(ap_id.eq(object_id))
+      .first::<Self>(conn)
+      .optional()
+  }
+}
+
+impl WikiPageRevision {
+  pub fn create(conn: &PgConnection, form: &WikiPageRevisionForm) -> Result<Self, Error> {
+    use crate::schema::wiki_page_revision::dsl::*;
+    insert_into(wiki_page_revision)
+      .values(form)
+      .get_result::<Self>(conn)
+  }
+
+  pub fn read(conn: &PgConnection, revision_id: WikiPageRevisionId) -> Result<Self, Error> {
+    use crate::schema::wiki_page_revision::dsl::*;
+    wiki_page_revision.find(revision_id).first::<Self>(conn)
+  }
+
+  /// The revision which came right before this one, if any
+  pub fn previous(&self, conn: &PgConnection) -> Result<Option<Self>, Error> {
+    use crate::schema::wiki_page_revision::dsl::*;
+    wiki_page_revision
+      .filter(wiki_page_id.eq(self.wiki_page_id))
+      .filter(id.lt(self.id))
+      .order_by(id.desc())
+      .first::<Self>(conn)
+      .optional()
+  }
+}
+
+impl WikiContributor {
+  pub fn add(conn: &PgConnection, form: &WikiContributorForm) -> Result<Self, Error> {
+    use crate::schema::wiki_contributor::dsl::*;
+    insert_into(wiki_contributor)
+      .values(form)
+      .on_conflict((community_id, person_id))
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+  }
+
+  pub fn remove(conn: &PgConnection, form: &WikiContributorForm) -> Result<usize, Error> {
+    use crate::schema::wiki_contributor::dsl::*;
+    diesel::delete(
+      wiki_contributor
+        .filter(community_id.eq(form.community_id))
+        .filter(person_id.eq(form.person_id)),
+    )
+    .execute(conn)
+  }
+
+  pub fn is_contributor(
+    conn: &PgConnection,
+    for_community_id: CommunityId,
+    for_person_id: PersonId,
+  ) -> Result<bool, Error> {
+    use crate::schema::wiki_contributor::dsl::*;
+    select(exists(
+      wiki_contributor
+        .filter(community_id.eq(for_community_id))
+        .filter(person_id.eq(for_person_id)),
+    ))
+    .get_result(conn)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    source::{
+      community::{Community, CommunityForm},
+      person::{Person, PersonForm},
+      wiki_page::*,
+    },
+    traits::Crud,
+    utils::establish_unpooled_connection,
+  };
+  use serial_test::serial;
+  use url::Url;
+
+  #[test]
+  #[serial]
+  fn test_crud() {
+    let conn = establish_unpooled_connection();
+
+    let new_person = PersonForm {
+      name: "wiki_editor".into(),
+      public_key: Some("pubkey".to_string()),
+      ..PersonForm::default()
+    };
+    let person = Person::create(&conn, &new_person).unwrap();
+
+    let new_community = CommunityForm {
+      name: "wiki_community".to_string(),
+      title: "nada".to_owned(),
+      public_key: Some("pubkey".to_string()),
+      ..CommunityForm::default()
+    };
+    let community = Community::create(&conn, &new_community).unwrap();
+
+    let page_form = WikiPageForm {
+      community_id: community.id,
+      name: "rules".to_string(),
+      title: "Rules".to_string(),
+      body: "Be nice".to_string(),
+      ap_id: Url::parse("https://example.com/c/wiki_community/wiki/rules")
+        .unwrap()
+        .into(),
+      local: None,
+      deleted: None,
+      published: None,
+      updated: None,
+    };
+    let page = WikiPage::create_with_revision(&conn, &page_form, person.id, None).unwrap();
+
+    let revision_form = WikiPageRevisionForm {
+      wiki_page_id: page.id,
+      editor_id: person.id,
+      title: "Community rules".to_string(),
+      body: "Be very nice".to_string(),
+      summary: Some("clarify".to_string()),
+    };
+    let edited = WikiPage::edit(&conn, &revision_form).unwrap();
+    assert_eq!("Community rules", edited.title);
+    assert_eq!("Be very nice", edited.body);
+    assert!(edited.updated.is_some());
+
+    let read_page = WikiPage::read_by_name(&conn, community.id, "rules").unwrap();
+    let pages = WikiPage::list_for_community(&conn, community.id).unwrap();
+    assert_eq!(edited, read_page);
+    assert_eq!(vec![edited.clone()], pages);
+
+    let from_apub_id = WikiPage::read_from_apub_id(&conn, page.ap_id.clone().into()).unwrap();
+    assert_eq!(Some(edited.clone()), from_apub_id);
+
+    let deleted = WikiPage::update_deleted(&conn, page.id, true).unwrap();
+    assert!(deleted.deleted);
+    assert!(WikiPage::list_for_community(&conn, community.id)
+      .unwrap()
+      .is_empty());
+
+    let contributor_form = WikiContributorForm {
+      community_id: community.id,
+      person_id: person.id,
+    };
+    assert!(!WikiContributor::is_contributor(&conn, community.id, person.id).unwrap());
+    WikiContributor::add(&conn, &contributor_form).unwrap();
+    WikiContributor::add(&conn, &contributor_form).unwrap();
+    assert!(WikiContributor::is_contributor(&conn, community.id, person.id).unwrap());
+    assert_eq!(
+      1,
+      WikiContributor::remove(&conn, &contributor_form).unwrap()
+    );
+
+    let num_deleted = WikiPage::delete(&conn, page.id).unwrap();
+    Community::delete(&conn, community.id).unwrap();
+    Person::delete(&conn, person.id).unwrap();
+    assert_eq!(1, num_deleted);
+  }
+}
@@ -53,6 +53,8 @@ pub enum ListingType {
   All,
   Local,
   Subscribed,
+  /// Content from persons which the user follows
+  FollowedUsers,
 }
 
 #[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy)]
@@ -26,6 +26,64 @@ table! {
         ap_id -> Varchar,
         local -> Bool,
         path -> Ltree,
+        pending -> Bool,
+    }
+}
+
+table! {
+    multi_community (id) {
+        id -> Int4,
+        creator_id -> Int4,
+        name -> Varchar,
+        description -> Nullable<Text>,
+        public -> Bool,
+        published -> Timestamp,
+        updated -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    multi_community_community (id) {
+        id -> Int4,
+        multi_community_id -> Int4,
+        community_id -> Int4,
+        published -> Timestamp,
+    }
+}
+
+table! {
+    wiki_page (id) {
+        id -> Int4,
+        community_id -> Int4,
+        name -> Varchar,
+        title -> Varchar,
+        body -> Text,
+        ap_id -> Varchar,
+        local -> Bool,
+        deleted -> Bool,
+        published -> Timestamp,
+        updated -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    wiki_page_revision (id) {
+        id -> Int4,
+        wiki_page_id -> Int4,
+        editor_id -> Int4,
+        title -> Varchar,
+        body -> Text,
+        summary -> Nullable<Text>,
+        published -> Timestamp,
+    }
+}
+
+table! {
+    wiki_contributor (id) {
+        id -> Int4,
+        community_id -> Int4,
+        person_id -> Int4,
+        published -> Timestamp,
     }
 }
 
@@ -98,6 +156,9 @@ table! {
         shared_inbox_url -> Nullable<Varchar>,
         hidden -> Bool,
         posting_restricted_to_mods -> Bool,
+        require_approval -> Bool,
+        instance_id -> Nullable<Int4>,
+        visibility -> Text,
     }
 }
 
@@ -165,6 +226,8 @@ table! {
         show_new_post_notifs -> Bool,
         email_verified -> Bool,
         accepted_application -> Bool,
+        email_digest_frequency -> Text,
+        last_email_digest -> Timestamp,
     }
 }
 
@@ -200,6 +263,28 @@ table! {
     }
 }
 
+table! {
+    mod_approve_comment (id) {
+        id -> Int4,
+        mod_person_id -> Int4,
+        comment_id -> Int4,
+        reason -> Nullable<Text>,
+        approved -> Nullable<Bool>,
+        when_ -> Timestamp,
+    }
+}
+
+table! {
+    mod_approve_post (id) {
+        id -> Int4,
+        mod_person_id -> Int4,
+        post_id -> Int4,
+        reason -> Nullable<Text>,
+        approved -> Nullable<Bool>,
+        when_ -> Timestamp,
+    }
+}
+
 table! {
     mod_ban (id) {
         id -> Int4,
@@ -311,6 +396,7 @@ table! {
         admin -> Bool,
         bot_account -> Bool,
         ban_expires -> Nullable<Timestamp>,
+        instance_id -> Nullable<Int4>,
     }
 }
 
@@ -374,6 +460,9 @@ table! {
         thumbnail_url -> Nullable<Text>,
         ap_id -> Varchar,
         local -> Bool,
+        pending -> Bool,
+        crosspost_of -> Nullable<Int4>,
+        url_canonical -> Nullable<Text>,
     }
 }
 
@@ -477,6 +566,8 @@ table! {
         default_theme -> Text,
         default_post_listing_type -> Text,
         legal_information -> Nullable<Text>,
+        instance_id -> Nullable<Int4>,
+        automod_person_id -> Nullable<Int4>,
     }
 }
 
@@ -570,6 +661,8 @@ table! {
   secret(id) {
     id -> Int4,
     jwt_secret -> Varchar,
+    vapid_private_key -> Nullable<Text>,
+    vapid_public_key -> Nullable<Text>,
   }
 }
 
@@ -643,6 +736,172 @@ table! {
     }
 }
 
+table! {
+    automod_rule (id) {
+        id -> Int4,
+        community_id -> Int4,
+        rule -> Jsonb,
+        published -> Timestamp,
+        updated -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    community_post_subscription (id) {
+        id -> Int4,
+        person_id -> Int4,
+        community_id -> Int4,
+        published -> Timestamp,
+    }
+}
+
+table! {
+    post_notification (id) {
+        id -> Int4,
+        recipient_id -> Int4,
+        post_id -> Int4,
+        read -> Bool,
+        published -> Timestamp,
+    }
+}
+
+table! {
+    person_follower (id) {
+        id -> Int4,
+        person_id -> Int4,
+        follower_id -> Int4,
+        published -> Timestamp,
+        pending -> Bool,
+    }
+}
+
+table! {
+    thread_subscription (id) {
+        id -> Int4,
+        person_id -> Int4,
+        post_id -> Int4,
+        comment_id -> Nullable<Int4>,
+        published -> Timestamp,
+    }
+}
+
+table! {
+    thread_mute (id) {
+        id -> Int4,
+        person_id -> Int4,
+        post_id -> Int4,
+        comment_id -> Nullable<Int4>,
+        published -> Timestamp,
+    }
+}
+
+table! {
+    push_subscription (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        endpoint -> Text,
+        p256dh -> Text,
+        auth -> Text,
+        notify_mentions -> Bool,
+        notify_replies -> Bool,
+        notify_private_messages -> Bool,
+        published -> Timestamp,
+        notify_new_posts -> Bool,
+    }
+}
+
+table! {
+    email_queue (id) {
+        id -> Int4,
+        to_email -> Text,
+        to_name -> Text,
+        subject -> Text,
+        html -> Text,
+        attempts -> Int4,
+        next_attempt -> Timestamp,
+        last_error -> Nullable<Text>,
+        failed -> Bool,
+        published -> Timestamp,
+        unsubscribe_url -> Nullable<Text>,
+    }
+}
+
+table! {
+    activity_delivery (id) {
+        id -> Int4,
+        activity_id -> Int4,
+        inbox -> Text,
+        actor_id -> Text,
+        attempts -> Int4,
+        next_attempt -> Timestamp,
+        last_error -> Nullable<Text>,
+        failed -> Bool,
+        published -> Timestamp,
+    }
+}
+
+table! {
+    instance (id) {
+        id -> Int4,
+        domain -> Text,
+        last_successful_delivery -> Nullable<Timestamp>,
+        failing_since -> Nullable<Timestamp>,
+        last_failed_delivery -> Nullable<Timestamp>,
+        published -> Timestamp,
+        software -> Nullable<Text>,
+        version -> Nullable<Text>,
+        last_seen -> Timestamp,
+    }
+}
+
+table! {
+    actor_key_rotation (id) {
+        id -> Int4,
+        actor_id -> Varchar,
+        previous_public_key -> Text,
+        rotated_at -> Timestamp,
+    }
+}
+
+table! {
+    relay (id) {
+        id -> Int4,
+        inbox_url -> Varchar,
+        actor_id -> Nullable<Varchar>,
+        public_key -> Nullable<Text>,
+        follow_id -> Varchar,
+        follow_object -> Varchar,
+        accepted -> Bool,
+        published -> Timestamp,
+        last_refreshed_at -> Timestamp,
+    }
+}
+
+table! {
+    slur_filter_entry (id) {
+        id -> Int4,
+        pattern -> Text,
+        action -> Text,
+        published -> Timestamp,
+        updated -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    local_user_keyword_filter (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        community_id -> Nullable<Int4>,
+        keyword -> Text,
+        regex -> Bool,
+        published -> Timestamp,
+    }
+}
+
+joinable!(activity_delivery -> activity (activity_id));
+joinable!(person -> instance (instance_id));
+joinable!(community -> instance (instance_id));
+joinable!(site -> instance (instance_id));
 joinable!(person_mention -> person_alias_1 (recipient_id));
 joinable!(comment_reply -> person_alias_1 (recipient_id));
 joinable!(post -> person_alias_1 (creator_id));
@@ -653,6 +912,16 @@ joinable!(comment_report -> person_alias_2 (resolver_id));
 
 joinable!(person_block -> person (person_id));
 joinable!(person_block -> person_alias_1 (target_id));
+joinable!(person_follower -> person (person_id));
+joinable!(person_follower -> person_alias_1 (follower_id));
+joinable!(multi_community -> person (creator_id));
+joinable!(multi_community_community -> multi_community (multi_community_id));
+joinable!(multi_community_community -> community (community_id));
+joinable!(wiki_page -> community (community_id));
+joinable!(wiki_page_revision -> wiki_page (wiki_page_id));
+joinable!(wiki_page_revision -> person (editor_id));
+joinable!(wiki_contributor -> community (community_id));
+joinable!(wiki_contributor -> person (person_id));
 
 joinable!(comment -> person (creator_id));
 joinable!(comment -> post (post_id));
@@ -676,6 +945,10 @@ joinable!(local_user -> person (person_id));
 joinable!(mod_add_community -> community (community_id));
 joinable!(mod_transfer_community -> community (community_id));
 joinable!(mod_ban_from_community -> community (community_id));
+joinable!(mod_approve_comment -> comment (comment_id));
+joinable!(mod_approve_comment -> person (mod_person_id));
+joinable!(mod_approve_post -> person (mod_person_id));
+joinable!(mod_approve_post -> post (post_id));
 joinable!(mod_lock_post -> person (mod_person_id));
 joinable!(mod_lock_post -> post (post_id));
 joinable!(mod_remove_comment -> comment (comment_id));
@@ -716,9 +989,27 @@ joinable!(admin_purge_community -> person (admin_person_id));
 joinable!(admin_purge_person -> person (admin_person_id));
 joinable!(admin_purge_post -> community (community_id));
 joinable!(admin_purge_post -> person (admin_person_id));
+joinable!(automod_rule -> community (community_id));
+joinable!(local_user_keyword_filter -> community (community_id));
+joinable!(local_user_keyword_filter -> local_user (local_user_id));
+joinable!(push_subscription -> local_user (local_user_id));
+joinable!(community_post_subscription -> person (person_id));
+joinable!(community_post_subscription -> community (community_id));
+joinable!(post_notification -> person (recipient_id));
+joinable!(post_notification -> post (post_id));
+joinable!(thread_subscription -> person (person_id));
+joinable!(thread_subscription -> post (post_id));
+joinable!(thread_subscription -> comment (comment_id));
+joinable!(thread_mute -> person (person_id));
+joinable!(thread_mute -> post (post_id));
+joinable!(thread_mute -> comment (comment_id));
 
 allow_tables_to_appear_in_same_query!(
   activity,
+  activity_delivery,
+  instance,
+  relay,
+  actor_key_rotation,
   comment,
   comment_aggregates,
   community_block,
@@ -765,5 +1056,22 @@ allow_tables_to_appear_in_same_query!(
   admin_purge_person,
   admin_purge_post,
   email_verification,
-  registration_application
+  registration_application,
+  automod_rule,
+  mod_approve_post,
+  mod_approve_comment,
+  local_user_keyword_filter,
+  slur_filter_entry,
+  email_queue,
+  push_subscription,
+  thread_subscription,
+  thread_mute,
+  community_post_subscription,
+  post_notification,
+  person_follower,
+  multi_community,
+  multi_community_community,
+  wiki_page,
+  wiki_page_revision,
+  wiki_contributor
 );
@@ -0,0 +1,31 @@
+use crate::newtypes::{ActivityDeliveryId, DbUrl};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "full")]
+use crate::schema::activity_delivery;
+
+/// An outgoing activity which still needs to be sent to the given inbox.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "activity_delivery")]
+pub struct ActivityDelivery {
+  pub id: ActivityDeliveryId,
+  pub activity_id: i32,
+  pub inbox: DbUrl,
+  /// The actor whose key signs the request
+  pub actor_id: DbUrl,
+  pub attempts: i32,
+  pub next_attempt: chrono::NaiveDateTime,
+  pub last_error: Option<String>,
+  /// Set once sending was given up on
+  pub failed: bool,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "activity_delivery")]
+pub struct ActivityDeliveryForm {
+  pub activity_id: i32,
+  pub inbox: DbUrl,
+  pub actor_id: DbUrl,
+}
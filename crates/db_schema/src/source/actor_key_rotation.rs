@@ -0,0 +1,25 @@
+use crate::newtypes::DbUrl;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "full")]
+use crate::schema::actor_key_rotation;
+
+/// The previous public key of a remote actor, which is still accepted for signatures for a short
+/// time after the key was rotated.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "actor_key_rotation")]
+pub struct ActorKeyRotation {
+  pub id: i32,
+  pub actor_id: DbUrl,
+  pub previous_public_key: String,
+  pub rotated_at: chrono::NaiveDateTime,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "actor_key_rotation")]
+pub struct ActorKeyRotationForm {
+  pub actor_id: DbUrl,
+  pub previous_public_key: String,
+  pub rotated_at: chrono::NaiveDateTime,
+}
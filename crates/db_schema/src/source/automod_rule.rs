@@ -0,0 +1,68 @@
+use crate::newtypes::{AutomodRuleId, CommunityId};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "full")]
+use crate::schema::automod_rule;
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "automod_rule")]
+pub struct AutomodRule {
+  pub id: AutomodRuleId,
+  pub community_id: CommunityId,
+  pub rule: AutomodRuleType,
+  pub published: chrono::NaiveDateTime,
+  pub updated: Option<chrono::NaiveDateTime>,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "automod_rule")]
+pub struct AutomodRuleForm {
+  pub community_id: CommunityId,
+  pub rule: AutomodRuleType,
+  pub updated: Option<chrono::NaiveDateTime>,
+}
+
+/// The different kinds of automod rules. These are stored as json in the `rule` column, tagged
+/// by `type`.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(AsExpression, FromSqlRow))]
+#[cfg_attr(feature = "full", sql_type = "diesel::sql_types::Jsonb")]
+#[serde(tag = "type")]
+pub enum AutomodRuleType {
+  /// Posts and comments by accounts younger than this many days
+  MinAccountAge { days: i64, action: AutomodAction },
+  /// Posts and comments matching any of these (case insensitive) regexes
+  ContentRegex {
+    patterns: Vec<String>,
+    action: AutomodAction,
+  },
+  /// Posts whose title doesn't start with one of these flairs, eg `[Question]`
+  FlairRequired {
+    flairs: Vec<String>,
+    action: AutomodAction,
+  },
+  /// Posts and comments containing any of these words (case insensitive, whole words only)
+  BannedWords {
+    words: Vec<String>,
+    action: AutomodAction,
+  },
+  /// Posts linking to any of these domains or their subdomains
+  BannedDomains {
+    domains: Vec<String>,
+    action: AutomodAction,
+  },
+  /// Lock posts once they are older than this many days
+  AutoLock { days: i64 },
+}
+
+/// What happens to a post or comment which matches an automod rule
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum AutomodAction {
+  /// Refuse to create it, returning an error
+  Reject,
+  /// Create it, but remove it right away and write a modlog entry
+  Remove,
+  /// Create it, but hold it for approval by a mod
+  Hold,
+}
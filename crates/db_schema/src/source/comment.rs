@@ -22,6 +22,8 @@ pub struct Comment {
   pub local: bool,
   #[serde(with = "LtreeDef")]
   pub path: Ltree,
+  /// Held for approval by a mod
+  pub pending: bool,
 }
 
 #[derive(Clone, Default)]
@@ -37,6 +39,7 @@ pub struct CommentForm {
   pub deleted: Option<bool>,
   pub ap_id: Option<DbUrl>,
   pub local: Option<bool>,
+  pub pending: Option<bool>,
 }
 
 #[derive(PartialEq, Debug, Clone)]
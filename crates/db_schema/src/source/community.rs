@@ -1,4 +1,4 @@
-use crate::newtypes::{CommunityId, DbUrl, PersonId};
+use crate::newtypes::{CommunityId, DbUrl, InstanceId, PersonId};
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "full")]
@@ -29,6 +29,9 @@ pub struct Community {
   pub shared_inbox_url: Option<DbUrl>,
   pub hidden: bool,
   pub posting_restricted_to_mods: bool,
+  pub require_approval: bool,
+  pub instance_id: Option<InstanceId>,
+  pub visibility: CommunityVisibility,
 }
 
 /// A safe representation of community, without the sensitive info
@@ -51,6 +54,8 @@ pub struct CommunitySafe {
   pub banner: Option<DbUrl>,
   pub hidden: bool,
   pub posting_restricted_to_mods: bool,
+  pub require_approval: bool,
+  pub visibility: CommunityVisibility,
 }
 
 #[derive(Debug, Default)]
@@ -77,6 +82,27 @@ pub struct CommunityForm {
   pub shared_inbox_url: Option<Option<DbUrl>>,
   pub hidden: Option<bool>,
   pub posting_restricted_to_mods: Option<bool>,
+  pub require_approval: Option<bool>,
+  pub instance_id: Option<Option<InstanceId>>,
+  pub visibility: Option<CommunityVisibility>,
+}
+
+/// Who can follow a community and see its posts
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(AsExpression, FromSqlRow))]
+#[cfg_attr(feature = "full", sql_type = "diesel::sql_types::Text")]
+pub enum CommunityVisibility {
+  Public,
+  /// Follows need to be approved by a mod, and only approved followers can see posts
+  Private,
+  /// Doesn't federate, and posts are only visible to logged in users
+  LocalOnly,
+}
+
+impl Default for CommunityVisibility {
+  fn default() -> Self {
+    CommunityVisibility::Public
+  }
 }
 
 #[derive(PartialEq, Debug)]
@@ -0,0 +1,50 @@
+use crate::newtypes::EmailQueueId;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "full")]
+use crate::schema::email_queue;
+
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "email_queue")]
+pub struct EmailQueue {
+  pub id: EmailQueueId,
+  pub to_email: String,
+  pub to_name: String,
+  pub subject: String,
+  pub html: String,
+  pub attempts: i32,
+  pub next_attempt: chrono::NaiveDateTime,
+  pub last_error: Option<String>,
+  /// Set once sending was given up on
+  pub failed: bool,
+  pub published: chrono::NaiveDateTime,
+  pub unsubscribe_url: Option<String>,
+}
+
+/// A queued email without the body and unsubscribe link, which may contain secrets like password
+/// reset links.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "email_queue")]
+pub struct EmailQueueSafe {
+  pub id: EmailQueueId,
+  pub to_email: String,
+  pub to_name: String,
+  pub subject: String,
+  pub attempts: i32,
+  pub next_attempt: chrono::NaiveDateTime,
+  pub last_error: Option<String>,
+  pub failed: bool,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "email_queue")]
+pub struct EmailQueueForm {
+  pub to_email: String,
+  pub to_name: String,
+  pub subject: String,
+  pub html: String,
+  pub unsubscribe_url: Option<String>,
+}
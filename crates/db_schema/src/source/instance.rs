@@ -0,0 +1,30 @@
+use crate::newtypes::InstanceId;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "full")]
+use crate::schema::instance;
+
+/// A known remote instance, with its software and the results of delivering activities to it.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "instance")]
+pub struct Instance {
+  pub id: InstanceId,
+  pub domain: String,
+  pub last_successful_delivery: Option<chrono::NaiveDateTime>,
+  /// Start of the current streak of failed deliveries
+  pub failing_since: Option<chrono::NaiveDateTime>,
+  pub last_failed_delivery: Option<chrono::NaiveDateTime>,
+  /// When the instance was first seen
+  pub published: chrono::NaiveDateTime,
+  /// Software name from nodeinfo, eg `lemmy` or `mastodon`
+  pub software: Option<String>,
+  pub version: Option<String>,
+  pub last_seen: chrono::NaiveDateTime,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "instance")]
+pub struct InstanceForm {
+  pub domain: String,
+}
@@ -26,6 +26,23 @@ pub struct LocalUser {
   pub show_new_post_notifs: bool,
   pub email_verified: bool,
   pub accepted_application: bool,
+  pub email_digest_frequency: EmailDigestFrequency,
+  /// Notifications published after this time are included in the next email digest
+  pub last_email_digest: chrono::NaiveDateTime,
+}
+
+/// How notification emails (mentions, replies, private messages) are sent to a user
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(AsExpression, FromSqlRow))]
+#[cfg_attr(feature = "full", sql_type = "diesel::sql_types::Text")]
+pub enum EmailDigestFrequency {
+  /// One email per notification
+  Immediate,
+  /// All notifications of the past hour in a single email
+  Hourly,
+  /// All notifications of the past day in a single email
+  Daily,
+  Off,
 }
 
 // TODO redo these, check table defaults
@@ -49,6 +66,8 @@ pub struct LocalUserForm {
   pub show_new_post_notifs: Option<bool>,
   pub email_verified: Option<bool>,
   pub accepted_application: Option<bool>,
+  pub email_digest_frequency: Option<EmailDigestFrequency>,
+  pub last_email_digest: Option<chrono::NaiveDateTime>,
 }
 
 /// A local user view that removes password encrypted
@@ -73,4 +92,5 @@ pub struct LocalUserSettings {
   pub show_new_post_notifs: bool,
   pub email_verified: bool,
   pub accepted_application: bool,
+  pub email_digest_frequency: EmailDigestFrequency,
 }
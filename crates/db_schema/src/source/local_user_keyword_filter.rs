@@ -0,0 +1,30 @@
+use crate::newtypes::{CommunityId, LocalUserId, LocalUserKeywordFilterId};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "full")]
+use crate::schema::local_user_keyword_filter;
+
+/// A keyword or regex which hides matching posts and comments from a local user.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "local_user_keyword_filter")]
+pub struct LocalUserKeywordFilter {
+  pub id: LocalUserKeywordFilterId,
+  pub local_user_id: LocalUserId,
+  /// If set, the filter only applies in this community
+  pub community_id: Option<CommunityId>,
+  pub keyword: String,
+  /// Treat the keyword as a regex instead of plain text
+  pub regex: bool,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "local_user_keyword_filter")]
+pub struct LocalUserKeywordFilterForm {
+  pub local_user_id: LocalUserId,
+  pub community_id: Option<CommunityId>,
+  pub keyword: String,
+  pub regex: bool,
+}
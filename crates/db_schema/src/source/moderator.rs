@@ -9,6 +9,8 @@ use crate::schema::{
   admin_purge_post,
   mod_add,
   mod_add_community,
+  mod_approve_comment,
+  mod_approve_post,
   mod_ban,
   mod_ban_from_community,
   mod_hide_community,
@@ -100,6 +102,48 @@ pub struct ModRemoveCommentForm {
   pub removed: Option<bool>,
 }
 
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "mod_approve_post")]
+pub struct ModApprovePost {
+  pub id: i32,
+  pub mod_person_id: PersonId,
+  pub post_id: PostId,
+  pub reason: Option<String>,
+  pub approved: Option<bool>,
+  pub when_: chrono::NaiveDateTime,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "mod_approve_post")]
+pub struct ModApprovePostForm {
+  pub mod_person_id: PersonId,
+  pub post_id: PostId,
+  pub reason: Option<String>,
+  pub approved: Option<bool>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "mod_approve_comment")]
+pub struct ModApproveComment {
+  pub id: i32,
+  pub mod_person_id: PersonId,
+  pub comment_id: CommentId,
+  pub reason: Option<String>,
+  pub approved: Option<bool>,
+  pub when_: chrono::NaiveDateTime,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "mod_approve_comment")]
+pub struct ModApproveCommentForm {
+  pub mod_person_id: PersonId,
+  pub comment_id: CommentId,
+  pub reason: Option<String>,
+  pub approved: Option<bool>,
+}
+
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
 #[cfg_attr(feature = "full", table_name = "mod_remove_community")]
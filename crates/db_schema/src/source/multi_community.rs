@@ -0,0 +1,49 @@
+use crate::newtypes::{CommunityId, MultiCommunityId, PersonId};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "full")]
+use crate::schema::{multi_community, multi_community_community};
+
+/// A named feed which combines the posts of several communities
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "multi_community")]
+pub struct MultiCommunity {
+  pub id: MultiCommunityId,
+  pub creator_id: PersonId,
+  pub name: String,
+  pub description: Option<String>,
+  /// Public feeds can be viewed by anyone who has the id
+  pub public: bool,
+  pub published: chrono::NaiveDateTime,
+  pub updated: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "multi_community")]
+pub struct MultiCommunityForm {
+  pub creator_id: PersonId,
+  pub name: String,
+  pub description: Option<Option<String>>,
+  pub public: Option<bool>,
+  pub updated: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "multi_community_community")]
+pub struct MultiCommunityCommunity {
+  pub id: i32,
+  pub multi_community_id: MultiCommunityId,
+  pub community_id: CommunityId,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "multi_community_community")]
+pub struct MultiCommunityCommunityForm {
+  pub multi_community_id: MultiCommunityId,
+  pub community_id: CommunityId,
+}
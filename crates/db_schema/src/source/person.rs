@@ -1,4 +1,4 @@
-use crate::newtypes::{DbUrl, PersonId};
+use crate::newtypes::{DbUrl, InstanceId, PersonId};
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "full")]
@@ -29,6 +29,7 @@ pub struct Person {
   pub admin: bool,
   pub bot_account: bool,
   pub ban_expires: Option<chrono::NaiveDateTime>,
+  pub instance_id: Option<InstanceId>,
 }
 
 /// A safe representation of person, without the sensitive info
@@ -182,4 +183,5 @@ pub struct PersonForm {
   pub admin: Option<bool>,
   pub bot_account: Option<bool>,
   pub ban_expires: Option<Option<chrono::NaiveDateTime>>,
+  pub instance_id: Option<Option<InstanceId>>,
 }
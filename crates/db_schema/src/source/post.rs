@@ -27,6 +27,12 @@ pub struct Post {
   pub thumbnail_url: Option<DbUrl>,
   pub ap_id: DbUrl,
   pub local: bool,
+  /// Held for approval by a mod
+  pub pending: bool,
+  /// The post which this one was crossposted from
+  pub crosspost_of: Option<PostId>,
+  /// Used to find other posts linking the same page, see `canonicalize_url`
+  pub url_canonical: Option<String>,
 }
 
 #[derive(Default)]
@@ -51,6 +57,9 @@ pub struct PostForm {
   pub thumbnail_url: Option<Option<DbUrl>>,
   pub ap_id: Option<DbUrl>,
   pub local: Option<bool>,
+  pub pending: Option<bool>,
+  pub crosspost_of: Option<PostId>,
+  pub url_canonical: Option<Option<String>>,
 }
 
 #[derive(PartialEq, Debug)]
@@ -0,0 +1,44 @@
+use crate::newtypes::{CommunityId, PersonId, PostId, PostNotificationId};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "full")]
+use crate::schema::{community_post_subscription, post_notification};
+
+/// The person gets a post notification for every new post in the community.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "community_post_subscription")]
+pub struct CommunityPostSubscription {
+  pub id: i32,
+  pub person_id: PersonId,
+  pub community_id: CommunityId,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "community_post_subscription")]
+pub struct CommunityPostSubscriptionForm {
+  pub person_id: PersonId,
+  pub community_id: CommunityId,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Associations, Identifiable))]
+#[cfg_attr(feature = "full", belongs_to(crate::source::post::Post))]
+#[cfg_attr(feature = "full", table_name = "post_notification")]
+/// This table keeps a list of new posts in communities which the recipient subscribed to.
+pub struct PostNotification {
+  pub id: PostNotificationId,
+  pub recipient_id: PersonId,
+  pub post_id: PostId,
+  pub read: bool,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "post_notification")]
+pub struct PostNotificationForm {
+  pub recipient_id: PersonId,
+  pub post_id: PostId,
+  pub read: Option<bool>,
+}
@@ -0,0 +1,36 @@
+use crate::newtypes::{LocalUserId, PushSubscriptionId};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "full")]
+use crate::schema::push_subscription;
+
+/// A Web Push subscription of a browser or app. The notify_* fields select which kinds of
+/// notifications are pushed to it.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "push_subscription")]
+pub struct PushSubscription {
+  pub id: PushSubscriptionId,
+  pub local_user_id: LocalUserId,
+  pub endpoint: String,
+  pub p256dh: String,
+  pub auth: String,
+  pub notify_mentions: bool,
+  pub notify_replies: bool,
+  pub notify_private_messages: bool,
+  pub published: chrono::NaiveDateTime,
+  pub notify_new_posts: bool,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "push_subscription")]
+pub struct PushSubscriptionForm {
+  pub local_user_id: LocalUserId,
+  pub endpoint: String,
+  pub p256dh: String,
+  pub auth: String,
+  pub notify_mentions: bool,
+  pub notify_replies: bool,
+  pub notify_private_messages: bool,
+  pub notify_new_posts: bool,
+}
@@ -0,0 +1,36 @@
+use crate::newtypes::{DbUrl, RelayId};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "full")]
+use crate::schema::relay;
+
+/// A relay which the instance actor follows, to receive public posts from other instances and
+/// spread local ones.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "relay")]
+pub struct Relay {
+  pub id: RelayId,
+  pub inbox_url: DbUrl,
+  /// Unknown for Mastodon style relays until they accept the follow
+  pub actor_id: Option<DbUrl>,
+  #[serde(skip)]
+  pub public_key: Option<String>,
+  /// Id of the follow activity which we sent to the relay
+  pub follow_id: DbUrl,
+  /// The relay actor, or the public collection for Mastodon style relays
+  pub follow_object: DbUrl,
+  pub accepted: bool,
+  pub published: chrono::NaiveDateTime,
+  pub last_refreshed_at: chrono::NaiveDateTime,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "relay")]
+pub struct RelayForm {
+  pub inbox_url: DbUrl,
+  pub actor_id: Option<DbUrl>,
+  pub public_key: Option<String>,
+  pub follow_id: DbUrl,
+  pub follow_object: DbUrl,
+}
@@ -7,4 +7,7 @@ use crate::schema::secret;
 pub struct Secret {
   pub id: i32,
   pub jwt_secret: String,
+  /// Keys for Web Push, PKCS#8 PEM and base64url encoded public point
+  pub vapid_private_key: Option<String>,
+  pub vapid_public_key: Option<String>,
 }
@@ -1,4 +1,4 @@
-use crate::newtypes::DbUrl;
+use crate::newtypes::{DbUrl, InstanceId, PersonId};
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "full")]
@@ -32,6 +32,9 @@ pub struct Site {
   pub default_theme: String,
   pub default_post_listing_type: String,
   pub legal_information: Option<String>,
+  pub instance_id: Option<InstanceId>,
+  /// Local bot account which automod actions are attributed to
+  pub automod_person_id: Option<PersonId>,
 }
 
 #[derive(Default)]
@@ -61,4 +64,5 @@ pub struct SiteForm {
   pub default_theme: Option<String>,
   pub default_post_listing_type: Option<String>,
   pub legal_information: Option<Option<String>>,
+  pub instance_id: Option<Option<InstanceId>>,
 }
@@ -0,0 +1,38 @@
+use crate::newtypes::SlurFilterEntryId;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "full")]
+use crate::schema::slur_filter_entry;
+
+/// A case insensitive regex which is checked against user content, managed by admins.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "slur_filter_entry")]
+pub struct SlurFilterEntry {
+  pub id: SlurFilterEntryId,
+  pub pattern: String,
+  pub action: SlurFilterAction,
+  pub published: chrono::NaiveDateTime,
+  pub updated: Option<chrono::NaiveDateTime>,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "slur_filter_entry")]
+pub struct SlurFilterEntryForm {
+  pub pattern: String,
+  pub action: SlurFilterAction,
+  pub updated: Option<chrono::NaiveDateTime>,
+}
+
+/// What happens to content which matches a slur filter entry
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(AsExpression, FromSqlRow))]
+#[cfg_attr(feature = "full", sql_type = "diesel::sql_types::Text")]
+pub enum SlurFilterAction {
+  /// Refuse the content, returning an error
+  Reject,
+  /// Replace the matching text with `*removed*`
+  Replace,
+  /// Hold the post or comment for approval by a mod. Other content is rejected.
+  Hold,
+}
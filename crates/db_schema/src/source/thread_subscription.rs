@@ -0,0 +1,48 @@
+use crate::newtypes::{CommentId, PersonId, PostId, ThreadMuteId, ThreadSubscriptionId};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "full")]
+use crate::schema::{thread_mute, thread_subscription};
+
+/// Notifies the person about all new comments in a post, or only those below the given comment.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "thread_subscription")]
+pub struct ThreadSubscription {
+  pub id: ThreadSubscriptionId,
+  pub person_id: PersonId,
+  pub post_id: PostId,
+  pub comment_id: Option<CommentId>,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "thread_subscription")]
+pub struct ThreadSubscriptionForm {
+  pub person_id: PersonId,
+  pub post_id: PostId,
+  pub comment_id: Option<CommentId>,
+}
+
+/// Suppresses all notifications from a post or from below a comment, including direct replies
+/// and mentions.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "thread_mute")]
+pub struct ThreadMute {
+  pub id: ThreadMuteId,
+  pub person_id: PersonId,
+  pub post_id: PostId,
+  pub comment_id: Option<CommentId>,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "thread_mute")]
+pub struct ThreadMuteForm {
+  pub person_id: PersonId,
+  pub post_id: PostId,
+  pub comment_id: Option<CommentId>,
+}
@@ -0,0 +1,82 @@
+use crate::newtypes::{CommunityId, DbUrl, PersonId, WikiPageId, WikiPageRevisionId};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "full")]
+use crate::schema::{wiki_contributor, wiki_page, wiki_page_revision};
+
+/// A markdown wiki page of a community, holding the content of its latest revision
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "wiki_page")]
+pub struct WikiPage {
+  pub id: WikiPageId,
+  pub community_id: CommunityId,
+  /// Used in the url of the page, can't be changed
+  pub name: String,
+  pub title: String,
+  pub body: String,
+  pub ap_id: DbUrl,
+  pub local: bool,
+  pub deleted: bool,
+  pub published: chrono::NaiveDateTime,
+  pub updated: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "wiki_page")]
+pub struct WikiPageForm {
+  pub community_id: CommunityId,
+  pub name: String,
+  pub title: String,
+  pub body: String,
+  pub ap_id: DbUrl,
+  pub local: Option<bool>,
+  pub deleted: Option<bool>,
+  pub published: Option<chrono::NaiveDateTime>,
+  pub updated: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "wiki_page_revision")]
+pub struct WikiPageRevision {
+  pub id: WikiPageRevisionId,
+  pub wiki_page_id: WikiPageId,
+  pub editor_id: PersonId,
+  pub title: String,
+  pub body: String,
+  /// Optional description of the edit
+  pub summary: Option<String>,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "wiki_page_revision")]
+pub struct WikiPageRevisionForm {
+  pub wiki_page_id: WikiPageId,
+  pub editor_id: PersonId,
+  pub title: String,
+  pub body: String,
+  pub summary: Option<String>,
+}
+
+/// A person who may edit the wiki of a community without being a mod
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", table_name = "wiki_contributor")]
+pub struct WikiContributor {
+  pub id: i32,
+  pub community_id: CommunityId,
+  pub person_id: PersonId,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", table_name = "wiki_contributor")]
+pub struct WikiContributorForm {
+  pub community_id: CommunityId,
+  pub person_id: PersonId,
+}
@@ -1,12 +1,23 @@
-use crate::{newtypes::DbUrl, CommentSortType, SortType};
+use crate::{
+  newtypes::DbUrl,
+  source::{
+    automod_rule::AutomodRuleType,
+    community::CommunityVisibility,
+    local_user::EmailDigestFrequency,
+    slur_filter_entry::SlurFilterAction,
+  },
+  CommentSortType,
+  SortType,
+};
 use activitypub_federation::{core::object_id::ObjectId, traits::ApubObject};
 use chrono::NaiveDateTime;
 use diesel::{
   backend::Backend,
   deserialize::FromSql,
+  pg::Pg,
   result::Error::QueryBuilderError,
   serialize::{Output, ToSql},
-  sql_types::Text,
+  sql_types::{Jsonb, Text},
   Connection,
   PgConnection,
 };
@@ -144,6 +155,24 @@ pub mod functions {
   }
 
   sql_function!(fn lower(x: Text) -> Text);
+
+  sql_function!(fn coalesce(x: Nullable<Text>, y: Text) -> Text);
+
+  sql_function! {
+    #[sql_name = "coalesce"]
+    fn coalesce_bool(x: Nullable<Bool>, y: Bool) -> Bool;
+  }
+
+  diesel_infix_operator!(RegexMatchInsensitive, " ~* ");
+
+  /// Case insensitive Postgres regex match, `left ~* right`
+  pub fn regex_match_insensitive<T, U>(left: T, right: U) -> RegexMatchInsensitive<T, U::Expression>
+  where
+    T: diesel::Expression<SqlType = Text>,
+    U: diesel::expression::AsExpression<Text>,
+  {
+    RegexMatchInsensitive::new(left, right.as_expression())
+  }
 }
 
 impl<DB: Backend> ToSql<Text, DB> for DbUrl
@@ -165,6 +194,88 @@ where
   }
 }
 
+impl ToSql<Jsonb, Pg> for AutomodRuleType {
+  fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> diesel::serialize::Result {
+    let value = serde_json::to_value(self)?;
+    <serde_json::Value as ToSql<Jsonb, Pg>>::to_sql(&value, out)
+  }
+}
+
+impl FromSql<Jsonb, Pg> for AutomodRuleType {
+  fn from_sql(bytes: Option<&[u8]>) -> diesel::deserialize::Result<Self> {
+    let value = <serde_json::Value as FromSql<Jsonb, Pg>>::from_sql(bytes)?;
+    Ok(serde_json::from_value(value)?)
+  }
+}
+
+impl ToSql<Text, Pg> for SlurFilterAction {
+  fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> diesel::serialize::Result {
+    let value = match self {
+      SlurFilterAction::Reject => "reject",
+      SlurFilterAction::Replace => "replace",
+      SlurFilterAction::Hold => "hold",
+    };
+    <str as ToSql<Text, Pg>>::to_sql(value, out)
+  }
+}
+
+impl FromSql<Text, Pg> for SlurFilterAction {
+  fn from_sql(bytes: Option<&[u8]>) -> diesel::deserialize::Result<Self> {
+    match <String as FromSql<Text, Pg>>::from_sql(bytes)?.as_str() {
+      "reject" => Ok(SlurFilterAction::Reject),
+      "replace" => Ok(SlurFilterAction::Replace),
+      "hold" => Ok(SlurFilterAction::Hold),
+      other => Err(format!("Unknown slur filter action: {}", other).into()),
+    }
+  }
+}
+
+impl ToSql<Text, Pg> for CommunityVisibility {
+  fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> diesel::serialize::Result {
+    let value = match self {
+      CommunityVisibility::Public => "public",
+      CommunityVisibility::Private => "private",
+      CommunityVisibility::LocalOnly => "local_only",
+    };
+    <str as ToSql<Text, Pg>>::to_sql(value, out)
+  }
+}
+
+impl FromSql<Text, Pg> for CommunityVisibility {
+  fn from_sql(bytes: Option<&[u8]>) -> diesel::deserialize::Result<Self> {
+    match <String as FromSql<Text, Pg>>::from_sql(bytes)?.as_str() {
+      "public" => Ok(CommunityVisibility::Public),
+      "private" => Ok(CommunityVisibility::Private),
+      "local_only" => Ok(CommunityVisibility::LocalOnly),
+      other => Err(format!("Unknown community visibility: {}", other).into()),
+    }
+  }
+}
+
+impl ToSql<Text, Pg> for EmailDigestFrequency {
+  fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> diesel::serialize::Result {
+    let value = match self {
+      EmailDigestFrequency::Immediate => "immediate",
+      EmailDigestFrequency::Hourly => "hourly",
+      EmailDigestFrequency::Daily => "daily",
+      EmailDigestFrequency::Off => "off",
+    };
+    <str as ToSql<Text, Pg>>::to_sql(value, out)
+  }
+}
+
+impl FromSql<Text, Pg> for EmailDigestFrequency {
+  fn from_sql(bytes: Option<&[u8]>) -> diesel::deserialize::Result<Self> {
+    match <String as FromSql<Text, Pg>>::from_sql(bytes)?.as_str() {
+      "immediate" => Ok(EmailDigestFrequency::Immediate),
+      "hourly" => Ok(EmailDigestFrequency::Hourly),
+      "daily" => Ok(EmailDigestFrequency::Daily),
+      "off" => Ok(EmailDigestFrequency::Off),
+      other => Err(format!("Unknown email digest frequency: {}", other).into()),
+    }
+  }
+}
+
 impl<Kind> From<ObjectId<Kind>> for DbUrl
 where
   Kind: ApubObject + Send + 'static,
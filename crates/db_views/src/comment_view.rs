@@ -12,20 +12,33 @@ use lemmy_db_schema::{
     community,
     community_block,
     community_follower,
+    community_moderator,
     community_person_ban,
     person,
     person_block,
+    person_follower,
     post,
   },
   source::{
     comment::{Comment, CommentSaved},
-    community::{Community, CommunityFollower, CommunityPersonBan, CommunitySafe},
+    community::{
+      Community,
+      CommunityFollower,
+      CommunityPersonBan,
+      CommunitySafe,
+      CommunityVisibility,
+    },
+    local_user_keyword_filter::LocalUserKeywordFilter,
     person::{Person, PersonSafe},
     person_block::PersonBlock,
     post::Post,
   },
   traits::{ToSafe, ViewToVec},
-  utils::{functions::hot_rank, fuzzy_search, limit_and_offset_unlimited},
+  utils::{
+    functions::{coalesce_bool, hot_rank, regex_match_insensitive},
+    fuzzy_search,
+    limit_and_offset_unlimited,
+  },
   CommentSortType,
   ListingType,
 };
@@ -163,6 +176,10 @@ pub struct CommentQuery<'a> {
   search_term: Option<String>,
   saved_only: Option<bool>,
   show_bot_accounts: Option<bool>,
+  /// Only list comments which are held for approval
+  pending_only: Option<bool>,
+  /// Admins can see all pending comments
+  admin: Option<bool>,
   page: Option<i64>,
   limit: Option<i64>,
   max_depth: Option<i32>,
@@ -276,6 +293,16 @@ impl<'a> CommentQuery<'a> {
               .or(community_follower::person_id.eq(person_id_join)),
           )
         }
+        ListingType::FollowedUsers => {
+          query = query.filter(
+            comment::creator_id.eq_any(
+              person_follower::table
+                .filter(person_follower::follower_id.eq(person_id_join))
+                .filter(person_follower::pending.eq(false))
+                .select(person_follower::person_id),
+            ),
+          )
+        }
       }
     };
 
@@ -301,6 +328,61 @@ impl<'a> CommentQuery<'a> {
       query = query.filter(person_block::person_id.is_null());
     }
 
+    // Hide comments matching the personal keyword filters
+    if let Some(my_person_id) = self.my_person_id {
+      for filter in LocalUserKeywordFilter::list_for_person(self.conn, my_person_id)? {
+        let pattern = match filter.pattern() {
+          Some(pattern) => pattern,
+          None => continue,
+        };
+        let matches = regex_match_insensitive(comment::content, pattern);
+        query = match filter.community_id {
+          Some(community_id) => query.filter(post::community_id.ne(community_id).or(not(matches))),
+          None => query.filter(not(matches)),
+        };
+      }
+    }
+
+    // Pending comments are only visible to their creator and to mods
+    let moderated_communities = community_moderator::table
+      .filter(community_moderator::person_id.eq(person_id_join))
+      .select(community_moderator::community_id);
+    if self.pending_only.unwrap_or(false) {
+      query = query.filter(comment::pending.eq(true));
+      if !self.admin.unwrap_or(false) {
+        query = query.filter(post::community_id.eq_any(moderated_communities));
+      }
+    } else if !self.admin.unwrap_or(false) {
+      query = query.filter(
+        comment::pending
+          .eq(false)
+          .or(comment::creator_id.eq(person_id_join))
+          .or(post::community_id.eq_any(moderated_communities)),
+      );
+    }
+
+    // Comments in local-only communities are only visible to logged in users
+    if self.my_person_id.is_none() {
+      query = query.filter(community::visibility.ne(CommunityVisibility::LocalOnly));
+    }
+
+    // Comments in private communities are only visible to approved followers and to mods
+    if !self.admin.unwrap_or(false) {
+      let moderated_communities = community_moderator::table
+        .filter(community_moderator::person_id.eq(person_id_join))
+        .select(community_moderator::community_id);
+      query = query.filter(
+        community::visibility
+          .ne(CommunityVisibility::Private)
+          .or(
+            community_follower::person_id
+              .is_not_null()
+              .and(not(coalesce_bool(community_follower::pending, false))),
+          )
+          .or(post::community_id.eq_any(moderated_communities)),
+      );
+    }
+
     // A Max depth given means its a tree fetch
     let (limit, offset) = if let Some(max_depth) = self.max_depth {
       let depth_limit = if let Some(parent_path) = self.parent_path.as_ref() {
@@ -374,7 +456,7 @@ mod tests {
   use lemmy_db_schema::{
     aggregates::structs::CommentAggregates,
     source::{comment::*, community::*, person::*, person_block::PersonBlockForm, post::*},
-    traits::{Blockable, Crud, Likeable},
+    traits::{Blockable, Crud, Followable, Likeable},
     utils::establish_unpooled_connection,
     SubscribedType,
   };
@@ -532,6 +614,7 @@ mod tests {
         updated: None,
         local: true,
         path: top_path,
+        pending: false,
       },
       creator: PersonSafe {
         id: inserted_person.id,
@@ -573,6 +656,9 @@ mod tests {
         thumbnail_url: None,
         ap_id: inserted_post.ap_id.to_owned(),
         local: true,
+        pending: false,
+        crosspost_of: None,
+        url_canonical: None,
       },
       community: CommunitySafe {
         id: inserted_community.id,
@@ -589,6 +675,8 @@ mod tests {
         banner: None,
         hidden: false,
         posting_restricted_to_mods: false,
+        require_approval: false,
+        visibility: CommunityVisibility::Public,
         published: inserted_community.published,
       },
       counts: CommentAggregates {
@@ -702,6 +790,45 @@ mod tests {
       .eq("Comment 3"));
     assert_eq!(3, read_comment_views_parent_max_depth.len());
 
+    // Comments in private communities are only visible to approved followers
+    let private_community = CommunityForm {
+      visibility: Some(CommunityVisibility::Private),
+      ..new_community
+    };
+    Community::update(&conn, inserted_community.id, &private_community).unwrap();
+    let list_visible = |my_person_id| {
+      CommentQuery::builder()
+        .conn(&conn)
+        .post_id(Some(inserted_post.id))
+        .my_person_id(my_person_id)
+        .build()
+        .list()
+        .unwrap()
+        .len()
+    };
+    assert_eq!(0, list_visible(None));
+    assert_eq!(0, list_visible(Some(inserted_person.id)));
+    let follower_form = CommunityFollowerForm {
+      community_id: inserted_community.id,
+      person_id: inserted_person.id,
+      pending: true,
+    };
+    CommunityFollower::follow(&conn, &follower_form).unwrap();
+    assert_eq!(0, list_visible(Some(inserted_person.id)));
+    CommunityFollower::follow_accepted(&conn, inserted_community.id, inserted_person.id).unwrap();
+    assert_eq!(5, list_visible(Some(inserted_person.id)));
+
+    // Comments in local-only communities are only visible to logged in users
+    let local_only_community = CommunityForm {
+      name: inserted_community.name.clone(),
+      title: inserted_community.title.clone(),
+      visibility: Some(CommunityVisibility::LocalOnly),
+      ..CommunityForm::default()
+    };
+    Community::update(&conn, inserted_community.id, &local_only_community).unwrap();
+    assert_eq!(0, list_visible(None));
+    assert_eq!(5, list_visible(Some(inserted_person.id)));
+
     // Delete everything
     let like_removed =
       CommentLike::remove(&conn, inserted_person.id, inserted_comment_0.id).unwrap();
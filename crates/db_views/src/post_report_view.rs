@@ -379,6 +379,8 @@ mod tests {
         banner: None,
         hidden: false,
         posting_restricted_to_mods: false,
+        require_approval: false,
+        visibility: CommunityVisibility::Public,
         published: inserted_community.published,
       },
       creator: PersonSafe {
@@ -2,14 +2,17 @@ use crate::structs::PostView;
 use diesel::{dsl::*, pg::Pg, result::Error, *};
 use lemmy_db_schema::{
   aggregates::structs::PostAggregates,
-  newtypes::{CommunityId, DbUrl, PersonId, PostId},
+  newtypes::{CommunityId, DbUrl, MultiCommunityId, PersonId, PostId},
   schema::{
     community,
     community_block,
     community_follower,
+    community_moderator,
     community_person_ban,
+    multi_community_community,
     person,
     person_block,
+    person_follower,
     post,
     post_aggregates,
     post_like,
@@ -17,13 +20,24 @@ use lemmy_db_schema::{
     post_saved,
   },
   source::{
-    community::{Community, CommunityFollower, CommunityPersonBan, CommunitySafe},
+    community::{
+      Community,
+      CommunityFollower,
+      CommunityPersonBan,
+      CommunitySafe,
+      CommunityVisibility,
+    },
+    local_user_keyword_filter::LocalUserKeywordFilter,
     person::{Person, PersonSafe},
     person_block::PersonBlock,
     post::{Post, PostRead, PostSaved},
   },
   traits::{ToSafe, ViewToVec},
-  utils::{functions::hot_rank, fuzzy_search, limit_and_offset},
+  utils::{
+    functions::{coalesce, coalesce_bool, hot_rank, regex_match_insensitive},
+    fuzzy_search,
+    limit_and_offset,
+  },
   ListingType,
   SortType,
 };
@@ -162,6 +176,10 @@ pub struct PostQuery<'a> {
   creator_id: Option<PersonId>,
   community_id: Option<CommunityId>,
   community_actor_id: Option<DbUrl>,
+  /// Only list posts from the member communities of this feed
+  multi_community_id: Option<MultiCommunityId>,
+  /// List the other posts linking the same page as this one, or crossposted from the same post
+  other_discussions_of: Option<&'a Post>,
   my_person_id: Option<PersonId>,
   search_term: Option<String>,
   url_search: Option<String>,
@@ -169,6 +187,10 @@ pub struct PostQuery<'a> {
   show_bot_accounts: Option<bool>,
   show_read_posts: Option<bool>,
   saved_only: Option<bool>,
+  /// Only list posts which are held for approval
+  pending_only: Option<bool>,
+  /// Admins can see all pending posts
+  admin: Option<bool>,
   page: Option<i64>,
   limit: Option<i64>,
 }
@@ -271,6 +293,16 @@ impl<'a> PostQuery<'a> {
               .or(community_follower::person_id.eq(person_id_join)),
           )
         }
+        ListingType::FollowedUsers => {
+          query = query.filter(
+            post::creator_id.eq_any(
+              person_follower::table
+                .filter(person_follower::follower_id.eq(person_id_join))
+                .filter(person_follower::pending.eq(false))
+                .select(person_follower::person_id),
+            ),
+          )
+        }
       }
     }
 
@@ -286,6 +318,28 @@ impl<'a> PostQuery<'a> {
         .then_order_by(post_aggregates::stickied.desc());
     }
 
+    if let Some(multi_community_id) = self.multi_community_id {
+      query = query.filter(
+        post::community_id.eq_any(
+          multi_community_community::table
+            .filter(multi_community_community::multi_community_id.eq(multi_community_id))
+            .select(multi_community_community::community_id),
+        ),
+      );
+    }
+
+    if let Some(other_post) = self.other_discussions_of {
+      let original_id = other_post.crosspost_of.unwrap_or(other_post.id);
+      query = query
+        .filter(
+          post::id
+            .eq(original_id)
+            .or(post::crosspost_of.eq(original_id))
+            .or(post::url_canonical.eq(other_post.url_canonical.to_owned())),
+        )
+        .filter(post::id.ne(other_post.id));
+    }
+
     if let Some(url_search) = self.url_search {
       query = query.filter(post::url.eq(url_search));
     }
@@ -329,6 +383,60 @@ impl<'a> PostQuery<'a> {
       query = query.filter(person_block::person_id.is_null());
     }
 
+    // Hide posts matching the personal keyword filters
+    if let Some(my_person_id) = self.my_person_id {
+      for filter in LocalUserKeywordFilter::list_for_person(self.conn, my_person_id)? {
+        let pattern = match filter.pattern() {
+          Some(pattern) => pattern,
+          None => continue,
+        };
+        let matches = regex_match_insensitive(post::name, pattern.to_owned())
+          .or(regex_match_insensitive(coalesce(post::body, ""), pattern));
+        query = match filter.community_id {
+          Some(community_id) => query.filter(post::community_id.ne(community_id).or(not(matches))),
+          None => query.filter(not(matches)),
+        };
+      }
+    }
+
+    let moderated_communities = community_moderator::table
+      .filter(community_moderator::person_id.eq(person_id_join))
+      .select(community_moderator::community_id);
+
+    // Pending posts are only visible to their creator and to mods
+    if self.pending_only.unwrap_or(false) {
+      query = query.filter(post::pending.eq(true));
+      if !self.admin.unwrap_or(false) {
+        query = query.filter(post::community_id.eq_any(moderated_communities));
+      }
+    } else if !self.admin.unwrap_or(false) {
+      query = query.filter(
+        post::pending
+          .eq(false)
+          .or(post::creator_id.eq(person_id_join))
+          .or(post::community_id.eq_any(moderated_communities)),
+      );
+    }
+
+    // Posts of local-only communities are only visible to logged in users
+    if self.my_person_id.is_none() {
+      query = query.filter(community::visibility.ne(CommunityVisibility::LocalOnly));
+    }
+
+    // Posts of private communities are only visible to approved followers and to mods
+    if !self.admin.unwrap_or(false) {
+      query = query.filter(
+        community::visibility
+          .ne(CommunityVisibility::Private)
+          .or(
+            community_follower::person_id
+              .is_not_null()
+              .and(not(coalesce_bool(community_follower::pending, false))),
+          )
+          .or(post::community_id.eq_any(moderated_communities)),
+      );
+    }
+
     query = match self.sort.unwrap_or(SortType::Hot) {
       SortType::Active => query
         .then_order_by(
@@ -416,11 +524,14 @@ mod tests {
     source::{
       community::*,
       community_block::{CommunityBlock, CommunityBlockForm},
+      local_user::{LocalUser, LocalUserForm},
+      local_user_keyword_filter::{LocalUserKeywordFilter, LocalUserKeywordFilterForm},
+      multi_community::{MultiCommunity, MultiCommunityForm},
       person::*,
       person_block::{PersonBlock, PersonBlockForm},
       post::*,
     },
-    traits::{Blockable, Crud, Likeable},
+    traits::{Blockable, Crud, Followable, Joinable, Likeable},
     utils::establish_unpooled_connection,
     SortType,
     SubscribedType,
@@ -570,6 +681,9 @@ mod tests {
         thumbnail_url: None,
         ap_id: inserted_post.ap_id.to_owned(),
         local: true,
+        pending: false,
+        crosspost_of: None,
+        url_canonical: None,
       },
       my_vote: None,
       creator: PersonSafe {
@@ -608,6 +722,8 @@ mod tests {
         banner: None,
         hidden: false,
         posting_restricted_to_mods: false,
+        require_approval: false,
+        visibility: CommunityVisibility::Public,
         published: inserted_community.published,
       },
       counts: PostAggregates {
@@ -688,4 +804,408 @@ mod tests {
     assert_eq!(1, like_removed);
     assert_eq!(1, num_deleted);
   }
+
+  #[test]
+  #[serial]
+  fn test_pending_posts() {
+    let conn = establish_unpooled_connection();
+
+    let new_person = |name: &str| PersonForm {
+      name: name.to_string(),
+      public_key: Some("pubkey".to_string()),
+      ..PersonForm::default()
+    };
+    let creator = Person::create(&conn, &new_person("pending_creator")).unwrap();
+    let moderator = Person::create(&conn, &new_person("pending_mod")).unwrap();
+    let other = Person::create(&conn, &new_person("pending_other")).unwrap();
+
+    let new_community = CommunityForm {
+      name: "test_community_pending".to_string(),
+      title: "nada".to_owned(),
+      public_key: Some("pubkey".to_string()),
+      require_approval: Some(true),
+      ..CommunityForm::default()
+    };
+    let community = Community::create(&conn, &new_community).unwrap();
+
+    let moderator_form = CommunityModeratorForm {
+      community_id: community.id,
+      person_id: moderator.id,
+    };
+    CommunityModerator::join(&conn, &moderator_form).unwrap();
+
+    let new_post = PostForm {
+      name: "pending post".to_string(),
+      creator_id: creator.id,
+      community_id: community.id,
+      pending: Some(true),
+      ..PostForm::default()
+    };
+    let pending_post = Post::create(&conn, &new_post).unwrap();
+
+    let list = |my_person_id, pending_only| {
+      PostQuery::builder()
+        .conn(&conn)
+        .community_id(Some(community.id))
+        .my_person_id(my_person_id)
+        .pending_only(Some(pending_only))
+        .build()
+        .list()
+        .unwrap()
+        .len()
+    };
+
+    // Only the creator and mods can see the pending post
+    assert_eq!(1, list(Some(creator.id), false));
+    assert_eq!(1, list(Some(moderator.id), false));
+    assert_eq!(0, list(Some(other.id), false));
+    assert_eq!(0, list(None, false));
+
+    // The mod queue only shows it to mods
+    assert_eq!(1, list(Some(moderator.id), true));
+    assert_eq!(0, list(Some(creator.id), true));
+
+    // Once approved, everyone can see it
+    Post::update_pending(&conn, pending_post.id, false).unwrap();
+    assert_eq!(1, list(Some(other.id), false));
+    assert_eq!(0, list(Some(moderator.id), true));
+
+    Community::delete(&conn, community.id).unwrap();
+    Person::delete(&conn, creator.id).unwrap();
+    Person::delete(&conn, moderator.id).unwrap();
+    Person::delete(&conn, other.id).unwrap();
+  }
+
+  #[test]
+  #[serial]
+  fn test_private_community_posts() {
+    let conn = establish_unpooled_connection();
+
+    let new_person = |name: &str| PersonForm {
+      name: name.to_string(),
+      public_key: Some("pubkey".to_string()),
+      ..PersonForm::default()
+    };
+    let moderator = Person::create(&conn, &new_person("private_mod")).unwrap();
+    let follower = Person::create(&conn, &new_person("private_follower")).unwrap();
+    let other = Person::create(&conn, &new_person("private_other")).unwrap();
+
+    let new_community = CommunityForm {
+      name: "test_community_private".to_string(),
+      title: "nada".to_owned(),
+      public_key: Some("pubkey".to_string()),
+      visibility: Some(CommunityVisibility::Private),
+      ..CommunityForm::default()
+    };
+    let community = Community::create(&conn, &new_community).unwrap();
+
+    let moderator_form = CommunityModeratorForm {
+      community_id: community.id,
+      person_id: moderator.id,
+    };
+    CommunityModerator::join(&conn, &moderator_form).unwrap();
+
+    let follower_form = CommunityFollowerForm {
+      community_id: community.id,
+      person_id: follower.id,
+      pending: true,
+    };
+    CommunityFollower::follow(&conn, &follower_form).unwrap();
+
+    let new_post = PostForm {
+      name: "private post".to_string(),
+      creator_id: moderator.id,
+      community_id: community.id,
+      ..PostForm::default()
+    };
+    Post::create(&conn, &new_post).unwrap();
+
+    let list = |my_person_id| {
+      PostQuery::builder()
+        .conn(&conn)
+        .community_id(Some(community.id))
+        .my_person_id(my_person_id)
+        .build()
+        .list()
+        .unwrap()
+        .len()
+    };
+
+    // Only mods can see the post while the follow is pending
+    assert_eq!(1, list(Some(moderator.id)));
+    assert_eq!(0, list(Some(follower.id)));
+    assert_eq!(0, list(Some(other.id)));
+    assert_eq!(0, list(None));
+
+    // Once the follow is approved, the follower can see it too
+    CommunityFollower::follow_accepted(&conn, community.id, follower.id).unwrap();
+    assert_eq!(1, list(Some(follower.id)));
+    assert_eq!(0, list(Some(other.id)));
+
+    Community::delete(&conn, community.id).unwrap();
+    Person::delete(&conn, moderator.id).unwrap();
+    Person::delete(&conn, follower.id).unwrap();
+    Person::delete(&conn, other.id).unwrap();
+  }
+
+  #[test]
+  #[serial]
+  fn test_local_only_community_posts() {
+    let conn = establish_unpooled_connection();
+
+    let new_person = PersonForm {
+      name: "local_only_viewer".to_string(),
+      public_key: Some("pubkey".to_string()),
+      ..PersonForm::default()
+    };
+    let person = Person::create(&conn, &new_person).unwrap();
+
+    let new_community = CommunityForm {
+      name: "test_community_local_only".to_string(),
+      title: "nada".to_owned(),
+      public_key: Some("pubkey".to_string()),
+      visibility: Some(CommunityVisibility::LocalOnly),
+      ..CommunityForm::default()
+    };
+    let community = Community::create(&conn, &new_community).unwrap();
+
+    let new_post = PostForm {
+      name: "local only post".to_string(),
+      creator_id: person.id,
+      community_id: community.id,
+      ..PostForm::default()
+    };
+    Post::create(&conn, &new_post).unwrap();
+
+    let list = |my_person_id| {
+      PostQuery::builder()
+        .conn(&conn)
+        .community_id(Some(community.id))
+        .my_person_id(my_person_id)
+        .build()
+        .list()
+        .unwrap()
+        .len()
+    };
+
+    // Logged in users can see the post, but not anonymous visitors
+    assert_eq!(1, list(Some(person.id)));
+    assert_eq!(0, list(None));
+
+    Community::delete(&conn, community.id).unwrap();
+    Person::delete(&conn, person.id).unwrap();
+  }
+
+  #[test]
+  #[serial]
+  fn test_keyword_filters() {
+    let conn = establish_unpooled_connection();
+
+    let new_person = PersonForm {
+      name: "keyword_filter_viewer".to_string(),
+      public_key: Some("pubkey".to_string()),
+      ..PersonForm::default()
+    };
+    let person = Person::create(&conn, &new_person).unwrap();
+    let local_user_form = LocalUserForm {
+      person_id: Some(person.id),
+      password_encrypted: Some("pass".to_string()),
+      ..LocalUserForm::default()
+    };
+    let local_user = LocalUser::create(&conn, &local_user_form).unwrap();
+
+    let new_community = |name: &str| CommunityForm {
+      name: name.to_string(),
+      title: "nada".to_owned(),
+      public_key: Some("pubkey".to_string()),
+      ..CommunityForm::default()
+    };
+    let community = Community::create(&conn, &new_community("keyword_filter_1")).unwrap();
+    let other_community = Community::create(&conn, &new_community("keyword_filter_2")).unwrap();
+
+    let new_post = |name: &str, body: Option<&str>, community_id| PostForm {
+      name: name.to_string(),
+      body: Some(body.map(ToString::to_string)),
+      creator_id: person.id,
+      community_id,
+      ..PostForm::default()
+    };
+    Post::create(&conn, &new_post("Election news", None, community.id)).unwrap();
+    Post::create(
+      &conn,
+      &new_post("Cats", Some("about ELECTIONS"), community.id),
+    )
+    .unwrap();
+    Post::create(&conn, &new_post("Dogs 2022", None, community.id)).unwrap();
+    Post::create(&conn, &new_post("Dogs", None, other_community.id)).unwrap();
+
+    let list = |my_person_id| {
+      let mut names = PostQuery::builder()
+        .conn(&conn)
+        .my_person_id(my_person_id)
+        .creator_id(Some(person.id))
+        .build()
+        .list()
+        .unwrap()
+        .into_iter()
+        .map(|p| p.post.name)
+        .collect::<Vec<String>>();
+      names.sort();
+      names
+    };
+    assert_eq!(4, list(Some(person.id)).len());
+
+    let filter_form = |keyword: &str, regex, community_id| LocalUserKeywordFilterForm {
+      local_user_id: local_user.id,
+      community_id,
+      keyword: keyword.to_string(),
+      regex,
+    };
+    let forms = [
+      filter_form("election", false, None),
+      filter_form("^dogs [0-9]+$", true, Some(community.id)),
+    ];
+    LocalUserKeywordFilter::replace(&conn, local_user.id, &forms).unwrap();
+
+    // Matches in title or body are hidden, scoped filters only apply in their community
+    assert_eq!(vec!["Dogs".to_string()], list(Some(person.id)));
+    // Filters don't affect anyone else
+    assert_eq!(4, list(None).len());
+
+    Community::delete(&conn, community.id).unwrap();
+    Community::delete(&conn, other_community.id).unwrap();
+    Person::delete(&conn, person.id).unwrap();
+  }
+
+  #[test]
+  #[serial]
+  fn test_multi_community() {
+    let conn = establish_unpooled_connection();
+
+    let new_person = PersonForm {
+      name: "multi_community_viewer".to_string(),
+      public_key: Some("pubkey".to_string()),
+      ..PersonForm::default()
+    };
+    let person = Person::create(&conn, &new_person).unwrap();
+
+    let new_community = |name: &str| CommunityForm {
+      name: name.to_string(),
+      title: "nada".to_owned(),
+      public_key: Some("pubkey".to_string()),
+      ..CommunityForm::default()
+    };
+    let communities = ["multi_feed_1", "multi_feed_2", "multi_feed_3"]
+      .iter()
+      .map(|n| Community::create(&conn, &new_community(n)).unwrap())
+      .collect::<Vec<Community>>();
+    for c in &communities {
+      let post_form = PostForm {
+        name: c.name.to_owned(),
+        creator_id: person.id,
+        community_id: c.id,
+        ..PostForm::default()
+      };
+      Post::create(&conn, &post_form).unwrap();
+    }
+
+    let multi_community_form = MultiCommunityForm {
+      creator_id: person.id,
+      name: "feed".to_string(),
+      ..MultiCommunityForm::default()
+    };
+    let multi_community = MultiCommunity::create(&conn, &multi_community_form).unwrap();
+    MultiCommunity::set_communities(
+      &conn,
+      multi_community.id,
+      &[communities[0].id, communities[2].id],
+    )
+    .unwrap();
+
+    let mut names = PostQuery::builder()
+      .conn(&conn)
+      .multi_community_id(Some(multi_community.id))
+      .build()
+      .list()
+      .unwrap()
+      .into_iter()
+      .map(|p| p.post.name)
+      .collect::<Vec<String>>();
+    names.sort();
+    assert_eq!(
+      vec!["multi_feed_1".to_string(), "multi_feed_3".to_string()],
+      names
+    );
+
+    MultiCommunity::delete(&conn, multi_community.id).unwrap();
+    for c in communities {
+      Community::delete(&conn, c.id).unwrap();
+    }
+    Person::delete(&conn, person.id).unwrap();
+  }
+
+  #[test]
+  #[serial]
+  fn test_other_discussions() {
+    let conn = establish_unpooled_connection();
+
+    let new_person = PersonForm {
+      name: "other_discussions_poster".to_string(),
+      public_key: Some("pubkey".to_string()),
+      ..PersonForm::default()
+    };
+    let person = Person::create(&conn, &new_person).unwrap();
+
+    let new_community = CommunityForm {
+      name: "other_discussions".to_string(),
+      title: "nada".to_owned(),
+      public_key: Some("pubkey".to_string()),
+      ..CommunityForm::default()
+    };
+    let community = Community::create(&conn, &new_community).unwrap();
+
+    let new_post = |name: &str, url_canonical: Option<&str>, crosspost_of| PostForm {
+      name: name.to_string(),
+      creator_id: person.id,
+      community_id: community.id,
+      crosspost_of,
+      url_canonical: Some(url_canonical.map(ToString::to_string)),
+      ..PostForm::default()
+    };
+    let link = Some("https://example.com/article");
+    let original = Post::create(&conn, &new_post("original", link, None)).unwrap();
+    let crosspost = Post::create(&conn, &new_post("crosspost", link, Some(original.id))).unwrap();
+    Post::create(&conn, &new_post("same link", link, None)).unwrap();
+    Post::create(
+      &conn,
+      &new_post("other link", Some("https://example.com"), None),
+    )
+    .unwrap();
+    Post::create(&conn, &new_post("no link", None, None)).unwrap();
+
+    let other_discussions = |post: &Post| {
+      let mut names = PostQuery::builder()
+        .conn(&conn)
+        .other_discussions_of(Some(post))
+        .build()
+        .list()
+        .unwrap()
+        .into_iter()
+        .map(|p| p.post.name)
+        .collect::<Vec<String>>();
+      names.sort();
+      names
+    };
+    assert_eq!(
+      vec!["crosspost".to_string(), "same link".to_string()],
+      other_discussions(&original)
+    );
+    assert_eq!(
+      vec!["original".to_string(), "same link".to_string()],
+      other_discussions(&crosspost)
+    );
+
+    Community::delete(&conn, community.id).unwrap();
+    Person::delete(&conn, person.id).unwrap();
+  }
 }
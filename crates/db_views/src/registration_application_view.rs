@@ -249,6 +249,7 @@ mod tests {
         show_new_post_notifs: inserted_sara_local_user.show_new_post_notifs,
         email_verified: inserted_sara_local_user.email_verified,
         accepted_application: inserted_sara_local_user.accepted_application,
+        email_digest_frequency: inserted_sara_local_user.email_digest_frequency,
       },
       creator: PersonSafe {
         id: inserted_sara_person.id,
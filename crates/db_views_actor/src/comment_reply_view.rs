@@ -1,5 +1,6 @@
 use crate::structs::CommentReplyView;
 use diesel::{dsl::*, result::Error, *};
+use diesel_ltree::LtreeExtensions;
 use lemmy_db_schema::{
   aggregates::structs::CommentAggregates,
   newtypes::{CommentReplyId, PersonId},
@@ -24,6 +25,7 @@ use lemmy_db_schema::{
     person::{Person, PersonAlias1, PersonSafe, PersonSafeAlias1},
     person_block::PersonBlock,
     post::Post,
+    thread_subscription::ThreadMute,
   },
   traits::{ToSafe, ViewToVec},
   utils::{functions::hot_rank, limit_and_offset},
@@ -152,11 +154,20 @@ impl CommentReplyView {
   pub fn get_unread_replies(conn: &PgConnection, my_person_id: PersonId) -> Result<i64, Error> {
     use diesel::dsl::*;
 
-    comment_reply::table
+    let mut query = comment_reply::table
+      .inner_join(comment::table)
       .filter(comment_reply::recipient_id.eq(my_person_id))
       .filter(comment_reply::read.eq(false))
-      .select(count(comment_reply::id))
-      .first::<i64>(conn)
+      .into_boxed();
+
+    for (muted_post_id, muted_path) in ThreadMute::list_for_person(conn, my_person_id)? {
+      query = match muted_path {
+        Some(muted_path) => query.filter(not(comment::path.contained_by(muted_path))),
+        None => query.filter(comment::post_id.ne(muted_post_id)),
+      };
+    }
+
+    query.select(count(comment_reply::id)).first::<i64>(conn)
   }
 }
 
@@ -246,6 +257,14 @@ impl<'a> CommentReplyQuery<'a> {
 
     if let Some(recipient_id) = self.recipient_id {
       query = query.filter(comment_reply::recipient_id.eq(recipient_id));
+
+      // Hide notifications from threads which the recipient muted
+      for (muted_post_id, muted_path) in ThreadMute::list_for_person(self.conn, recipient_id)? {
+        query = match muted_path {
+          Some(muted_path) => query.filter(not(comment::path.contained_by(muted_path))),
+          None => query.filter(comment::post_id.ne(muted_post_id)),
+        };
+      }
     }
 
     if self.unread_only.unwrap_or(false) {
@@ -8,6 +8,7 @@ use lemmy_db_schema::{
     person::{Person, PersonSafe},
   },
   traits::{ToSafe, ViewToVec},
+  utils::limit_and_offset,
 };
 
 type CommunityFollowerViewTuple = (CommunitySafe, PersonSafe);
@@ -22,12 +23,42 @@ impl CommunityFollowerView {
         Person::safe_columns_tuple(),
       ))
       .filter(community_follower::community_id.eq(community_id))
+      .filter(
+        community_follower::pending
+          .is_null()
+          .or(community_follower::pending.eq(false)),
+      )
       .order_by(community::title)
       .load::<CommunityFollowerViewTuple>(conn)?;
 
     Ok(Self::from_tuple_to_vec(res))
   }
 
+  /// Follows of a community which are still waiting for approval, oldest first
+  pub fn list_pending(
+    conn: &PgConnection,
+    community_id: CommunityId,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    let res = community_follower::table
+      .inner_join(community::table)
+      .inner_join(person::table)
+      .select((
+        Community::safe_columns_tuple(),
+        Person::safe_columns_tuple(),
+      ))
+      .filter(community_follower::community_id.eq(community_id))
+      .filter(community_follower::pending.eq(true))
+      .order_by(community_follower::published)
+      .limit(limit)
+      .offset(offset)
+      .load::<CommunityFollowerViewTuple>(conn)?;
+
+    Ok(Self::from_tuple_to_vec(res))
+  }
+
   pub fn for_person(conn: &PgConnection, person_id: PersonId) -> Result<Vec<Self>, Error> {
     let res = community_follower::table
       .inner_join(community::table)
@@ -11,9 +11,17 @@ pub mod community_person_ban_view;
 #[cfg(feature = "full")]
 pub mod community_view;
 #[cfg(feature = "full")]
+pub mod multi_community_view;
+#[cfg(feature = "full")]
 pub mod person_block_view;
 #[cfg(feature = "full")]
 pub mod person_mention_view;
 #[cfg(feature = "full")]
 pub mod person_view;
+#[cfg(feature = "full")]
+pub mod post_notification_view;
 pub mod structs;
+#[cfg(feature = "full")]
+pub mod wiki_contributor_view;
+#[cfg(feature = "full")]
+pub mod wiki_page_revision_view;
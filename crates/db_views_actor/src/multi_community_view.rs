@@ -0,0 +1,91 @@
+use crate::structs::MultiCommunityView;
+use diesel::{result::Error, *};
+use lemmy_db_schema::{
+  newtypes::{MultiCommunityId, PersonId},
+  schema::{community, multi_community, multi_community_community, person},
+  source::{
+    community::{Community, CommunitySafe},
+    multi_community::MultiCommunity,
+    person::{Person, PersonSafe},
+  },
+  traits::ToSafe,
+  utils::limit_and_offset,
+};
+
+type MultiCommunityViewTuple = (MultiCommunity, PersonSafe);
+
+impl MultiCommunityView {
+  pub fn read(conn: &PgConnection, multi_community_id: MultiCommunityId) -> Result<Self, Error> {
+    let res = multi_community::table
+      .find(multi_community_id)
+      .inner_join(person::table)
+      .select((multi_community::all_columns, Person::safe_columns_tuple()))
+      .first::<MultiCommunityViewTuple>(conn)?;
+
+    Ok(Self::with_communities(conn, vec![res])?.remove(0))
+  }
+
+  pub fn for_creator(conn: &PgConnection, creator_id: PersonId) -> Result<Vec<Self>, Error> {
+    let res = multi_community::table
+      .inner_join(person::table)
+      .select((multi_community::all_columns, Person::safe_columns_tuple()))
+      .filter(multi_community::creator_id.eq(creator_id))
+      .order_by(multi_community::name)
+      .load::<MultiCommunityViewTuple>(conn)?;
+
+    Self::with_communities(conn, res)
+  }
+
+  pub fn list_public(
+    conn: &PgConnection,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    let res = multi_community::table
+      .inner_join(person::table)
+      .select((multi_community::all_columns, Person::safe_columns_tuple()))
+      .filter(multi_community::public.eq(true))
+      .order_by(multi_community::published.desc())
+      .limit(limit)
+      .offset(offset)
+      .load::<MultiCommunityViewTuple>(conn)?;
+
+    Self::with_communities(conn, res)
+  }
+
+  /// Loads the member communities of all the given feeds at once
+  fn with_communities(
+    conn: &PgConnection,
+    items: Vec<MultiCommunityViewTuple>,
+  ) -> Result<Vec<Self>, Error> {
+    let ids = items.iter().map(|i| i.0.id).collect::<Vec<_>>();
+    let members = multi_community_community::table
+      .inner_join(community::table)
+      .select((
+        multi_community_community::multi_community_id,
+        Community::safe_columns_tuple(),
+      ))
+      .filter(multi_community_community::multi_community_id.eq_any(ids))
+      .order_by(multi_community_community::published)
+      .load::<(MultiCommunityId, CommunitySafe)>(conn)?;
+
+    Ok(
+      items
+        .into_iter()
+        .map(|(multi_community, creator)| {
+          let communities = members
+            .iter()
+            .filter(|m| m.0 == multi_community.id)
+            .map(|m| m.1.to_owned())
+            .collect();
+          Self {
+            multi_community,
+            creator,
+            communities,
+          }
+        })
+        .collect(),
+    )
+  }
+}
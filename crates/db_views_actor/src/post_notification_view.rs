@@ -0,0 +1,135 @@
+use crate::structs::PostNotificationView;
+use diesel::{result::Error, *};
+use lemmy_db_schema::{
+  aggregates::structs::PostAggregates,
+  newtypes::{PersonId, PostNotificationId},
+  schema::{community, person, post, post_aggregates, post_notification},
+  source::{
+    community::{Community, CommunitySafe},
+    person::{Person, PersonSafe},
+    post::Post,
+    post_notification::PostNotification,
+  },
+  traits::{ToSafe, ViewToVec},
+  utils::limit_and_offset,
+};
+use typed_builder::TypedBuilder;
+
+type PostNotificationViewTuple = (
+  PostNotification,
+  Post,
+  PersonSafe,
+  CommunitySafe,
+  PostAggregates,
+);
+
+impl PostNotificationView {
+  pub fn read(
+    conn: &PgConnection,
+    post_notification_id: PostNotificationId,
+  ) -> Result<Self, Error> {
+    let (post_notification, post, creator, community, counts) = post_notification::table
+      .find(post_notification_id)
+      .inner_join(post::table)
+      .inner_join(person::table.on(post::creator_id.eq(person::id)))
+      .inner_join(community::table.on(post::community_id.eq(community::id)))
+      .inner_join(post_aggregates::table.on(post::id.eq(post_aggregates::post_id)))
+      .select((
+        post_notification::all_columns,
+        post::all_columns,
+        Person::safe_columns_tuple(),
+        Community::safe_columns_tuple(),
+        post_aggregates::all_columns,
+      ))
+      .first::<PostNotificationViewTuple>(conn)?;
+
+    Ok(PostNotificationView {
+      post_notification,
+      post,
+      creator,
+      community,
+      counts,
+    })
+  }
+
+  /// Gets the number of unread post notifications
+  pub fn get_unread_post_notifications(
+    conn: &PgConnection,
+    my_person_id: PersonId,
+  ) -> Result<i64, Error> {
+    use diesel::dsl::*;
+
+    post_notification::table
+      .inner_join(post::table)
+      .filter(post_notification::recipient_id.eq(my_person_id))
+      .filter(post_notification::read.eq(false))
+      .filter(post::deleted.eq(false))
+      .filter(post::removed.eq(false))
+      .select(count(post_notification::id))
+      .first::<i64>(conn)
+  }
+}
+
+#[derive(TypedBuilder)]
+#[builder(field_defaults(default))]
+pub struct PostNotificationQuery<'a> {
+  #[builder(!default)]
+  conn: &'a PgConnection,
+  #[builder(!default)]
+  recipient_id: PersonId,
+  unread_only: Option<bool>,
+  page: Option<i64>,
+  limit: Option<i64>,
+}
+
+impl<'a> PostNotificationQuery<'a> {
+  pub fn list(self) -> Result<Vec<PostNotificationView>, Error> {
+    let mut query = post_notification::table
+      .inner_join(post::table)
+      .inner_join(person::table.on(post::creator_id.eq(person::id)))
+      .inner_join(community::table.on(post::community_id.eq(community::id)))
+      .inner_join(post_aggregates::table.on(post::id.eq(post_aggregates::post_id)))
+      .filter(post_notification::recipient_id.eq(self.recipient_id))
+      // Posts which were removed after the notification was sent aren't shown anymore
+      .filter(post::deleted.eq(false))
+      .filter(post::removed.eq(false))
+      .select((
+        post_notification::all_columns,
+        post::all_columns,
+        Person::safe_columns_tuple(),
+        Community::safe_columns_tuple(),
+        post_aggregates::all_columns,
+      ))
+      .into_boxed();
+
+    if self.unread_only.unwrap_or(false) {
+      query = query.filter(post_notification::read.eq(false));
+    }
+
+    let (limit, offset) = limit_and_offset(self.page, self.limit)?;
+
+    let res = query
+      .order_by(post_notification::published.desc())
+      .limit(limit)
+      .offset(offset)
+      .load::<PostNotificationViewTuple>(self.conn)?;
+
+    Ok(PostNotificationView::from_tuple_to_vec(res))
+  }
+}
+
+impl ViewToVec for PostNotificationView {
+  type DbTuple = PostNotificationViewTuple;
+  fn from_tuple_to_vec(items: Vec<Self::DbTuple>) -> Vec<Self> {
+    items
+      .into_iter()
+      .map(|a| Self {
+        post_notification: a.0,
+        post: a.1,
+        creator: a.2,
+        community: a.3,
+        counts: a.4,
+      })
+      .collect::<Vec<Self>>()
+  }
+}
@@ -1,12 +1,15 @@
 use lemmy_db_schema::{
-  aggregates::structs::{CommentAggregates, CommunityAggregates, PersonAggregates},
+  aggregates::structs::{CommentAggregates, CommunityAggregates, PersonAggregates, PostAggregates},
   source::{
     comment::Comment,
     comment_reply::CommentReply,
     community::CommunitySafe,
+    multi_community::MultiCommunity,
     person::{PersonSafe, PersonSafeAlias1},
     person_mention::PersonMention,
     post::Post,
+    post_notification::PostNotification,
+    wiki_page::WikiPageRevision,
   },
   SubscribedType,
 };
@@ -44,6 +47,13 @@ pub struct CommunityView {
   pub counts: CommunityAggregates,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct MultiCommunityView {
+  pub multi_community: MultiCommunity,
+  pub creator: PersonSafe,
+  pub communities: Vec<CommunitySafe>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PersonBlockView {
   pub person: PersonSafe,
@@ -82,8 +92,29 @@ pub struct CommentReplyView {
   pub my_vote: Option<i16>,                // Left join to CommentLike
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct PostNotificationView {
+  pub post_notification: PostNotification,
+  pub post: Post,
+  pub creator: PersonSafe,
+  pub community: CommunitySafe,
+  pub counts: PostAggregates,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PersonViewSafe {
   pub person: PersonSafe,
   pub counts: PersonAggregates,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WikiContributorView {
+  pub community: CommunitySafe,
+  pub contributor: PersonSafe,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct WikiPageRevisionView {
+  pub revision: WikiPageRevision,
+  pub editor: PersonSafe,
+}
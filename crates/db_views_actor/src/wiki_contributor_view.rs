@@ -0,0 +1,43 @@
+use crate::structs::WikiContributorView;
+use diesel::{result::Error, *};
+use lemmy_db_schema::{
+  newtypes::CommunityId,
+  schema::{community, person, wiki_contributor},
+  source::{
+    community::{Community, CommunitySafe},
+    person::{Person, PersonSafe},
+  },
+  traits::{ToSafe, ViewToVec},
+};
+
+type WikiContributorViewTuple = (CommunitySafe, PersonSafe);
+
+impl WikiContributorView {
+  pub fn for_community(conn: &PgConnection, community_id: CommunityId) -> Result<Vec<Self>, Error> {
+    let res = wiki_contributor::table
+      .inner_join(community::table)
+      .inner_join(person::table)
+      .select((
+        Community::safe_columns_tuple(),
+        Person::safe_columns_tuple(),
+      ))
+      .filter(wiki_contributor::community_id.eq(community_id))
+      .order_by(wiki_contributor::published)
+      .load::<WikiContributorViewTuple>(conn)?;
+
+    Ok(Self::from_tuple_to_vec(res))
+  }
+}
+
+impl ViewToVec for WikiContributorView {
+  type DbTuple = WikiContributorViewTuple;
+  fn from_tuple_to_vec(items: Vec<Self::DbTuple>) -> Vec<Self> {
+    items
+      .into_iter()
+      .map(|a| Self {
+        community: a.0,
+        contributor: a.1,
+      })
+      .collect::<Vec<Self>>()
+  }
+}
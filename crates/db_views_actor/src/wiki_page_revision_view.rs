@@ -0,0 +1,52 @@
+use crate::structs::WikiPageRevisionView;
+use diesel::{result::Error, *};
+use lemmy_db_schema::{
+  newtypes::WikiPageId,
+  schema::{person, wiki_page_revision},
+  source::{
+    person::{Person, PersonSafe},
+    wiki_page::WikiPageRevision,
+  },
+  traits::{ToSafe, ViewToVec},
+  utils::limit_and_offset,
+};
+
+type WikiPageRevisionViewTuple = (WikiPageRevision, PersonSafe);
+
+impl WikiPageRevisionView {
+  /// Revisions of the page, newest first
+  pub fn list_for_page(
+    conn: &PgConnection,
+    wiki_page_id: WikiPageId,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    let res = wiki_page_revision::table
+      .inner_join(person::table)
+      .select((
+        wiki_page_revision::all_columns,
+        Person::safe_columns_tuple(),
+      ))
+      .filter(wiki_page_revision::wiki_page_id.eq(wiki_page_id))
+      .order_by(wiki_page_revision::id.desc())
+      .limit(limit)
+      .offset(offset)
+      .load::<WikiPageRevisionViewTuple>(conn)?;
+
+    Ok(Self::from_tuple_to_vec(res))
+  }
+}
+
+impl ViewToVec for WikiPageRevisionView {
+  type DbTuple = WikiPageRevisionViewTuple;
+  fn from_tuple_to_vec(items: Vec<Self::DbTuple>) -> Vec<Self> {
+    items
+      .into_iter()
+      .map(|a| Self {
+        revision: a.0,
+        editor: a.1,
+      })
+      .collect::<Vec<Self>>()
+  }
+}
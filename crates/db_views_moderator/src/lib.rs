@@ -11,6 +11,10 @@ pub mod mod_add_community_view;
 #[cfg(feature = "full")]
 pub mod mod_add_view;
 #[cfg(feature = "full")]
+pub mod mod_approve_comment_view;
+#[cfg(feature = "full")]
+pub mod mod_approve_post_view;
+#[cfg(feature = "full")]
 pub mod mod_ban_from_community_view;
 #[cfg(feature = "full")]
 pub mod mod_ban_view;
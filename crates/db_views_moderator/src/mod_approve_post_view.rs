@@ -0,0 +1,71 @@
+use crate::structs::ModApprovePostView;
+use diesel::{result::Error, *};
+use lemmy_db_schema::{
+  newtypes::{CommunityId, PersonId},
+  schema::{community, mod_approve_post, person, post},
+  source::{
+    community::{Community, CommunitySafe},
+    moderator::ModApprovePost,
+    person::{Person, PersonSafe},
+    post::Post,
+  },
+  traits::{ToSafe, ViewToVec},
+  utils::limit_and_offset,
+};
+
+type ModApprovePostViewTuple = (ModApprovePost, PersonSafe, Post, CommunitySafe);
+
+impl ModApprovePostView {
+  pub fn list(
+    conn: &PgConnection,
+    community_id: Option<CommunityId>,
+    mod_person_id: Option<PersonId>,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    let mut query = mod_approve_post::table
+      .inner_join(person::table)
+      .inner_join(post::table)
+      .inner_join(community::table.on(post::community_id.eq(community::id)))
+      .select((
+        mod_approve_post::all_columns,
+        Person::safe_columns_tuple(),
+        post::all_columns,
+        Community::safe_columns_tuple(),
+      ))
+      .into_boxed();
+
+    if let Some(community_id) = community_id {
+      query = query.filter(post::community_id.eq(community_id));
+    };
+
+    if let Some(mod_person_id) = mod_person_id {
+      query = query.filter(mod_approve_post::mod_person_id.eq(mod_person_id));
+    };
+
+    let (limit, offset) = limit_and_offset(page, limit)?;
+
+    let res = query
+      .limit(limit)
+      .offset(offset)
+      .order_by(mod_approve_post::when_.desc())
+      .load::<ModApprovePostViewTuple>(conn)?;
+
+    Ok(Self::from_tuple_to_vec(res))
+  }
+}
+
+impl ViewToVec for ModApprovePostView {
+  type DbTuple = ModApprovePostViewTuple;
+  fn from_tuple_to_vec(items: Vec<Self::DbTuple>) -> Vec<Self> {
+    items
+      .into_iter()
+      .map(|a| Self {
+        mod_approve_post: a.0,
+        moderator: a.1,
+        post: a.2,
+        community: a.3,
+      })
+      .collect::<Vec<Self>>()
+  }
+}
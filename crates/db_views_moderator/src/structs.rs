@@ -8,6 +8,8 @@ use lemmy_db_schema::source::{
     AdminPurgePost,
     ModAdd,
     ModAddCommunity,
+    ModApproveComment,
+    ModApprovePost,
     ModBan,
     ModBanFromCommunity,
     ModHideCommunity,
@@ -60,6 +62,24 @@ pub struct ModHideCommunityView {
   pub community: CommunitySafe,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModApprovePostView {
+  pub mod_approve_post: ModApprovePost,
+  pub moderator: PersonSafe,
+  pub post: Post,
+  pub community: CommunitySafe,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModApproveCommentView {
+  pub mod_approve_comment: ModApproveComment,
+  pub moderator: PersonSafe,
+  pub comment: Comment,
+  pub commenter: PersonSafeAlias1,
+  pub post: Post,
+  pub community: CommunitySafe,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ModLockPostView {
   pub mod_lock_post: ModLockPost,
@@ -3,7 +3,10 @@ use anyhow::Context;
 use lemmy_api_common::utils::blocking;
 use lemmy_apub::fetcher::webfinger::{WebfingerLink, WebfingerResponse};
 use lemmy_db_schema::{
-  source::{community::Community, person::Person},
+  source::{
+    community::{Community, CommunityVisibility},
+    person::Person,
+  },
   traits::ApubActor,
 };
 use lemmy_utils::{error::LemmyError, location_info, settings::structs::Settings};
@@ -56,6 +59,8 @@ async fn get_webfinger_response(
   })
   .await?
   .ok()
+  // Local-only communities aren't exposed to other instances
+  .filter(|c| c.visibility != CommunityVisibility::LocalOnly)
   .map(|c| c.actor_id.into());
 
   // Mastodon seems to prioritize the last webfinger item in case of duplicates. Put
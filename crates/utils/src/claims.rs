@@ -1,10 +1,13 @@
 use crate::error::LemmyError;
-use chrono::Utc;
+use chrono::{Duration, Utc};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, TokenData, Validation};
 use serde::{Deserialize, Serialize};
 
 type Jwt = String;
 
+/// Unsubscribe links in emails stop working after this many days
+const UNSUBSCRIBE_TOKEN_VALID_DAYS: i64 = 90;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
   /// local_user_id, standard claim by RFC 7519.
@@ -34,3 +37,79 @@ impl Claims {
     Ok(encode(&Header::default(), &my_claims, &key)?)
   }
 }
+
+/// Token in the unsubscribe link of notification emails, which works without logging in. It is
+/// signed with a different key than login tokens, so that it can't be used as one. Unlike login
+/// tokens it expires, and it is also revoked by changing the password.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnsubscribeClaims {
+  /// local_user_id
+  pub sub: i32,
+  pub iss: String,
+  pub iat: i64,
+  /// Expiry as UNIX-timestamp in seconds, checked when decoding
+  pub exp: i64,
+}
+
+impl UnsubscribeClaims {
+  pub fn decode(jwt: &str, jwt_secret: &str) -> Result<TokenData<UnsubscribeClaims>, LemmyError> {
+    let validation = Validation::default();
+    let key = DecodingKey::from_secret(Self::signing_key(jwt_secret).as_ref());
+    Ok(decode::<UnsubscribeClaims>(jwt, &key, &validation)?)
+  }
+
+  pub fn jwt(local_user_id: i32, jwt_secret: &str, hostname: &str) -> Result<Jwt, LemmyError> {
+    let now = Utc::now();
+    let my_claims = UnsubscribeClaims {
+      sub: local_user_id,
+      iss: hostname.to_string(),
+      iat: now.timestamp(),
+      exp: (now + Duration::days(UNSUBSCRIBE_TOKEN_VALID_DAYS)).timestamp(),
+    };
+
+    let key = EncodingKey::from_secret(Self::signing_key(jwt_secret).as_ref());
+    Ok(encode(&Header::default(), &my_claims, &key)?)
+  }
+
+  fn signing_key(jwt_secret: &str) -> String {
+    format!("unsubscribe:{}", jwt_secret)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_unsubscribe_token_is_not_login_token() {
+    let secret = "jwt_secret";
+    let token = UnsubscribeClaims::jwt(5, secret, "example.com").unwrap();
+    assert_eq!(
+      5,
+      UnsubscribeClaims::decode(&token, secret)
+        .unwrap()
+        .claims
+        .sub
+    );
+    assert!(Claims::decode(&token, secret).is_err());
+    assert!(UnsubscribeClaims::decode(&token, "other_secret").is_err());
+
+    let login_token = Claims::jwt(5, secret, "example.com").unwrap();
+    assert!(UnsubscribeClaims::decode(&login_token, secret).is_err());
+  }
+
+  #[test]
+  fn test_unsubscribe_token_expires() {
+    let secret = "jwt_secret";
+    let issued = Utc::now() - Duration::days(UNSUBSCRIBE_TOKEN_VALID_DAYS + 1);
+    let claims = UnsubscribeClaims {
+      sub: 5,
+      iss: "example.com".to_string(),
+      iat: issued.timestamp(),
+      exp: (issued + Duration::days(UNSUBSCRIBE_TOKEN_VALID_DAYS)).timestamp(),
+    };
+    let key = EncodingKey::from_secret(UnsubscribeClaims::signing_key(secret).as_ref());
+    let token = encode(&Header::default(), &claims, &key).unwrap();
+    assert!(UnsubscribeClaims::decode(&token, secret).is_err());
+  }
+}
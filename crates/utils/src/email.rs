@@ -1,25 +1,77 @@
 use crate::{error::LemmyError, settings::structs::Settings};
 use html2text;
 use lettre::{
-  message::{Mailbox, MultiPart},
+  message::{
+    header::{Header, HeaderName, HeaderValue},
+    Mailbox,
+    MultiPart,
+  },
   transport::smtp::{authentication::Credentials, extension::ClientId},
   Address,
+  FileTransport,
   Message,
   SmtpTransport,
   Transport,
 };
-use std::str::FromStr;
+use std::{str::FromStr, time::Duration};
 use uuid::Uuid;
 
+/// Limits each step of talking to the SMTP server, so that a hanging server can't block sending
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+
 pub mod translations {
   rosetta_i18n::include_translations!();
 }
 
+/// `List-Unsubscribe` header, so that mail clients can show an unsubscribe button
+#[derive(Clone)]
+struct ListUnsubscribe(String);
+
+impl Header for ListUnsubscribe {
+  fn name() -> HeaderName {
+    HeaderName::new_from_ascii_str("List-Unsubscribe")
+  }
+
+  fn parse(s: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(Self(
+      s.trim()
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+        .to_string(),
+    ))
+  }
+
+  fn display(&self) -> HeaderValue {
+    HeaderValue::new(Self::name(), format!("<{}>", self.0))
+  }
+}
+
+/// `List-Unsubscribe-Post` header, marks the unsubscribe link as one-click (RFC 8058)
+#[derive(Clone)]
+struct ListUnsubscribePost;
+
+impl Header for ListUnsubscribePost {
+  fn name() -> HeaderName {
+    HeaderName::new_from_ascii_str("List-Unsubscribe-Post")
+  }
+
+  fn parse(_s: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(Self)
+  }
+
+  fn display(&self) -> HeaderValue {
+    HeaderValue::new(Self::name(), "List-Unsubscribe=One-Click".to_string())
+  }
+}
+
+/// Sends an email right away. Outside of the background task which sends queued emails, use
+/// `queue_email` from `lemmy_api_common` instead.
 pub fn send_email(
   subject: &str,
   to_email: &str,
   to_username: &str,
   html: &str,
+  unsubscribe_url: Option<&str>,
   settings: &Settings,
 ) -> Result<(), LemmyError> {
   let email_config = settings
@@ -28,6 +80,40 @@ pub fn send_email(
     .ok_or_else(|| LemmyError::from_message("no_email_setup"))?;
   let domain = settings.hostname.to_owned();
 
+  // the message length before wrap, 78, is somewhat arbritary but looks good to me
+  let plain_text = html2text::from_read(html.as_bytes(), 78);
+
+  let from_address = email_config
+    .smtp_from_address
+    .parse()
+    .map_err(|e| LemmyError::from_error_message(e, "email_from_address_invalid"))?;
+  let to_address = Address::from_str(to_email)
+    .map_err(|e| LemmyError::from_error_message(e, "email_to_address_invalid"))?;
+  let mut message_builder = Message::builder();
+  if let Some(url) = unsubscribe_url {
+    message_builder = message_builder
+      .header(ListUnsubscribe(url.to_string()))
+      .header(ListUnsubscribePost);
+  }
+  let email = message_builder
+    .from(from_address)
+    .to(Mailbox::new(Some(to_username.to_string()), to_address))
+    .message_id(Some(format!("{}@{}", Uuid::new_v4(), settings.hostname)))
+    .subject(subject)
+    .multipart(MultiPart::alternative_plain_html(
+      plain_text,
+      html.to_string(),
+    ))
+    .map_err(|e| LemmyError::from_error_message(e, "email_send_failed"))?;
+
+  // Write the email to a file instead, eg for testing
+  if let Some(dir) = &email_config.file_transport_dir {
+    return FileTransport::new(dir)
+      .send(&email)
+      .map(|_| ())
+      .map_err(|e| LemmyError::from_error_message(e, "email_send_failed"));
+  }
+
   let (smtp_server, smtp_port) = {
     let email_and_port = email_config.smtp_server.split(':').collect::<Vec<&str>>();
     if email_and_port.len() == 1 {
@@ -40,32 +126,10 @@ pub fn send_email(
       email_and_port[0],
       email_and_port[1]
         .parse::<u16>()
-        .expect("email needs a port"),
+        .map_err(|e| LemmyError::from_error_message(e, "email needs a port"))?,
     )
   };
 
-  // the message length before wrap, 78, is somewhat arbritary but looks good to me
-  let plain_text = html2text::from_read(html.as_bytes(), 78);
-
-  let email = Message::builder()
-    .from(
-      email_config
-        .smtp_from_address
-        .parse()
-        .expect("email from address isn't valid"),
-    )
-    .to(Mailbox::new(
-      Some(to_username.to_string()),
-      Address::from_str(to_email).expect("email to address isn't valid"),
-    ))
-    .message_id(Some(format!("{}@{}", Uuid::new_v4(), settings.hostname)))
-    .subject(subject)
-    .multipart(MultiPart::alternative_plain_html(
-      plain_text,
-      html.to_string(),
-    ))
-    .expect("email built incorrectly");
-
   // don't worry about 'dangeous'. it's just that leaving it at the default configuration
   // is bad.
 
@@ -83,7 +147,10 @@ pub fn send_email(
     builder = builder.credentials(Credentials::new(username, password));
   }
 
-  let mailer = builder.hello_name(ClientId::Domain(domain)).build();
+  let mailer = builder
+    .hello_name(ClientId::Domain(domain))
+    .timeout(Some(SMTP_TIMEOUT))
+    .build();
 
   let result = mailer.send(&email);
 
@@ -92,3 +159,43 @@ pub fn send_email(
     Err(e) => Err(LemmyError::from_error_message(e, "email_send_failed")),
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::settings::structs::EmailConfig;
+
+  #[test]
+  fn test_send_email_to_file() {
+    let dir = std::env::temp_dir().join(format!("lemmy-email-test-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let settings = Settings {
+      email: Some(EmailConfig {
+        smtp_from_address: "noreply@example.com".to_string(),
+        file_transport_dir: Some(dir.to_string_lossy().to_string()),
+        ..EmailConfig::default()
+      }),
+      ..Settings::default()
+    };
+
+    send_email(
+      "Hello",
+      "user@example.com",
+      "user",
+      "<p>Hi there</p>",
+      Some("https://example.com/api/v3/user/unsubscribe?token=abc"),
+      &settings,
+    )
+    .unwrap();
+
+    let files = std::fs::read_dir(&dir).unwrap().collect::<Vec<_>>();
+    assert_eq!(1, files.len());
+    let content = std::fs::read_to_string(files[0].as_ref().unwrap().path()).unwrap();
+    assert!(content.contains("Subject: Hello"));
+    assert!(
+      content.contains("List-Unsubscribe: <https://example.com/api/v3/user/unsubscribe?token=abc>")
+    );
+    assert!(content.contains("List-Unsubscribe-Post: List-Unsubscribe=One-Click"));
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+}
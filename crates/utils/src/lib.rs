@@ -15,6 +15,7 @@ pub mod request;
 mod test;
 pub mod utils;
 pub mod version;
+pub mod web_push;
 
 use std::{fmt, time::Duration};
 
@@ -119,6 +119,11 @@ pub struct EmailConfig {
   #[default("none")]
   #[doku(example = "none")]
   pub tls_type: String,
+  /// Write emails as files into this directory, instead of sending them via smtp. Useful for
+  /// testing.
+  #[default(None)]
+  #[doku(example = "/tmp/lemmy-emails")]
+  pub file_transport_dir: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
@@ -146,11 +151,17 @@ pub struct FederationConfig {
   /// Maximum number of HTTP requests allowed to handle a single incoming activity (or a single object fetch through the search).
   #[default(25)]
   pub http_fetch_retry_limit: i32,
-  /// Number of workers for sending outgoing activities. Search logs for "Activity queue stats" to
-  /// see information. If "running" number is consistently close to the worker_count, you should
-  /// increase it.
+  /// Maximum number of outgoing activities which are sent at the same time. Pending and failed
+  /// deliveries are listed by the admin api. It should be increased if there are consistently
+  /// many pending deliveries.
   #[default(64)]
   pub worker_count: u64,
+  /// Secure mode (also called authorized fetch). If enabled, ActivityPub GET requests need a valid
+  /// HTTP signature from an instance which isn't blocked. Other software which doesn't sign its
+  /// fetches can't read from this instance. If enabled, our own fetches are also signed with the
+  /// instance actor.
+  #[default(false)]
+  pub signed_fetch: bool,
   /// Use federation debug mode. Allows connecting to http and localhost urls. Also sends outgoing
   /// activities synchronously for easier testing. Do not use in production.
   #[default(false)]
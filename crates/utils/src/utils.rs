@@ -5,7 +5,7 @@ use itertools::Itertools;
 use once_cell::sync::Lazy;
 use rand::{distributions::Alphanumeric, thread_rng, Rng};
 use regex::Regex;
-use url::Url;
+use url::{form_urlencoded, Url};
 
 static MENTIONS_REGEX: Lazy<Regex> = Lazy::new(|| {
   Regex::new(r"@(?P<name>[\w.]+)@(?P<domain>[a-zA-Z0-9._:-]+)").expect("compile regex")
@@ -165,22 +165,51 @@ pub fn get_ip(conn_info: &ConnectionInfo) -> IpAddr {
   )
 }
 
+/// Removes tracking params. The remaining params are encoded again after decoding, so that eg
+/// `a=1%262` doesn't turn into `a=1&2`.
 pub fn clean_url_params(url: &Url) -> Url {
   let mut url_out = url.to_owned();
   if url.query().is_some() {
-    let new_query = url
-      .query_pairs()
-      .filter(|q| !CLEAN_URL_PARAMS_REGEX.is_match(&q.0))
-      .map(|q| format!("{}={}", q.0, q.1))
-      .join("&");
+    let new_query = form_urlencoded::Serializer::new(String::new())
+      .extend_pairs(
+        url
+          .query_pairs()
+          .filter(|q| !CLEAN_URL_PARAMS_REGEX.is_match(&q.0)),
+      )
+      .finish();
     url_out.set_query(Some(&new_query));
   }
   url_out
 }
 
+/// Canonical form of a post url, so that links to the same page can be found even if they are
+/// written differently. On top of `clean_url_params`, this ignores the scheme, a leading `www.`,
+/// the fragment, a trailing slash and the order of query params.
+pub fn canonicalize_url(url: &Url) -> String {
+  let mut url = clean_url_params(url);
+  if url.scheme() == "http" {
+    // Can only fail for non-special schemes
+    url.set_scheme("https").ok();
+  }
+  if let Some(host) = url.host_str().and_then(|h| h.strip_prefix("www.")) {
+    let host = host.to_string();
+    url.set_host(Some(&host)).ok();
+  }
+  url.set_fragment(None);
+  url.set_username("").ok();
+  url.set_password(None).ok();
+  let path = url.path().trim_end_matches('/').to_string();
+  url.set_path(&path);
+  let query = form_urlencoded::Serializer::new(String::new())
+    .extend_pairs(url.query_pairs().sorted())
+    .finish();
+  url.set_query(if query.is_empty() { None } else { Some(&query) });
+  url.to_string()
+}
+
 #[cfg(test)]
 mod tests {
-  use crate::utils::{clean_url_params, is_valid_post_title};
+  use crate::utils::{canonicalize_url, clean_url_params, is_valid_post_title};
   use url::Url;
 
   #[test]
@@ -195,6 +224,35 @@ mod tests {
     assert_eq!(url.to_string(), cleaned.to_string());
   }
 
+  #[test]
+  fn test_canonicalize_url() {
+    let canonical = |u: &str| canonicalize_url(&Url::parse(u).unwrap());
+    let expected = "https://example.com/path/123?a=1&b=2";
+    assert_eq!(expected, canonical("https://example.com/path/123?a=1&b=2"));
+    assert_eq!(
+      expected,
+      canonical("http://www.example.com/path/123/?b=2&utm_source=feed&a=1#comments")
+    );
+    assert_eq!(
+      expected,
+      canonical("HTTPS://Example.com:443/path/123?b=2&a=1")
+    );
+    assert_eq!(
+      "https://example.com/",
+      canonical("https://example.com/?utm_medium=social")
+    );
+    assert_ne!(expected, canonical("https://example.com/path/123?a=1"));
+    // Encoded separators stay part of the value
+    assert_eq!(
+      "https://example.com/?a=1%262",
+      canonical("https://example.com/?a=1%262")
+    );
+    assert_ne!(
+      canonical("https://example.com/?a=1&b=2"),
+      canonical("https://example.com/?a=1%26b%3D2")
+    );
+  }
+
   #[test]
   fn regex_checks() {
     assert!(!is_valid_post_title("hi"));
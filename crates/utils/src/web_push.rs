@@ -0,0 +1,344 @@
+//! Web Push message encryption (RFC 8291) and VAPID authentication (RFC 8292)
+use crate::error::LemmyError;
+use chrono::Utc;
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use openssl::{
+  bn::BigNumContext,
+  derive::Deriver,
+  ec::{EcGroup, EcKey, EcPoint, PointConversionForm},
+  hash::MessageDigest,
+  nid::Nid,
+  pkey::{PKey, Private},
+  rand::rand_bytes,
+  sign::Signer,
+  symm::{encrypt_aead, Cipher},
+};
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use url::{Host, Url};
+
+/// Record size written in the header. Payloads are limited to a single record anyway.
+const RECORD_SIZE: u32 = 4096;
+/// VAPID tokens can be valid for at most 24 hours
+const VAPID_EXPIRY_HOURS: i64 = 12;
+
+pub struct VapidKeys {
+  /// PKCS#8 PEM
+  pub private_key: String,
+  /// Uncompressed P-256 point, base64url encoded. This is the `applicationServerKey` which
+  /// clients need to create a subscription.
+  pub public_key: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct VapidClaims {
+  aud: String,
+  exp: i64,
+  sub: String,
+}
+
+fn p256_group() -> Result<EcGroup, LemmyError> {
+  Ok(EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)?)
+}
+
+fn public_key_bytes(key: &EcKey<Private>) -> Result<Vec<u8>, LemmyError> {
+  let mut ctx = BigNumContext::new()?;
+  Ok(
+    key
+      .public_key()
+      .to_bytes(key.group(), PointConversionForm::UNCOMPRESSED, &mut ctx)?,
+  )
+}
+
+pub fn base64_url_decode(value: &str) -> Result<Vec<u8>, LemmyError> {
+  base64::decode_config(value.trim_end_matches('='), base64::URL_SAFE_NO_PAD)
+    .map_err(|e| LemmyError::from_error_message(e, "invalid_base64"))
+}
+
+fn base64_url_encode(value: &[u8]) -> String {
+  base64::encode_config(value, base64::URL_SAFE_NO_PAD)
+}
+
+pub fn generate_vapid_keys() -> Result<VapidKeys, LemmyError> {
+  let group = p256_group()?;
+  let key = EcKey::generate(&group)?;
+  let public_key = base64_url_encode(&public_key_bytes(&key)?);
+  let private_key = PKey::from_ec_key(key)?.private_key_to_pem_pkcs8()?;
+  Ok(VapidKeys {
+    private_key: String::from_utf8(private_key)?,
+    public_key,
+  })
+}
+
+/// Value of the `Authorization` header for a push message to `endpoint`. `subject` is a contact
+/// for the push service, either a `mailto:` or an `https:` url.
+pub fn vapid_authorization(
+  endpoint: &Url,
+  keys: &VapidKeys,
+  subject: &str,
+) -> Result<String, LemmyError> {
+  let claims = VapidClaims {
+    aud: endpoint.origin().ascii_serialization(),
+    exp: Utc::now().timestamp() + VAPID_EXPIRY_HOURS * 60 * 60,
+    sub: subject.to_string(),
+  };
+  let key = EncodingKey::from_ec_pem(keys.private_key.as_bytes())?;
+  let token = encode(&Header::new(Algorithm::ES256), &claims, &key)?;
+  Ok(format!("vapid t={}, k={}", token, keys.public_key))
+}
+
+/// Checks that the keys of a push subscription have the right format
+pub fn validate_subscription_keys(p256dh: &str, auth: &str) -> Result<(), LemmyError> {
+  let group = p256_group()?;
+  let mut ctx = BigNumContext::new()?;
+  EcPoint::from_bytes(&group, &base64_url_decode(p256dh)?, &mut ctx)
+    .map_err(|e| LemmyError::from_error_message(e, "invalid_push_subscription"))?;
+  if base64_url_decode(auth)?.len() != 16 {
+    return Err(LemmyError::from_message("invalid_push_subscription"));
+  }
+  Ok(())
+}
+
+/// Checks that a push endpoint is an https url on the default port of a public domain, so that
+/// push messages can't be used to make requests to the local network.
+pub fn validate_push_endpoint(endpoint: &str) -> Result<Url, LemmyError> {
+  let url = Url::parse(endpoint)
+    .map_err(|e| LemmyError::from_error_message(e, "invalid_push_subscription"))?;
+  // The port is only set if it isn't the default one (443)
+  if url.scheme() != "https" || url.port().is_some() {
+    return Err(LemmyError::from_message("invalid_push_subscription"));
+  }
+  // IP addresses are never used by push services
+  let domain = match url.host() {
+    Some(Host::Domain(domain)) => domain.trim_end_matches('.').to_lowercase(),
+    _ => return Err(LemmyError::from_message("invalid_push_subscription")),
+  };
+  let is_internal = !domain.contains('.')
+    || [".localhost", ".local", ".internal", ".lan", ".home.arpa"]
+      .iter()
+      .any(|suffix| domain.ends_with(suffix));
+  if is_internal {
+    return Err(LemmyError::from_message("invalid_push_subscription"));
+  }
+  Ok(url)
+}
+
+/// Whether the address is reachable on the internet. Push endpoints are resolved before sending,
+/// because a public domain can still point to a loopback or private address.
+pub fn is_public_ip(ip: &IpAddr) -> bool {
+  match ip {
+    IpAddr::V4(ip) => is_public_ipv4(ip),
+    // Also covers mapped addresses like ::ffff:127.0.0.1
+    IpAddr::V6(ip) => match ip.to_ipv4() {
+      Some(ip) => is_public_ipv4(&ip),
+      None => is_public_ipv6(ip),
+    },
+  }
+}
+
+fn is_public_ipv4(ip: &Ipv4Addr) -> bool {
+  let [a, b, ..] = ip.octets();
+  !(ip.is_loopback()
+    || ip.is_private()
+    || ip.is_link_local()
+    || ip.is_unspecified()
+    || ip.is_broadcast()
+    || ip.is_multicast()
+    || ip.is_documentation()
+    // "this network" and carrier-grade NAT
+    || a == 0
+    || (a == 100 && (64..128).contains(&b)))
+}
+
+fn is_public_ipv6(ip: &Ipv6Addr) -> bool {
+  let first = ip.segments()[0];
+  !(ip.is_loopback()
+    || ip.is_unspecified()
+    || ip.is_multicast()
+    // unique local (fc00::/7) and link-local (fe80::/10)
+    || (first & 0xfe00) == 0xfc00
+    || (first & 0xffc0) == 0xfe80)
+}
+
+/// Encrypts the payload with the `p256dh` and `auth` keys of a subscription, returning the body
+/// for a push message with `Content-Encoding: aes128gcm`.
+pub fn encrypt_payload(payload: &[u8], p256dh: &str, auth: &str) -> Result<Vec<u8>, LemmyError> {
+  let group = p256_group()?;
+  let ephemeral_key = EcKey::generate(&group)?;
+  let mut salt = [0; 16];
+  rand_bytes(&mut salt)?;
+  encrypt_payload_with(
+    payload,
+    &base64_url_decode(p256dh)?,
+    &base64_url_decode(auth)?,
+    &ephemeral_key,
+    &salt,
+  )
+}
+
+fn encrypt_payload_with(
+  payload: &[u8],
+  ua_public: &[u8],
+  auth_secret: &[u8],
+  as_key: &EcKey<Private>,
+  salt: &[u8; 16],
+) -> Result<Vec<u8>, LemmyError> {
+  // A single record, so the payload has to fit into it with the delimiter and the tag
+  if payload.len() + 17 > RECORD_SIZE as usize {
+    return Err(LemmyError::from_message("push_payload_too_large"));
+  }
+  let group = p256_group()?;
+  let mut ctx = BigNumContext::new()?;
+  let ua_point = EcPoint::from_bytes(&group, ua_public, &mut ctx)?;
+  let ua_key = PKey::from_ec_key(EcKey::from_public_key(&group, &ua_point)?)?;
+  let as_pkey = PKey::from_ec_key(as_key.to_owned())?;
+  let mut deriver = Deriver::new(&as_pkey)?;
+  deriver.set_peer(&ua_key)?;
+  let ecdh_secret = deriver.derive_to_vec()?;
+  let as_public = public_key_bytes(as_key)?;
+
+  let mut key_info = b"WebPush: info\0".to_vec();
+  key_info.extend_from_slice(ua_public);
+  key_info.extend_from_slice(&as_public);
+  let ikm = hkdf(auth_secret, &ecdh_secret, &key_info, 32)?;
+  let cek = hkdf(salt, &ikm, b"Content-Encoding: aes128gcm\0", 16)?;
+  let nonce = hkdf(salt, &ikm, b"Content-Encoding: nonce\0", 12)?;
+
+  // 0x02 marks the last (and only) record, without further padding
+  let mut plaintext = payload.to_vec();
+  plaintext.push(2);
+  let mut tag = [0; 16];
+  let ciphertext = encrypt_aead(
+    Cipher::aes_128_gcm(),
+    &cek,
+    Some(&nonce),
+    &[],
+    &plaintext,
+    &mut tag,
+  )?;
+
+  let mut body = salt.to_vec();
+  body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+  body.push(as_public.len() as u8);
+  body.extend_from_slice(&as_public);
+  body.extend_from_slice(&ciphertext);
+  body.extend_from_slice(&tag);
+  Ok(body)
+}
+
+/// HKDF with SHA-256, for output lengths of at most one hash
+fn hkdf(salt: &[u8], ikm: &[u8], info: &[u8], length: usize) -> Result<Vec<u8>, LemmyError> {
+  let prk = hmac_sha256(salt, ikm)?;
+  let mut input = info.to_vec();
+  input.push(1);
+  let mut output = hmac_sha256(&prk, &input)?;
+  output.truncate(length);
+  Ok(output)
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Result<Vec<u8>, LemmyError> {
+  let key = PKey::hmac(key)?;
+  let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+  signer.update(data)?;
+  Ok(signer.sign_to_vec()?)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use openssl::bn::BigNum;
+
+  #[test]
+  fn test_encrypt_payload_rfc8291_example() {
+    // https://www.rfc-editor.org/rfc/rfc8291#appendix-A
+    let group = p256_group().unwrap();
+    let mut ctx = BigNumContext::new().unwrap();
+    let as_private = BigNum::from_slice(
+      &base64_url_decode("yfWPiYE-n46HLnH0KqZOF1fJJU3MYrct3AELtAQ-oRw").unwrap(),
+    )
+    .unwrap();
+    let mut as_public = EcPoint::new(&group).unwrap();
+    as_public.mul_generator(&group, &as_private, &ctx).unwrap();
+    let as_key = EcKey::from_private_components(&group, &as_private, &as_public).unwrap();
+    let salt: [u8; 16] = base64_url_decode("DGv6ra1nlYgDCS1FRnbzlw")
+      .unwrap()
+      .try_into()
+      .unwrap();
+
+    let body = encrypt_payload_with(
+      b"When I grow up, I want to be a watermelon",
+      &base64_url_decode(
+        "BCVxsr7N_eNgVRqvHtD0zTZsEc6-VV-JvLexhqUzORcxaOzi6-AYWXvTBHm4bjyPjs7Vd8pZGH6SRpkNtoIAiw4",
+      )
+      .unwrap(),
+      &base64_url_decode("BTBZMqHH6r4Tts7J_aSIgg").unwrap(),
+      &as_key,
+      &salt,
+    )
+    .unwrap();
+
+    assert_eq!(
+      "DGv6ra1nlYgDCS1FRnbzlwAAEABBBP4z9KsN6nGRTbVYI_c7VJSPQTBtkgcy27mlmlMoZIIgDll6e3vCYLocInmYWAmS6TlzAC8wEqKK6PBru3jl7A_yl95bQpu6cVPTpK4Mqgkf1CXztLVBSt2Ks3oZwbuwXPXLWyouBWLVWGNWQexSgSxsj_Qulcy4a-fN",
+      base64_url_encode(&body)
+    );
+    assert_eq!(
+      public_key_bytes(&as_key).unwrap(),
+      as_public
+        .to_bytes(&group, PointConversionForm::UNCOMPRESSED, &mut ctx)
+        .unwrap()
+    );
+  }
+
+  #[test]
+  fn test_validate_push_endpoint() {
+    assert!(validate_push_endpoint("https://fcm.googleapis.com/fcm/send/abc").is_ok());
+    assert!(validate_push_endpoint("https://updates.push.services.mozilla.com:443/wpush").is_ok());
+    for invalid in [
+      "http://push.example.com/abc",
+      "https://push.example.com:8080/abc",
+      "https://127.0.0.1/abc",
+      "https://[::1]/abc",
+      "https://10.0.0.1/abc",
+      "https://localhost/abc",
+      "https://metadata/abc",
+      "https://push.localhost/abc",
+      "https://router.lan/abc",
+      "not a url",
+    ] {
+      assert!(validate_push_endpoint(invalid).is_err(), "{}", invalid);
+    }
+  }
+
+  #[test]
+  fn test_is_public_ip() {
+    for public in ["1.1.1.1", "142.250.74.74", "2a00:1450:4001::200e"] {
+      assert!(is_public_ip(&public.parse().unwrap()), "{}", public);
+    }
+    for internal in [
+      "127.0.0.1",
+      "10.1.2.3",
+      "172.16.0.1",
+      "192.168.1.1",
+      "169.254.169.254",
+      "100.64.0.1",
+      "0.0.0.0",
+      "::1",
+      "::",
+      "fd00::1",
+      "fe80::1",
+      "::ffff:127.0.0.1",
+    ] {
+      assert!(!is_public_ip(&internal.parse().unwrap()), "{}", internal);
+    }
+  }
+
+  #[test]
+  fn test_vapid_authorization() {
+    let keys = generate_vapid_keys().unwrap();
+    let endpoint = Url::parse("https://push.example.com/send/abc").unwrap();
+    let header = vapid_authorization(&endpoint, &keys, "https://lemmy.example.com").unwrap();
+    assert!(header.starts_with("vapid t="));
+    assert!(header.ends_with(&format!(", k={}", keys.public_key)));
+    assert_eq!(65, base64_url_decode(&keys.public_key).unwrap().len());
+  }
+}
@@ -103,16 +103,31 @@ pub enum UserOperation {
   CreatePostLike,
   LockPost,
   StickyPost,
+  ApprovePost,
+  ApproveComment,
+  ListModQueue,
   MarkPostAsRead,
   SavePost,
+  SubscribeToThread,
+  MuteThread,
   CreatePostReport,
   ResolvePostReport,
   ListPostReports,
   GetReportCount,
   GetUnreadCount,
   VerifyEmail,
+  Unsubscribe,
+  GetPushPublicKey,
+  CreatePushSubscription,
+  DeletePushSubscription,
+  ListPushSubscriptions,
   FollowCommunity,
+  ListPendingFollows,
+  ApproveFollow,
+  SubscribeToCommunityPosts,
   GetReplies,
+  GetPostNotifications,
+  MarkPostNotificationAsRead,
   GetPersonMentions,
   MarkPersonMentionAsRead,
   MarkCommentReplyAsRead,
@@ -130,6 +145,21 @@ pub enum UserOperation {
   MarkAllAsRead,
   SaveUserSettings,
   TransferCommunity,
+  BackfillCommunity,
+  ListAutomodRules,
+  CreateAutomodRule,
+  EditAutomodRule,
+  DeleteAutomodRule,
+  GetWikiPage,
+  ListWikiPages,
+  CreateWikiPage,
+  EditWikiPage,
+  DeleteWikiPage,
+  ListWikiPageRevisions,
+  GetWikiPageDiff,
+  RevertWikiPage,
+  AddWikiContributor,
+  ListWikiContributors,
   LeaveAdmin,
   PasswordReset,
   PasswordChange,
@@ -142,10 +172,21 @@ pub enum UserOperation {
   GetSiteMetadata,
   BlockCommunity,
   BlockPerson,
+  FollowPerson,
   PurgePerson,
   PurgeCommunity,
   PurgePost,
   PurgeComment,
+  ListSlurFilterEntries,
+  CreateSlurFilterEntry,
+  EditSlurFilterEntry,
+  DeleteSlurFilterEntry,
+  ListFailedEmails,
+  ListActivityDeliveries,
+  ListRelays,
+  AddRelay,
+  RemoveRelay,
+  RotateKeys,
 }
 
 #[derive(EnumString, Display, Debug, Clone)]
@@ -161,6 +202,12 @@ pub enum UserOperationCrud {
   EditCommunity,
   DeleteCommunity,
   RemoveCommunity,
+  // Multi community
+  CreateMultiCommunity,
+  GetMultiCommunity,
+  ListMultiCommunities,
+  EditMultiCommunity,
+  DeleteMultiCommunity,
   // Post
   CreatePost,
   GetPost,
@@ -168,6 +215,7 @@ pub enum UserOperationCrud {
   EditPost,
   DeletePost,
   RemovePost,
+  CrosspostPost,
   // Comment
   CreateComment,
   GetComment,
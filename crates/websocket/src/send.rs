@@ -8,16 +8,27 @@ use lemmy_api_common::{
   community::CommunityResponse,
   person::PrivateMessageResponse,
   post::PostResponse,
-  utils::{blocking, check_person_block, get_user_lang, send_email_to_user},
+  push::{send_push_notification, PushCategory, PushNotification},
+  utils::{
+    blocking,
+    check_community_visible,
+    check_person_block,
+    get_user_lang,
+    post_notification_email,
+    send_email_to_user,
+  },
 };
 use lemmy_db_schema::{
   newtypes::{CommentId, CommunityId, LocalUserId, PersonId, PostId, PrivateMessageId},
   source::{
     comment::Comment,
     comment_reply::{CommentReply, CommentReplyForm},
+    community::Community,
     person::Person,
     person_mention::{PersonMention, PersonMentionForm},
     post::Post,
+    post_notification::{CommunityPostSubscription, PostNotification, PostNotificationForm},
+    thread_subscription::{ThreadMute, ThreadSubscription},
   },
   traits::{Crud, DeleteableOrRemoveable},
   SubscribedType,
@@ -25,6 +36,7 @@ use lemmy_db_schema::{
 use lemmy_db_views::structs::{CommentView, LocalUserView, PostView, PrivateMessageView};
 use lemmy_db_views_actor::structs::CommunityView;
 use lemmy_utils::{error::LemmyError, utils::MentionData, ConnectionId};
+use tracing::error;
 
 #[tracing::instrument(skip_all)]
 pub async fn send_post_ws_message<OP: ToString + Send + OperationType + 'static>(
@@ -41,11 +53,14 @@ pub async fn send_post_ws_message<OP: ToString + Send + OperationType + 'static>
 
   let res = PostResponse { post_view };
 
-  context.chat_server().do_send(SendPost {
-    op,
-    post: res.clone(),
-    websocket_id,
-  });
+  // Pending posts are only returned to the creator, until a mod approves them
+  if !res.post_view.post.pending {
+    context.chat_server().do_send(SendPost {
+      op,
+      post: res.clone(),
+      websocket_id,
+    });
+  }
 
   Ok(res)
 }
@@ -87,11 +102,14 @@ pub async fn send_comment_ws_message<OP: ToString + Send + OperationType + 'stat
     form_id: None,
   };
 
-  context.chat_server().do_send(SendComment {
-    op,
-    comment: res.clone(),
-    websocket_id,
-  });
+  // Pending comments are only returned to the creator, until a mod approves them
+  if !res.comment_view.comment.pending {
+    context.chat_server().do_send(SendComment {
+      op,
+      comment: res.clone(),
+      websocket_id,
+    });
+  }
 
   // The recipient_ids should be empty for returns
   res.recipient_ids = Vec::new();
@@ -192,6 +210,10 @@ pub async fn send_local_notifs(
     })
     .await?;
     if let Ok(mention_user_view) = user_view {
+      if is_thread_muted(mention_user_view.person.id, comment, context).await? {
+        continue;
+      }
+
       // TODO
       // At some point, make it so you can't tag the parent creator either
       // This can cause two notifications, one for reply and the other for mention
@@ -218,8 +240,18 @@ pub async fn send_local_notifs(
           &mention_user_view,
           &lang.notification_mentioned_by_subject(&person.name),
           &lang.notification_mentioned_by_body(&comment.content, &inbox_link, &person.name),
+          context.pool(),
+          context.secret(),
           context.settings(),
         )
+        .await;
+        push_comment_notification(
+          &mention_user_view,
+          PushCategory::Mention,
+          lang.notification_mentioned_by_subject(&person.name),
+          comment,
+          context,
+        );
       }
     }
   }
@@ -240,7 +272,10 @@ pub async fn send_local_notifs(
       .is_err();
 
     // Don't send a notif to yourself
-    if parent_comment.creator_id != person.id && !creator_blocked {
+    if parent_comment.creator_id != person.id
+      && !creator_blocked
+      && !is_thread_muted(parent_creator_id, comment, context).await?
+    {
       let user_view = blocking(context.pool(), move |conn| {
         LocalUserView::read_person(conn, parent_creator_id)
       })
@@ -268,8 +303,18 @@ pub async fn send_local_notifs(
             &parent_user_view,
             &lang.notification_comment_reply_subject(&person.name),
             &lang.notification_comment_reply_body(&comment.content, &inbox_link, &person.name),
+            context.pool(),
+            context.secret(),
             context.settings(),
           )
+          .await;
+          push_comment_notification(
+            &parent_user_view,
+            PushCategory::Reply,
+            lang.notification_comment_reply_subject(&person.name),
+            comment,
+            context,
+          );
         }
       }
     }
@@ -280,7 +325,10 @@ pub async fn send_local_notifs(
       .await
       .is_err();
 
-    if post.creator_id != person.id && !creator_blocked {
+    if post.creator_id != person.id
+      && !creator_blocked
+      && !is_thread_muted(post.creator_id, comment, context).await?
+    {
       let creator_id = post.creator_id;
       let parent_user = blocking(context.pool(), move |conn| {
         LocalUserView::read_person(conn, creator_id)
@@ -309,12 +357,262 @@ pub async fn send_local_notifs(
             &parent_user_view,
             &lang.notification_post_reply_subject(&person.name),
             &lang.notification_post_reply_body(&comment.content, &inbox_link, &person.name),
+            context.pool(),
+            context.secret(),
             context.settings(),
           )
+          .await;
+          push_comment_notification(
+            &parent_user_view,
+            PushCategory::Reply,
+            lang.notification_post_reply_subject(&person.name),
+            comment,
+            context,
+          );
         }
       }
     }
   }
 
+  // Send comment_reply to everyone who subscribed to the post or to a parent comment
+  let comment_ = comment.clone();
+  let subscriber_ids = blocking(context.pool(), move |conn| {
+    ThreadSubscription::list_subscribers(conn, &comment_)
+  })
+  .await??;
+  for subscriber_id in subscriber_ids {
+    if subscriber_id == person.id
+      || check_person_block(person.id, subscriber_id, context.pool())
+        .await
+        .is_err()
+      || is_thread_muted(subscriber_id, comment, context).await?
+    {
+      continue;
+    }
+    let user_view = blocking(context.pool(), move |conn| {
+      LocalUserView::read_person(conn, subscriber_id)
+    })
+    .await?;
+    let subscriber_view = match user_view {
+      // Skip those who were already notified as mentioned or as parent creator
+      Ok(view) if !recipient_ids.contains(&view.local_user.id) => view,
+      _ => continue,
+    };
+    // Subscribers who lost access to a private community aren't notified anymore
+    if check_community_visible(
+      post.community_id,
+      Some(&subscriber_view.person),
+      context.pool(),
+    )
+    .await
+    .is_err()
+    {
+      continue;
+    }
+    recipient_ids.push(subscriber_view.local_user.id);
+
+    let comment_reply_form = CommentReplyForm {
+      recipient_id: subscriber_view.person.id,
+      comment_id: comment.id,
+      read: None,
+    };
+
+    // Allow this to fail softly, since comment edits might re-update or replace it
+    blocking(context.pool(), move |conn| {
+      CommentReply::create(conn, &comment_reply_form)
+    })
+    .await?
+    .ok();
+
+    if do_send_email {
+      let lang = get_user_lang(&subscriber_view);
+      send_email_to_user(
+        &subscriber_view,
+        &lang.notification_comment_reply_subject(&person.name),
+        &lang.notification_comment_reply_body(&comment.content, &inbox_link, &person.name),
+        context.pool(),
+        context.secret(),
+        context.settings(),
+      )
+      .await;
+      push_comment_notification(
+        &subscriber_view,
+        PushCategory::Reply,
+        lang.notification_comment_reply_subject(&person.name),
+        comment,
+        context,
+      );
+    }
+  }
+
   Ok(recipient_ids)
 }
+
+/// Whether the person muted the thread which the comment belongs to
+async fn is_thread_muted(
+  person_id: PersonId,
+  comment: &Comment,
+  context: &LemmyContext,
+) -> Result<bool, LemmyError> {
+  let comment = comment.clone();
+  let muted = blocking(context.pool(), move |conn| {
+    ThreadMute::is_muted(conn, person_id, &comment)
+  })
+  .await??;
+  Ok(muted)
+}
+
+/// Push a notification about a new comment to the user's browsers and apps
+fn push_comment_notification(
+  local_user_view: &LocalUserView,
+  category: PushCategory,
+  title: String,
+  comment: &Comment,
+  context: &LemmyContext,
+) {
+  let notification = PushNotification {
+    category,
+    title,
+    body: comment.content.to_owned(),
+    url: format!(
+      "{}/comment/{}",
+      context.settings().get_protocol_and_hostname(),
+      comment.id
+    ),
+  };
+  send_push_notification(
+    local_user_view.local_user.id,
+    notification,
+    context.pool(),
+    context.secret(),
+    context.settings(),
+  );
+}
+
+/// Push a notification about a new private message to the recipient, if they are local
+#[tracing::instrument(skip_all)]
+pub async fn send_pm_push_notification(
+  private_message_view: &PrivateMessageView,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  if !private_message_view.recipient.local {
+    return Ok(());
+  }
+  let recipient_id = private_message_view.recipient.id;
+  let local_recipient = blocking(context.pool(), move |conn| {
+    LocalUserView::read_person(conn, recipient_id)
+  })
+  .await??;
+  let lang = get_user_lang(&local_recipient);
+  let notification = PushNotification {
+    category: PushCategory::PrivateMessage,
+    title: lang.notification_private_message_subject(&private_message_view.creator.name),
+    body: private_message_view.private_message.content.to_owned(),
+    url: format!("{}/inbox", context.settings().get_protocol_and_hostname()),
+  };
+  send_push_notification(
+    local_recipient.local_user.id,
+    notification,
+    context.pool(),
+    context.secret(),
+    context.settings(),
+  );
+  Ok(())
+}
+
+/// Notifies everyone who subscribed to new posts in the community of the post. Posts which are
+/// removed or held for approval are skipped, approving them sends the notifications instead.
+/// Communities can have many subscribers, so this runs in the background.
+pub fn send_post_notifs(post: Post, context: &LemmyContext) {
+  if post.removed || post.deleted || post.pending {
+    return;
+  }
+  let context = context.clone();
+  actix_web::rt::spawn(async move {
+    if let Err(e) = notify_post_subscribers(&post, &context).await {
+      error!("Failed to send notifications for post {}: {}", post.id, e);
+    }
+  });
+}
+
+#[tracing::instrument(skip_all)]
+async fn notify_post_subscribers(post: &Post, context: &LemmyContext) -> Result<(), LemmyError> {
+  let creator_id = post.creator_id;
+  let community_id = post.community_id;
+  let (creator, community, subscriber_ids) = blocking(context.pool(), move |conn| {
+    let creator = Person::read(conn, creator_id)?;
+    let community = Community::read(conn, community_id)?;
+    let subscriber_ids = CommunityPostSubscription::list_subscribers(conn, community_id)?;
+    Ok::<_, diesel::result::Error>((creator, community, subscriber_ids))
+  })
+  .await??;
+
+  for subscriber_id in subscriber_ids {
+    if subscriber_id == creator_id
+      || check_person_block(creator_id, subscriber_id, context.pool())
+        .await
+        .is_err()
+    {
+      continue;
+    }
+    let user_view = blocking(context.pool(), move |conn| {
+      LocalUserView::read_person(conn, subscriber_id)
+    })
+    .await?;
+    let subscriber_view = match user_view {
+      Ok(view) => view,
+      Err(_) => continue,
+    };
+    // Subscribers who lost access to a private community aren't notified anymore
+    if check_community_visible(community_id, Some(&subscriber_view.person), context.pool())
+      .await
+      .is_err()
+    {
+      continue;
+    }
+
+    let form = PostNotificationForm {
+      recipient_id: subscriber_id,
+      post_id: post.id,
+      read: None,
+    };
+    let created = blocking(context.pool(), move |conn| {
+      PostNotification::create_if_new(conn, &form)
+    })
+    .await??;
+    // The same post was received twice, don't notify again
+    if created.is_none() {
+      continue;
+    }
+
+    let (subject, body) =
+      post_notification_email(post, &creator.name, &community.name, context.settings());
+    send_email_to_user(
+      &subscriber_view,
+      &subject,
+      &body,
+      context.pool(),
+      context.secret(),
+      context.settings(),
+    )
+    .await;
+    let notification = PushNotification {
+      category: PushCategory::NewPost,
+      title: subject,
+      body: post.name.to_owned(),
+      url: format!(
+        "{}/post/{}",
+        context.settings().get_protocol_and_hostname(),
+        post.id
+      ),
+    };
+    send_push_notification(
+      subscriber_view.local_user.id,
+      notification,
+      context.pool(),
+      context.secret(),
+      context.settings(),
+    );
+  }
+  Ok(())
+}
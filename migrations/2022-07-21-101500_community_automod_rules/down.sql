@@ -0,0 +1 @@
+drop table automod_rule;
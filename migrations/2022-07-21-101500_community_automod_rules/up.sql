@@ -0,0 +1,11 @@
+-- Automoderator rules, editable by community mods. The rule itself is stored as json,
+-- tagged by its type, so that new kinds of rules dont need a migration.
+create table automod_rule (
+  id serial primary key,
+  community_id int references community on update cascade on delete cascade not null,
+  rule jsonb not null,
+  published timestamp not null default now(),
+  updated timestamp null
+);
+
+create index idx_automod_rule_community on automod_rule (community_id);
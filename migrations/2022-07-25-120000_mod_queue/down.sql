@@ -0,0 +1,5 @@
+drop table mod_approve_comment;
+drop table mod_approve_post;
+alter table post drop column pending;
+alter table comment drop column pending;
+alter table community drop column require_approval;
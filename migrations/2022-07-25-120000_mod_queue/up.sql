@@ -0,0 +1,25 @@
+-- Posts and comments which are held for approval by a mod
+alter table post add column pending boolean default false not null;
+alter table comment add column pending boolean default false not null;
+alter table community add column require_approval boolean default false not null;
+
+create index idx_post_pending on post (community_id) where pending;
+create index idx_comment_pending on comment (post_id) where pending;
+
+create table mod_approve_post (
+  id serial primary key,
+  mod_person_id int references person on update cascade on delete cascade not null,
+  post_id int references post on update cascade on delete cascade not null,
+  reason text,
+  approved boolean default true,
+  when_ timestamp not null default now()
+);
+
+create table mod_approve_comment (
+  id serial primary key,
+  mod_person_id int references person on update cascade on delete cascade not null,
+  comment_id int references comment on update cascade on delete cascade not null,
+  reason text,
+  approved boolean default true,
+  when_ timestamp not null default now()
+);
@@ -0,0 +1 @@
+drop table local_user_keyword_filter;
@@ -0,0 +1,12 @@
+-- Personal keyword filters. Posts and comments matching one of them are hidden from the user,
+-- either everywhere or only in a single community.
+create table local_user_keyword_filter (
+  id serial primary key,
+  local_user_id int references local_user on update cascade on delete cascade not null,
+  community_id int references community on update cascade on delete cascade,
+  keyword text not null,
+  regex boolean default false not null,
+  published timestamp not null default now()
+);
+
+create index idx_local_user_keyword_filter_local_user on local_user_keyword_filter (local_user_id);
@@ -0,0 +1 @@
+drop table slur_filter_entry;
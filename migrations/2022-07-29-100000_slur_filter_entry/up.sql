@@ -0,0 +1,8 @@
+-- Slur filter entries managed by admins, in addition to the slur_filter from the config file
+create table slur_filter_entry (
+  id serial primary key,
+  pattern text not null,
+  action text not null check (action in ('reject', 'replace', 'hold')),
+  published timestamp not null default now(),
+  updated timestamp null
+);
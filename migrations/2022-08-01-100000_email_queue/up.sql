@@ -0,0 +1,16 @@
+-- Outgoing emails, sent by a background task. Emails which couldn't be sent after several
+-- attempts are marked as failed and kept for inspection by admins.
+create table email_queue (
+  id serial primary key,
+  to_email text not null,
+  to_name text not null,
+  subject text not null,
+  html text not null,
+  attempts int default 0 not null,
+  next_attempt timestamp not null default now(),
+  last_error text,
+  failed boolean default false not null,
+  published timestamp not null default now()
+);
+
+create index idx_email_queue_next_attempt on email_queue (next_attempt) where not failed;
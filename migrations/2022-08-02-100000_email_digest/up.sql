@@ -0,0 +1,5 @@
+-- How notification emails are sent to a user: one by one, batched into a digest, or not at all
+alter table local_user add column email_digest_frequency text not null default 'immediate'
+  check (email_digest_frequency in ('immediate', 'hourly', 'daily', 'off'));
+-- Notifications published after this time are included in the next digest
+alter table local_user add column last_email_digest timestamp not null default now();
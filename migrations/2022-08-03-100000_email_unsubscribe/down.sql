@@ -0,0 +1 @@
+alter table email_queue drop column unsubscribe_url;
@@ -0,0 +1,2 @@
+-- Link for the List-Unsubscribe header of notification emails
+alter table email_queue add column unsubscribe_url text;
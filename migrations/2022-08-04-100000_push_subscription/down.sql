@@ -0,0 +1,3 @@
+drop table push_subscription;
+alter table secret drop column vapid_private_key;
+alter table secret drop column vapid_public_key;
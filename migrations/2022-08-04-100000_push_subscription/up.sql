@@ -0,0 +1,19 @@
+-- Keys for authenticating Web Push messages (VAPID), generated on startup
+alter table secret add column vapid_private_key text;
+alter table secret add column vapid_public_key text;
+
+-- A browser or app which receives push notifications for a user
+create table push_subscription (
+  id serial primary key,
+  local_user_id int references local_user on update cascade on delete cascade not null,
+  endpoint text not null,
+  p256dh text not null,
+  auth text not null,
+  notify_mentions boolean default true not null,
+  notify_replies boolean default true not null,
+  notify_private_messages boolean default true not null,
+  published timestamp not null default now(),
+  unique (local_user_id, endpoint)
+);
+
+create index idx_push_subscription_local_user on push_subscription (local_user_id);
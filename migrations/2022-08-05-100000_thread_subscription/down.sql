@@ -0,0 +1,2 @@
+drop table thread_subscription;
+drop table thread_mute;
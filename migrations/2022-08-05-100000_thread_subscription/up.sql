@@ -0,0 +1,28 @@
+-- Subscribers are notified about all new comments in a post, or below a comment if comment_id
+-- is set. Muted threads don't create any notifications.
+create table thread_subscription (
+  id serial primary key,
+  person_id int references person on update cascade on delete cascade not null,
+  post_id int references post on update cascade on delete cascade not null,
+  comment_id int references comment on update cascade on delete cascade,
+  published timestamp not null default now()
+);
+
+create unique index idx_thread_subscription_post on thread_subscription (person_id, post_id)
+  where comment_id is null;
+create unique index idx_thread_subscription_comment on thread_subscription (person_id, comment_id)
+  where comment_id is not null;
+create index idx_thread_subscription_post_id on thread_subscription (post_id);
+
+create table thread_mute (
+  id serial primary key,
+  person_id int references person on update cascade on delete cascade not null,
+  post_id int references post on update cascade on delete cascade not null,
+  comment_id int references comment on update cascade on delete cascade,
+  published timestamp not null default now()
+);
+
+create unique index idx_thread_mute_post on thread_mute (person_id, post_id)
+  where comment_id is null;
+create unique index idx_thread_mute_comment on thread_mute (person_id, comment_id)
+  where comment_id is not null;
@@ -0,0 +1,3 @@
+alter table push_subscription drop column notify_new_posts;
+drop table post_notification;
+drop table community_post_subscription;
@@ -0,0 +1,22 @@
+-- Communities for which a person wants to be notified about every new post
+create table community_post_subscription (
+  id serial primary key,
+  person_id int references person on update cascade on delete cascade not null,
+  community_id int references community on update cascade on delete cascade not null,
+  published timestamp not null default now(),
+  unique (person_id, community_id)
+);
+
+create index idx_community_post_subscription_community on community_post_subscription (community_id);
+
+-- Inbox entries for new posts, like comment_reply and person_mention for comments
+create table post_notification (
+  id serial primary key,
+  recipient_id int references person on update cascade on delete cascade not null,
+  post_id int references post on update cascade on delete cascade not null,
+  read boolean default false not null,
+  published timestamp not null default now(),
+  unique (recipient_id, post_id)
+);
+
+alter table push_subscription add column notify_new_posts boolean default true not null;
@@ -0,0 +1,11 @@
+-- Person to person follows. Pending is true until a remote person accepts the follow.
+create table person_follower (
+  id serial primary key,
+  person_id int references person on update cascade on delete cascade not null,
+  follower_id int references person on update cascade on delete cascade not null,
+  published timestamp not null default now(),
+  pending boolean not null default false,
+  unique (follower_id, person_id)
+);
+
+create index idx_person_follower_person on person_follower (person_id);
@@ -0,0 +1,19 @@
+-- Named feeds which combine the posts of several communities, without following them
+create table multi_community (
+  id serial primary key,
+  creator_id int references person on update cascade on delete cascade not null,
+  name varchar(100) not null,
+  description text,
+  public boolean default false not null,
+  published timestamp not null default now(),
+  updated timestamp,
+  unique (creator_id, name)
+);
+
+create table multi_community_community (
+  id serial primary key,
+  multi_community_id int references multi_community on update cascade on delete cascade not null,
+  community_id int references community on update cascade on delete cascade not null,
+  published timestamp not null default now(),
+  unique (multi_community_id, community_id)
+);
@@ -0,0 +1,3 @@
+drop table wiki_contributor;
+drop table wiki_page_revision;
+drop table wiki_page;
@@ -0,0 +1,36 @@
+-- Markdown wiki pages of a community. The current version is stored in the page itself, all
+-- versions including the current one in wiki_page_revision.
+create table wiki_page (
+  id serial primary key,
+  community_id int references community on update cascade on delete cascade not null,
+  name varchar(100) not null,
+  title varchar(200) not null,
+  body text not null,
+  ap_id varchar(255) not null unique,
+  local boolean default true not null,
+  deleted boolean default false not null,
+  published timestamp not null default now(),
+  updated timestamp,
+  unique (community_id, name)
+);
+
+create table wiki_page_revision (
+  id serial primary key,
+  wiki_page_id int references wiki_page on update cascade on delete cascade not null,
+  editor_id int references person on update cascade on delete cascade not null,
+  title varchar(200) not null,
+  body text not null,
+  summary text,
+  published timestamp not null default now()
+);
+
+create index idx_wiki_page_revision_page on wiki_page_revision (wiki_page_id);
+
+-- People besides the mods who may edit the wiki of a community
+create table wiki_contributor (
+  id serial primary key,
+  community_id int references community on update cascade on delete cascade not null,
+  person_id int references person on update cascade on delete cascade not null,
+  published timestamp not null default now(),
+  unique (community_id, person_id)
+);
@@ -0,0 +1,2 @@
+alter table post drop column crosspost_of;
+alter table post drop column url_canonical;
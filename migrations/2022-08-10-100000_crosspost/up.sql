@@ -0,0 +1,7 @@
+-- The original post of a crosspost, and the canonical form of the post url, which is used to find
+-- other posts linking the same page. Existing urls are canonicalized by a code migration.
+alter table post add column crosspost_of int references post on update cascade on delete set null;
+alter table post add column url_canonical text;
+
+create index idx_post_crosspost_of on post (crosspost_of);
+create index idx_post_url_canonical on post (url_canonical);
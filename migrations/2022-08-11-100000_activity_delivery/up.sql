@@ -0,0 +1,17 @@
+-- Outgoing activities, with one row for each inbox they are sent to. Failed deliveries are
+-- retried with backoff, and kept for inspection by admins once sending is given up on.
+create table activity_delivery (
+  id serial primary key,
+  activity_id int references activity on update cascade on delete cascade not null,
+  inbox text not null,
+  -- The actor whose key signs the request
+  actor_id text not null,
+  attempts int default 0 not null,
+  next_attempt timestamp not null default now(),
+  last_error text,
+  failed boolean default false not null,
+  published timestamp not null default now()
+);
+
+create index idx_activity_delivery_next_attempt on activity_delivery (next_attempt) where not failed;
+create index idx_activity_delivery_inbox on activity_delivery (inbox) where not failed;
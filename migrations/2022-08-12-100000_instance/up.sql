@@ -0,0 +1,18 @@
+-- Remote instances which activities are delivered to, with their delivery health. An instance
+-- whose deliveries keep failing for a long time is considered dead, and only probed occasionally.
+create table instance (
+  id serial primary key,
+  domain text not null unique,
+  last_successful_delivery timestamp,
+  -- Start of the current streak of failed deliveries, reset by a successful delivery
+  failing_since timestamp,
+  last_failed_delivery timestamp,
+  published timestamp not null default now()
+);
+
+-- Instances which are already known as remote sites
+insert into instance (domain)
+select distinct substring(actor_id from '^https?://([^/:]+)')
+from site
+where id <> (select min(id) from site)
+on conflict do nothing;
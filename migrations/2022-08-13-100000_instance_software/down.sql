@@ -0,0 +1,8 @@
+alter table person drop column instance_id;
+alter table community drop column instance_id;
+alter table site drop column instance_id;
+
+alter table instance
+  drop column software,
+  drop column version,
+  drop column last_seen;
@@ -0,0 +1,28 @@
+-- Software of remote instances as reported by nodeinfo, and when they were last seen
+alter table instance
+  add column software text,
+  add column version text,
+  add column last_seen timestamp not null default now();
+
+alter table person add column instance_id int references instance on update cascade on delete set null;
+alter table community add column instance_id int references instance on update cascade on delete set null;
+alter table site add column instance_id int references instance on update cascade on delete set null;
+
+-- Link existing remote actors to their instances
+insert into instance (domain)
+select distinct substring(actor_id from '^https?://([^/:]+)') from person where not local
+union
+select distinct substring(actor_id from '^https?://([^/:]+)') from community where not local
+on conflict do nothing;
+
+update person p set instance_id = i.id
+from instance i
+where not p.local and i.domain = substring(p.actor_id from '^https?://([^/:]+)');
+
+update community c set instance_id = i.id
+from instance i
+where not c.local and i.domain = substring(c.actor_id from '^https?://([^/:]+)');
+
+update site s set instance_id = i.id
+from instance i
+where s.id <> (select min(id) from site) and i.domain = substring(s.actor_id from '^https?://([^/:]+)');
@@ -0,0 +1,14 @@
+-- Relays which the instance actor subscribed to. For Mastodon style relays only the inbox is
+-- known until the relay accepts the follow.
+create table relay (
+  id serial primary key,
+  inbox_url varchar(255) not null unique,
+  actor_id varchar(255) unique,
+  public_key text,
+  follow_id varchar(255) not null unique,
+  -- Object of the follow: the relay actor, or the public collection for Mastodon style relays
+  follow_object varchar(255) not null,
+  accepted boolean not null default false,
+  published timestamp not null default now(),
+  last_refreshed_at timestamp not null default now()
+);
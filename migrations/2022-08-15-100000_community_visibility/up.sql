@@ -0,0 +1,3 @@
+-- Follows of private communities need to be approved by a mod, and only approved followers can
+-- see the posts.
+alter table community add column visibility text not null default 'public';
@@ -0,0 +1 @@
+drop table actor_key_rotation;
@@ -0,0 +1,8 @@
+-- Previous public keys of remote actors which rotated their keys. Signatures made with the
+-- previous key are still accepted for a short time, for activities which were already on the way.
+create table actor_key_rotation (
+  id serial primary key,
+  actor_id varchar(255) not null unique,
+  previous_public_key text not null,
+  rotated_at timestamp not null default now()
+);
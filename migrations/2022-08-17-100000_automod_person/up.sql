@@ -0,0 +1,2 @@
+-- Local bot account in whose name automod actions are written to the modlog and federated
+alter table site add column automod_person_id int references person on update cascade on delete set null;
@@ -11,3 +11,5 @@ sed -i "s/,\$//" "$dest"
 # remove quotes around json keys
 sed -i "s/\"//" "$dest"
 sed -i "s/\"//" "$dest"
+# options which are only for testing, and shouldn't be enabled by copying this file
+sed -i "s/^\([[:space:]]*\)file_transport_dir:/\1# file_transport_dir:/" "$dest"
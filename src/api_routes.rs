@@ -1,8 +1,16 @@
 use actix_web::*;
 use lemmy_api::Perform;
-use lemmy_api_common::{comment::*, community::*, person::*, post::*, site::*, websocket::*};
+use lemmy_api_common::{
+  comment::*,
+  community::*,
+  person::*,
+  post::*,
+  site::*,
+  websocket::*,
+  wiki::*,
+};
 use lemmy_api_crud::PerformCrud;
-use lemmy_utils::rate_limit::RateLimit;
+use lemmy_utils::{claims::UnsubscribeClaims, rate_limit::RateLimit};
 use lemmy_websocket::{routes::chat_route, LemmyContext};
 use serde::Deserialize;
 
@@ -50,6 +58,10 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimit) {
           .route("/hide", web::put().to(route_post::<HideCommunity>))
           .route("/list", web::get().to(route_get_crud::<ListCommunities>))
           .route("/follow", web::post().to(route_post::<FollowCommunity>))
+          .route(
+            "/post_notifications",
+            web::post().to(route_post::<SubscribeToCommunityPosts>),
+          )
           .route("/block", web::post().to(route_post::<BlockCommunity>))
           .route(
             "/delete",
@@ -61,11 +73,63 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimit) {
             web::post().to(route_post_crud::<RemoveCommunity>),
           )
           .route("/transfer", web::post().to(route_post::<TransferCommunity>))
+          .route("/backfill", web::post().to(route_post::<BackfillCommunity>))
           .route("/ban_user", web::post().to(route_post::<BanFromCommunity>))
           .route("/mod", web::post().to(route_post::<AddModToCommunity>))
+          .route("/mod_queue", web::get().to(route_get::<ListModQueue>))
+          .route(
+            "/pending_follows",
+            web::get().to(route_get::<ListPendingFollows>),
+          )
+          .route(
+            "/pending_follows/approve",
+            web::post().to(route_post::<ApproveFollow>),
+          )
+          .route("/automod", web::get().to(route_get::<ListAutomodRules>))
+          .route("/automod", web::post().to(route_post::<CreateAutomodRule>))
+          .route("/automod", web::put().to(route_post::<EditAutomodRule>))
+          .route(
+            "/automod/delete",
+            web::post().to(route_post::<DeleteAutomodRule>),
+          )
+          .route("/wiki", web::get().to(route_get::<GetWikiPage>))
+          .route("/wiki", web::post().to(route_post::<CreateWikiPage>))
+          .route("/wiki", web::put().to(route_post::<EditWikiPage>))
+          .route("/wiki/list", web::get().to(route_get::<ListWikiPages>))
+          .route("/wiki/delete", web::post().to(route_post::<DeleteWikiPage>))
+          .route(
+            "/wiki/revisions",
+            web::get().to(route_get::<ListWikiPageRevisions>),
+          )
+          .route("/wiki/diff", web::get().to(route_get::<GetWikiPageDiff>))
+          .route("/wiki/revert", web::post().to(route_post::<RevertWikiPage>))
+          .route(
+            "/wiki/contributor",
+            web::get().to(route_get::<ListWikiContributors>),
+          )
+          .route(
+            "/wiki/contributor",
+            web::post().to(route_post::<AddWikiContributor>),
+          )
           .route("/join", web::post().to(route_post::<CommunityJoin>))
           .route("/mod/join", web::post().to(route_post::<ModJoin>)),
       )
+      // Multi community
+      .service(
+        web::scope("/multi_community")
+          .wrap(rate_limit.message())
+          .route("", web::get().to(route_get_crud::<GetMultiCommunity>))
+          .route("", web::post().to(route_post_crud::<CreateMultiCommunity>))
+          .route("", web::put().to(route_post_crud::<EditMultiCommunity>))
+          .route(
+            "/delete",
+            web::post().to(route_post_crud::<DeleteMultiCommunity>),
+          )
+          .route(
+            "/list",
+            web::get().to(route_get_crud::<ListMultiCommunities>),
+          ),
+      )
       // Post
       .service(
         // Handle POST to /post separately to add the post() rate limitter
@@ -74,6 +138,12 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimit) {
           .wrap(rate_limit.post())
           .route(web::post().to(route_post_crud::<CreatePost>)),
       )
+      .service(
+        web::resource("/post/crosspost")
+          .guard(guard::Post())
+          .wrap(rate_limit.post())
+          .route(web::post().to(route_post_crud::<CrosspostPost>)),
+      )
       .service(
         web::scope("/post")
           .wrap(rate_limit.message())
@@ -86,10 +156,16 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimit) {
             web::post().to(route_post::<MarkPostAsRead>),
           )
           .route("/lock", web::post().to(route_post::<LockPost>))
+          .route("/approve", web::post().to(route_post::<ApprovePost>))
           .route("/sticky", web::post().to(route_post::<StickyPost>))
           .route("/list", web::get().to(route_get_crud::<GetPosts>))
           .route("/like", web::post().to(route_post::<CreatePostLike>))
           .route("/save", web::put().to(route_post::<SavePost>))
+          .route(
+            "/subscribe",
+            web::post().to(route_post::<SubscribeToThread>),
+          )
+          .route("/mute", web::post().to(route_post::<MuteThread>))
           .route("/join", web::post().to(route_post::<PostJoin>))
           .route("/report", web::post().to(route_post::<CreatePostReport>))
           .route(
@@ -123,6 +199,7 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimit) {
           )
           .route("/like", web::post().to(route_post::<CreateCommentLike>))
           .route("/save", web::put().to(route_post::<SaveComment>))
+          .route("/approve", web::post().to(route_post::<ApproveComment>))
           .route("/list", web::get().to(route_get_crud::<GetComments>))
           .route("/report", web::post().to(route_post::<CreateCommentReport>))
           .route(
@@ -176,11 +253,20 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimit) {
             web::post().to(route_post::<MarkPersonMentionAsRead>),
           )
           .route("/replies", web::get().to(route_get::<GetReplies>))
+          .route(
+            "/post_notifications",
+            web::get().to(route_get::<GetPostNotifications>),
+          )
+          .route(
+            "/post_notifications/mark_as_read",
+            web::post().to(route_post::<MarkPostNotificationAsRead>),
+          )
           .route("/join", web::post().to(route_post::<UserJoin>))
           // Admin action. I don't like that it's in /user
           .route("/ban", web::post().to(route_post::<BanPerson>))
           .route("/banned", web::get().to(route_get::<GetBannedPersons>))
           .route("/block", web::post().to(route_post::<BlockPerson>))
+          .route("/follow", web::post().to(route_post::<FollowPerson>))
           // Account actions. I don't like that they're in /user maybe /accounts
           .route("/login", web::post().to(route_post::<Login>))
           .route(
@@ -211,6 +297,23 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimit) {
           .route("/report_count", web::get().to(route_get::<GetReportCount>))
           .route("/unread_count", web::get().to(route_get::<GetUnreadCount>))
           .route("/verify_email", web::post().to(route_post::<VerifyEmail>))
+          // The token is passed as query parameter, also for the one-click POST from mail clients.
+          // Only POST changes anything, GET shows a confirmation page.
+          .route("/unsubscribe", web::get().to(unsubscribe_confirmation))
+          .route("/unsubscribe", web::post().to(route_get::<Unsubscribe>))
+          .route(
+            "/push/public_key",
+            web::get().to(route_get::<GetPushPublicKey>),
+          )
+          .route("/push", web::get().to(route_get::<ListPushSubscriptions>))
+          .route(
+            "/push",
+            web::post().to(route_post::<CreatePushSubscription>),
+          )
+          .route(
+            "/push/delete",
+            web::post().to(route_post::<DeletePushSubscription>),
+          )
           .route("/leave_admin", web::post().to(route_post::<LeaveAdmin>)),
       )
       // Admin Actions
@@ -229,7 +332,35 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimit) {
           .route(
             "/registration_application/approve",
             web::put().to(route_post::<ApproveRegistrationApplication>),
-          ),
+          )
+          .route(
+            "/slur_filter",
+            web::get().to(route_get::<ListSlurFilterEntries>),
+          )
+          .route(
+            "/slur_filter",
+            web::post().to(route_post::<CreateSlurFilterEntry>),
+          )
+          .route(
+            "/slur_filter",
+            web::put().to(route_post::<EditSlurFilterEntry>),
+          )
+          .route(
+            "/slur_filter/delete",
+            web::post().to(route_post::<DeleteSlurFilterEntry>),
+          )
+          .route(
+            "/email/failed",
+            web::get().to(route_get::<ListFailedEmails>),
+          )
+          .route(
+            "/federation/deliveries",
+            web::get().to(route_get::<ListActivityDeliveries>),
+          )
+          .route("/relay", web::get().to(route_get::<ListRelays>))
+          .route("/relay", web::post().to(route_post::<AddRelay>))
+          .route("/relay/delete", web::post().to(route_post::<RemoveRelay>))
+          .route("/rotate_keys", web::post().to(route_post::<RotateKeys>)),
       )
       .service(
         web::scope("/admin/purge")
@@ -242,6 +373,34 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimit) {
   );
 }
 
+/// Links in emails are opened with GET, also by the link scanners of mail providers, so the link
+/// only shows a form which sends the POST request that actually unsubscribes.
+async fn unsubscribe_confirmation(
+  data: web::Query<Unsubscribe>,
+  context: web::Data<LemmyContext>,
+) -> HttpResponse {
+  // Only tokens which we signed are put into the page
+  if UnsubscribeClaims::decode(&data.token, &context.secret().jwt_secret).is_err() {
+    return HttpResponse::BadRequest().body("invalid_unsubscribe_token");
+  }
+  HttpResponse::Ok()
+    .content_type("text/html; charset=utf-8")
+    .body(format!(
+      r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"><title>Unsubscribe</title></head>
+<body>
+<form method="post" action="?token={}">
+<p>Stop receiving notification emails from {}?</p>
+<button type="submit">Unsubscribe</button>
+</form>
+</body>
+</html>"#,
+      data.token,
+      context.settings().hostname
+    ))
+}
+
 async fn perform<Request>(
   data: Request,
   context: web::Data<LemmyContext>,
@@ -4,6 +4,7 @@ use diesel::{
   sql_types::{Nullable, Text},
   *,
 };
+use lemmy_api_common::automod::create_automod_person;
 use lemmy_apub::{
   generate_followers_url,
   generate_inbox_url,
@@ -19,12 +20,13 @@ use lemmy_db_schema::{
     person::{Person, PersonForm},
     post::Post,
     private_message::PrivateMessage,
+    secret::Secret,
     site::{Site, SiteForm},
   },
   traits::Crud,
   utils::naive_now,
 };
-use lemmy_utils::error::LemmyError;
+use lemmy_utils::{error::LemmyError, utils::canonicalize_url, web_push::generate_vapid_keys};
 use std::default::Default;
 use tracing::info;
 use url::Url;
@@ -42,6 +44,9 @@ pub fn run_advanced_migrations(
   apub_columns_2021_02_02(conn)?;
   instance_actor_2022_01_28(conn, protocol_and_hostname)?;
   regenerate_public_keys_2022_07_05(conn)?;
+  vapid_keys_2022_08_04(conn)?;
+  post_url_canonical_2022_08_10(conn)?;
+  create_automod_person(conn, protocol_and_hostname)?;
 
   Ok(())
 }
@@ -372,3 +377,37 @@ fn regenerate_public_keys_2022_07_05(conn: &PgConnection) -> Result<(), LemmyErr
   }
   Ok(())
 }
+
+/// Generate the keys for sending Web Push notifications, which are used by all users
+fn vapid_keys_2022_08_04(conn: &PgConnection) -> Result<(), LemmyError> {
+  info!("Running vapid_keys_2022_08_04");
+  let secret = Secret::init(conn)?;
+  if secret.vapid_private_key.is_none() || secret.vapid_public_key.is_none() {
+    let keys = generate_vapid_keys()?;
+    Secret::update_vapid_keys(conn, &keys.private_key, &keys.public_key)?;
+  }
+  Ok(())
+}
+
+/// Fill in the canonical url of existing posts, so that they show up in other discussions.
+fn post_url_canonical_2022_08_10(conn: &PgConnection) -> Result<(), LemmyError> {
+  use lemmy_db_schema::schema::post::dsl::*;
+  info!("Running post_url_canonical_2022_08_10");
+
+  let posts = post
+    .filter(url.is_not_null())
+    .filter(url_canonical.is_null())
+    .load::<Post>(conn)?;
+
+  for cpost in &posts {
+    if let Some(post_url) = &cpost.url {
+      diesel::update(post.find(cpost.id))
+        .set(url_canonical.eq(canonicalize_url(post_url)))
+        .execute(conn)?;
+    }
+  }
+
+  info!("{} post rows updated.", posts.len());
+
+  Ok(())
+}
@@ -14,6 +14,10 @@ use lemmy_api_common::{
   utils::{blocking, check_private_instance_and_federation_enabled},
 };
 use lemmy_api_crud::match_websocket_operation_crud;
+use lemmy_apub::{
+  delivery_queue::run_delivery_queue,
+  fetcher::signed_fetch::SignedFetchMiddleware,
+};
 use lemmy_db_schema::{source::secret::Secret, utils::get_database_url_from_env};
 use lemmy_routes::{feeds, images, nodeinfo, webfinger};
 use lemmy_server::{
@@ -21,7 +25,7 @@ use lemmy_server::{
   code_migrations::run_advanced_migrations,
   init_logging,
   root_span_builder::QuieterRootSpanBuilder,
-  scheduled_tasks,
+  scheduled_tasks::{self, run_automod_lock_old_posts, run_email_queue},
 };
 use lemmy_utils::{
   error::LemmyError,
@@ -82,10 +86,16 @@ async fn main() -> Result<(), LemmyError> {
   })
   .await??;
 
+  // Initialize the secrets
+  let conn = pool.get()?;
+  let secret = Secret::init(&conn).expect("Couldn't initialize secrets.");
+
   // Schedules various cleanup tasks for the DB
   let pool2 = pool.clone();
+  let settings2 = settings.clone();
+  let secret2 = secret.clone();
   thread::spawn(move || {
-    scheduled_tasks::setup(pool2).expect("Couldn't set up scheduled_tasks");
+    scheduled_tasks::setup(pool2, settings2, secret2).expect("Couldn't set up scheduled_tasks");
   });
 
   // Set up the rate limiter
@@ -94,10 +104,6 @@ async fn main() -> Result<(), LemmyError> {
     rate_limit_config: settings.rate_limit.to_owned().unwrap_or_default(),
   };
 
-  // Initialize the secrets
-  let conn = pool.get()?;
-  let secret = Secret::init(&conn).expect("Couldn't initialize secrets.");
-
   println!(
     "Starting http server at {}:{}",
     settings.bind, settings.port
@@ -115,10 +121,15 @@ async fn main() -> Result<(), LemmyError> {
     backoff_exponent: 2,
   };
 
-  let client = ClientBuilder::new(reqwest_client.clone())
+  let mut client = ClientBuilder::new(reqwest_client.clone())
     .with(TracingMiddleware)
-    .with(RetryTransientMiddleware::new_with_policy(retry_policy))
-    .build();
+    .with(RetryTransientMiddleware::new_with_policy(retry_policy));
+  // Sign our own fetches, so that remote instances with secure mode or private communities
+  // allow them
+  if settings.federation.signed_fetch {
+    client = client.with(SignedFetchMiddleware { pool: pool.clone() });
+  }
+  let client = client.build();
 
   // Pictrs cannot use the retry middleware
   let pictrs_client = ClientBuilder::new(reqwest_client.clone())
@@ -138,6 +149,18 @@ async fn main() -> Result<(), LemmyError> {
   )
   .start();
 
+  // Send outgoing activities from the queue, and lock old posts for automod in the background
+  let background_context = LemmyContext::create(
+    pool.clone(),
+    chat_server.to_owned(),
+    client.clone(),
+    settings.to_owned(),
+    secret.to_owned(),
+  );
+  actix_web::rt::spawn(run_delivery_queue(background_context.clone()));
+  actix_web::rt::spawn(run_email_queue(background_context.clone()));
+  actix_web::rt::spawn(run_automod_lock_old_posts(background_context));
+
   // Create Http server with websocket support
   let settings_bind = settings.clone();
   HttpServer::new(move || {
@@ -1,14 +1,52 @@
 // Scheduler, and trait for .seconds(), .minutes(), etc.
 use clokwerk::{Scheduler, TimeUnits};
 // Import week days and WeekDay
+use actix_web::rt::{task::spawn_blocking, time::sleep};
 use diesel::{sql_query, PgConnection, RunQueryDsl};
-use lemmy_db_schema::{source::activity::Activity, utils::DbPool};
-use lemmy_utils::error::LemmyError;
+use lemmy_api_common::{
+  automod::automod_person_id,
+  utils::{blocking, queue_email_digest},
+};
+use lemmy_apub::{
+  objects::{community::ApubCommunity, person::ApubPerson},
+  protocol::activities::{
+    community::add_mod::AddMod,
+    create_or_update::post::CreateOrUpdatePost,
+    CreateOrUpdateType,
+  },
+};
+use lemmy_db_schema::{
+  newtypes::CommunityId,
+  source::{
+    activity::Activity,
+    automod_rule::AutomodRule,
+    community::{Community, CommunityModerator, CommunityModeratorForm},
+    email_queue::EmailQueue,
+    local_user::{EmailDigestFrequency, LocalUser},
+    moderator::{ModLockPost, ModLockPostForm},
+    person::Person,
+    post::Post,
+    secret::Secret,
+  },
+  traits::{Crud, Joinable},
+  utils::{naive_now, DbPool},
+};
+use lemmy_utils::{email::send_email, error::LemmyError, settings::structs::Settings};
+use lemmy_websocket::LemmyContext;
 use std::{thread, time::Duration};
-use tracing::info;
+use tracing::{error, info, warn};
+
+/// How many queued emails are sent per run of the email task
+const EMAIL_BATCH_SIZE: i64 = 50;
+/// How often the email queue is checked for emails which are due
+const EMAIL_QUEUE_INTERVAL: Duration = Duration::from_secs(10);
+/// After this many failed attempts, an email is marked as failed and not retried anymore
+const MAX_EMAIL_ATTEMPTS: i32 = 10;
+/// How often old posts are locked for `AutoLock` automod rules
+const AUTOMOD_LOCK_INTERVAL: Duration = Duration::from_secs(60 * 60);
 
 /// Schedules various cleanup tasks for lemmy in a background thread
-pub fn setup(pool: DbPool) -> Result<(), LemmyError> {
+pub fn setup(pool: DbPool, settings: Settings, secret: Secret) -> Result<(), LemmyError> {
   let mut scheduler = Scheduler::new();
 
   let conn = pool.get()?;
@@ -30,6 +68,11 @@ pub fn setup(pool: DbPool) -> Result<(), LemmyError> {
     clear_old_activities(&conn);
   });
 
+  let conn = pool.get()?;
+  scheduler.every(1.hour()).run(move || {
+    queue_email_digests(&conn, &secret, &settings);
+  });
+
   // Manually run the scheduler in an event loop
   loop {
     scheduler.run_pending();
@@ -103,3 +146,189 @@ fn update_banned_when_expired(conn: &PgConnection) {
     .execute(conn)
     .expect("update banned when expires");
 }
+
+/// Locks posts in communities with an `AutoLock` automod rule once they are old enough. Runs as a
+/// background task instead of in the scheduler, because federating the locks needs a
+/// `LemmyContext`.
+pub async fn run_automod_lock_old_posts(context: LemmyContext) {
+  loop {
+    if let Err(e) = automod_lock_old_posts(&context).await {
+      error!("Failed to lock old posts for automod: {}", e);
+    }
+    sleep(AUTOMOD_LOCK_INTERVAL).await;
+  }
+}
+
+/// The locks are written to the modlog and federated in the name of the automod account.
+async fn automod_lock_old_posts(context: &LemmyContext) -> Result<(), LemmyError> {
+  info!("Locking old posts for automod ...");
+  let (rules, automod_person, moderated) = blocking(context.pool(), |conn| {
+    let rules = AutomodRule::list_auto_lock(conn)?;
+    let automod_person = Person::read(conn, automod_person_id(conn)?)?;
+    let moderated = CommunityModerator::get_person_moderated_communities(conn, automod_person.id)?;
+    Ok((rules, automod_person, moderated))
+      as Result<(Vec<(CommunityId, i64)>, Person, Vec<CommunityId>), LemmyError>
+  })
+  .await??;
+  let automod_person: ApubPerson = automod_person.into();
+
+  for (community_id, days) in rules {
+    if !moderated.contains(&community_id) {
+      if let Err(e) = add_automod_as_mod(&automod_person, community_id, context).await {
+        error!(
+          "Failed to add automod as mod of community {}: {}",
+          community_id.0, e
+        );
+        continue;
+      }
+    }
+    let locked_posts = blocking(context.pool(), move |conn| {
+      Post::update_locked_older_than(conn, community_id, days)
+    })
+    .await?;
+    let locked_posts = match locked_posts {
+      Ok(posts) => posts,
+      Err(e) => {
+        error!(
+          "Failed to lock old posts in community {}: {}",
+          community_id.0, e
+        );
+        continue;
+      }
+    };
+    for post in locked_posts {
+      let form = ModLockPostForm {
+        mod_person_id: automod_person.id,
+        post_id: post.id,
+        locked: Some(true),
+      };
+      let post_id = post.id;
+      if let Err(e) = blocking(context.pool(), move |conn| ModLockPost::create(conn, &form)).await?
+      {
+        error!("Failed to write modlog for post {}: {}", post_id.0, e);
+      }
+      let update = CreateOrUpdatePost::send(
+        post.into(),
+        &automod_person,
+        CreateOrUpdateType::Update,
+        context,
+      );
+      if let Err(e) = update.await {
+        error!("Failed to federate lock of post {}: {}", post_id.0, e);
+      }
+    }
+  }
+  info!("Done.");
+  Ok(())
+}
+
+/// Other instances only accept the federated locks from a mod of the community, so the automod
+/// account is made a mod of each community with an `AutoLock` rule.
+async fn add_automod_as_mod(
+  automod_person: &ApubPerson,
+  community_id: CommunityId,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  let form = CommunityModeratorForm {
+    community_id,
+    person_id: automod_person.id,
+  };
+  let community: ApubCommunity = blocking(context.pool(), move |conn| {
+    CommunityModerator::join(conn, &form)?;
+    Community::read(conn, community_id)
+  })
+  .await??
+  .into();
+  AddMod::send(&community, automod_person, automod_person, context).await
+}
+
+/// Sends queued emails which are due. Runs as its own background task instead of in the
+/// scheduler, so that a slow SMTP server doesn't hold up the other tasks.
+pub async fn run_email_queue(context: LemmyContext) {
+  loop {
+    if let Err(e) = send_queued_emails(&context).await {
+      error!("Failed to read email queue: {}", e);
+    }
+    sleep(EMAIL_QUEUE_INTERVAL).await;
+  }
+}
+
+/// Send emails from the queue which are due. Failed emails are retried later with backoff.
+async fn send_queued_emails(context: &LemmyContext) -> Result<(), LemmyError> {
+  let emails = blocking(context.pool(), |conn| {
+    EmailQueue::list_due(conn, EMAIL_BATCH_SIZE)
+  })
+  .await??;
+  for email in emails {
+    let settings = context.settings();
+    let email_ = email.clone();
+    let sent = spawn_blocking(move || {
+      send_email(
+        &email_.subject,
+        &email_.to_email,
+        &email_.to_name,
+        &email_.html,
+        email_.unsubscribe_url.as_deref(),
+        settings,
+      )
+    })
+    .await?;
+    let updated = match sent {
+      Ok(_) => {
+        let email_id = email.id;
+        blocking(context.pool(), move |conn| {
+          EmailQueue::delete(conn, email_id)
+        })
+        .await?
+        .map(|_| ())
+      }
+      Err(e) => {
+        warn!("Failed to send email to {}: {}", email.to_email, e);
+        blocking(context.pool(), move |conn| {
+          EmailQueue::mark_attempt_failed(conn, &email, &e.to_string(), MAX_EMAIL_ATTEMPTS)
+        })
+        .await?
+        .map(|_| ())
+      }
+    };
+    if let Err(e) = updated {
+      error!("Failed to update email queue: {}", e);
+    }
+  }
+  Ok(())
+}
+
+/// Batch the notifications of users who chose an hourly or daily email digest into one email
+fn queue_email_digests(conn: &PgConnection, secret: &Secret, settings: &Settings) {
+  if settings.email.is_none() {
+    return;
+  }
+  info!("Queueing email digests ...");
+  // Taken before sending, so that the digests don't shift later by the time this task takes
+  let now = naive_now();
+  for frequency in [EmailDigestFrequency::Hourly, EmailDigestFrequency::Daily] {
+    let local_users = match LocalUser::list_for_email_digest(conn, frequency, now) {
+      Ok(local_users) => local_users,
+      Err(e) => {
+        error!("Failed to read users for email digest: {}", e);
+        continue;
+      }
+    };
+    for local_user in local_users {
+      if let Err(e) = queue_email_digest(
+        conn,
+        local_user.id,
+        local_user.last_email_digest,
+        secret,
+        settings,
+      ) {
+        error!("Failed to queue email digest: {}", e);
+        continue;
+      }
+      if let Err(e) = LocalUser::update_last_email_digest(conn, local_user.id, now) {
+        error!("Failed to update last email digest: {}", e);
+      }
+    }
+  }
+  info!("Done.");
+}